@@ -0,0 +1,176 @@
+/// Minimum equipment of a furnished dwelling (décret n° 2015-981 du 31 juillet
+/// 2015, art. 2), with the wording of the décret.
+pub const MANDATORY_FURNITURE: [(&str, &str); 11] = [
+    ("literie", "Literie comprenant couette ou couverture"),
+    ("occultation", "Dispositif d'occultation des fenêtres dans les chambres"),
+    ("plaques", "Plaques de cuisson"),
+    ("four", "Four ou four à micro-ondes"),
+    ("refrigerateur", "Réfrigérateur avec congélateur ou compartiment à -6 °C au plus"),
+    ("vaisselle", "Vaisselle en nombre suffisant pour prendre les repas"),
+    ("ustensiles", "Ustensiles de cuisine"),
+    ("table_sieges", "Table et sièges"),
+    ("rangements", "Étagères de rangement"),
+    ("luminaires", "Luminaires"),
+    ("entretien", "Matériel d'entretien ménager adapté au logement"),
+];
+
+pub fn mandatory_furniture_label(code: &str) -> Option<&'static str> {
+    MANDATORY_FURNITURE
+        .iter()
+        .find(|(item, _)| *item == code)
+        .map(|(_, label)| *label)
+}
+
+/// Words that identify each mandatory item in free-form furniture rows. An
+/// item is present when every group has at least one word somewhere in the
+/// inventory: a bed and a duvet or blanket, a fridge and a freezer space, a
+/// table and seats. Words are matched whole, accents ignored, plural allowed.
+const MANDATORY_FURNITURE_WORDS: [(&str, &[&[&str]]); 11] = [
+    (
+        "literie",
+        &[
+            &["lit", "literie", "matelas", "sommier", "convertible", "clic clac", "bz"],
+            &["couette", "couverture", "duvet", "plaid"],
+        ],
+    ),
+    ("occultation", &[&["rideau", "volet", "store", "occultant", "occultation"]]),
+    (
+        "plaques",
+        &[&["plaque", "table de cuisson", "cuisiniere", "gaziniere", "induction", "vitroceramique"]],
+    ),
+    ("four", &[&["four", "micro ondes", "micro onde", "microondes"]]),
+    (
+        "refrigerateur",
+        &[
+            &["refrigerateur", "frigo", "frigidaire", "combine"],
+            &["congelateur", "freezer", "compartiment", "congelation", "combine", "americain"],
+        ],
+    ),
+    ("vaisselle", &[&["vaisselle", "assiette", "verre", "bol", "tasse", "couvert"]]),
+    (
+        "ustensiles",
+        &[&["ustensile", "casserole", "poele", "faitout", "cocotte", "spatule", "louche"]],
+    ),
+    (
+        "table_sieges",
+        &[
+            &["table"],
+            &["chaise", "siege", "tabouret", "banc", "banquette", "fauteuil", "canape"],
+        ],
+    ),
+    (
+        "rangements",
+        &[&["etagere", "rangement", "placard", "armoire", "commode", "penderie", "bibliotheque", "dressing", "buffet"]],
+    ),
+    (
+        "luminaires",
+        &[&["luminaire", "lampe", "lustre", "plafonnier", "applique", "suspension", "spot", "eclairage"]],
+    ),
+    (
+        "entretien",
+        &[&["aspirateur", "balai", "balayette", "serpilliere", "seau", "raclette", "entretien"]],
+    ),
+];
+
+/// Lowercase, strip French accents and turn punctuation into single spaces,
+/// padded so whole words can be found with `" word "`.
+fn normalize(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len() + 2);
+    for c in text.to_lowercase().chars() {
+        match c {
+            'à' | 'â' | 'ä' => folded.push('a'),
+            'é' | 'è' | 'ê' | 'ë' => folded.push('e'),
+            'î' | 'ï' => folded.push('i'),
+            'ô' | 'ö' => folded.push('o'),
+            'ù' | 'û' | 'ü' => folded.push('u'),
+            'ç' => folded.push('c'),
+            'œ' => folded.push_str("oe"),
+            c if c.is_alphanumeric() => folded.push(c),
+            _ => folded.push(' '),
+        }
+    }
+    format!(" {} ", folded.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn mentions(text: &str, word: &str) -> bool {
+    ["", "s", "x"]
+        .iter()
+        .any(|plural| text.contains(&format!(" {}{} ", word, plural)))
+}
+
+/// Mandatory items of a furnished dwelling that none of the descriptions
+/// covers, as codes of `MANDATORY_FURNITURE`. Descriptions are furniture rows
+/// ("category name") and the lease's free-text furniture inventory.
+pub fn missing_mandatory_furniture<'a>(descriptions: impl IntoIterator<Item = &'a str>) -> Vec<&'static str> {
+    let text = normalize(&descriptions.into_iter().collect::<Vec<_>>().join("\n"));
+    MANDATORY_FURNITURE
+        .iter()
+        .map(|(code, _)| *code)
+        .filter(|code| {
+            let groups = MANDATORY_FURNITURE_WORDS
+                .iter()
+                .find(|(item, _)| item == code)
+                .map(|(_, groups)| *groups)
+                .unwrap_or_default();
+            !groups
+                .iter()
+                .all(|words| words.iter().any(|word| mentions(&text, word)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULLY_FURNISHED: [&str; 14] = [
+        "Chambre Lit double 140",
+        "Chambre Couette",
+        "Chambre Rideaux occultants",
+        "Cuisine Plaques à induction",
+        "Cuisine Micro-ondes",
+        "Cuisine Réfrigérateur combiné",
+        "Cuisine Assiettes",
+        "Cuisine Casseroles",
+        "Séjour Table",
+        "Séjour Chaises",
+        "Séjour Étagère",
+        "Séjour Lampes",
+        "Entretien Aspirateur",
+        "Entretien Balai",
+    ];
+
+    #[test]
+    fn fully_furnished_flat_misses_nothing() {
+        assert!(missing_mandatory_furniture(FULLY_FURNISHED).is_empty());
+    }
+
+    #[test]
+    fn reports_every_item_when_nothing_is_listed() {
+        assert_eq!(missing_mandatory_furniture([]).len(), MANDATORY_FURNITURE.len());
+    }
+
+    #[test]
+    fn requires_a_blanket_with_the_bed_and_a_freezer_with_the_fridge() {
+        let mut rows: Vec<&str> = FULLY_FURNISHED
+            .iter()
+            .copied()
+            .filter(|row| !row.contains("Couette") && !row.contains("Réfrigérateur"))
+            .collect();
+        rows.push("Cuisine Frigo");
+        assert_eq!(missing_mandatory_furniture(rows.clone()), vec!["literie", "refrigerateur"]);
+
+        rows.push("Cuisine Congélateur");
+        rows.push("Chambre Couverture");
+        assert!(missing_mandatory_furniture(rows).is_empty());
+    }
+
+    #[test]
+    fn matches_whole_words_only() {
+        // "fourchettes" is not an oven, "couverts" is not a blanket.
+        let missing = missing_mandatory_furniture(["Cuisine Fourchettes", "Chambre Lit", "Cuisine Couverts"]);
+        assert!(missing.contains(&"four"));
+        assert!(missing.contains(&"literie"));
+        assert!(!missing.contains(&"vaisselle"));
+    }
+}
@@ -0,0 +1,724 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{Months, NaiveDate};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::report::{ComplianceReport, ComplianceViolation, SEVERITY_BLOCKING, SEVERITY_WARNING};
+use crate::rules::{dpe_expiry, ReletCap, RuleSet};
+
+/// Situations that open the bail mobilité to a tenant (loi 89, art. 25-12).
+pub const MOBILITY_REASONS: [(&str, &str); 7] = [
+    ("formation_professionnelle", "Formation professionnelle"),
+    ("etudes_superieures", "Études supérieures"),
+    ("apprentissage", "Contrat d'apprentissage"),
+    ("stage", "Stage"),
+    ("service_civique", "Engagement volontaire dans le cadre d'un service civique"),
+    ("mutation_professionnelle", "Mutation professionnelle"),
+    ("mission_temporaire", "Mission temporaire dans le cadre de son activité professionnelle"),
+];
+
+pub fn mobility_reason_label(reason: &str) -> Option<&'static str> {
+    MOBILITY_REASONS
+        .iter()
+        .find(|(code, _)| *code == reason)
+        .map(|(_, label)| *label)
+}
+
+/// The fields of a lease payload the compliance rules read. Field names match
+/// the backend's `CreateLease`, so the form can pass the payload it is about
+/// to submit; other fields are ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaseTerms {
+    pub tenant_ids: Vec<Uuid>,
+    pub start_date: NaiveDate,
+    pub duration_months: i32,
+    pub monthly_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub deposit: BigDecimal,
+    pub lease_kind: Option<String>,
+    pub mobility_reason: Option<String>,
+    pub mobility_justification: Option<String>,
+    pub is_colocation: Option<bool>,
+    pub habitable_surface: Option<BigDecimal>,
+    pub main_room_count: Option<i32>,
+    pub dpe_class: Option<String>,
+    pub is_dom_tom: Option<bool>,
+    pub dpe_date: Option<NaiveDate>,
+    pub rent_controlled: Option<bool>,
+    pub reference_rent: Option<BigDecimal>,
+    pub reference_rent_majorated: Option<BigDecimal>,
+    pub rent_complement: Option<BigDecimal>,
+    pub rent_complement_justification: Option<String>,
+    pub previous_tenant_departure_date: Option<NaiveDate>,
+    pub previous_tenant_last_rent: Option<BigDecimal>,
+    /// Last revision of the previous tenant's rent, if it was ever revised.
+    pub previous_rent_revised_on: Option<NaiveDate>,
+    /// IRL the previous rent was last revised on, or the one in force when
+    /// the previous lease was signed.
+    pub previous_rent_irl: Option<BigDecimal>,
+    /// Monthly rent usually charged for comparable dwellings nearby, when the
+    /// previous rent was manifestly undervalued.
+    pub comparable_rent: Option<BigDecimal>,
+    /// Value of the lease's IRL reference quarter; the server sets it from
+    /// the loaded indices.
+    pub irl_value: Option<BigDecimal>,
+    pub professional_mandate: Option<bool>,
+    pub agency_fee_tenant: Option<BigDecimal>,
+    pub agency_fee_landlord: Option<BigDecimal>,
+    pub custom_clauses: Option<String>,
+    pub irl_reference_quarter: Option<i32>,
+    pub irl_reference_year: Option<i32>,
+    pub legal_notice_provided: bool,
+    pub annex_entry_inventory_provided: Option<bool>,
+    pub annex_furniture_inventory_provided: Option<bool>,
+    pub annex_dpe_provided: Option<bool>,
+    pub identifiant_fiscal: Option<String>,
+    pub habitat_type: Option<String>,
+    pub regime_juridique: Option<String>,
+    pub construction_period: Option<String>,
+    pub electrical_installation_over_15y: Option<bool>,
+    pub gas_installation_over_15y: Option<bool>,
+    pub in_risk_zone: Option<bool>,
+    pub annex_lead_provided: Option<bool>,
+    pub annex_electrical_provided: Option<bool>,
+    pub annex_gas_provided: Option<bool>,
+    pub annex_risk_provided: Option<bool>,
+    /// Whether the property lies in a zone tendue; the server sets it from
+    /// the property's classification.
+    pub zone_tendue: Option<bool>,
+    pub works_amount: Option<BigDecimal>,
+    pub works_date: Option<NaiveDate>,
+}
+
+const LEASE_KINDS: [&str; 3] = ["standard", "student", "mobility"];
+
+fn str_is_blank(value: Option<&str>) -> bool {
+    value.map(|v| v.trim().is_empty()).unwrap_or(true)
+}
+
+fn dpe_rank(dpe_class: &str) -> Option<i32> {
+    match dpe_class.trim().to_ascii_uppercase().as_str() {
+        "A" => Some(1),
+        "B" => Some(2),
+        "C" => Some(3),
+        "D" => Some(4),
+        "E" => Some(5),
+        "F" => Some(6),
+        "G" => Some(7),
+        _ => None,
+    }
+}
+
+/// Violations collected while walking the rules.
+#[derive(Default)]
+struct Findings(Vec<ComplianceViolation>);
+
+impl Findings {
+    fn push(&mut self, severity: &str, code: &str, field: &str, legal_reference: &str, message: &str) {
+        self.0.push(ComplianceViolation {
+            code: code.to_string(),
+            severity: severity.to_string(),
+            field: field.to_string(),
+            legal_reference: legal_reference.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    fn blocking(&mut self, code: &str, field: &str, legal_reference: &str, message: &str) {
+        self.push(SEVERITY_BLOCKING, code, field, legal_reference, message);
+    }
+
+    fn warning(&mut self, code: &str, field: &str, legal_reference: &str, message: &str) {
+        self.push(SEVERITY_WARNING, code, field, legal_reference, message);
+    }
+}
+
+/// Every compliance rule applied to a lease payload at once, so the form can
+/// show all the problems instead of the first one. Dated thresholds are those
+/// in force on the lease start date.
+pub fn lease_compliance_report(data: &LeaseTerms, lease_type: &str, landlord_is_legal_person: bool) -> ComplianceReport {
+    let rules = RuleSet::in_force_on(data.start_date);
+    let mut findings = Findings::default();
+    check_amounts(&mut findings, data);
+
+    if lease_type == "ancillary" {
+        check_ancillary_lease(&mut findings, data);
+    } else {
+        check_dwelling(&mut findings, data);
+        check_lease_type_rules(&mut findings, &rules, data, lease_type, landlord_is_legal_person);
+        check_parties(&mut findings, data);
+        check_dpe(&mut findings, &rules, data);
+        check_rent_and_fees(&mut findings, &rules, data);
+        check_characterisation_and_annexes(&mut findings, data);
+    }
+
+    ComplianceReport::new(rules.version, findings.0)
+}
+
+fn check_amounts(findings: &mut Findings, data: &LeaseTerms) {
+    let zero = BigDecimal::zero();
+    if data.monthly_rent <= zero {
+        findings.blocking(
+            "rent.positive",
+            "monthly_rent",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3",
+            "Le loyer mensuel doit être supérieur à 0.",
+        );
+    }
+    if data.charges < zero {
+        findings.blocking(
+            "charges.non_negative",
+            "charges",
+            "Loi n° 89-462 du 6 juillet 1989, art. 23",
+            "Les charges ne peuvent pas être négatives.",
+        );
+    }
+    if data.deposit < zero {
+        findings.blocking(
+            "deposit.non_negative",
+            "deposit",
+            "Loi n° 89-462 du 6 juillet 1989, art. 22",
+            "Le dépôt de garantie ne peut pas être négatif.",
+        );
+    }
+
+    match (data.irl_reference_quarter, data.irl_reference_year) {
+        (Some(quarter), Some(_)) if !(1..=4).contains(&quarter) => findings.blocking(
+            "irl.quarter_range",
+            "irl_reference_quarter",
+            "Loi n° 89-462 du 6 juillet 1989, art. 17-1",
+            "Le trimestre de référence de l'IRL doit être compris entre 1 et 4.",
+        ),
+        (Some(_), None) | (None, Some(_)) => findings.blocking(
+            "irl.incomplete",
+            "irl_reference_year",
+            "Loi n° 89-462 du 6 juillet 1989, art. 17-1",
+            "Le trimestre et l'année de référence de l'IRL vont ensemble.",
+        ),
+        _ => {}
+    }
+}
+
+/// Ancillary premises let under the code civil (art. 1709 and following):
+/// the terms are free, so only the parties and a positive duration are
+/// checked. No DPE, IFL or residential annex applies.
+fn check_ancillary_lease(findings: &mut Findings, data: &LeaseTerms) {
+    if data.lease_kind.as_deref().unwrap_or("standard") != "standard" {
+        findings.blocking(
+            "lease_kind.dwelling_only",
+            "lease_kind",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-9 et 25-12",
+            "Les baux étudiant et mobilité ne concernent que les logements.",
+        );
+    }
+    if data.duration_months < 1 {
+        findings.blocking(
+            "duration.ancillary_min",
+            "duration_months",
+            "Code civil, art. 1709",
+            "La durée du bail doit être d'au moins un mois.",
+        );
+    }
+    check_parties(findings, data);
+}
+
+fn check_dwelling(findings: &mut Findings, data: &LeaseTerms) {
+    let zero = BigDecimal::zero();
+    if data.habitable_surface.as_ref().map(|v| v <= &zero).unwrap_or(true) {
+        findings.blocking(
+            "surface.required",
+            "habitable_surface",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3",
+            "La surface habitable est obligatoire et doit être supérieure à 0.",
+        );
+    }
+    if data.main_room_count.unwrap_or(0) <= 0 {
+        findings.blocking(
+            "main_rooms.required",
+            "main_room_count",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3",
+            "Le nombre de pièces principales est obligatoire et doit être supérieur à 0.",
+        );
+    }
+}
+
+/// Duration and deposit rules that depend on the contract regime.
+///
+/// Furnished (loi 89, art. 25-6 and 25-7): one year, or nine months for a
+/// student lease, deposit up to two months and a furniture inventory.
+/// Unfurnished (art. 10 and 22): three years for a natural person or family
+/// SCI landlord, six years for a legal person, deposit up to one month.
+/// Bail mobilité (art. 25-12 to 25-18): furnished, one to ten months, no
+/// deposit and a stated tenant situation.
+fn check_lease_type_rules(
+    findings: &mut Findings,
+    rules: &RuleSet,
+    data: &LeaseTerms,
+    lease_type: &str,
+    landlord_is_legal_person: bool,
+) {
+    let lease_kind = data.lease_kind.as_deref().unwrap_or("standard");
+    if !LEASE_KINDS.contains(&lease_kind) {
+        findings.blocking(
+            "lease_kind.unknown",
+            "lease_kind",
+            "Loi n° 89-462 du 6 juillet 1989, titre Ier bis",
+            "Le type de bail doit être standard, étudiant ou mobilité.",
+        );
+        return;
+    }
+
+    if lease_type == "unfurnished" {
+        match lease_kind {
+            "student" => findings.blocking(
+                "student.furnished_only",
+                "lease_kind",
+                "Loi n° 89-462 du 6 juillet 1989, art. 25-9",
+                "Le bail étudiant est réservé aux locations meublées.",
+            ),
+            "mobility" => findings.blocking(
+                "mobility.furnished_only",
+                "lease_kind",
+                "Loi n° 89-462 du 6 juillet 1989, art. 25-12",
+                "Le bail mobilité est réservé aux locations meublées.",
+            ),
+            _ => {}
+        }
+        let (min_months, message) = if landlord_is_legal_person {
+            (72, "Une location nue consentie par une personne morale doit durer au moins six ans.")
+        } else {
+            (36, "Une location nue consentie par une personne physique doit durer au moins trois ans.")
+        };
+        if data.duration_months < min_months {
+            findings.blocking(
+                "duration.unfurnished_min",
+                "duration_months",
+                "Loi n° 89-462 du 6 juillet 1989, art. 10",
+                message,
+            );
+        }
+        let deposit_cap = rules
+            .unfurnished_deposit_months
+            .map(|months| &data.monthly_rent * BigDecimal::from(months));
+        if deposit_cap.is_some_and(|cap| data.deposit > cap) {
+            findings.blocking(
+                "deposit.unfurnished_max",
+                "deposit",
+                "Loi n° 89-462 du 6 juillet 1989, art. 22",
+                "Le dépôt de garantie d'une location nue ne peut dépasser un mois de loyer hors charges.",
+            );
+        }
+        return;
+    }
+
+    match lease_kind {
+        "student" => {
+            if data.duration_months != 9 {
+                findings.blocking(
+                    "duration.student",
+                    "duration_months",
+                    "Loi n° 89-462 du 6 juillet 1989, art. 25-7",
+                    "Un bail étudiant doit durer neuf mois.",
+                );
+            }
+        }
+        "mobility" => check_mobility_lease(findings, data),
+        _ => {
+            if data.duration_months < 12 {
+                findings.blocking(
+                    "duration.furnished_min",
+                    "duration_months",
+                    "Loi n° 89-462 du 6 juillet 1989, art. 25-7",
+                    "Un bail meublé doit durer au moins un an.",
+                );
+            }
+        }
+    }
+
+    let deposit_cap = rules
+        .furnished_deposit_months
+        .map(|months| &data.monthly_rent * BigDecimal::from(months));
+    if deposit_cap.is_some_and(|cap| data.deposit > cap) {
+        findings.blocking(
+            "deposit.furnished_max",
+            "deposit",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-6",
+            "Le dépôt de garantie ne peut dépasser deux mois de loyer hors charges.",
+        );
+    }
+
+    if !data.annex_furniture_inventory_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.furniture_inventory",
+            "annex_furniture_inventory_provided",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-5",
+            "L'inventaire du mobilier est obligatoire pour un logement meublé.",
+        );
+    }
+}
+
+fn check_mobility_lease(findings: &mut Findings, data: &LeaseTerms) {
+    if !(1..=10).contains(&data.duration_months) {
+        findings.blocking(
+            "mobility.duration",
+            "duration_months",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-14",
+            "Un bail mobilité doit durer entre un et dix mois.",
+        );
+    }
+    if !data.deposit.is_zero() {
+        findings.blocking(
+            "mobility.no_deposit",
+            "deposit",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-17",
+            "Aucun dépôt de garantie ne peut être exigé pour un bail mobilité.",
+        );
+    }
+    if mobility_reason_label(data.mobility_reason.as_deref().unwrap_or("")).is_none() {
+        findings.blocking(
+            "mobility.reason",
+            "mobility_reason",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-12",
+            "Le bail mobilité requiert la situation du locataire (formation, études, apprentissage, stage, service civique, mutation ou mission temporaire).",
+        );
+    }
+    if str_is_blank(data.mobility_justification.as_deref()) {
+        findings.blocking(
+            "mobility.justification",
+            "mobility_justification",
+            "Loi n° 89-462 du 6 juillet 1989, art. 25-13",
+            "Le bail mobilité requiert un justificatif de la situation du locataire.",
+        );
+    }
+}
+
+fn check_parties(findings: &mut Findings, data: &LeaseTerms) {
+    let is_colocation = data.is_colocation.unwrap_or(false);
+    let tenant_count = data.tenant_ids.len();
+    if data.tenant_ids.is_empty() {
+        findings.blocking(
+            "tenants.required",
+            "tenant_ids",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3",
+            "Au moins un locataire est requis.",
+        );
+        return;
+    }
+    let mut seen = std::collections::HashSet::new();
+    if !data.tenant_ids.iter().all(|id| seen.insert(*id)) {
+        findings.blocking(
+            "tenants.duplicate",
+            "tenant_ids",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3",
+            "Un même locataire ne peut figurer deux fois au bail.",
+        );
+    }
+    if is_colocation && tenant_count < 2 {
+        findings.blocking(
+            "colocation.min_tenants",
+            "tenant_ids",
+            "Loi n° 89-462 du 6 juillet 1989, art. 8-1",
+            "Une colocation requiert au moins deux colocataires.",
+        );
+    }
+    if !is_colocation && tenant_count > 1 {
+        findings.blocking(
+            "colocation.required",
+            "is_colocation",
+            "Loi n° 89-462 du 6 juillet 1989, art. 8-1",
+            "Plusieurs locataires nécessitent d'activer la colocation.",
+        );
+    }
+}
+
+fn check_dpe(findings: &mut Findings, rules: &RuleSet, data: &LeaseTerms) {
+    let dpe_class = data.dpe_class.as_deref().unwrap_or("");
+    if str_is_blank(Some(dpe_class)) {
+        findings.blocking(
+            "dpe.class_required",
+            "dpe_class",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3-3",
+            "La classe énergétique du DPE est obligatoire.",
+        );
+    } else {
+        match dpe_rank(dpe_class) {
+            None => findings.blocking(
+                "dpe.class_invalid",
+                "dpe_class",
+                "Code de la construction et de l'habitation, art. L126-26",
+                "La classe énergétique doit être comprise entre A et G.",
+            ),
+            Some(rank) if rank > rules.dpe_worst_rank(data.is_dom_tom.unwrap_or(false)) => {
+                findings.blocking(
+                    "dpe.below_threshold",
+                    "dpe_class",
+                    "Loi n° 89-462 du 6 juillet 1989, art. 6 ; décret n° 2002-120 du 30 janvier 2002, art. 3 bis",
+                    "La classe énergétique est en dessous du seuil de décence à la date de prise d'effet et pour ce territoire.",
+                )
+            }
+            Some(_) => {}
+        }
+    }
+
+    if let Some(dpe_date) = data.dpe_date {
+        if dpe_expiry(dpe_date) < data.start_date {
+            findings.warning(
+                "dpe.expired",
+                "dpe_date",
+                "Code de la construction et de l'habitation, art. D126-19",
+                "Le DPE n'est plus valide à la date de prise d'effet du bail.",
+            );
+        }
+    }
+
+    if !data.legal_notice_provided {
+        findings.blocking(
+            "notice.legal_information",
+            "legal_notice_provided",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3 ; arrêté du 29 mai 2015",
+            "La notice d'information légale est obligatoire.",
+        );
+    }
+    if !data.annex_dpe_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.dpe",
+            "annex_dpe_provided",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3-3",
+            "L'annexe DPE est obligatoire.",
+        );
+    }
+    if !data.annex_entry_inventory_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.entry_inventory",
+            "annex_entry_inventory_provided",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3-2",
+            "L'annexe état des lieux d'entrée est obligatoire.",
+        );
+    }
+}
+
+fn check_rent_and_fees(findings: &mut Findings, rules: &RuleSet, data: &LeaseTerms) {
+    let zero = BigDecimal::zero();
+    let has_complement = data.rent_complement.as_ref().map(|v| v > &zero).unwrap_or(false);
+    let complement_justified = !str_is_blank(data.rent_complement_justification.as_deref());
+
+    if data.rent_controlled.unwrap_or(false) {
+        if data.reference_rent.is_none() || data.reference_rent_majorated.is_none() {
+            findings.blocking(
+                "rent_control.reference_required",
+                "reference_rent",
+                "Loi n° 2018-1021 du 23 novembre 2018 (ELAN), art. 140",
+                "En zone encadrée, les loyers de référence et de référence majoré sont requis.",
+            );
+        }
+        if has_complement && !complement_justified {
+            findings.blocking(
+                "rent_control.complement_justification",
+                "rent_complement_justification",
+                "Loi n° 2018-1021 du 23 novembre 2018 (ELAN), art. 140",
+                "Un complément de loyer nécessite une justification.",
+            );
+        }
+        // Reference rents are stated per m² of habitable surface, as in the
+        // arrêtés.
+        if let (Some(majorated), Some(surface)) =
+            (data.reference_rent_majorated.as_ref(), data.habitable_surface.as_ref())
+        {
+            if data.monthly_rent > majorated * surface && !(has_complement && complement_justified) {
+                findings.blocking(
+                    "rent_control.above_majorated",
+                    "monthly_rent",
+                    "Loi n° 2018-1021 du 23 novembre 2018 (ELAN), art. 140",
+                    "En zone encadrée, le loyer dépasse le loyer de référence majoré sans complément justifié.",
+                );
+            }
+        }
+    }
+
+    if data.professional_mandate.unwrap_or(false) {
+        match (data.agency_fee_tenant.as_ref(), data.agency_fee_landlord.as_ref()) {
+            (None, _) => findings.blocking(
+                "agency_fee.tenant_required",
+                "agency_fee_tenant",
+                "Loi n° 89-462 du 6 juillet 1989, art. 5",
+                "Les honoraires locataire sont requis en cas de mandat professionnel.",
+            ),
+            (_, None) => findings.blocking(
+                "agency_fee.landlord_required",
+                "agency_fee_landlord",
+                "Loi n° 89-462 du 6 juillet 1989, art. 5",
+                "Les honoraires bailleur sont requis en cas de mandat professionnel.",
+            ),
+            (Some(tenant_fee), Some(landlord_fee)) if tenant_fee > landlord_fee => findings.blocking(
+                "agency_fee.tenant_exceeds_landlord",
+                "agency_fee_tenant",
+                "Loi n° 89-462 du 6 juillet 1989, art. 5",
+                "Les honoraires locataire ne peuvent pas excéder les honoraires bailleur.",
+            ),
+            _ => {}
+        }
+    }
+
+    if let (Some(previous_departure_date), Some(window_days)) =
+        (data.previous_tenant_departure_date, rules.previous_rent_window_days)
+    {
+        let signed_days = (data.start_date - previous_departure_date).num_days();
+        if (0..=window_days).contains(&signed_days) {
+            match data.previous_tenant_last_rent.as_ref() {
+                None => findings.blocking(
+                    "previous_rent.required",
+                    "previous_tenant_last_rent",
+                    "Loi n° 89-462 du 6 juillet 1989, art. 3",
+                    "Le dernier loyer du précédent locataire est requis s'il est parti depuis moins de dix-huit mois.",
+                ),
+                Some(previous_rent) => check_relet_cap(findings, rules, data, previous_rent),
+            }
+        }
+    }
+
+    if let Some(custom_clauses) = data.custom_clauses.as_deref() {
+        let clauses = custom_clauses.to_lowercase();
+        if rules.banned_clause_patterns.iter().any(|pattern| clauses.contains(pattern)) {
+            findings.blocking(
+                "clauses.prohibited",
+                "custom_clauses",
+                "Loi n° 89-462 du 6 juillet 1989, art. 4",
+                "Les clauses particulières contiennent une stipulation interdite.",
+            );
+        }
+    }
+}
+
+/// The previous rent, raised by the IRL since its last revision when none
+/// took place in the months before the lease start and both indices are
+/// known.
+fn indexed_previous_rent(cap: &ReletCap, previous_rent: &BigDecimal, data: &LeaseTerms) -> BigDecimal {
+    let revised_recently = data.previous_rent_revised_on.is_some_and(|revised_on| {
+        data.start_date
+            .checked_sub_months(Months::new(cap.unrevised_months as u32))
+            .is_none_or(|since| revised_on > since)
+    });
+    match (data.previous_rent_irl.as_ref(), data.irl_value.as_ref()) {
+        (Some(previous_irl), Some(irl)) if !revised_recently && previous_irl > &BigDecimal::zero() && irl > previous_irl => {
+            previous_rent * irl / previous_irl
+        }
+        _ => previous_rent.clone(),
+    }
+}
+
+/// Highest rent of a relet in a zone tendue: the previous tenant's last rent,
+/// indexed if it was not revised lately, plus the larger of the monthly share
+/// of the improvement works and half the gap to comparable rents when the
+/// previous rent was undervalued. Works dated after the lease start do not
+/// count yet; the undervaluation rule does not apply where the rents are
+/// controlled, the reference rents taking its place.
+pub fn relet_rent_ceiling(cap: &ReletCap, previous_rent: &BigDecimal, data: &LeaseTerms) -> BigDecimal {
+    let indexed = indexed_previous_rent(cap, previous_rent, data);
+    let works_allowance = match (data.works_amount.as_ref(), data.works_date) {
+        (Some(amount), works_date)
+            if works_date.is_none_or(|works_date| works_date <= data.start_date)
+                && amount >= &(previous_rent * BigDecimal::from(cap.works_min_rent_months)) =>
+        {
+            amount * BigDecimal::from(cap.works_yearly_percent) / BigDecimal::from(100 * 12)
+        }
+        _ => BigDecimal::zero(),
+    };
+    let undervaluation_allowance = match data.comparable_rent.as_ref() {
+        Some(comparable) if !data.rent_controlled.unwrap_or(false) && comparable > &indexed => {
+            (comparable - &indexed) * BigDecimal::from(cap.undervalued_gap_percent) / BigDecimal::from(100)
+        }
+        _ => BigDecimal::zero(),
+    };
+    (indexed + works_allowance.max(undervaluation_allowance)).with_scale_round(2, RoundingMode::Down)
+}
+
+fn check_relet_cap(findings: &mut Findings, rules: &RuleSet, data: &LeaseTerms, previous_rent: &BigDecimal) {
+    let Some(cap) = rules.relet_cap.filter(|_| data.zone_tendue.unwrap_or(false)) else {
+        return;
+    };
+    let ceiling = relet_rent_ceiling(&cap, previous_rent, data);
+    if data.monthly_rent > ceiling {
+        findings.blocking(
+            "previous_rent.relet_cap",
+            "monthly_rent",
+            "Loi n° 89-462 du 6 juillet 1989, art. 18 ; décret n° 2017-1198 du 27 juillet 2017",
+            &format!(
+                "En zone tendue, le loyer de relocation ne peut dépasser {} € : le dernier loyer du précédent locataire, révisé selon l'IRL s'il ne l'a pas été depuis {} mois, augmenté au plus de {} % par an du coût des travaux d'amélioration d'au moins {} mois de ce loyer ou, s'il était manifestement sous-évalué, de {} % de l'écart avec les loyers du voisinage.",
+                ceiling,
+                cap.unrevised_months,
+                cap.works_yearly_percent,
+                cap.works_min_rent_months,
+                cap.undervalued_gap_percent
+            ),
+        );
+    }
+}
+
+fn check_characterisation_and_annexes(findings: &mut Findings, data: &LeaseTerms) {
+    if !data.is_dom_tom.unwrap_or(false) && str_is_blank(data.identifiant_fiscal.as_deref()) {
+        findings.blocking(
+            "ifl.required",
+            "identifiant_fiscal",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3",
+            "L'identifiant fiscal du logement est obligatoire (sauf DOM-TOM).",
+        );
+    }
+    if str_is_blank(data.habitat_type.as_deref()) {
+        findings.blocking(
+            "habitat_type.required",
+            "habitat_type",
+            "Décret n° 2015-587 du 29 mai 2015, annexe 1",
+            "Le type d'habitat (collectif ou individuel) est obligatoire.",
+        );
+    }
+    if str_is_blank(data.regime_juridique.as_deref()) {
+        findings.blocking(
+            "regime_juridique.required",
+            "regime_juridique",
+            "Décret n° 2015-587 du 29 mai 2015, annexe 1",
+            "Le régime juridique (monopropriété ou copropriété) est obligatoire.",
+        );
+    }
+    let construction_period = data.construction_period.as_deref().unwrap_or("");
+    if str_is_blank(Some(construction_period)) {
+        findings.blocking(
+            "construction_period.required",
+            "construction_period",
+            "Décret n° 2015-587 du 29 mai 2015, annexe 1",
+            "La période de construction est obligatoire.",
+        );
+    }
+
+    if construction_period == "avant_1949" && !data.annex_lead_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.lead",
+            "annex_lead_provided",
+            "Code de la santé publique, art. L1334-7",
+            "Le constat de risque d'exposition au plomb est obligatoire pour une construction antérieure à 1949.",
+        );
+    }
+    if data.electrical_installation_over_15y.unwrap_or(false) && !data.annex_electrical_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.electrical",
+            "annex_electrical_provided",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3-3",
+            "Le diagnostic électricité est obligatoire pour une installation de plus de quinze ans.",
+        );
+    }
+    if data.gas_installation_over_15y.unwrap_or(false) && !data.annex_gas_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.gas",
+            "annex_gas_provided",
+            "Loi n° 89-462 du 6 juillet 1989, art. 3-3",
+            "Le diagnostic gaz est obligatoire pour une installation de plus de quinze ans.",
+        );
+    }
+    if data.in_risk_zone.unwrap_or(false) && !data.annex_risk_provided.unwrap_or(false) {
+        findings.blocking(
+            "annex.risks",
+            "annex_risk_provided",
+            "Code de l'environnement, art. L125-5",
+            "L'état des risques est obligatoire en zone à risques.",
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod furniture;
+pub mod lease;
+pub mod report;
+pub mod rules;
+#[cfg(feature = "wasm")]
+mod wasm;
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// The lease cannot be saved while the violation stands.
+pub const SEVERITY_BLOCKING: &str = "blocking";
+/// The lease is saved but flagged non compliant.
+pub const SEVERITY_WARNING: &str = "warning";
+
+/// One broken legal rule, as reported to the lease form and stored on the
+/// lease in `compliance_errors`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceViolation {
+    /// Stable rule identifier, e.g. "deposit.furnished_max".
+    pub code: String,
+    /// `SEVERITY_BLOCKING` or `SEVERITY_WARNING`.
+    pub severity: String,
+    /// Path of the payload field to correct, e.g. "deposit".
+    pub field: String,
+    pub legal_reference: String,
+    /// French message shown to the user.
+    pub message: String,
+}
+
+/// Every violation found on a lease payload, blocking ones first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    /// Version of the dated rules the lease was judged by, e.g.
+    /// "regles-2025-01-01".
+    pub rule_set_version: String,
+    pub compliant: bool,
+    pub blocking: bool,
+    pub violations: Vec<ComplianceViolation>,
+}
+
+impl ComplianceReport {
+    pub fn new(rule_set_version: String, mut violations: Vec<ComplianceViolation>) -> Self {
+        violations.sort_by_key(|violation| violation.severity != SEVERITY_BLOCKING);
+        Self {
+            rule_set_version,
+            compliant: violations.is_empty(),
+            blocking: violations.iter().any(|violation| violation.severity == SEVERITY_BLOCKING),
+            violations,
+        }
+    }
+
+    /// "compliant" or "non_compliant", as stored on the lease.
+    pub fn status(&self) -> &'static str {
+        if self.compliant {
+            "compliant"
+        } else {
+            "non_compliant"
+        }
+    }
+
+    pub fn extend(&mut self, violations: Vec<ComplianceViolation>) {
+        let mut all = std::mem::take(&mut self.violations);
+        all.extend(violations);
+        *self = Self::new(std::mem::take(&mut self.rule_set_version), all);
+    }
+}
@@ -0,0 +1,273 @@
+use chrono::{Months, NaiveDate};
+
+const fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    match NaiveDate::from_ymd_opt(year, month, day) {
+        Some(date) => date,
+        None => panic!("invalid rule date"),
+    }
+}
+
+/// Rules with no earlier version in the catalogue apply from this date.
+const SINCE_LOI_89: NaiveDate = date(1989, 7, 6);
+
+/// One value of a legal threshold, applying from `from` (inclusive) to
+/// `until` (exclusive, open-ended when `None`). A rule lists its successive
+/// values; a lease is judged by those in force on its start date, so a change
+/// in the law is a new row, never an edit of an existing one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InForce<T: 'static> {
+    pub from: NaiveDate,
+    pub until: Option<NaiveDate>,
+    pub value: T,
+}
+
+impl<T> InForce<T> {
+    fn applies_on(&self, on: NaiveDate) -> bool {
+        self.from <= on && self.until.is_none_or(|until| on < until)
+    }
+}
+
+/// Worst DPE rank (1 = A … 7 = G) a dwelling may have to be let in
+/// metropolitan France (décret n° 2002-120, art. 3 bis, loi Climat et
+/// résilience).
+pub const DPE_WORST_RANK_METROPOLE: [InForce<i32>; 4] = [
+    InForce { from: SINCE_LOI_89, until: Some(date(2025, 1, 1)), value: 7 },
+    InForce { from: date(2025, 1, 1), until: Some(date(2028, 1, 1)), value: 6 },
+    InForce { from: date(2028, 1, 1), until: Some(date(2034, 1, 1)), value: 5 },
+    InForce { from: date(2034, 1, 1), until: None, value: 4 },
+];
+
+/// Same threshold in the overseas departments, phased in later.
+pub const DPE_WORST_RANK_DOM_TOM: [InForce<i32>; 3] = [
+    InForce { from: SINCE_LOI_89, until: Some(date(2028, 1, 1)), value: 7 },
+    InForce { from: date(2028, 1, 1), until: Some(date(2031, 1, 1)), value: 6 },
+    InForce { from: date(2031, 1, 1), until: None, value: 5 },
+];
+
+/// Days after the previous tenant left during which the lease must state the
+/// last rent they paid (loi 89, art. 3, from the loi ALUR: eighteen months).
+pub const PREVIOUS_RENT_WINDOW_DAYS: [InForce<i64>; 1] = [
+    InForce { from: date(2014, 3, 27), until: None, value: 548 },
+];
+
+/// Deposit cap of a furnished lease, in months of rent excluding charges
+/// (loi 89, art. 25-6).
+pub const FURNISHED_DEPOSIT_MONTHS: [InForce<i32>; 1] = [
+    InForce { from: date(2014, 3, 27), until: None, value: 2 },
+];
+
+/// Deposit cap of an unfurnished lease, in months of rent excluding charges
+/// (loi 89, art. 22, as amended by the loi du 8 février 2008).
+pub const UNFURNISHED_DEPOSIT_MONTHS: [InForce<i32>; 1] = [
+    InForce { from: date(2008, 2, 9), until: None, value: 1 },
+];
+
+/// Rent rise allowed on a relet in a zone tendue (loi 89, art. 18, and the
+/// yearly décrets since n° 2012-894, now n° 2017-1198): none, except that a
+/// previous rent not revised during the last `unrevised_months` follows the
+/// IRL, improvement works worth at least `works_min_rent_months` of the
+/// previous rent allow a yearly rise of `works_yearly_percent` % of their
+/// cost, and a manifestly undervalued previous rent may rise by
+/// `undervalued_gap_percent` % of its gap to comparable rents nearby.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReletCap {
+    pub unrevised_months: i32,
+    pub works_min_rent_months: i32,
+    pub works_yearly_percent: i32,
+    pub undervalued_gap_percent: i32,
+}
+
+pub const RELET_RENT_CAP: [InForce<ReletCap>; 1] = [InForce {
+    from: date(2012, 8, 1),
+    until: None,
+    value: ReletCap {
+        unrevised_months: 12,
+        works_min_rent_months: 6,
+        works_yearly_percent: 15,
+        undervalued_gap_percent: 50,
+    },
+}];
+
+/// Clause wordings réputées non écrites (loi 89, art. 4), lowercase.
+pub const BANNED_CLAUSE_PATTERNS: [InForce<&str>; 7] = [
+    InForce { from: date(2014, 3, 27), until: None, value: "prélèvement automatique comme seul mode" },
+    InForce { from: date(2014, 3, 27), until: None, value: "prelevement automatique comme seul mode" },
+    InForce { from: SINCE_LOI_89, until: None, value: "interdiction d'heberger" },
+    InForce { from: SINCE_LOI_89, until: None, value: "interdiction d'héberger" },
+    InForce { from: SINCE_LOI_89, until: None, value: "interdiction d’héberger" },
+    InForce { from: SINCE_LOI_89, until: None, value: "frais de quittance" },
+    InForce { from: SINCE_LOI_89, until: None, value: "frais d'envoi de quittance" },
+];
+
+/// A DPE is valid ten years.
+pub const DPE_VALIDITY_YEARS: u32 = 10;
+
+/// Expiry of a DPE. Those carried out before the July 2021 reform lapsed
+/// early: end of 2022 for 2013-2017, end of 2024 for 2018 to June 2021.
+pub fn dpe_expiry(performed_on: NaiveDate) -> NaiveDate {
+    if performed_on >= date(2013, 1, 1) && performed_on <= date(2017, 12, 31) {
+        date(2022, 12, 31)
+    } else if performed_on >= date(2018, 1, 1) && performed_on <= date(2021, 6, 30) {
+        date(2024, 12, 31)
+    } else {
+        performed_on
+            .checked_add_months(Months::new(12 * DPE_VALIDITY_YEARS))
+            .unwrap_or(performed_on)
+    }
+}
+
+fn in_force<T: Copy>(rule: &[InForce<T>], on: NaiveDate) -> Option<InForce<T>> {
+    rule.iter().copied().find(|version| version.applies_on(on))
+}
+
+/// Thresholds in force on one date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSet {
+    /// "regles-<date>", the date of the latest rule change in force, so two
+    /// leases judged by the same law share the same version.
+    pub version: String,
+    pub dpe_worst_rank_metropole: i32,
+    pub dpe_worst_rank_dom_tom: i32,
+    /// `None` before the rule existed.
+    pub previous_rent_window_days: Option<i64>,
+    pub furnished_deposit_months: Option<i32>,
+    pub unfurnished_deposit_months: Option<i32>,
+    pub banned_clause_patterns: Vec<&'static str>,
+    pub relet_cap: Option<ReletCap>,
+}
+
+impl RuleSet {
+    pub fn in_force_on(on: NaiveDate) -> Self {
+        let dpe_metropole = in_force(&DPE_WORST_RANK_METROPOLE, on);
+        let dpe_dom_tom = in_force(&DPE_WORST_RANK_DOM_TOM, on);
+        let previous_rent = in_force(&PREVIOUS_RENT_WINDOW_DAYS, on);
+        let furnished_deposit = in_force(&FURNISHED_DEPOSIT_MONTHS, on);
+        let unfurnished_deposit = in_force(&UNFURNISHED_DEPOSIT_MONTHS, on);
+        let relet_cap = in_force(&RELET_RENT_CAP, on);
+        let banned: Vec<InForce<&str>> = BANNED_CLAUSE_PATTERNS
+            .iter()
+            .copied()
+            .filter(|pattern| pattern.applies_on(on))
+            .collect();
+
+        let latest_change = [
+            dpe_metropole.map(|v| v.from),
+            dpe_dom_tom.map(|v| v.from),
+            previous_rent.map(|v| v.from),
+            furnished_deposit.map(|v| v.from),
+            unfurnished_deposit.map(|v| v.from),
+            relet_cap.map(|v| v.from),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(banned.iter().map(|pattern| pattern.from))
+        .max()
+        .unwrap_or(SINCE_LOI_89);
+
+        Self {
+            version: format!("regles-{}", latest_change),
+            dpe_worst_rank_metropole: dpe_metropole.map_or(7, |v| v.value),
+            dpe_worst_rank_dom_tom: dpe_dom_tom.map_or(7, |v| v.value),
+            previous_rent_window_days: previous_rent.map(|v| v.value),
+            furnished_deposit_months: furnished_deposit.map(|v| v.value),
+            unfurnished_deposit_months: unfurnished_deposit.map(|v| v.value),
+            banned_clause_patterns: banned.into_iter().map(|pattern| pattern.value).collect(),
+            relet_cap: relet_cap.map(|v| v.value),
+        }
+    }
+
+    pub fn dpe_worst_rank(&self, is_dom_tom: bool) -> i32 {
+        if is_dom_tom {
+            self.dpe_worst_rank_dom_tom
+        } else {
+            self.dpe_worst_rank_metropole
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each rule's versions follow each other without gap or overlap.
+    fn assert_contiguous<T>(rule: &[InForce<T>]) {
+        for pair in rule.windows(2) {
+            assert_eq!(pair[0].until, Some(pair[1].from));
+        }
+        assert_eq!(rule.last().and_then(|version| version.until), None);
+    }
+
+    #[test]
+    fn rule_versions_are_contiguous() {
+        assert_contiguous(&DPE_WORST_RANK_METROPOLE);
+        assert_contiguous(&DPE_WORST_RANK_DOM_TOM);
+        assert_contiguous(&PREVIOUS_RENT_WINDOW_DAYS);
+        assert_contiguous(&FURNISHED_DEPOSIT_MONTHS);
+        assert_contiguous(&UNFURNISHED_DEPOSIT_MONTHS);
+        assert_contiguous(&RELET_RENT_CAP);
+    }
+
+    #[test]
+    fn dpe_threshold_tightens_on_each_milestone() {
+        let rank = |y, m, d, dom_tom| RuleSet::in_force_on(date(y, m, d)).dpe_worst_rank(dom_tom);
+        assert_eq!(rank(2024, 12, 31, false), 7);
+        assert_eq!(rank(2025, 1, 1, false), 6);
+        assert_eq!(rank(2028, 1, 1, false), 5);
+        assert_eq!(rank(2033, 12, 31, false), 5);
+        assert_eq!(rank(2034, 1, 1, false), 4);
+
+        assert_eq!(rank(2027, 12, 31, true), 7);
+        assert_eq!(rank(2028, 1, 1, true), 6);
+        assert_eq!(rank(2031, 1, 1, true), 5);
+    }
+
+    #[test]
+    fn previous_rent_window_applies_since_alur() {
+        assert_eq!(RuleSet::in_force_on(date(2014, 3, 26)).previous_rent_window_days, None);
+        assert_eq!(RuleSet::in_force_on(date(2014, 3, 27)).previous_rent_window_days, Some(548));
+    }
+
+    #[test]
+    fn deposit_caps_follow_their_laws() {
+        let before_2008 = RuleSet::in_force_on(date(2007, 6, 1));
+        assert_eq!(before_2008.unfurnished_deposit_months, None);
+        assert_eq!(before_2008.furnished_deposit_months, None);
+
+        let today = RuleSet::in_force_on(date(2026, 10, 17));
+        assert_eq!(today.unfurnished_deposit_months, Some(1));
+        assert_eq!(today.furnished_deposit_months, Some(2));
+    }
+
+    #[test]
+    fn relet_cap_applies_since_the_first_decret() {
+        assert_eq!(RuleSet::in_force_on(date(2012, 7, 31)).relet_cap, None);
+        let cap = RuleSet::in_force_on(date(2026, 1, 1)).relet_cap.expect("in force");
+        assert_eq!((cap.works_min_rent_months, cap.works_yearly_percent), (6, 15));
+    }
+
+    #[test]
+    fn banned_clauses_depend_on_the_date() {
+        let before_alur = RuleSet::in_force_on(date(2010, 1, 1));
+        assert!(before_alur.banned_clause_patterns.contains(&"frais de quittance"));
+        assert!(!before_alur.banned_clause_patterns.contains(&"prelevement automatique comme seul mode"));
+        assert_eq!(RuleSet::in_force_on(date(2026, 1, 1)).banned_clause_patterns.len(), 7);
+    }
+
+    #[test]
+    fn dpe_validity_follows_the_2021_reform() {
+        assert_eq!(dpe_expiry(date(2015, 6, 1)), date(2022, 12, 31));
+        assert_eq!(dpe_expiry(date(2021, 6, 30)), date(2024, 12, 31));
+        assert_eq!(dpe_expiry(date(2021, 7, 1)), date(2031, 7, 1));
+    }
+
+    #[test]
+    fn version_names_the_latest_change_in_force() {
+        assert_eq!(RuleSet::in_force_on(date(2020, 5, 1)).version, "regles-2014-03-27");
+        assert_eq!(RuleSet::in_force_on(date(2026, 6, 1)).version, "regles-2025-01-01");
+        assert_eq!(RuleSet::in_force_on(date(2029, 6, 1)).version, "regles-2028-01-01");
+        assert_eq!(
+            RuleSet::in_force_on(date(2026, 6, 1)),
+            RuleSet::in_force_on(date(2027, 12, 31))
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::lease::{lease_compliance_report, LeaseTerms};
+
+/// Same report as the backend's, for the lease form. `payload` is the
+/// `CreateLease` object the form is about to submit; the result is a
+/// `ComplianceReport`.
+#[wasm_bindgen(js_name = leaseComplianceReport)]
+pub fn lease_compliance_report_js(
+    payload: JsValue,
+    lease_type: &str,
+    landlord_is_legal_person: bool,
+) -> Result<JsValue, JsError> {
+    let terms: LeaseTerms = serde_wasm_bindgen::from_value(payload)?;
+    let report = lease_compliance_report(&terms, lease_type, landlord_is_legal_person);
+    Ok(report.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
@@ -0,0 +1,66 @@
+use quittance_compliance::lease::{lease_compliance_report, LeaseTerms};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// The cases the frontend's `leaseCompliance.test.ts` runs against the wasm
+/// build of this crate; both suites read the same file so they cannot drift.
+const CASES: &str = include_str!("../../../frontend/src/utils/leaseCompliance.cases.json");
+
+#[derive(Deserialize)]
+struct Fixture {
+    base: Map<String, Value>,
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    lease_type: Option<String>,
+    #[serde(default)]
+    landlord_is_legal_person: bool,
+    #[serde(default)]
+    payload: Map<String, Value>,
+    /// Exact list of violation codes, in report order.
+    codes: Option<Vec<String>>,
+    #[serde(default)]
+    includes: Vec<String>,
+    #[serde(default)]
+    excludes: Vec<String>,
+    /// Expected message of the violation with each code.
+    #[serde(default)]
+    messages: BTreeMap<String, String>,
+}
+
+#[test]
+fn engine_matches_the_shared_frontend_cases() {
+    let fixture: Fixture = serde_json::from_str(CASES).expect("valid fixture");
+    assert!(!fixture.cases.is_empty());
+
+    for case in fixture.cases {
+        let mut payload = fixture.base.clone();
+        payload.extend(case.payload);
+        let terms: LeaseTerms = serde_json::from_value(Value::Object(payload))
+            .unwrap_or_else(|err| panic!("{}: invalid payload: {}", case.name, err));
+        let report = lease_compliance_report(
+            &terms,
+            case.lease_type.as_deref().unwrap_or("furnished"),
+            case.landlord_is_legal_person,
+        );
+        let codes: Vec<&str> = report.violations.iter().map(|v| v.code.as_str()).collect();
+
+        if let Some(expected) = &case.codes {
+            assert_eq!(&codes, expected, "{}", case.name);
+        }
+        for code in &case.includes {
+            assert!(codes.contains(&code.as_str()), "{}: missing {} in {:?}", case.name, code, codes);
+        }
+        for code in &case.excludes {
+            assert!(!codes.contains(&code.as_str()), "{}: unexpected {} in {:?}", case.name, code, codes);
+        }
+        for (code, message) in &case.messages {
+            let violation = report.violations.iter().find(|v| &v.code == code);
+            assert_eq!(violation.map(|v| v.message.as_str()), Some(message.as_str()), "{}: {}", case.name, code);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+-- Add snapshot JSONB column to receipts table
+-- Stores the frozen quittance content (parties, property, period, amounts,
+-- template version) captured when the receipt is issued or marked paid.
+
+ALTER TABLE receipts
+ADD COLUMN snapshot JSONB DEFAULT NULL;
+
+-- Index on snapshot for querying by template_version
+CREATE INDEX idx_receipts_snapshot_template_version
+ON receipts USING GIN ((snapshot -> 'template_version'));
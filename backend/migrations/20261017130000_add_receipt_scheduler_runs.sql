@@ -0,0 +1,10 @@
+-- Track monthly runs of the background receipt scheduler so that a month is
+-- issued only once, even across server restarts or multiple instances.
+
+CREATE TABLE receipt_scheduler_runs (
+    run_year INTEGER NOT NULL,
+    run_month INTEGER NOT NULL CHECK (run_month BETWEEN 1 AND 12),
+    issued_count INTEGER NOT NULL DEFAULT 0,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (run_year, run_month)
+);
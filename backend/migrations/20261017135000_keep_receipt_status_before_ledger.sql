@@ -0,0 +1,8 @@
+-- Keep each receipt's status from before the payments ledger, whose backfill
+-- (20261017140000) marks every existing receipt paid. 20261017410000 uses it
+-- to take the backfilled payment back from receipts nobody had marked paid.
+-- On a database that already applied the ledger every receipt reads 'paid'
+-- here, and the backfill is kept as it was.
+ALTER TABLE receipts ADD COLUMN status_before_ledger VARCHAR(50);
+
+UPDATE receipts SET status_before_ledger = status;
@@ -0,0 +1,57 @@
+-- Payments ledger: every amount received for a lease, allocated against the
+-- receipts (due periods) it settles. A quittance may only be issued for a
+-- fully settled period; a partially paid period gets a reçu.
+
+CREATE TABLE payments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    -- Colocataire who paid (NULL for direct CAF payments or unknown payer)
+    payer_tenant_id UUID REFERENCES tenants(id) ON DELETE SET NULL,
+    -- Period the payer asked to settle (imputation); NULL allocates to the oldest due period
+    receipt_id UUID REFERENCES receipts(id) ON DELETE SET NULL,
+    amount DECIMAL(10, 2) NOT NULL CHECK (amount > 0),
+    payment_date DATE NOT NULL,
+    -- 'autre' only exists for payments backfilled from receipts issued before the ledger
+    method VARCHAR(20) NOT NULL CHECK (method IN ('virement', 'cheque', 'especes', 'caf', 'autre')),
+    reference VARCHAR(255),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_payments_lease_id ON payments(lease_id);
+CREATE INDEX idx_payments_payment_date ON payments(payment_date);
+
+CREATE TABLE payment_allocations (
+    payment_id UUID NOT NULL REFERENCES payments(id) ON DELETE CASCADE,
+    receipt_id UUID NOT NULL REFERENCES receipts(id) ON DELETE CASCADE,
+    amount DECIMAL(10, 2) NOT NULL CHECK (amount > 0),
+    PRIMARY KEY (payment_id, receipt_id)
+);
+
+CREATE INDEX idx_payment_allocations_receipt_id ON payment_allocations(receipt_id);
+
+ALTER TABLE receipts
+ADD COLUMN amount_paid DECIMAL(10, 2) NOT NULL DEFAULT 0;
+
+ALTER TABLE receipts DROP CONSTRAINT IF EXISTS receipts_status_check;
+ALTER TABLE receipts
+ADD CONSTRAINT receipts_status_check
+CHECK (status IN ('generated', 'sent', 'partially_paid', 'paid'));
+
+-- Backfill: until now every receipt was issued as a quittance for rent the
+-- landlord had already received. Record that payment so existing quittances
+-- stay valid under the ledger.
+INSERT INTO payments (lease_id, receipt_id, amount, payment_date, method, reference)
+SELECT lease_id, id, total_amount, payment_date, 'autre', 'Reprise des quittances antérieures au registre des paiements'
+FROM receipts
+WHERE total_amount > 0;
+
+INSERT INTO payment_allocations (payment_id, receipt_id, amount)
+SELECT id, receipt_id, amount
+FROM payments
+WHERE receipt_id IS NOT NULL;
+
+UPDATE receipts
+SET amount_paid = total_amount,
+    status = 'paid'
+WHERE total_amount > 0;
@@ -0,0 +1,36 @@
+-- Avis d'échéance (rent calls): what the tenants owe for a period, sent
+-- ahead of its due date. Each notice is closed by the receipt of the same
+-- period once that period is fully paid.
+
+CREATE TABLE rent_calls (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    period_month INTEGER NOT NULL CHECK (period_month BETWEEN 1 AND 12),
+    period_year INTEGER NOT NULL CHECK (period_year >= 2020),
+    due_date DATE NOT NULL,
+    base_rent DECIMAL(10, 2) NOT NULL,
+    charges DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    -- Balancing line from an annual charges regularisation (may be negative)
+    regularisation DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    -- Outstanding balance of earlier periods when the notice was issued
+    arrears DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    total_due DECIMAL(10, 2) GENERATED ALWAYS AS (base_rent + charges + regularisation + arrears) STORED,
+    status VARCHAR(20) NOT NULL DEFAULT 'issued' CHECK (status IN ('issued', 'sent', 'settled')),
+    -- Receipt that closed this notice (set once the period is fully paid)
+    receipt_id UUID REFERENCES receipts(id) ON DELETE SET NULL,
+    snapshot JSONB DEFAULT NULL,
+    pdf_path VARCHAR(500),
+    email_sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_rent_call_per_period UNIQUE (lease_id, period_month, period_year)
+);
+
+CREATE INDEX idx_rent_calls_lease_id ON rent_calls(lease_id);
+CREATE INDEX idx_rent_calls_period ON rent_calls(period_year, period_month);
+
+-- Email logs now cover rent calls as well as receipts.
+ALTER TABLE email_logs
+ADD COLUMN rent_call_id UUID REFERENCES rent_calls(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_email_logs_rent_call_id ON email_logs(rent_call_id);
@@ -0,0 +1,34 @@
+-- IRL (Indice de Référence des Loyers) quarterly values published by INSEE,
+-- loaded from data/irl.csv (or IRL_DATA_FILE) at startup.
+CREATE TABLE irl_indices (
+    year INTEGER NOT NULL CHECK (year >= 2000),
+    quarter INTEGER NOT NULL CHECK (quarter BETWEEN 1 AND 4),
+    value DECIMAL(8, 2) NOT NULL CHECK (value > 0),
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (year, quarter)
+);
+
+-- IRL quarter named in the lease (the index value known at signature).
+ALTER TABLE leases
+ADD COLUMN irl_reference_quarter INTEGER CHECK (irl_reference_quarter BETWEEN 1 AND 4),
+ADD COLUMN irl_reference_year INTEGER;
+
+-- Dated rent changes of a lease. Receipts use the latest entry in force for
+-- their period; without any entry, the lease's monthly_rent and charges apply.
+CREATE TABLE lease_rent_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    effective_date DATE NOT NULL,
+    monthly_rent DECIMAL(10, 2) NOT NULL,
+    charges DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    reason VARCHAR(30) NOT NULL CHECK (reason IN ('irl_revision')),
+    previous_rent DECIMAL(10, 2),
+    irl_year INTEGER,
+    irl_quarter INTEGER CHECK (irl_quarter BETWEEN 1 AND 4),
+    irl_value DECIMAL(8, 2),
+    previous_irl_value DECIMAL(8, 2),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_rent_change_per_date UNIQUE (lease_id, effective_date)
+);
+
+CREATE INDEX idx_lease_rent_history_lease_id ON lease_rent_history(lease_id, effective_date);
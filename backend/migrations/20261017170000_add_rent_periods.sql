@@ -0,0 +1,14 @@
+-- Every lease now carries its full rent history: an 'initial' period at the
+-- start date with the contractual amounts, then IRL revisions and manual
+-- adjustments. leases.monthly_rent and charges remain the signed terms.
+ALTER TABLE lease_rent_history DROP CONSTRAINT lease_rent_history_reason_check;
+ALTER TABLE lease_rent_history
+ADD CONSTRAINT lease_rent_history_reason_check
+    CHECK (reason IN ('initial', 'irl_revision', 'adjustment'));
+
+ALTER TABLE lease_rent_history ADD COLUMN note TEXT;
+
+INSERT INTO lease_rent_history (lease_id, effective_date, monthly_rent, charges, reason)
+SELECT id, start_date, monthly_rent, charges, 'initial'
+FROM leases
+ON CONFLICT (lease_id, effective_date) DO NOTHING;
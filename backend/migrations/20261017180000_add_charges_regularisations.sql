@@ -0,0 +1,47 @@
+-- Yearly regularisation of recoverable charges for leases paying provisions.
+-- Actual expenses are recorded by décret 87-713 category, prorated to the
+-- tenant's occupancy and compared with the provisions called on receipts.
+CREATE TABLE charges_regularisations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    year INTEGER NOT NULL CHECK (year >= 2000),
+    occupancy_start DATE NOT NULL,
+    occupancy_end DATE NOT NULL,
+    total_expenses DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    tenant_share DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    provisions_collected DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    -- Positive: the tenant owes the difference; negative: the landlord refunds it
+    balance DECIMAL(10, 2) GENERATED ALWAYS AS (tenant_share - provisions_collected) STORED,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft'
+        CHECK (status IN ('draft', 'finalized', 'applied')),
+    -- Avis d'échéance carrying the balancing line once applied
+    rent_call_id UUID REFERENCES rent_calls(id) ON DELETE SET NULL,
+    snapshot JSONB,
+    pdf_path TEXT,
+    finalized_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_regularisation_per_year UNIQUE (lease_id, year)
+);
+
+CREATE INDEX idx_charges_regularisations_lease_id ON charges_regularisations(lease_id);
+
+CREATE TABLE charges_regularisation_expenses (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    regularisation_id UUID NOT NULL REFERENCES charges_regularisations(id) ON DELETE CASCADE,
+    category VARCHAR(40) NOT NULL CHECK (category IN (
+        'ascenseurs', 'eau_chauffage_collectif', 'installations_individuelles',
+        'parties_communes', 'espaces_exterieurs', 'hygiene',
+        'equipements_divers', 'impositions_redevances'
+    )),
+    label TEXT NOT NULL,
+    amount DECIMAL(10, 2) NOT NULL CHECK (amount >= 0),
+    -- Period the expense relates to (the building's accounting period)
+    covered_start DATE NOT NULL,
+    covered_end DATE NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CHECK (covered_start <= covered_end)
+);
+
+CREATE INDEX idx_charges_regularisation_expenses_reg_id
+    ON charges_regularisation_expenses(regularisation_id);
@@ -0,0 +1,44 @@
+-- Security deposit (dépôt de garantie) held for a lease: collection, exit,
+-- itemised deductions and return within the deadline of article 22 of the
+-- loi du 6 juillet 1989.
+CREATE TABLE lease_deposits (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL UNIQUE REFERENCES leases(id) ON DELETE CASCADE,
+    amount DECIMAL(10, 2) NOT NULL CHECK (amount >= 0),
+    received_on DATE NOT NULL,
+    received_method VARCHAR(20) NOT NULL DEFAULT 'virement'
+        CHECK (received_method IN ('virement', 'cheque', 'especes', 'prelevement', 'autre')),
+    -- Exit: keys handed back and whether the exit inventory matches the entry one
+    keys_returned_on DATE,
+    exit_inventory_conforms BOOLEAN,
+    return_deadline DATE,
+    returned_on DATE,
+    returned_amount DECIMAL(10, 2),
+    late_penalty DECIMAL(10, 2),
+    status VARCHAR(20) NOT NULL DEFAULT 'held'
+        CHECK (status IN ('held', 'awaiting_return', 'returned')),
+    snapshot JSONB,
+    pdf_path TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_lease_deposits_return_deadline
+    ON lease_deposits(return_deadline) WHERE status = 'awaiting_return';
+
+CREATE TABLE deposit_deductions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    deposit_id UUID NOT NULL REFERENCES lease_deposits(id) ON DELETE CASCADE,
+    category VARCHAR(30) NOT NULL CHECK (category IN (
+        'repairs', 'unpaid_rent', 'unpaid_charges', 'charges_provision', 'other'
+    )),
+    label TEXT NOT NULL,
+    amount DECIMAL(10, 2) NOT NULL CHECK (amount > 0),
+    -- Unpaid period this deduction settles, for unpaid rent or charges
+    receipt_id UUID REFERENCES receipts(id) ON DELETE SET NULL,
+    -- Quote, invoice or exit inventory reference backing a repair
+    justification TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_deposit_deductions_deposit_id ON deposit_deductions(deposit_id);
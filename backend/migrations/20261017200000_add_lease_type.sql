@@ -0,0 +1,11 @@
+-- Contract regime of the lease: furnished (loi 89, titre I bis) or
+-- unfurnished (location nue, titre I). Existing leases were all issued on
+-- the furnished template.
+ALTER TABLE leases
+ADD COLUMN lease_type VARCHAR(20) NOT NULL DEFAULT 'furnished'
+    CHECK (lease_type IN ('furnished', 'unfurnished'));
+
+-- A student lease only exists for furnished dwellings.
+ALTER TABLE leases
+ADD CONSTRAINT leases_student_lease_furnished
+    CHECK (lease_kind <> 'student' OR lease_type = 'furnished');
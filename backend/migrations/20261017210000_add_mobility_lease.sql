@@ -0,0 +1,23 @@
+-- Bail mobilité (loi 89, titre Ier ter): furnished, 1 to 10 months, no
+-- deposit, and the tenant's situation must be stated in the contract.
+ALTER TABLE leases DROP CONSTRAINT leases_lease_kind_check;
+ALTER TABLE leases
+ADD CONSTRAINT leases_lease_kind_check
+    CHECK (lease_kind IN ('standard', 'student', 'mobility'));
+
+ALTER TABLE leases
+ADD COLUMN mobility_reason VARCHAR(40)
+    CHECK (mobility_reason IN (
+        'formation_professionnelle',
+        'etudes_superieures',
+        'apprentissage',
+        'stage',
+        'service_civique',
+        'mutation_professionnelle',
+        'mission_temporaire'
+    )),
+ADD COLUMN mobility_justification TEXT;
+
+ALTER TABLE leases
+ADD CONSTRAINT leases_mobility_lease_furnished
+    CHECK (lease_kind <> 'mobility' OR (lease_type = 'furnished' AND mobility_reason IS NOT NULL));
@@ -0,0 +1,15 @@
+-- Parking spaces, garages and storage units let on their own fall outside
+-- the loi du 6 juillet 1989 and are leased under the code civil.
+ALTER TABLE properties DROP CONSTRAINT properties_property_type_check;
+ALTER TABLE properties
+ADD CONSTRAINT properties_property_type_check
+    CHECK (property_type IN ('apartment', 'house', 'studio', 'other', 'parking', 'garage', 'storage'));
+
+ALTER TABLE leases DROP CONSTRAINT leases_lease_type_check;
+ALTER TABLE leases
+ADD CONSTRAINT leases_lease_type_check
+    CHECK (lease_type IN ('furnished', 'unfurnished', 'ancillary'));
+
+ALTER TABLE leases
+ADD CONSTRAINT leases_ancillary_lease_standard
+    CHECK (lease_type <> 'ancillary' OR lease_kind = 'standard');
@@ -0,0 +1,18 @@
+-- Explicit lease lifecycle: draft -> ready_for_signature -> signed -> active
+-- -> notice_given -> terminated -> archived. Transitions are validated by the
+-- server; the canonical snapshot is frozen from signature onwards.
+ALTER TABLE leases DROP CONSTRAINT leases_status_check;
+
+UPDATE leases SET status = 'terminated' WHERE status = 'expired';
+
+ALTER TABLE leases ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE leases
+ADD CONSTRAINT leases_status_check
+    CHECK (status IN ('draft', 'ready_for_signature', 'signed', 'active', 'notice_given', 'terminated', 'archived'));
+
+ALTER TABLE leases
+ADD COLUMN signed_at TIMESTAMPTZ,
+ADD COLUMN status_changed_at TIMESTAMPTZ;
+
+-- Leases already in force were signed before the lifecycle existed.
+UPDATE leases SET signed_at = COALESCE(created_at, CURRENT_TIMESTAMP) WHERE status IN ('active', 'terminated');
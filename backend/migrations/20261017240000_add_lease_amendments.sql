@@ -0,0 +1,21 @@
+-- Avenants to signed leases. Each stores its field-level diff against the
+-- contract as it stood (the signed snapshot plus earlier avenants), the
+-- frozen avenant document and the date the change takes effect.
+CREATE TABLE lease_amendments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    sequence_number INTEGER NOT NULL CHECK (sequence_number > 0),
+    effective_date DATE NOT NULL,
+    summary TEXT NOT NULL,
+    changes JSONB NOT NULL,
+    snapshot JSONB,
+    pdf_path TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (lease_id, sequence_number)
+);
+
+-- A rent or charges change agreed by avenant is billed from its effective date.
+ALTER TABLE lease_rent_history DROP CONSTRAINT lease_rent_history_reason_check;
+ALTER TABLE lease_rent_history
+ADD CONSTRAINT lease_rent_history_reason_check
+    CHECK (reason IN ('initial', 'irl_revision', 'adjustment', 'amendment'));
@@ -0,0 +1,24 @@
+-- Every canonical snapshot generated for a lease, so the contract as it stood
+-- on any date can be produced and compared with later versions.
+CREATE TABLE lease_snapshots (
+    id UUID PRIMARY KEY,
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    legal_template_version VARCHAR(50) NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL,
+    snapshot JSONB NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_lease_snapshots_lease_generated ON lease_snapshots(lease_id, generated_at);
+
+-- Seed the history with the snapshot each lease currently holds.
+INSERT INTO lease_snapshots (id, lease_id, legal_template_version, generated_at, snapshot)
+SELECT
+    COALESCE((canonical_snapshot ->> 'id')::UUID, gen_random_uuid()),
+    id,
+    COALESCE(canonical_snapshot ->> 'legal_template_version', 'unknown'),
+    COALESCE((canonical_snapshot ->> 'generated_at')::TIMESTAMPTZ, updated_at, CURRENT_TIMESTAMP),
+    canonical_snapshot
+FROM leases
+WHERE canonical_snapshot IS NOT NULL
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,25 @@
+-- Congés served by the tenant or the landlord. The effective date computed
+-- from the notice period replaces the lease end date for billing while the
+-- notice is active; withdrawing it puts the lease back in force.
+CREATE TABLE lease_notices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    given_by VARCHAR(20) NOT NULL CHECK (given_by IN ('tenant', 'landlord')),
+    served_on DATE NOT NULL,
+    notice_months INTEGER NOT NULL CHECK (notice_months > 0),
+    effective_date DATE NOT NULL,
+    ground VARCHAR(30) CHECK (ground IN ('vente', 'reprise', 'motif_legitime')),
+    reduced_notice_reason VARCHAR(30),
+    beneficiary_name TEXT,
+    beneficiary_relationship TEXT,
+    sale_price DECIMAL(12, 2),
+    details TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'withdrawn')),
+    snapshot JSONB,
+    pdf_path TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_one_active_notice_per_lease
+    ON lease_notices (lease_id)
+    WHERE status = 'active';
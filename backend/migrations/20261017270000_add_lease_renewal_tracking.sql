@@ -0,0 +1,7 @@
+-- Diagnostic dates drive their expiry deadlines; last_renewed_on records the
+-- latest tacit renewal, when end_date was rolled forward to the next term.
+ALTER TABLE leases
+ADD COLUMN dpe_date DATE,
+ADD COLUMN electrical_diagnostic_date DATE,
+ADD COLUMN gas_diagnostic_date DATE,
+ADD COLUMN last_renewed_on DATE;
@@ -0,0 +1,18 @@
+-- États des lieux: one entry and one exit inventory per lease. Rooms with
+-- their rated elements, meter readings and keys are stored as JSON documents.
+-- Saving an inventory refreshes its document content and drops the stored PDF.
+CREATE TABLE lease_inventories (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('entry', 'exit')),
+    performed_on DATE NOT NULL,
+    rooms JSONB NOT NULL DEFAULT '[]',
+    meters JSONB NOT NULL DEFAULT '[]',
+    keys JSONB NOT NULL DEFAULT '[]',
+    general_comment TEXT,
+    snapshot JSONB,
+    pdf_path TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (lease_id, kind)
+);
@@ -0,0 +1,21 @@
+-- Store the structured compliance report (rule code, severity, field, legal
+-- reference, message) instead of plain messages.
+ALTER TABLE leases ADD COLUMN compliance_report JSONB NOT NULL DEFAULT '[]';
+
+UPDATE leases l
+SET compliance_report = (
+    SELECT COALESCE(
+        jsonb_agg(jsonb_build_object(
+            'code', 'legacy',
+            'severity', 'warning',
+            'field', '',
+            'legal_reference', '',
+            'message', e.message
+        )),
+        '[]'::jsonb
+    )
+    FROM unnest(l.compliance_errors) AS e(message)
+);
+
+ALTER TABLE leases DROP COLUMN compliance_errors;
+ALTER TABLE leases RENAME COLUMN compliance_report TO compliance_errors;
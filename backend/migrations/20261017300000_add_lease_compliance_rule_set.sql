@@ -0,0 +1,2 @@
+-- Version of the dated compliance rules the lease was last judged by.
+ALTER TABLE leases ADD COLUMN compliance_rule_set VARCHAR(40);
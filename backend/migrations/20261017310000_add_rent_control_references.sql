@@ -0,0 +1,20 @@
+-- Reference rents fixed by préfectoral arrêté where rents are capped
+-- (encadrement des loyers), loaded from data/rent_control.csv (or
+-- RENT_CONTROL_DATA_FILE) at startup. Amounts are per m² of habitable surface
+-- and per month; the highest room_count of a city covers larger dwellings.
+CREATE TABLE rent_control_references (
+    city VARCHAR(100) NOT NULL,
+    zone VARCHAR(20) NOT NULL,
+    room_count INTEGER NOT NULL CHECK (room_count >= 1),
+    construction_period VARCHAR(20) NOT NULL,
+    furnished BOOLEAN NOT NULL,
+    valid_from DATE NOT NULL,
+    reference_rent DECIMAL(8, 2) NOT NULL CHECK (reference_rent > 0),
+    reference_rent_majorated DECIMAL(8, 2) NOT NULL,
+    reference_rent_minorated DECIMAL(8, 2) NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (city, zone, room_count, construction_period, furnished, valid_from)
+);
+
+-- Zone of the arrêté the property lies in (secteur géographique).
+ALTER TABLE properties ADD COLUMN rent_control_zone VARCHAR(20);
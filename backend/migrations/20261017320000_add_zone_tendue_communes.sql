@@ -0,0 +1,14 @@
+-- Communes of the zones tendues (décret n° 2013-392, annex as amended),
+-- loaded from data/zones_tendues.csv (or ZONE_TENDUE_DATA_FILE) at startup.
+-- Communes are keyed by the département part of their postcode and their
+-- normalized name, as read from a property address.
+CREATE TABLE zone_tendue_communes (
+    departement VARCHAR(3) NOT NULL,
+    commune VARCHAR(100) NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (departement, commune)
+);
+
+-- Classification of the property address against that list, refreshed when
+-- the property is saved and when the list is reloaded.
+ALTER TABLE properties ADD COLUMN zone_tendue BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+-- The issued quittance or reçu PDF, kept in the database so every download
+-- serves the same bytes even when the server's disk is ephemeral. pdf_path
+-- only remains for documents stored on disk before this column existed.
+ALTER TABLE receipts ADD COLUMN pdf_data BYTEA;
@@ -0,0 +1,6 @@
+-- Messages written by the file or stdout transport never reach the tenant
+-- and are recorded as 'logged' rather than 'sent'.
+ALTER TABLE email_logs DROP CONSTRAINT email_logs_status_check;
+ALTER TABLE email_logs
+ADD CONSTRAINT email_logs_status_check
+    CHECK (status IN ('pending', 'sent', 'logged', 'failed'));
@@ -0,0 +1,12 @@
+-- Track each lease's part of a monthly scheduler run. A month is recorded in
+-- receipt_scheduler_runs only once every lease went through, so a lease that
+-- failed is retried on the next check without re-running the others.
+
+CREATE TABLE receipt_scheduler_lease_runs (
+    lease_id UUID NOT NULL REFERENCES leases(id) ON DELETE CASCADE,
+    run_year INTEGER NOT NULL,
+    run_month INTEGER NOT NULL CHECK (run_month BETWEEN 1 AND 12),
+    issued_count INTEGER NOT NULL DEFAULT 0,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (lease_id, run_year, run_month)
+);
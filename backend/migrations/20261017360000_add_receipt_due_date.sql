@@ -0,0 +1,7 @@
+-- Keep the due date of each period apart from payment_date, which now holds
+-- the date of the last payment allocated to it once the ledger credits it.
+ALTER TABLE receipts ADD COLUMN due_date DATE;
+
+UPDATE receipts SET due_date = payment_date;
+
+ALTER TABLE receipts ALTER COLUMN due_date SET NOT NULL;
@@ -0,0 +1,9 @@
+-- Unpaid rent or charges kept from the deposit are paid out of it: record
+-- them on the payments ledger so the period stops counting as arrears.
+ALTER TABLE payments DROP CONSTRAINT payments_method_check;
+ALTER TABLE payments
+ADD CONSTRAINT payments_method_check
+    CHECK (method IN ('virement', 'cheque', 'especes', 'caf', 'autre', 'depot_garantie'));
+
+ALTER TABLE deposit_deductions
+ADD COLUMN payment_id UUID REFERENCES payments(id) ON DELETE SET NULL;
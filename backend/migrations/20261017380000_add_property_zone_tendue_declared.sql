@@ -0,0 +1,4 @@
+-- The bundled list of the zones tendues may miss a commune: let the owner
+-- classify the property by hand. NULL keeps the classification from the list.
+ALTER TABLE properties
+ADD COLUMN zone_tendue_declared BOOLEAN;
@@ -0,0 +1,7 @@
+-- Inputs of the relet cap exceptions of décret n° 2017-1198: a previous rent
+-- not revised in the last twelve months may follow the IRL since its last
+-- revision, and a manifestly undervalued one may close half its gap to the
+-- rents of comparable dwellings nearby.
+ALTER TABLE leases ADD COLUMN previous_rent_revised_on DATE;
+ALTER TABLE leases ADD COLUMN previous_rent_irl DECIMAL(8, 2);
+ALTER TABLE leases ADD COLUMN comparable_rent DECIMAL(10, 2);
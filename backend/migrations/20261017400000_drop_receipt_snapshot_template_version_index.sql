@@ -0,0 +1,2 @@
+-- Nothing queries snapshots by template version.
+DROP INDEX IF EXISTS idx_receipts_snapshot_template_version;
@@ -0,0 +1,29 @@
+-- The payments ledger backfill recorded a payment for every receipt issued
+-- before it, paid or not. Only receipts already marked paid were quittances
+-- for rent the landlord had received: take the backfilled payment back from
+-- the others, unless a document was issued for them since.
+DELETE FROM payments p
+USING receipts r
+WHERE p.receipt_id = r.id
+  AND p.method = 'autre'
+  AND p.reference = 'Reprise des quittances antérieures au registre des paiements'
+  AND r.status_before_ledger <> 'paid'
+  AND r.pdf_data IS NULL;
+
+UPDATE receipts r
+SET amount_paid = allocated.amount,
+    status = CASE
+        WHEN allocated.amount >= r.total_amount THEN 'paid'
+        WHEN allocated.amount > 0 THEN 'partially_paid'
+        ELSE r.status_before_ledger
+    END
+FROM (
+    SELECT r.id, COALESCE(SUM(a.amount), 0) AS amount
+    FROM receipts r
+    LEFT JOIN payment_allocations a ON a.receipt_id = r.id
+    WHERE r.status_before_ledger <> 'paid' AND r.pdf_data IS NULL
+    GROUP BY r.id
+) allocated
+WHERE r.id = allocated.id;
+
+ALTER TABLE receipts DROP COLUMN status_before_ledger;
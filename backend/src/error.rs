@@ -23,6 +23,9 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
     
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    
     #[error("Internal server error")]
     Internal,
 }
@@ -38,6 +41,7 @@ impl IntoResponse for AppError {
             AppError::NotFound(ref msg) => (StatusCode::NOT_FOUND, msg.clone()),
             AppError::BadRequest(ref msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             AppError::Validation(ref msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::Conflict(ref msg) => (StatusCode::CONFLICT, msg.clone()),
             AppError::Internal => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Internal server error".to_string(),
@@ -40,6 +40,31 @@ async fn main() {
     // Run migrations
     database.run_migrations().await.expect("Failed to run migrations");
 
+    // Refuse to start with an unusable mail configuration rather than
+    // failing on the first send, and say so when receipts won't be delivered
+    match services::mailer::Mailer::from_env() {
+        Ok(mailer) if !mailer.delivers() => tracing::warn!(
+            "Outgoing mail is only logged, not delivered: set SMTP_HOST (and MAIL_TRANSPORT=smtp) to email receipts"
+        ),
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("{}. Fix the MAIL_TRANSPORT/SMTP_* variables or set MAIL_TRANSPORT=stdout", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Refresh the IRL series, reference rents and zones tendues from the
+    // bundled data files
+    services::irl::sync_from_data_file(&database).await;
+    services::rent_control::sync_from_data_file(&database).await;
+    services::zone_tendue::sync_from_data_file(&database).await;
+
+    // Issue each active lease's receipts every month in the background
+    services::receipt_scheduler::spawn(
+        database.clone(),
+        services::receipt_scheduler::SchedulerConfig::from_env(),
+    );
+
     // Create API router
     let api_router = Router::new()
         .route("/health", get(health_check))
@@ -49,6 +74,11 @@ async fn main() {
         .nest("/tenants", routes::tenants::router())
         .nest("/leases", routes::leases::router())
         .nest("/receipts", routes::receipts::router())
+        .nest("/payments", routes::payments::router())
+        .nest("/rent-calls", routes::rent_calls::router())
+        .nest("/irl", routes::irl::router())
+        .nest("/regularisations", routes::regularisations::router())
+        .nest("/deposits", routes::deposits::router())
         .with_state(database);
 
     // Determine frontend path (different in dev vs production)
@@ -70,6 +70,10 @@ fn default_landlord_kind() -> String {
     "natural".to_string()
 }
 
+fn default_lease_type() -> String {
+    "furnished".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LesseeParty {
     pub full_name: String,
@@ -119,7 +123,15 @@ pub struct PropertySection {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaseTermsSection {
-    pub lease_kind: String,  // "standard", "student"
+    pub lease_kind: String,  // "standard", "student", "mobility"
+    #[serde(default = "default_lease_type")]
+    pub lease_type: String,  // "furnished", "unfurnished", "ancillary"
+    /// Bail mobilité: tenant situation code and the supporting details stated
+    /// in the contract (loi 89, art. 25-13).
+    #[serde(default)]
+    pub mobility_reason: Option<String>,
+    #[serde(default)]
+    pub mobility_justification: Option<String>,
     pub start_date: NaiveDate,
     pub duration_months: i32,
     pub end_date: NaiveDate,
@@ -184,7 +196,8 @@ pub struct PreviousTenancySection {
     pub previous_tenant_departure_date: Option<NaiveDate>,
 }
 
-/// The 11 legal sections of the furnished residential lease contract (Décret n°2015-587)
+/// The 11 legal sections of the residential lease contract (Décret n°2015-587),
+/// shared by the furnished and unfurnished templates.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaseSections {
     pub section_i_parties: LeaseSection,
@@ -256,6 +269,43 @@ pub struct ComplianceSection {
     pub compliance_status: String,  // "compliant", "non_compliant", "pending"
     pub compliance_errors: Vec<String>,
     pub lease_valid_for_issuance: bool,
+    /// Dated rule set the lease was judged by; absent from snapshots frozen
+    /// before rules were versioned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_set_version: Option<String>,
+}
+
+/// One field that differs between two versions of a contract. `path`
+/// locates it in the snapshot, e.g. "financial_terms.monthly_rent".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotChange {
+    pub path: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+/// A persisted version of a lease's canonical snapshot.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize)]
+pub struct LeaseSnapshotVersion {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub legal_template_version: String,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Changes between two snapshot versions within one section (parties,
+/// financial_terms, diagnostics…).
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotSectionDiff {
+    pub section: String,
+    pub changes: Vec<SnapshotChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SnapshotDiff {
+    pub from: LeaseSnapshotVersion,
+    pub to: LeaseSnapshotVersion,
+    pub sections: Vec<SnapshotSectionDiff>,
 }
 
 impl CanonicalSnapshot {
@@ -309,6 +359,9 @@ impl CanonicalSnapshot {
             },
             lease_terms: LeaseTermsSection {
                 lease_kind: String::new(),
+                lease_type: default_lease_type(),
+                mobility_reason: None,
+                mobility_justification: None,
                 start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
                 duration_months: 0,
                 end_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
@@ -385,6 +438,7 @@ impl CanonicalSnapshot {
                 compliance_status: "pending".to_string(),
                 compliance_errors: vec![],
                 lease_valid_for_issuance: false,
+                rule_set_version: None,
             },
         }
     }
@@ -473,11 +527,14 @@ impl CanonicalSnapshot {
         };
 
         // --- Lease terms ---
-        // Student leases (9 months) have NO automatic renewal; standard leases do.
-        let is_student = lease.lease_kind == "student";
-        let auto_renewal = !is_student;
+        // Student (9 months) and mobility leases have NO automatic renewal;
+        // standard leases do.
+        let auto_renewal = lease.lease_kind == "standard";
         snapshot.lease_terms = LeaseTermsSection {
             lease_kind: lease.lease_kind.clone(),
+            lease_type: lease.lease_type.clone(),
+            mobility_reason: lease.mobility_reason.clone(),
+            mobility_justification: lease.mobility_justification.clone(),
             start_date: lease.start_date,
             duration_months: lease.duration_months,
             end_date: lease
@@ -546,20 +603,7 @@ impl CanonicalSnapshot {
         };
 
         // --- Section III: duration text (fixes student-lease renewal bug) ---
-        let duration_text = if is_student {
-            "Le bail est conclu pour une durée de neuf (9) mois. Conformément à \
-             l'article 25-7 de la loi du 6 juillet 1989, le bail étudiant ne se \
-             renouvelle pas par tacite reconduction et prend fin de plein droit \
-             à son terme."
-                .to_string()
-        } else {
-            format!(
-                "Le bail est conclu pour une durée de {} mois. Il se renouvellera \
-                 automatiquement par tacite reconduction pour la même durée, sauf \
-                 congé délivré dans les conditions légales.",
-                lease.duration_months
-            )
-        };
+        let duration_text = Self::duration_text(&snapshot.lease_terms, false);
         snapshot.lease_sections.section_iii_duration.text = Some(duration_text);
         snapshot.lease_sections.section_iii_duration.computed_for_lease_kind =
             Some(lease.lease_kind.clone());
@@ -580,13 +624,20 @@ impl CanonicalSnapshot {
         }
 
         // --- Section VIII: mandatory resolutory clause (locked) ---
-        snapshot.lease_sections.section_viii_resolutory.text = Some(
+        // Outside the loi du 6 juillet 1989 the two-month delay of article 24
+        // does not apply; the civil lease keeps a one-month delay.
+        let resolutory_text = if lease.lease_type == "ancillary" {
+            "À défaut de paiement d'un seul terme de loyer ou des charges à son \
+             échéance, ou de versement du dépôt de garantie, le présent bail sera \
+             résilié de plein droit un mois après un commandement de payer demeuré \
+             infructueux."
+        } else {
             "À défaut de paiement du loyer ou des charges aux échéances convenues, de \
              versement du dépôt de garantie, ou de souscription d'une assurance des \
              risques locatifs, le présent bail sera résilié de plein droit deux mois \
              après un commandement de payer demeuré infructueux."
-                .to_string(),
-        );
+        };
+        snapshot.lease_sections.section_viii_resolutory.text = Some(resolutory_text.to_string());
 
         // --- Section X: user custom clauses (validated upstream) ---
         snapshot.lease_sections.section_x_custom.text = lease.custom_clauses.clone();
@@ -618,13 +669,66 @@ impl CanonicalSnapshot {
         let is_compliant = lease.compliance_status == "compliant";
         snapshot.compliance = ComplianceSection {
             compliance_status: lease.compliance_status.clone(),
-            compliance_errors: lease.compliance_errors.clone(),
+            compliance_errors: lease.compliance_errors.iter().map(|violation| violation.message.clone()).collect(),
             lease_valid_for_issuance: is_compliant,
+            rule_set_version: lease.compliance_rule_set.clone(),
         };
 
         snapshot
     }
 
+    /// Section III text for the contract regime. An unfurnished lease renews
+    /// for three years, or six when the landlord is a legal person.
+    fn duration_text(terms: &LeaseTermsSection, landlord_is_legal_person: bool) -> String {
+        if terms.lease_kind == "student" {
+            return "Le bail est conclu pour une durée de neuf (9) mois. Conformément à \
+                    l'article 25-7 de la loi du 6 juillet 1989, le bail étudiant ne se \
+                    renouvelle pas par tacite reconduction et prend fin de plein droit \
+                    à son terme."
+                .to_string();
+        }
+
+        if terms.lease_type == "ancillary" {
+            return format!(
+                "Le bail est conclu pour une durée de {} mois. Il est régi par les \
+                 articles 1709 et suivants du code civil et n'est pas soumis à la loi du \
+                 6 juillet 1989. À son terme, il se poursuit par tacite reconduction pour \
+                 une durée indéterminée, chaque partie pouvant alors y mettre fin à tout \
+                 moment par lettre recommandée moyennant un préavis d'un mois.",
+                terms.duration_months
+            );
+        }
+
+        if terms.lease_kind == "mobility" {
+            return format!(
+                "Le bail mobilité est conclu pour une durée de {} mois. Conformément à \
+                 l'article 25-14 de la loi du 6 juillet 1989, il ne peut être ni renouvelé \
+                 ni reconduit. Sa durée peut être modifiée une fois par avenant, sans que \
+                 la durée totale du contrat n'excède dix mois. Si, au terme du contrat, \
+                 les parties concluent un nouveau bail portant sur le même logement, ce \
+                 nouveau bail est soumis au titre Ier bis de la loi.",
+                terms.duration_months
+            );
+        }
+
+        if terms.lease_type == "unfurnished" {
+            let renewal = if landlord_is_legal_person { "six (6) ans" } else { "trois (3) ans" };
+            return format!(
+                "Le bail est conclu pour une durée de {} mois. Conformément à l'article 10 \
+                 de la loi du 6 juillet 1989, il est reconduit tacitement à son terme pour \
+                 une durée de {}, sauf congé délivré dans les conditions légales.",
+                terms.duration_months, renewal
+            );
+        }
+
+        format!(
+            "Le bail est conclu pour une durée de {} mois. Il se renouvellera \
+             automatiquement par tacite reconduction pour la même durée, sauf \
+             congé délivré dans les conditions légales.",
+            terms.duration_months
+        )
+    }
+
     /// Apply an organization (legal-person) landlord to the snapshot parties,
     /// overriding the natural-person defaults. The représentant signs for the SCI.
     pub fn apply_organization_landlord(&mut self, org: &crate::models::organization::Organization) {
@@ -644,6 +748,9 @@ impl CanonicalSnapshot {
         self.parties.landlord_representative_name = org.representative_name.clone();
         self.parties.landlord_representative_role = org.representative_role.clone();
         self.parties.landlord_is_family_sci = org.is_family_sci;
+        // A family SCI renews like a natural person (loi 89, art. 10 and 13).
+        self.lease_sections.section_iii_duration.text =
+            Some(Self::duration_text(&self.lease_terms, !org.is_family_sci));
     }
 }
 
@@ -672,6 +779,9 @@ mod tests {
             rent_revision: false,
             annual_charges_regularization: false,
             lease_kind: lease_kind.to_string(),
+            lease_type: "furnished".to_string(),
+            mobility_reason: None,
+            mobility_justification: None,
             is_colocation: false,
             tenant_count: 1,
             destination: "habitation".to_string(),
@@ -693,6 +803,9 @@ mod tests {
             rent_complement_justification: None,
             previous_tenant_departure_date: None,
             previous_tenant_last_rent: None,
+            previous_rent_revised_on: None,
+            previous_rent_irl: None,
+            comparable_rent: None,
             professional_mandate: false,
             agency_fee_tenant: None,
             agency_fee_landlord: None,
@@ -733,15 +846,38 @@ mod tests {
             works_amount: None,
             works_date: None,
             rent_revision_conditions: None,
+            irl_reference_quarter: None,
+            irl_reference_year: None,
+            dpe_date: None,
+            electrical_diagnostic_date: None,
+            gas_diagnostic_date: None,
+            last_renewed_on: None,
             compliance_status: "compliant".to_string(),
-            compliance_errors: vec![],
+            compliance_errors: sqlx::types::Json(vec![]),
+            compliance_rule_set: Some("regles-2025-01-01".to_string()),
             status: "active".to_string(),
+            signed_at: None,
+            status_changed_at: None,
             pdf_path: None,
             created_at: None,
             updated_at: None,
         }
     }
 
+    #[test]
+    fn stored_lease_replays_through_the_compliance_rules() {
+        use quittance_compliance::lease::lease_compliance_report;
+
+        let mut lease = make_lease("standard", 12);
+        let report = lease_compliance_report(&lease.as_payload().compliance_terms(), "furnished", false);
+        assert!(!report.blocking);
+
+        // Three months of deposit on a furnished lease.
+        lease.deposit = BigDecimal::from(2400);
+        let report = lease_compliance_report(&lease.as_payload().compliance_terms(), "furnished", false);
+        assert!(report.blocking);
+    }
+
     fn make_property() -> Property {
         Property {
             id: Uuid::new_v4(),
@@ -754,6 +890,9 @@ mod tests {
             rooms: Some(2),
             max_occupants: 2,
             description: None,
+            rent_control_zone: None,
+            zone_tendue: false,
+            zone_tendue_declared: None,
             created_at: None,
             updated_at: None,
         }
@@ -828,6 +967,70 @@ mod tests {
         assert!(text.contains("tacite reconduction"));
     }
 
+    #[test]
+    fn mobility_lease_is_neither_renewed_nor_extended_beyond_ten_months() {
+        let mut lease = make_lease("mobility", 6);
+        lease.mobility_reason = Some("stage".to_string());
+        lease.mobility_justification = Some("Convention de stage".to_string());
+        let snapshot = CanonicalSnapshot::from_entities(
+            &lease,
+            &make_property(),
+            &[make_tenant()],
+            &make_landlord(),
+            "2026-06-18".to_string(),
+        );
+        assert!(!snapshot.lease_terms.auto_renewal);
+        assert_eq!(snapshot.lease_terms.mobility_reason.as_deref(), Some("stage"));
+        let text = snapshot
+            .lease_sections
+            .section_iii_duration
+            .text
+            .unwrap();
+        assert!(text.contains("ni renouvelé"));
+        assert!(text.contains("dix mois"));
+    }
+
+    #[test]
+    fn ancillary_lease_is_governed_by_the_civil_code() {
+        let mut lease = make_lease("standard", 12);
+        lease.lease_type = "ancillary".to_string();
+        let mut property = make_property();
+        property.property_type = "garage".to_string();
+        let snapshot = CanonicalSnapshot::from_entities(
+            &lease,
+            &property,
+            &[make_tenant()],
+            &make_landlord(),
+            "bail-annexe-2026-10-17".to_string(),
+        );
+        let duration = snapshot.lease_sections.section_iii_duration.text.unwrap();
+        assert!(duration.contains("code civil"));
+        assert!(!duration.contains("trois (3) ans"));
+        let resolutory = snapshot.lease_sections.section_viii_resolutory.text.unwrap();
+        assert!(resolutory.contains("un mois après"));
+    }
+
+    #[test]
+    fn unfurnished_lease_renews_for_three_years() {
+        let mut lease = make_lease("standard", 36);
+        lease.lease_type = "unfurnished".to_string();
+        let snapshot = CanonicalSnapshot::from_entities(
+            &lease,
+            &make_property(),
+            &[make_tenant()],
+            &make_landlord(),
+            "bail-nu-2026-10-17".to_string(),
+        );
+        assert_eq!(snapshot.lease_terms.lease_type, "unfurnished");
+        let text = snapshot
+            .lease_sections
+            .section_iii_duration
+            .text
+            .unwrap();
+        assert!(text.contains("article 10"));
+        assert!(text.contains("trois (3) ans"));
+    }
+
     #[test]
     fn snapshot_survives_json_round_trip() {
         // Guards the persist (to_value) / load (from_value) path used by the DB column.
@@ -938,6 +1141,14 @@ mod tests {
         snapshot.apply_organization_landlord(&org);
 
         assert_eq!(snapshot.parties.landlord_kind, "legal");
+        // Furnished leases renew for their own duration whoever the landlord is.
+        assert!(snapshot
+            .lease_sections
+            .section_iii_duration
+            .text
+            .as_deref()
+            .unwrap()
+            .contains("pour la même durée"));
         assert_eq!(snapshot.parties.landlord_full_name, "SCI MD16");
         // SIREN = first 9 digits of the SIRET.
         assert_eq!(
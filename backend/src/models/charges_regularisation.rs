@@ -0,0 +1,129 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::PartiesSection;
+
+/// Yearly regularisation of the charges of a lease paying provisions.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ChargesRegularisation {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub year: i32,
+    pub occupancy_start: NaiveDate,
+    pub occupancy_end: NaiveDate,
+    pub total_expenses: BigDecimal,
+    pub tenant_share: BigDecimal,
+    pub provisions_collected: BigDecimal,
+    /// Positive when the tenants owe the difference, negative when refunded.
+    pub balance: BigDecimal,
+    /// "draft", "finalized" or "applied" (carried by an avis d'échéance).
+    pub status: String,
+    pub rent_call_id: Option<Uuid>,
+    pub pdf_path: Option<String>,
+    pub finalized_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// An actual recoverable expense of the year.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RegularisationExpense {
+    pub id: Uuid,
+    pub regularisation_id: Uuid,
+    /// Category of the annex to décret n° 87-713.
+    pub category: String,
+    pub label: String,
+    pub amount: BigDecimal,
+    pub covered_start: NaiveDate,
+    pub covered_end: NaiveDate,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRegularisation {
+    pub lease_id: Uuid,
+    pub year: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRegularisationExpense {
+    pub category: String,
+    pub label: String,
+    pub amount: BigDecimal,
+    /// Defaults to the regularised calendar year.
+    pub covered_start: Option<NaiveDate>,
+    pub covered_end: Option<NaiveDate>,
+}
+
+/// Regularisation computed from its expenses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegularisationStatement {
+    pub year: i32,
+    pub occupancy_start: NaiveDate,
+    pub occupancy_end: NaiveDate,
+    pub occupancy_days: i64,
+    pub lines: Vec<StatementLine>,
+    pub category_totals: Vec<CategoryTotal>,
+    pub total_expenses: BigDecimal,
+    pub tenant_share: BigDecimal,
+    pub provisions_collected: BigDecimal,
+    pub balance: BigDecimal,
+    /// Split between colocataires; empty for a single tenant.
+    pub colocataire_shares: Vec<ColocataireShare>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementLine {
+    pub category: String,
+    pub category_label: String,
+    pub label: String,
+    pub amount: BigDecimal,
+    pub covered_start: NaiveDate,
+    pub covered_end: NaiveDate,
+    /// Part of the expense falling within the occupancy.
+    pub tenant_share: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryTotal {
+    pub category: String,
+    pub category_label: String,
+    pub amount: BigDecimal,
+    pub tenant_share: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColocataireShare {
+    pub tenant_name: String,
+    pub tenant_share: BigDecimal,
+    pub provisions_collected: BigDecimal,
+    pub balance: BigDecimal,
+}
+
+/// A regularisation with its expenses and the statement they yield.
+#[derive(Debug, Serialize)]
+pub struct RegularisationDetail {
+    #[serde(flatten)]
+    pub regularisation: ChargesRegularisation,
+    pub expenses: Vec<RegularisationExpense>,
+    pub statement: RegularisationStatement,
+}
+
+/// Frozen content of the regularisation statement sent to the tenants,
+/// captured when the regularisation is finalized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegularisationSnapshot {
+    pub id: Uuid,
+    pub regularisation_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "regularisation-charges-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub statement: RegularisationStatement,
+}
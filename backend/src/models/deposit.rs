@@ -0,0 +1,127 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::PartiesSection;
+
+/// Security deposit held for a lease, from collection to return.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Deposit {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub amount: BigDecimal,
+    pub received_on: NaiveDate,
+    pub received_method: String,
+    pub keys_returned_on: Option<NaiveDate>,
+    pub exit_inventory_conforms: Option<bool>,
+    /// One month after the keys are returned, two if the exit inventory
+    /// shows damage.
+    pub return_deadline: Option<NaiveDate>,
+    pub returned_on: Option<NaiveDate>,
+    pub returned_amount: Option<BigDecimal>,
+    pub late_penalty: Option<BigDecimal>,
+    /// "held", "awaiting_return" or "returned".
+    pub status: String,
+    pub pdf_path: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DepositDeduction {
+    pub id: Uuid,
+    pub deposit_id: Uuid,
+    /// "repairs", "unpaid_rent", "unpaid_charges", "charges_provision" or "other".
+    pub category: String,
+    pub label: String,
+    pub amount: BigDecimal,
+    pub receipt_id: Option<Uuid>,
+    pub justification: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDeposit {
+    pub lease_id: Uuid,
+    /// Defaults to the deposit stated in the lease.
+    pub amount: Option<BigDecimal>,
+    pub received_on: NaiveDate,
+    pub received_method: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordDepositExit {
+    pub keys_returned_on: NaiveDate,
+    pub exit_inventory_conforms: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDepositDeduction {
+    pub category: String,
+    pub label: String,
+    /// Defaults to the outstanding balance of `receipt_id` for unpaid amounts.
+    pub amount: Option<BigDecimal>,
+    pub receipt_id: Option<Uuid>,
+    pub justification: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReturnDeposit {
+    pub returned_on: NaiveDate,
+}
+
+/// What is owed on the deposit as of a date.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositSettlement {
+    pub amount_held: BigDecimal,
+    pub total_deductions: BigDecimal,
+    /// Deposit left after deductions, never negative.
+    pub amount_to_return: BigDecimal,
+    /// Deductions exceeding the deposit, still owed by the tenant.
+    pub tenant_balance_due: BigDecimal,
+    pub return_deadline: Option<NaiveDate>,
+    /// Started months past the deadline on the settlement date.
+    pub months_late: i32,
+    pub late_penalty: BigDecimal,
+    pub total_due_to_tenant: BigDecimal,
+    pub settled_on: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepositDetail {
+    #[serde(flatten)]
+    pub deposit: Deposit,
+    pub deductions: Vec<DepositDeduction>,
+    pub settlement: DepositSettlement,
+}
+
+/// Deposit awaiting return, for the landlord's reminders.
+#[derive(Debug, Serialize, FromRow)]
+pub struct PendingDepositReturn {
+    pub deposit_id: Uuid,
+    pub lease_id: Uuid,
+    pub property_address: String,
+    pub keys_returned_on: NaiveDate,
+    pub return_deadline: NaiveDate,
+}
+
+/// Frozen content of the deposit settlement letter, captured on return.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositSnapshot {
+    pub id: Uuid,
+    pub deposit_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "restitution-depot-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub lease_start_date: NaiveDate,
+    pub keys_returned_on: Option<NaiveDate>,
+    pub exit_inventory_conforms: Option<bool>,
+    pub deductions: Vec<DepositDeduction>,
+    pub settlement: DepositSettlement,
+}
@@ -0,0 +1,150 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::PartiesSection;
+
+/// Condition grid of the état des lieux, best first.
+pub const CONDITION_RATINGS: [(&str, &str); 4] = [
+    ("neuf", "Neuf ou très bon état"),
+    ("bon", "Bon état"),
+    ("moyen", "État d'usage"),
+    ("mauvais", "Mauvais état"),
+];
+
+pub const INVENTORY_KINDS: [&str; 2] = ["entry", "exit"];
+
+pub fn condition_label(condition: &str) -> Option<&'static str> {
+    CONDITION_RATINGS
+        .iter()
+        .find(|(code, _)| *code == condition)
+        .map(|(_, label)| *label)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryElement {
+    /// e.g. "Sol", "Murs", "Fenêtre", "Évier".
+    pub name: String,
+    /// One of `CONDITION_RATINGS`.
+    pub condition: String,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryRoom {
+    pub name: String,
+    pub elements: Vec<InventoryElement>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeterReading {
+    /// e.g. "Électricité heures pleines", "Eau froide".
+    pub meter: String,
+    #[serde(default)]
+    pub serial_number: Option<String>,
+    pub reading: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyHandover {
+    /// e.g. "Clé porte d'entrée", "Badge parking".
+    pub label: String,
+    pub count: i32,
+}
+
+/// Entry or exit état des lieux of a lease.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct LeaseInventory {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    /// "entry" or "exit".
+    pub kind: String,
+    pub performed_on: NaiveDate,
+    pub rooms: Json<Vec<InventoryRoom>>,
+    pub meters: Json<Vec<MeterReading>>,
+    pub keys: Json<Vec<KeyHandover>>,
+    pub general_comment: Option<String>,
+    pub pdf_path: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveLeaseInventory {
+    pub performed_on: NaiveDate,
+    pub rooms: Vec<InventoryRoom>,
+    #[serde(default)]
+    pub meters: Vec<MeterReading>,
+    #[serde(default)]
+    pub keys: Vec<KeyHandover>,
+    pub general_comment: Option<String>,
+}
+
+/// An element found in a worse condition at exit than at entry, or missing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InventoryDegradation {
+    pub room: String,
+    pub element: String,
+    pub entry_condition: String,
+    /// `None` when the element is missing from the exit inventory.
+    pub exit_condition: Option<String>,
+    pub exit_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyShortfall {
+    pub label: String,
+    pub handed_over: i32,
+    pub returned: i32,
+}
+
+/// Entry-versus-exit comparison feeding the deposit settlement.
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryComparison {
+    pub entry_id: Uuid,
+    pub exit_id: Uuid,
+    pub degradations: Vec<InventoryDegradation>,
+    pub missing_keys: Vec<KeyShortfall>,
+    /// Nothing degraded and every key returned: the deposit is due within one
+    /// month instead of two.
+    pub conforms: bool,
+}
+
+/// Repair cost the landlord charges for one degradation.
+#[derive(Debug, Deserialize)]
+pub struct DegradationCharge {
+    pub room: String,
+    pub element: String,
+    pub amount: BigDecimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateDegradationDeductions {
+    pub charges: Vec<DegradationCharge>,
+}
+
+/// Frozen content of the état des lieux document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventorySnapshot {
+    pub id: Uuid,
+    pub inventory_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "etat-des-lieux-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    pub kind: String,
+    pub performed_on: NaiveDate,
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub lease_start_date: NaiveDate,
+    pub rooms: Vec<InventoryRoom>,
+    pub meters: Vec<MeterReading>,
+    pub keys: Vec<KeyHandover>,
+    pub general_comment: Option<String>,
+}
@@ -1,9 +1,12 @@
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, NaiveDate, Utc};
+use quittance_compliance::lease::LeaseTerms;
+use quittance_compliance::report::ComplianceViolation;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
 use uuid::Uuid;
 
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct Lease {
     pub id: Uuid,
@@ -21,6 +24,12 @@ pub struct Lease {
     pub rent_revision: bool,
     pub annual_charges_regularization: bool,
     pub lease_kind: String,
+    /// "furnished", "unfurnished" (location nue) or "ancillary" (parking,
+    /// garage or storage let under the code civil).
+    pub lease_type: String,
+    /// Tenant situation justifying a bail mobilité (see `MOBILITY_REASONS`).
+    pub mobility_reason: Option<String>,
+    pub mobility_justification: Option<String>,
     pub is_colocation: bool,
     pub tenant_count: i32,
     pub destination: String,
@@ -42,6 +51,9 @@ pub struct Lease {
     pub rent_complement_justification: Option<String>,
     pub previous_tenant_departure_date: Option<NaiveDate>,
     pub previous_tenant_last_rent: Option<BigDecimal>,
+    pub previous_rent_revised_on: Option<NaiveDate>,
+    pub previous_rent_irl: Option<BigDecimal>,
+    pub comparable_rent: Option<BigDecimal>,
     pub professional_mandate: bool,
     pub agency_fee_tenant: Option<BigDecimal>,
     pub agency_fee_landlord: Option<BigDecimal>,
@@ -88,14 +100,126 @@ pub struct Lease {
     pub works_date: Option<NaiveDate>,
     // Layer 2: Rent revision conditions
     pub rent_revision_conditions: Option<String>,
+    /// IRL quarter (1-4) and year named in the lease as revision reference.
+    pub irl_reference_quarter: Option<i32>,
+    pub irl_reference_year: Option<i32>,
+    /// Dates the DPE and the electricity and gas diagnostics were carried out.
+    pub dpe_date: Option<NaiveDate>,
+    pub electrical_diagnostic_date: Option<NaiveDate>,
+    pub gas_diagnostic_date: Option<NaiveDate>,
+    /// Anniversary on which the lease was last tacitly renewed.
+    pub last_renewed_on: Option<NaiveDate>,
     pub compliance_status: String,
-    pub compliance_errors: Vec<String>,
+    /// Violations found when the lease was last saved; warnings only, since
+    /// blocking ones prevent saving.
+    pub compliance_errors: Json<Vec<ComplianceViolation>>,
+    /// Version of the dated compliance rules applied at the last save.
+    pub compliance_rule_set: Option<String>,
     pub status: String,
+    /// Set when the lease moves to `signed`; its snapshot is frozen from then on.
+    pub signed_at: Option<DateTime<Utc>>,
+    pub status_changed_at: Option<DateTime<Utc>>,
     pub pdf_path: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+impl Lease {
+    /// The stored lease as a payload, to run the compliance rules on it again.
+    pub fn as_payload(&self) -> CreateLease {
+        CreateLease {
+            property_id: self.property_id,
+            tenant_ids: self.tenant_ids.clone(),
+            start_date: self.start_date,
+            duration_months: self.duration_months,
+            monthly_rent: self.monthly_rent.clone(),
+            charges: self.charges.clone(),
+            deposit: self.deposit.clone(),
+            rent_revision: self.rent_revision,
+            annual_charges_regularization: self.annual_charges_regularization,
+            lease_kind: Some(self.lease_kind.clone()),
+            lease_type: Some(self.lease_type.clone()),
+            mobility_reason: self.mobility_reason.clone(),
+            mobility_justification: self.mobility_justification.clone(),
+            is_colocation: Some(self.is_colocation),
+            destination: Some(self.destination.clone()),
+            habitable_surface: self.habitable_surface.clone(),
+            main_room_count: self.main_room_count,
+            heating_mode: self.heating_mode.clone(),
+            hot_water_mode: self.hot_water_mode.clone(),
+            dpe_class: self.dpe_class.clone(),
+            is_dom_tom: Some(self.is_dom_tom),
+            energy_cost_annual: self.energy_cost_annual.clone(),
+            energy_cost_year: self.energy_cost_year,
+            rent_payment_frequency: Some(self.rent_payment_frequency.clone()),
+            rent_payment_timing: Some(self.rent_payment_timing.clone()),
+            rent_payment_period: self.rent_payment_period.clone(),
+            rent_controlled: Some(self.rent_controlled),
+            reference_rent: self.reference_rent.clone(),
+            reference_rent_majorated: self.reference_rent_majorated.clone(),
+            rent_complement: self.rent_complement.clone(),
+            rent_complement_justification: self.rent_complement_justification.clone(),
+            previous_tenant_departure_date: self.previous_tenant_departure_date,
+            previous_tenant_last_rent: self.previous_tenant_last_rent.clone(),
+            previous_rent_revised_on: self.previous_rent_revised_on,
+            previous_rent_irl: self.previous_rent_irl.clone(),
+            comparable_rent: self.comparable_rent.clone(),
+            professional_mandate: Some(self.professional_mandate),
+            agency_fee_tenant: self.agency_fee_tenant.clone(),
+            agency_fee_landlord: self.agency_fee_landlord.clone(),
+            custom_clauses: self.custom_clauses.clone(),
+            inventory_date: self.inventory_date,
+            private_room_label: self.private_room_label.clone(),
+            shared_areas_text: self.shared_areas_text.clone(),
+            furniture_set_ids: self.furniture_set_ids.clone(),
+            furniture_inventory: self.furniture_inventory.clone(),
+            dpe: self.dpe.clone(),
+            erp: self.erp.clone(),
+            home_insurance: self.home_insurance.clone(),
+            legal_notice_provided: self.legal_notice_provided,
+            annex_entry_inventory_provided: Some(self.annex_entry_inventory_provided),
+            annex_furniture_inventory_provided: Some(self.annex_furniture_inventory_provided),
+            annex_dpe_provided: Some(self.annex_dpe_provided),
+            annex_erp_provided: Some(self.annex_erp_provided),
+            annex_home_insurance_provided: Some(self.annex_home_insurance_provided),
+            identifiant_fiscal: self.identifiant_fiscal.clone(),
+            habitat_type: self.habitat_type.clone(),
+            regime_juridique: self.regime_juridique.clone(),
+            construction_period: self.construction_period.clone(),
+            electrical_installation_over_15y: Some(self.electrical_installation_over_15y),
+            gas_installation_over_15y: Some(self.gas_installation_over_15y),
+            in_risk_zone: Some(self.in_risk_zone),
+            annex_lead_provided: Some(self.annex_lead_provided),
+            annex_electrical_provided: Some(self.annex_electrical_provided),
+            annex_gas_provided: Some(self.annex_gas_provided),
+            annex_risk_provided: Some(self.annex_risk_provided),
+            autres_parties: self.autres_parties.clone(),
+            elements_equipement: self.elements_equipement.clone(),
+            privatifs_accessoires: self.privatifs_accessoires.clone(),
+            parties_communes: self.parties_communes.clone(),
+            tech_equipements: self.tech_equipements.clone(),
+            charges_settlement_mode: self.charges_settlement_mode.clone(),
+            colocation_insurance_amount: self.colocation_insurance_amount.clone(),
+            works_nature: self.works_nature.clone(),
+            works_amount: self.works_amount.clone(),
+            works_date: self.works_date,
+            rent_revision_conditions: self.rent_revision_conditions.clone(),
+            irl_reference_quarter: self.irl_reference_quarter,
+            irl_reference_year: self.irl_reference_year,
+            dpe_date: self.dpe_date,
+            electrical_diagnostic_date: self.electrical_diagnostic_date,
+            gas_diagnostic_date: self.gas_diagnostic_date,
+            zone_tendue: None,
+            irl_value: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransitionLease {
+    pub status: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateLease {
     pub property_id: Uuid,
@@ -110,6 +234,12 @@ pub struct CreateLease {
     pub rent_revision: bool,
     pub annual_charges_regularization: bool,
     pub lease_kind: Option<String>,
+    /// Defaults to "ancillary" for parking, garage and storage properties,
+    /// otherwise to the property's furnished flag.
+    pub lease_type: Option<String>,
+    /// Required for a bail mobilité.
+    pub mobility_reason: Option<String>,
+    pub mobility_justification: Option<String>,
     pub is_colocation: Option<bool>,
     pub destination: Option<String>,
     pub habitable_surface: Option<BigDecimal>,
@@ -130,6 +260,11 @@ pub struct CreateLease {
     pub rent_complement_justification: Option<String>,
     pub previous_tenant_departure_date: Option<NaiveDate>,
     pub previous_tenant_last_rent: Option<BigDecimal>,
+    /// Last revision of the previous tenant's rent, and the IRL it used.
+    pub previous_rent_revised_on: Option<NaiveDate>,
+    pub previous_rent_irl: Option<BigDecimal>,
+    /// Rent of comparable dwellings nearby, for an undervalued previous rent.
+    pub comparable_rent: Option<BigDecimal>,
     pub professional_mandate: Option<bool>,
     pub agency_fee_tenant: Option<BigDecimal>,
     pub agency_fee_landlord: Option<BigDecimal>,
@@ -176,4 +311,76 @@ pub struct CreateLease {
     pub works_date: Option<NaiveDate>,
     // Layer 2: Rent revision conditions
     pub rent_revision_conditions: Option<String>,
+    /// IRL quarter (1-4) and year named in the lease as revision reference.
+    pub irl_reference_quarter: Option<i32>,
+    pub irl_reference_year: Option<i32>,
+    pub dpe_date: Option<NaiveDate>,
+    pub electrical_diagnostic_date: Option<NaiveDate>,
+    pub gas_diagnostic_date: Option<NaiveDate>,
+    /// Classification of the property, set by the server before the
+    /// compliance check; never read from the payload.
+    #[serde(skip_deserializing)]
+    pub zone_tendue: Option<bool>,
+    /// Value of the IRL reference quarter, set by the server from the loaded
+    /// indices for the relet cap; never read from the payload.
+    #[serde(skip_deserializing)]
+    pub irl_value: Option<BigDecimal>,
+}
+
+impl CreateLease {
+    /// The part of the payload the compliance engine checks.
+    pub fn compliance_terms(&self) -> LeaseTerms {
+        LeaseTerms {
+            tenant_ids: self.tenant_ids.clone(),
+            start_date: self.start_date,
+            duration_months: self.duration_months,
+            monthly_rent: self.monthly_rent.clone(),
+            charges: self.charges.clone(),
+            deposit: self.deposit.clone(),
+            lease_kind: self.lease_kind.clone(),
+            mobility_reason: self.mobility_reason.clone(),
+            mobility_justification: self.mobility_justification.clone(),
+            is_colocation: self.is_colocation,
+            habitable_surface: self.habitable_surface.clone(),
+            main_room_count: self.main_room_count,
+            dpe_class: self.dpe_class.clone(),
+            is_dom_tom: self.is_dom_tom,
+            dpe_date: self.dpe_date,
+            rent_controlled: self.rent_controlled,
+            reference_rent: self.reference_rent.clone(),
+            reference_rent_majorated: self.reference_rent_majorated.clone(),
+            rent_complement: self.rent_complement.clone(),
+            rent_complement_justification: self.rent_complement_justification.clone(),
+            previous_tenant_departure_date: self.previous_tenant_departure_date,
+            previous_tenant_last_rent: self.previous_tenant_last_rent.clone(),
+            previous_rent_revised_on: self.previous_rent_revised_on,
+            previous_rent_irl: self.previous_rent_irl.clone(),
+            comparable_rent: self.comparable_rent.clone(),
+            irl_value: self.irl_value.clone(),
+            professional_mandate: self.professional_mandate,
+            agency_fee_tenant: self.agency_fee_tenant.clone(),
+            agency_fee_landlord: self.agency_fee_landlord.clone(),
+            custom_clauses: self.custom_clauses.clone(),
+            irl_reference_quarter: self.irl_reference_quarter,
+            irl_reference_year: self.irl_reference_year,
+            legal_notice_provided: self.legal_notice_provided,
+            annex_entry_inventory_provided: self.annex_entry_inventory_provided,
+            annex_furniture_inventory_provided: self.annex_furniture_inventory_provided,
+            annex_dpe_provided: self.annex_dpe_provided,
+            identifiant_fiscal: self.identifiant_fiscal.clone(),
+            habitat_type: self.habitat_type.clone(),
+            regime_juridique: self.regime_juridique.clone(),
+            construction_period: self.construction_period.clone(),
+            electrical_installation_over_15y: self.electrical_installation_over_15y,
+            gas_installation_over_15y: self.gas_installation_over_15y,
+            in_risk_zone: self.in_risk_zone,
+            annex_lead_provided: self.annex_lead_provided,
+            annex_electrical_provided: self.annex_electrical_provided,
+            annex_gas_provided: self.annex_gas_provided,
+            annex_risk_provided: self.annex_risk_provided,
+            zone_tendue: self.zone_tendue,
+            works_amount: self.works_amount.clone(),
+            works_date: self.works_date,
+        }
+    }
 }
@@ -0,0 +1,57 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::{PartiesSection, SnapshotChange};
+
+/// An avenant to a signed lease. Amendments apply in `sequence_number` order
+/// on top of the signed canonical snapshot.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct LeaseAmendment {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub sequence_number: i32,
+    pub effective_date: NaiveDate,
+    pub summary: String,
+    pub changes: Json<Vec<SnapshotChange>>,
+    pub pdf_path: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLeaseAmendment {
+    pub effective_date: NaiveDate,
+    pub summary: String,
+    /// JSON merge patch (RFC 7386) over the contract sections of the canonical
+    /// snapshot, e.g. `{"financial_terms": {"monthly_rent": "900.00"}}`.
+    pub changes: serde_json::Value,
+}
+
+/// Field-level line of the avenant document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmendmentChangeLine {
+    pub label: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Frozen content of the avenant document, captured when it is recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmendmentSnapshot {
+    pub id: Uuid,
+    pub amendment_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "avenant-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    pub sequence_number: i32,
+    pub effective_date: NaiveDate,
+    pub summary: String,
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub lease_start_date: NaiveDate,
+    pub signed_at: Option<DateTime<Utc>>,
+    pub changes: Vec<AmendmentChangeLine>,
+}
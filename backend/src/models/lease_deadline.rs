@@ -0,0 +1,31 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A dated step in the life of a lease: end of term, notice window, rent
+/// revision or diagnostic expiry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeaseDeadline {
+    /// "term_end", "notice_end", "landlord_notice", "irl_revision",
+    /// "dpe_expiry", "electrical_diagnostic_expiry" or "gas_diagnostic_expiry".
+    pub kind: String,
+    pub date: NaiveDate,
+    pub label: String,
+    /// Negative once the date has passed.
+    pub days_remaining: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LeaseDeadlines {
+    pub lease_id: Uuid,
+    /// Standard leases renew tacitly at each term; student and mobility
+    /// leases end at their term.
+    pub renewable: bool,
+    /// End of the current term after tacit renewals, or the effective date of
+    /// a running notice.
+    pub term_end: Option<NaiveDate>,
+    /// Student or mobility lease that has reached its term.
+    pub ended_without_renewal: bool,
+    /// Sorted by date.
+    pub deadlines: Vec<LeaseDeadline>,
+}
@@ -0,0 +1,105 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::PartiesSection;
+
+/// Grounds on which a landlord may give notice for the lease term
+/// (loi 89, art. 15-I for unfurnished leases, art. 25-8 for furnished ones).
+pub const LANDLORD_NOTICE_GROUNDS: [(&str, &str); 3] = [
+    ("vente", "Vente du logement"),
+    ("reprise", "Reprise du logement pour y habiter"),
+    ("motif_legitime", "Motif légitime et sérieux"),
+];
+
+/// Situations reducing the tenant's notice on an unfurnished lease from three
+/// months to one (loi 89, art. 15-I).
+pub const REDUCED_NOTICE_REASONS: [(&str, &str); 8] = [
+    ("zone_tendue", "Logement situé en zone tendue"),
+    ("premier_emploi", "Obtention d'un premier emploi"),
+    ("mutation", "Mutation professionnelle"),
+    ("perte_emploi", "Perte d'emploi"),
+    ("nouvel_emploi", "Nouvel emploi consécutif à une perte d'emploi"),
+    ("sante", "État de santé justifiant un changement de domicile"),
+    ("rsa_aah", "Bénéficiaire du RSA ou de l'AAH"),
+    ("logement_social", "Attribution d'un logement social"),
+];
+
+pub fn landlord_notice_ground_label(ground: &str) -> Option<&'static str> {
+    LANDLORD_NOTICE_GROUNDS
+        .iter()
+        .find(|(code, _)| *code == ground)
+        .map(|(_, label)| *label)
+}
+
+pub fn reduced_notice_reason_label(reason: &str) -> Option<&'static str> {
+    REDUCED_NOTICE_REASONS
+        .iter()
+        .find(|(code, _)| *code == reason)
+        .map(|(_, label)| *label)
+}
+
+/// A congé served by the tenant or the landlord. Only one notice per lease is
+/// `active`; withdrawing it puts the lease back in force.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct LeaseNotice {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    /// "tenant" or "landlord".
+    pub given_by: String,
+    /// Date the addressee received the notice; the notice period runs from it.
+    pub served_on: NaiveDate,
+    pub notice_months: i32,
+    /// Last day of the lease: rent is due up to and including it.
+    pub effective_date: NaiveDate,
+    pub ground: Option<String>,
+    pub reduced_notice_reason: Option<String>,
+    pub beneficiary_name: Option<String>,
+    pub beneficiary_relationship: Option<String>,
+    pub sale_price: Option<BigDecimal>,
+    pub details: Option<String>,
+    /// "active" or "withdrawn".
+    pub status: String,
+    pub pdf_path: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLeaseNotice {
+    pub given_by: String,
+    pub served_on: NaiveDate,
+    pub ground: Option<String>,
+    pub reduced_notice_reason: Option<String>,
+    pub beneficiary_name: Option<String>,
+    pub beneficiary_relationship: Option<String>,
+    pub sale_price: Option<BigDecimal>,
+    pub details: Option<String>,
+}
+
+/// Frozen content of the notice letter, captured when the notice is recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoticeSnapshot {
+    pub id: Uuid,
+    pub notice_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "conge-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    pub given_by: String,
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub lease_type: String,
+    pub lease_start_date: NaiveDate,
+    pub served_on: NaiveDate,
+    pub notice_months: i32,
+    pub effective_date: NaiveDate,
+    pub ground: Option<String>,
+    pub reduced_notice_reason: Option<String>,
+    pub beneficiary_name: Option<String>,
+    pub beneficiary_relationship: Option<String>,
+    pub sale_price: Option<BigDecimal>,
+    pub details: Option<String>,
+}
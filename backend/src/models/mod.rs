@@ -6,3 +6,14 @@ pub mod lease;
 pub mod receipt;
 pub mod organization;
 pub mod canonical_snapshot;
+pub mod receipt_snapshot;
+pub mod payment;
+pub mod rent_call;
+pub mod rent_call_snapshot;
+pub mod rent_history;
+pub mod charges_regularisation;
+pub mod deposit;
+pub mod lease_amendment;
+pub mod lease_notice;
+pub mod lease_deadline;
+pub mod inventory;
@@ -0,0 +1,36 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Payment methods accepted for new payments. `autre` only exists on
+/// payments backfilled from receipts issued before the ledger, and
+/// `depot_garantie` on rent kept from the deposit.
+pub const PAYMENT_METHODS: [&str; 4] = ["virement", "cheque", "especes", "caf"];
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Payment {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub payer_tenant_id: Option<Uuid>,
+    pub receipt_id: Option<Uuid>,
+    pub amount: BigDecimal,
+    pub payment_date: NaiveDate,
+    pub method: String,
+    pub reference: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePayment {
+    pub lease_id: Uuid,
+    pub payer_tenant_id: Option<Uuid>,
+    /// Period the payer asked to settle; defaults to the oldest due period.
+    pub receipt_id: Option<Uuid>,
+    pub amount: BigDecimal,
+    pub payment_date: NaiveDate,
+    pub method: String,
+    pub reference: Option<String>,
+}
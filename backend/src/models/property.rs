@@ -4,6 +4,14 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Premises let on their own under the code civil rather than the loi du
+/// 6 juillet 1989.
+pub const ANCILLARY_PROPERTY_TYPES: [&str; 3] = ["parking", "garage", "storage"];
+
+pub fn is_ancillary_property_type(property_type: &str) -> bool {
+    ANCILLARY_PROPERTY_TYPES.contains(&property_type)
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct Property {
     pub id: Uuid,
@@ -16,6 +24,13 @@ pub struct Property {
     pub rooms: Option<i32>,
     pub max_occupants: i32,
     pub description: Option<String>,
+    /// Zone of the rent-control arrêté covering the address, e.g. "1".
+    pub rent_control_zone: Option<String>,
+    /// Whether the address lies in a zone tendue: the owner's declaration
+    /// when given, otherwise classified by the server from the address.
+    pub zone_tendue: bool,
+    /// Classification set by hand, for a commune the bundled list misses.
+    pub zone_tendue_declared: Option<bool>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
@@ -32,4 +47,6 @@ pub struct CreateProperty {
     pub rooms: Option<i32>,
     pub max_occupants: i32,
     pub description: Option<String>,
+    pub rent_control_zone: Option<String>,
+    pub zone_tendue_declared: Option<bool>,
 }
@@ -15,6 +15,8 @@ pub struct Receipt {
     pub total_amount: BigDecimal,
     pub payment_date: NaiveDate,
     pub status: String,
+    /// Amount allocated to this period from the payments ledger.
+    pub amount_paid: BigDecimal,
     pub email_sent_at: Option<DateTime<Utc>>,
     pub pdf_path: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
@@ -38,3 +40,13 @@ pub struct UpdateReceipt {
     pub status: Option<String>,
     pub payment_date: Option<NaiveDate>,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct MarkReceiptPaid {
+    /// Defaults to the payment date already recorded on the receipt.
+    pub payment_date: Option<NaiveDate>,
+    /// Payment method; defaults to `virement`.
+    pub method: Option<String>,
+    /// Colocataire who paid, if known.
+    pub payer_tenant_id: Option<Uuid>,
+}
@@ -0,0 +1,267 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::{CanonicalSnapshot, LesseeParty, PartiesSection};
+use crate::models::receipt::Receipt;
+
+/// Receipt Snapshot
+///
+/// Frozen copy of everything printed on a quittance, captured when the receipt
+/// is issued or marked paid. Every re-render reads this document instead of the
+/// live landlord/tenant/property rows, so a copy requested years later is
+/// identical to the one originally delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptSnapshot {
+    pub id: Uuid,
+    pub receipt_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "quittance-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    /// Landlord (natural person or SCI designation) and every colocataire.
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub period: ReceiptPeriod,
+    pub amounts: ReceiptAmounts,
+    pub payment_date: NaiveDate,
+    pub status: String,
+    /// "quittance" for a fully settled period, "recu" for a partial payment,
+    /// "unpaid" when nothing has been received yet.
+    #[serde(default = "default_document_kind")]
+    pub document_kind: String,
+}
+
+fn default_document_kind() -> String {
+    "quittance".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptPeriod {
+    pub month: i32,
+    pub year: i32,
+    /// First and last day actually covered (partial months at lease start/end).
+    pub covered_from: NaiveDate,
+    pub covered_to: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptAmounts {
+    pub base_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub total_amount: BigDecimal,
+    #[serde(default)]
+    pub amount_paid: BigDecimal,
+    #[serde(default)]
+    pub balance_due: BigDecimal,
+}
+
+impl ReceiptSnapshot {
+    /// Build a receipt snapshot from the lease parties at issuance time and the
+    /// receipt row. Returns `None` when the receipt period is not a valid month.
+    pub fn from_lease_snapshot(
+        lease: &CanonicalSnapshot,
+        receipt: &Receipt,
+        template_version: String,
+    ) -> Option<Self> {
+        let month_start = NaiveDate::from_ymd_opt(
+            receipt.period_year,
+            u32::try_from(receipt.period_month).ok()?,
+            1,
+        )?;
+        let month_end = (month_start + chrono::Months::new(1)).pred_opt()?;
+
+        // Partial months (lease start/end) only cover the leased days.
+        let lease_start = lease.lease_terms.start_date;
+        let lease_end = lease.lease_terms.end_date;
+        let covered_from = month_start.max(lease_start);
+        let covered_to = if lease_end > lease_start {
+            month_end.min(lease_end)
+        } else {
+            month_end
+        };
+
+        let mut parties = lease.parties.clone();
+        if parties.lessees.is_empty() {
+            parties.lessees.push(LesseeParty {
+                full_name: parties.lessee_full_name.clone(),
+                address: parties.lessee_address.clone(),
+                email: parties.lessee_email.clone(),
+                birth_date: parties.lessee_birth_date,
+                birth_place: parties.lessee_birth_place.clone(),
+            });
+        }
+
+        Some(ReceiptSnapshot {
+            id: Uuid::new_v4(),
+            receipt_id: receipt.id,
+            lease_id: receipt.lease_id,
+            template_version,
+            generated_at: Utc::now(),
+            language: "fr".to_string(),
+            parties,
+            property_address: lease.property.address.clone(),
+            period: ReceiptPeriod {
+                month: month_start.month() as i32,
+                year: month_start.year(),
+                covered_from,
+                covered_to,
+            },
+            amounts: ReceiptAmounts {
+                base_rent: receipt.base_rent.clone(),
+                charges: receipt.charges.clone(),
+                total_amount: receipt.total_amount.clone(),
+                amount_paid: receipt.amount_paid.clone(),
+                balance_due: (&receipt.total_amount - &receipt.amount_paid).max(BigDecimal::zero()),
+            },
+            payment_date: receipt.payment_date,
+            status: receipt.status.clone(),
+            document_kind: Self::document_kind_for(receipt).to_string(),
+        })
+    }
+
+    /// Which document a receipt entitles the tenants to. French law (loi du
+    /// 6 juillet 1989, art. 21) only allows a quittance once the whole period
+    /// is paid; a partial payment gets a reçu.
+    pub fn document_kind_for(receipt: &Receipt) -> &'static str {
+        if receipt.amount_paid >= receipt.total_amount {
+            "quittance"
+        } else if receipt.amount_paid > BigDecimal::zero() {
+            "recu"
+        } else {
+            "unpaid"
+        }
+    }
+
+    /// Names of every lessee, primary first.
+    pub fn lessee_names(&self) -> Vec<String> {
+        self.parties.lessees.iter().map(|l| l.full_name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn make_receipt(month: i32, year: i32) -> Receipt {
+        Receipt {
+            id: Uuid::new_v4(),
+            lease_id: Uuid::new_v4(),
+            period_month: month,
+            period_year: year,
+            base_rent: BigDecimal::from_str("650.00").unwrap(),
+            charges: BigDecimal::from_str("50.00").unwrap(),
+            total_amount: BigDecimal::from_str("700.00").unwrap(),
+            amount_paid: BigDecimal::from_str("700.00").unwrap(),
+            payment_date: NaiveDate::from_ymd_opt(year, month as u32, 5).unwrap(),
+            status: "generated".to_string(),
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn make_lease(start: NaiveDate, end: NaiveDate) -> CanonicalSnapshot {
+        let mut s = CanonicalSnapshot::new(Uuid::new_v4(), "2026-06-18".to_string());
+        s.parties.landlord_full_name = "Jean Dupont".to_string();
+        s.parties.lessee_full_name = "Marie Martin".to_string();
+        s.property.address = "1 rue de Paris".to_string();
+        s.lease_terms.start_date = start;
+        s.lease_terms.end_date = end;
+        s
+    }
+
+    #[test]
+    fn freezes_parties_address_and_amounts() {
+        let lease = make_lease(
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2027, 1, 1).unwrap(),
+        );
+        let receipt = make_receipt(10, 2026);
+        let snapshot =
+            ReceiptSnapshot::from_lease_snapshot(&lease, &receipt, "quittance-2026-10-17".to_string())
+                .expect("valid period");
+
+        assert_eq!(snapshot.receipt_id, receipt.id);
+        assert_eq!(snapshot.property_address, "1 rue de Paris");
+        assert_eq!(snapshot.parties.landlord_full_name, "Jean Dupont");
+        assert_eq!(snapshot.lessee_names(), vec!["Marie Martin".to_string()]);
+        assert_eq!(snapshot.amounts.total_amount, BigDecimal::from(700));
+        assert_eq!(snapshot.period.covered_from, NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+        assert_eq!(snapshot.period.covered_to, NaiveDate::from_ymd_opt(2026, 10, 31).unwrap());
+        assert_eq!(snapshot.template_version, "quittance-2026-10-17");
+        assert_eq!(snapshot.document_kind, "quittance");
+    }
+
+    #[test]
+    fn partial_payment_yields_recu() {
+        let mut receipt = make_receipt(10, 2026);
+        receipt.amount_paid = BigDecimal::from_str("350.00").unwrap();
+        assert_eq!(ReceiptSnapshot::document_kind_for(&receipt), "recu");
+        receipt.amount_paid = BigDecimal::zero();
+        assert_eq!(ReceiptSnapshot::document_kind_for(&receipt), "unpaid");
+
+        let lease = make_lease(
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2027, 1, 1).unwrap(),
+        );
+        receipt.amount_paid = BigDecimal::from_str("350.00").unwrap();
+        let snapshot =
+            ReceiptSnapshot::from_lease_snapshot(&lease, &receipt, "recu-2026-10-17".to_string())
+                .expect("valid period");
+        assert_eq!(snapshot.amounts.balance_due, BigDecimal::from_str("350.00").unwrap());
+    }
+
+    #[test]
+    fn partial_last_month_ends_with_lease() {
+        let lease = make_lease(
+            NaiveDate::from_ymd_opt(2025, 11, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(),
+        );
+        let snapshot = ReceiptSnapshot::from_lease_snapshot(
+            &lease,
+            &make_receipt(10, 2026),
+            "quittance-2026-10-17".to_string(),
+        )
+        .expect("valid period");
+        assert_eq!(snapshot.period.covered_to, NaiveDate::from_ymd_opt(2026, 10, 20).unwrap());
+    }
+
+    #[test]
+    fn survives_json_round_trip() {
+        let lease = make_lease(
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2027, 1, 1).unwrap(),
+        );
+        let snapshot = ReceiptSnapshot::from_lease_snapshot(
+            &lease,
+            &make_receipt(3, 2026),
+            "quittance-2026-10-17".to_string(),
+        )
+        .expect("valid period");
+        let value = serde_json::to_value(&snapshot).unwrap();
+        let restored: ReceiptSnapshot = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.amounts.base_rent, snapshot.amounts.base_rent);
+        assert_eq!(restored.parties.lessees.len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_period() {
+        let lease = make_lease(
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2027, 1, 1).unwrap(),
+        );
+        let mut receipt = make_receipt(12, 2026);
+        receipt.period_month = 13;
+        assert!(ReceiptSnapshot::from_lease_snapshot(
+            &lease,
+            &receipt,
+            "quittance-2026-10-17".to_string(),
+        )
+        .is_none());
+    }
+}
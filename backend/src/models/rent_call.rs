@@ -0,0 +1,35 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Avis d'échéance: the amount called for one period, before it is due.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RentCall {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub period_month: i32,
+    pub period_year: i32,
+    pub due_date: NaiveDate,
+    pub base_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub regularisation: BigDecimal,
+    pub arrears: BigDecimal,
+    pub total_due: BigDecimal,
+    /// "issued", "sent" or "settled".
+    pub status: String,
+    /// Receipt that closed the notice once the period was fully paid.
+    pub receipt_id: Option<Uuid>,
+    pub email_sent_at: Option<DateTime<Utc>>,
+    pub pdf_path: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRentCall {
+    pub lease_id: Uuid,
+    pub period_month: i32,
+    pub period_year: i32,
+}
@@ -0,0 +1,168 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::canonical_snapshot::{CanonicalSnapshot, LesseeParty, PartiesSection};
+use crate::models::receipt_snapshot::ReceiptPeriod;
+use crate::models::rent_call::RentCall;
+
+/// Rent Call Snapshot
+///
+/// Frozen content of an avis d'échéance, captured when the notice is issued,
+/// so the copy sent to the tenants can be reproduced exactly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RentCallSnapshot {
+    pub id: Uuid,
+    pub rent_call_id: Uuid,
+    pub lease_id: Uuid,
+    pub template_version: String,  // e.g., "avis-echeance-2026-10-17"
+    pub generated_at: DateTime<Utc>,
+    pub language: String,  // "fr"
+
+    pub parties: PartiesSection,
+    pub property_address: String,
+    pub period: ReceiptPeriod,
+    pub due_date: NaiveDate,
+    pub amounts: RentCallAmounts,
+    /// Earlier periods still (partly) unpaid when the notice was issued.
+    pub arrears_lines: Vec<ArrearsLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RentCallAmounts {
+    pub base_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub regularisation: BigDecimal,
+    pub arrears: BigDecimal,
+    pub total_due: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ArrearsLine {
+    pub period_month: i32,
+    pub period_year: i32,
+    pub balance: BigDecimal,
+}
+
+impl RentCallSnapshot {
+    /// Build the notice snapshot from the lease parties and the rent call row.
+    /// Returns `None` when the period is not a valid month.
+    pub fn from_lease_snapshot(
+        lease: &CanonicalSnapshot,
+        rent_call: &RentCall,
+        arrears_lines: Vec<ArrearsLine>,
+        template_version: String,
+    ) -> Option<Self> {
+        let month_start = NaiveDate::from_ymd_opt(
+            rent_call.period_year,
+            u32::try_from(rent_call.period_month).ok()?,
+            1,
+        )?;
+        let month_end = (month_start + chrono::Months::new(1)).pred_opt()?;
+
+        let lease_start = lease.lease_terms.start_date;
+        let lease_end = lease.lease_terms.end_date;
+        let covered_from = month_start.max(lease_start);
+        let covered_to = if lease_end > lease_start {
+            month_end.min(lease_end)
+        } else {
+            month_end
+        };
+
+        let mut parties = lease.parties.clone();
+        if parties.lessees.is_empty() {
+            parties.lessees.push(LesseeParty {
+                full_name: parties.lessee_full_name.clone(),
+                address: parties.lessee_address.clone(),
+                email: parties.lessee_email.clone(),
+                birth_date: parties.lessee_birth_date,
+                birth_place: parties.lessee_birth_place.clone(),
+            });
+        }
+
+        Some(RentCallSnapshot {
+            id: Uuid::new_v4(),
+            rent_call_id: rent_call.id,
+            lease_id: rent_call.lease_id,
+            template_version,
+            generated_at: Utc::now(),
+            language: "fr".to_string(),
+            parties,
+            property_address: lease.property.address.clone(),
+            period: ReceiptPeriod {
+                month: month_start.month() as i32,
+                year: month_start.year(),
+                covered_from,
+                covered_to,
+            },
+            due_date: rent_call.due_date,
+            amounts: RentCallAmounts {
+                base_rent: rent_call.base_rent.clone(),
+                charges: rent_call.charges.clone(),
+                regularisation: rent_call.regularisation.clone(),
+                arrears: rent_call.arrears.clone(),
+                total_due: rent_call.total_due.clone(),
+            },
+            arrears_lines,
+        })
+    }
+
+    /// Names of every lessee, primary first.
+    pub fn lessee_names(&self) -> Vec<String> {
+        self.parties.lessees.iter().map(|l| l.full_name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn freezes_amounts_arrears_and_partial_first_month() {
+        let mut lease = CanonicalSnapshot::new(Uuid::new_v4(), "2026-06-18".to_string());
+        lease.parties.lessee_full_name = "Marie Martin".to_string();
+        lease.property.address = "1 rue de Paris".to_string();
+        lease.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        lease.lease_terms.end_date = NaiveDate::from_ymd_opt(2027, 10, 14).unwrap();
+
+        let rent_call = RentCall {
+            id: Uuid::new_v4(),
+            lease_id: lease.lease_id,
+            period_month: 10,
+            period_year: 2026,
+            due_date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+            base_rent: BigDecimal::from_str("340").unwrap(),
+            charges: BigDecimal::from_str("30").unwrap(),
+            regularisation: BigDecimal::from(0),
+            arrears: BigDecimal::from_str("120").unwrap(),
+            total_due: BigDecimal::from_str("490").unwrap(),
+            status: "issued".to_string(),
+            receipt_id: None,
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let arrears = vec![ArrearsLine {
+            period_month: 9,
+            period_year: 2026,
+            balance: BigDecimal::from_str("120").unwrap(),
+        }];
+
+        let snapshot = RentCallSnapshot::from_lease_snapshot(
+            &lease,
+            &rent_call,
+            arrears,
+            "avis-echeance-2026-10-17".to_string(),
+        )
+        .expect("valid period");
+
+        assert_eq!(snapshot.rent_call_id, rent_call.id);
+        assert_eq!(snapshot.period.covered_from, NaiveDate::from_ymd_opt(2026, 10, 15).unwrap());
+        assert_eq!(snapshot.amounts.total_due, BigDecimal::from(490));
+        assert_eq!(snapshot.arrears_lines.len(), 1);
+        assert_eq!(snapshot.lessee_names(), vec!["Marie Martin".to_string()]);
+    }
+}
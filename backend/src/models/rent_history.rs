@@ -0,0 +1,155 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A dated change of the rent and charges of a lease.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RentHistoryEntry {
+    pub id: Uuid,
+    pub lease_id: Uuid,
+    pub effective_date: NaiveDate,
+    pub monthly_rent: BigDecimal,
+    pub charges: BigDecimal,
+    /// "initial", "irl_revision", "adjustment" or "amendment".
+    pub reason: String,
+    pub previous_rent: Option<BigDecimal>,
+    pub irl_year: Option<i32>,
+    pub irl_quarter: Option<i32>,
+    pub irl_value: Option<BigDecimal>,
+    pub previous_irl_value: Option<BigDecimal>,
+    pub note: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RentRevisionRequest {
+    /// Date of the landlord's request; defaults to today.
+    pub requested_on: Option<NaiveDate>,
+}
+
+/// Manual change of the rent or charges from a given date, e.g. a new
+/// provision for charges after the yearly regularisation.
+#[derive(Debug, Deserialize)]
+pub struct CreateRentPeriod {
+    pub effective_date: NaiveDate,
+    pub monthly_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub note: Option<String>,
+}
+
+/// Monthly amounts in force from `effective_date` until the next period.
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize)]
+pub struct RentPeriod {
+    pub effective_date: NaiveDate,
+    pub monthly_rent: BigDecimal,
+    pub charges: BigDecimal,
+}
+
+pub fn prorated_amount(amount: &BigDecimal, covered_days: i64, days_in_month: i64) -> BigDecimal {
+    if covered_days <= 0 || days_in_month <= 0 {
+        return BigDecimal::from(0);
+    }
+
+    let covered = BigDecimal::from(covered_days);
+    let month_days = BigDecimal::from(days_in_month);
+    ((amount * covered) / month_days).with_scale(2)
+}
+
+/// Period in force on `on`. Days before the first period fall under it.
+pub fn in_force_on(periods: &[RentPeriod], on: NaiveDate) -> Option<&RentPeriod> {
+    periods
+        .iter()
+        .rev()
+        .find(|period| period.effective_date <= on)
+        .or_else(|| periods.first())
+}
+
+/// Rent and charges owed for the days `from..=to` of a month of
+/// `days_in_month` days, each period weighted by the days it covers. A change
+/// in the middle of the month is therefore split at its effective date.
+pub fn amounts_over(
+    periods: &[RentPeriod],
+    from: NaiveDate,
+    to: NaiveDate,
+    days_in_month: i64,
+) -> (BigDecimal, BigDecimal) {
+    let mut rent = BigDecimal::from(0);
+    let mut charges = BigDecimal::from(0);
+
+    for (index, period) in periods.iter().enumerate() {
+        let segment_start = if index == 0 { from } else { from.max(period.effective_date) };
+        let segment_end = match periods.get(index + 1).and_then(|next| next.effective_date.pred_opt()) {
+            Some(day_before_next) => to.min(day_before_next),
+            None => to,
+        };
+        if segment_start > segment_end {
+            continue;
+        }
+        let days = (segment_end - segment_start).num_days() + 1;
+        rent += prorated_amount(&period.monthly_rent, days, days_in_month);
+        charges += prorated_amount(&period.charges, days, days_in_month);
+    }
+
+    (rent, charges)
+}
+
+/// Expected amounts of one month of the lease, compared with what was invoiced.
+#[derive(Debug, Clone, Serialize)]
+pub struct RentScheduleLine {
+    pub period_month: i32,
+    pub period_year: i32,
+    pub base_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub total_amount: BigDecimal,
+    /// Total of the receipt issued for the month, if any.
+    pub invoiced_amount: Option<BigDecimal>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RentScheduleQuery {
+    pub year: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn period(effective_date: NaiveDate, rent: &str, charges: &str) -> RentPeriod {
+        RentPeriod {
+            effective_date,
+            monthly_rent: BigDecimal::from_str(rent).unwrap(),
+            charges: BigDecimal::from_str(charges).unwrap(),
+        }
+    }
+
+    #[test]
+    fn prorated_amount_uses_covered_days() {
+        let amount = prorated_amount(&BigDecimal::from(310), 10, 31);
+        assert_eq!(amount, BigDecimal::from_str("100.00").unwrap());
+        assert_eq!(prorated_amount(&BigDecimal::from(310), 0, 31), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn splits_a_month_at_a_mid_month_change() {
+        let periods = vec![
+            period(d(2025, 1, 1), "600.00", "40.00"),
+            period(d(2026, 4, 16), "620.00", "40.00"),
+        ];
+
+        let (rent, charges) = amounts_over(&periods, d(2026, 4, 1), d(2026, 4, 30), 30);
+        assert_eq!(rent, BigDecimal::from_str("610.00").unwrap());
+        assert_eq!(charges, BigDecimal::from_str("40.00").unwrap());
+
+        let (rent, _) = amounts_over(&periods, d(2026, 3, 1), d(2026, 3, 31), 31);
+        assert_eq!(rent, BigDecimal::from_str("600.00").unwrap());
+        assert_eq!(in_force_on(&periods, d(2026, 5, 1)).unwrap().monthly_rent, BigDecimal::from(620));
+        assert_eq!(in_force_on(&periods, d(2024, 5, 1)).unwrap().monthly_rent, BigDecimal::from(600));
+    }
+}
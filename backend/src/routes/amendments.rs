@@ -0,0 +1,367 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
+};
+use bigdecimal::{BigDecimal, num_traits::Signed};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::{CanonicalSnapshot, LesseeParty};
+use crate::models::lease_amendment::{AmendmentSnapshot, CreateLeaseAmendment, LeaseAmendment};
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, ensure_lease_compliant, fetch_lease_by_id, load_or_build_snapshot};
+use crate::routes::receipts::receipts_storage_dir;
+use crate::routes::rent_history::{ensure_rent_increase_allowed, rent_in_force};
+use crate::services::lease_amendment::{amend, change_lines, contract_view};
+use crate::services::lease_lifecycle::is_snapshot_frozen;
+use crate::services::pdf_renderer::PdfRenderer;
+
+/// Current avenant template version recorded in snapshots.
+const CURRENT_AMENDMENT_TEMPLATE_VERSION: &str = "avenant-2026-10-17";
+
+const AMENDMENT_COLUMNS: &str = r#"
+    id, lease_id, sequence_number, effective_date, summary, changes, pdf_path, created_at
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct ContractQuery {
+    /// Date the contract is read at; defaults to today.
+    pub as_of: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AmendmentPdfQuery {
+    inline: Option<bool>,
+}
+
+async fn fetch_amendments(db: &Database, lease_id: Uuid) -> Result<Vec<LeaseAmendment>, AppError> {
+    Ok(sqlx::query_as::<_, LeaseAmendment>(&format!(
+        "SELECT {} FROM lease_amendments WHERE lease_id = $1 ORDER BY sequence_number",
+        AMENDMENT_COLUMNS
+    ))
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?)
+}
+
+async fn fetch_amendment(db: &Database, lease_id: Uuid, id: Uuid) -> Result<LeaseAmendment, AppError> {
+    sqlx::query_as::<_, LeaseAmendment>(&format!(
+        "SELECT {} FROM lease_amendments WHERE id = $1 AND lease_id = $2",
+        AMENDMENT_COLUMNS
+    ))
+    .bind(id)
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Amendment not found".to_string()))
+}
+
+/// The signed contract with the amendments in force on `as_of`.
+pub(crate) async fn current_contract(
+    db: &Database,
+    lease_id: Uuid,
+    requesting_user_id: Uuid,
+    as_of: NaiveDate,
+) -> Result<CanonicalSnapshot, AppError> {
+    let signed = load_or_build_snapshot(db, lease_id, requesting_user_id).await?;
+    let amendments = fetch_amendments(db, lease_id).await?;
+    contract_view(
+        &signed,
+        amendments
+            .iter()
+            .filter(|amendment| amendment.effective_date <= as_of)
+            .map(|amendment| amendment.changes.0.as_slice()),
+    )
+}
+
+fn parse_amount(value: &str, label: &str) -> Result<BigDecimal, AppError> {
+    BigDecimal::from_str(value)
+        .map_err(|_| AppError::Validation(format!("{} must be a decimal amount", label)))
+}
+
+/// GET /api/leases/{id}/contract?as_of=
+/// The contract in force: the signed snapshot plus its amendments.
+pub async fn get_current_contract(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ContractQuery>,
+) -> Result<Json<CanonicalSnapshot>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let as_of = params.as_of.unwrap_or_else(|| Utc::now().date_naive());
+    Ok(Json(current_contract(&db, id, user_id, as_of).await?))
+}
+
+/// GET /api/leases/{id}/amendments
+pub async fn list_amendments(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<LeaseAmendment>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    Ok(Json(fetch_amendments(&db, id).await?))
+}
+
+/// POST /api/leases/{id}/amendments
+/// Record an avenant to a signed lease. The changes are a merge patch over the
+/// contract as it currently stands; the term and billing fields the lease row
+/// keeps cannot change. A new rent or charges amount is checked like the
+/// signed one and billed from the effective date.
+pub async fn create_amendment(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateLeaseAmendment>,
+) -> Result<(StatusCode, Json<LeaseAmendment>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    if !is_snapshot_frozen(&lease.status) {
+        return Err(AppError::Validation(
+            "An unsigned lease is edited directly, not through an amendment".to_string(),
+        ));
+    }
+    if matches!(lease.status.as_str(), "terminated" | "archived") {
+        return Err(AppError::Validation("A terminated lease cannot be amended".to_string()));
+    }
+    let summary = payload.summary.trim();
+    if summary.is_empty() {
+        return Err(AppError::Validation("An amendment requires a summary".to_string()));
+    }
+    if payload.effective_date < lease.start_date {
+        return Err(AppError::Validation(
+            "An amendment cannot take effect before the lease start date".to_string(),
+        ));
+    }
+    if lease.end_date.is_some_and(|end| payload.effective_date > end) {
+        return Err(AppError::Validation(
+            "An amendment cannot take effect after the lease end date".to_string(),
+        ));
+    }
+
+    let previous = fetch_amendments(&db, id).await?;
+    if let Some(last) = previous.last() {
+        if payload.effective_date < last.effective_date {
+            return Err(AppError::Validation(format!(
+                "Amendment n°{} already takes effect on {}; a later amendment cannot take effect before it",
+                last.sequence_number, last.effective_date
+            )));
+        }
+    }
+    let sequence_number = previous.last().map_or(1, |last| last.sequence_number + 1);
+
+    let signed = load_or_build_snapshot(&db, id, user_id).await?;
+    let contract = contract_view(&signed, previous.iter().map(|a| a.changes.0.as_slice()))?;
+    let (amended, changes) = amend(&contract, &payload.changes)?;
+
+    let rent_changed = changes.iter().any(|change| {
+        matches!(change.path.as_str(), "financial_terms.monthly_rent" | "financial_terms.charges_monthly")
+    });
+    let new_amounts = if rent_changed {
+        let monthly_rent = parse_amount(&amended.financial_terms.monthly_rent, "Monthly rent")?;
+        let charges = parse_amount(&amended.financial_terms.charges_monthly, "Charges")?;
+        if !monthly_rent.is_positive() || charges.is_negative() {
+            return Err(AppError::Validation(
+                "Rent must be positive and charges cannot be negative".to_string(),
+            ));
+        }
+        // The new amounts face the same rules as the signed ones: the
+        // compliance report, rent control and the IRL cap on increases.
+        let mut amended_lease = lease.clone();
+        amended_lease.monthly_rent = monthly_rent.clone();
+        amended_lease.charges = charges.clone();
+        ensure_lease_compliant(&db, &amended_lease).await?;
+        ensure_rent_increase_allowed(&db, &lease, payload.effective_date, &monthly_rent).await?;
+        Some((monthly_rent, charges))
+    } else {
+        None
+    };
+
+    let changes_json = serde_json::to_value(&changes).map_err(|e| {
+        tracing::error!("amendment serialization failed for lease {}: {}", id, e);
+        AppError::Internal
+    })?;
+
+    let mut tx = db.pool.begin().await?;
+
+    let amendment = sqlx::query_as::<_, LeaseAmendment>(&format!(
+        r#"
+        INSERT INTO lease_amendments (lease_id, sequence_number, effective_date, summary, changes)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING {}
+        "#,
+        AMENDMENT_COLUMNS
+    ))
+    .bind(id)
+    .bind(sequence_number)
+    .bind(payload.effective_date)
+    .bind(summary)
+    .bind(changes_json)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Some((monthly_rent, charges)) = &new_amounts {
+        let (previous_rent, _) = rent_in_force(&db, id, payload.effective_date).await?;
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO lease_rent_history (
+                lease_id, effective_date, monthly_rent, charges, reason, previous_rent, note
+            )
+            VALUES ($1, $2, $3, $4, 'amendment', $5, $6)
+            ON CONFLICT (lease_id, effective_date) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(payload.effective_date)
+        .bind(monthly_rent)
+        .bind(charges)
+        .bind(previous_rent)
+        .bind(format!("Avenant n°{}", sequence_number))
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Err(AppError::Validation(format!(
+                "The rent already changes on {}",
+                payload.effective_date
+            )));
+        }
+    }
+
+    // The avenant is signed by the parties as they stand once it applies.
+    let mut parties = amended.parties.clone();
+    if parties.lessees.is_empty() {
+        parties.lessees.push(LesseeParty {
+            full_name: parties.lessee_full_name.clone(),
+            address: parties.lessee_address.clone(),
+            email: parties.lessee_email.clone(),
+            birth_date: parties.lessee_birth_date,
+            birth_place: parties.lessee_birth_place.clone(),
+        });
+    }
+    let snapshot = AmendmentSnapshot {
+        id: Uuid::new_v4(),
+        amendment_id: amendment.id,
+        lease_id: id,
+        template_version: CURRENT_AMENDMENT_TEMPLATE_VERSION.to_string(),
+        generated_at: Utc::now(),
+        language: "fr".to_string(),
+        sequence_number,
+        effective_date: payload.effective_date,
+        summary: summary.to_string(),
+        parties,
+        property_address: amended.property.address.clone(),
+        lease_start_date: lease.start_date,
+        signed_at: lease.signed_at,
+        changes: change_lines(&changes),
+    };
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for amendment {}: {}", amendment.id, e);
+        AppError::Internal
+    })?;
+    sqlx::query("UPDATE lease_amendments SET snapshot = $1 WHERE id = $2")
+        .bind(snapshot_json)
+        .bind(amendment.id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(amendment)))
+}
+
+async fn load_amendment_snapshot(db: &Database, id: Uuid) -> Result<AmendmentSnapshot, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM lease_amendments WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten()
+    .ok_or_else(|| AppError::NotFound("Amendment document not found".to_string()))?;
+
+    serde_json::from_value::<AmendmentSnapshot>(stored).map_err(|e| {
+        tracing::error!("Stored snapshot for amendment {} is invalid: {}", id, e);
+        AppError::Internal
+    })
+}
+
+/// GET /api/leases/{id}/amendments/{amendment_id}/pdf
+/// The avenant document, rendered from its frozen snapshot on first access.
+pub async fn get_amendment_pdf(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((lease_id, amendment_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<AmendmentPdfQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+    let amendment = fetch_amendment(&db, lease_id, amendment_id).await?;
+
+    let stored = match amendment.pdf_path.as_deref() {
+        Some(path) => tokio::fs::read(path)
+            .await
+            .map_err(|e| tracing::warn!("Stored PDF {} for amendment {} is unreadable ({}); re-rendering", path, amendment_id, e))
+            .ok(),
+        None => None,
+    };
+
+    let pdf_bytes = match stored {
+        Some(bytes) => bytes,
+        None => {
+            let snapshot = load_amendment_snapshot(&db, amendment_id).await?;
+            let renderer = PdfRenderer::from_env().map_err(|e| {
+                tracing::error!("Failed to initialize PDF renderer: {}", e);
+                AppError::Internal
+            })?;
+            let bytes = renderer.generate_amendment_pdf(&snapshot).await.map_err(|e| {
+                tracing::error!("Amendment PDF generation failed for {}: {}", amendment_id, e);
+                AppError::BadRequest(format!("PDF generation failed: {}", e))
+            })?;
+
+            let storage_dir = receipts_storage_dir();
+            let pdf_path = storage_dir.join(format!("avenant_{}.pdf", amendment_id));
+            tokio::fs::create_dir_all(&storage_dir).await.map_err(|e| {
+                tracing::error!("Failed to create receipt storage dir {:?}: {}", storage_dir, e);
+                AppError::Internal
+            })?;
+            tokio::fs::write(&pdf_path, &bytes).await.map_err(|e| {
+                tracing::error!("Failed to store amendment PDF {:?}: {}", pdf_path, e);
+                AppError::Internal
+            })?;
+            sqlx::query("UPDATE lease_amendments SET pdf_path = $1 WHERE id = $2")
+                .bind(pdf_path.to_string_lossy().to_string())
+                .bind(amendment_id)
+                .execute(&db.pool)
+                .await?;
+
+            bytes
+        }
+    };
+
+    let filename = format!("avenant_{}_{}.pdf", amendment.sequence_number, lease_id);
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+    http::HeaderMap,
+};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::lease_deadline::LeaseDeadlines;
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, fetch_lease_by_id, get_property_landlord_facts};
+use crate::services::lease_renewal::{lease_deadlines, TermFacts};
+
+#[derive(Debug, Deserialize)]
+pub struct DeadlinesQuery {
+    /// Only deadlines falling within this many days, overdue ones included
+    /// (default 90).
+    pub within_days: Option<i64>,
+}
+
+async fn deadlines_for(db: &Database, lease_id: Uuid, today: NaiveDate) -> Result<LeaseDeadlines, AppError> {
+    let lease = fetch_lease_by_id(db, lease_id).await?;
+    let notice_effective_date = sqlx::query_scalar::<_, NaiveDate>(
+        "SELECT effective_date FROM lease_notices WHERE lease_id = $1 AND status = 'active'",
+    )
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    let landlord = get_property_landlord_facts(db, lease.property_id).await?;
+
+    Ok(lease_deadlines(
+        lease.id,
+        &TermFacts::from_lease(&lease, landlord.is_legal_person, notice_effective_date),
+        today,
+    ))
+}
+
+/// GET /api/leases/{id}/deadlines
+/// Term end, landlord notice window, IRL revision and diagnostic expiries.
+pub async fn get_lease_deadlines(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<LeaseDeadlines>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    Ok(Json(deadlines_for(&db, id, Utc::now().date_naive()).await?))
+}
+
+/// GET /api/leases/deadlines?within_days=
+/// Upcoming deadlines of every signed or in-force lease the user manages.
+/// Leases with nothing due in the window are left out.
+pub async fn list_upcoming_deadlines(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Query(params): Query<DeadlinesQuery>,
+) -> Result<Json<Vec<LeaseDeadlines>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let within_days = params.within_days.unwrap_or(90);
+    if within_days < 0 {
+        return Err(AppError::Validation("within_days cannot be negative".to_string()));
+    }
+
+    let lease_ids = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT l.id
+        FROM leases l
+        JOIN properties p ON l.property_id = p.id
+        WHERE l.status IN ('signed', 'active', 'notice_given')
+          AND (
+              p.user_id = $1 OR EXISTS (
+                  SELECT 1
+                  FROM organization_members om
+                  WHERE om.organization_id = p.organization_id
+                      AND om.user_id = $1
+              )
+          )
+        ORDER BY l.start_date
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let today = Utc::now().date_naive();
+    let mut upcoming = Vec::new();
+    for lease_id in lease_ids {
+        let mut deadlines = deadlines_for(&db, lease_id, today).await?;
+        deadlines.deadlines.retain(|deadline| deadline.days_remaining <= within_days);
+        if !deadlines.deadlines.is_empty() {
+            upcoming.push(deadlines);
+        }
+    }
+
+    Ok(Json(upcoming))
+}
@@ -0,0 +1,568 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
+};
+use bigdecimal::{BigDecimal, num_traits::Signed};
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::LesseeParty;
+use crate::models::deposit::{
+    CreateDeposit, CreateDepositDeduction, Deposit, DepositDeduction, DepositDetail, DepositSnapshot,
+    PendingDepositReturn, RecordDepositExit, ReturnDeposit,
+};
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, fetch_lease_by_id};
+use crate::routes::payments::{ensure_payment_removable, reconcile_lease};
+use crate::routes::receipts::{current_lease_snapshot, receipts_storage_dir};
+use crate::routes::rent_history::rent_in_force;
+use crate::services::deposit::{credits_ledger, return_deadline, settle, DEDUCTION_CATEGORIES};
+use crate::services::pdf_renderer::PdfRenderer;
+
+/// Current deposit settlement letter template version recorded in snapshots.
+const CURRENT_DEPOSIT_TEMPLATE_VERSION: &str = "restitution-depot-2026-10-17";
+
+const PAYMENT_METHODS: [&str; 5] = ["virement", "cheque", "especes", "prelevement", "autre"];
+
+pub fn router() -> Router<Database> {
+    Router::new()
+        .route("/", post(create_deposit))
+        .route("/pending-returns", get(list_pending_returns))
+        .route("/lease/:lease_id", get(get_lease_deposit))
+        .route("/:id/exit", put(record_exit))
+        .route("/:id/deductions", post(add_deduction))
+        .route("/:id/deductions/:deduction_id", axum::routing::delete(delete_deduction))
+        .route("/:id/return", post(return_deposit))
+        .route("/:id/letter", get(get_settlement_letter))
+}
+
+#[derive(Debug, Deserialize)]
+struct LetterQuery {
+    inline: Option<bool>,
+}
+
+const DEPOSIT_COLUMNS: &str = r#"
+    id, lease_id, amount, received_on, received_method, keys_returned_on,
+    exit_inventory_conforms, return_deadline, returned_on, returned_amount, late_penalty,
+    status, pdf_path, created_at, updated_at
+"#;
+
+const DEDUCTION_COLUMNS: &str = r#"
+    id, deposit_id, category, label, amount, receipt_id, justification, created_at
+"#;
+
+async fn fetch_deposit_by_id(db: &Database, id: Uuid) -> Result<Deposit, AppError> {
+    sqlx::query_as::<_, Deposit>(&format!(
+        "SELECT {} FROM lease_deposits WHERE id = $1",
+        DEPOSIT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Deposit not found".to_string()))
+}
+
+async fn fetch_deductions(db: &Database, deposit_id: Uuid) -> Result<Vec<DepositDeduction>, AppError> {
+    let deductions = sqlx::query_as::<_, DepositDeduction>(&format!(
+        "SELECT {} FROM deposit_deductions WHERE deposit_id = $1 ORDER BY created_at",
+        DEDUCTION_COLUMNS
+    ))
+    .bind(deposit_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(deductions)
+}
+
+/// Deposit with its deductions, settled as of its return date, or today
+/// while it is still held.
+async fn deposit_detail(db: &Database, deposit: Deposit) -> Result<DepositDetail, AppError> {
+    let deductions = fetch_deductions(db, deposit.id).await?;
+    let on = deposit.returned_on.unwrap_or_else(|| Utc::now().date_naive());
+    let (monthly_rent, _) = rent_in_force(db, deposit.lease_id, on).await?;
+    let settlement = settle(&deposit.amount, &deductions, deposit.return_deadline, &monthly_rent, on);
+
+    Ok(DepositDetail { deposit, deductions, settlement })
+}
+
+fn ensure_not_returned(deposit: &Deposit) -> Result<(), AppError> {
+    if deposit.status == "returned" {
+        return Err(AppError::Validation("This deposit has already been returned".to_string()));
+    }
+    Ok(())
+}
+
+/// POST /api/deposits
+/// Record the collection of the deposit stated in the lease.
+async fn create_deposit(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateDeposit>,
+) -> Result<(StatusCode, Json<DepositDetail>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, payload.lease_id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, payload.lease_id).await?;
+
+    let amount = payload.amount.unwrap_or_else(|| lease.deposit.clone());
+    if amount.is_negative() {
+        return Err(AppError::Validation("Deposit cannot be negative".to_string()));
+    }
+    if amount > lease.deposit {
+        return Err(AppError::Validation(format!(
+            "The deposit collected cannot exceed the {} stated in the lease",
+            lease.deposit
+        )));
+    }
+    let method = payload.received_method.unwrap_or_else(|| "virement".to_string());
+    if !PAYMENT_METHODS.contains(&method.as_str()) {
+        return Err(AppError::Validation(format!("Unknown payment method '{}'", method)));
+    }
+
+    let deposit = sqlx::query_as::<_, Deposit>(&format!(
+        r#"
+        INSERT INTO lease_deposits (lease_id, amount, received_on, received_method)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (lease_id) DO NOTHING
+        RETURNING {}
+        "#,
+        DEPOSIT_COLUMNS
+    ))
+    .bind(lease.id)
+    .bind(&amount)
+    .bind(payload.received_on)
+    .bind(&method)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::Validation("The deposit of this lease is already recorded".to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(deposit_detail(&db, deposit).await?)))
+}
+
+/// GET /api/deposits/lease/{lease_id}
+async fn get_lease_deposit(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(lease_id): Path<Uuid>,
+) -> Result<Json<DepositDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+
+    let deposit = sqlx::query_as::<_, Deposit>(&format!(
+        "SELECT {} FROM lease_deposits WHERE lease_id = $1",
+        DEPOSIT_COLUMNS
+    ))
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("No deposit recorded for this lease".to_string()))?;
+
+    Ok(Json(deposit_detail(&db, deposit).await?))
+}
+
+/// GET /api/deposits/pending-returns
+/// Deposits to return across the user's leases, most urgent deadline first.
+async fn list_pending_returns(
+    State(db): State<Database>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<PendingDepositReturn>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+
+    let pending = sqlx::query_as::<_, PendingDepositReturn>(
+        r#"
+        SELECT DISTINCT d.id AS deposit_id, d.lease_id, p.address AS property_address,
+               d.keys_returned_on, d.return_deadline
+        FROM lease_deposits d
+        JOIN leases l ON l.id = d.lease_id
+        JOIN properties p ON p.id = l.property_id
+        LEFT JOIN organization_members om ON p.organization_id = om.organization_id
+        WHERE d.status = 'awaiting_return'
+          AND (p.user_id = $1 OR om.user_id = $1)
+        ORDER BY d.return_deadline
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(pending))
+}
+
+/// PUT /api/deposits/{id}/exit
+/// Record the return of the keys, which starts the return deadline.
+async fn record_exit(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RecordDepositExit>,
+) -> Result<Json<DepositDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let deposit = fetch_deposit_by_id(&db, id).await?;
+    ensure_lease_access(&db, deposit.lease_id, user_id).await?;
+    ensure_not_returned(&deposit)?;
+
+    if payload.keys_returned_on < deposit.received_on {
+        return Err(AppError::Validation(
+            "Keys cannot be returned before the deposit was received".to_string(),
+        ));
+    }
+    let deadline = return_deadline(payload.keys_returned_on, payload.exit_inventory_conforms)
+        .ok_or_else(|| AppError::Validation("Invalid key return date".to_string()))?;
+
+    let deposit = sqlx::query_as::<_, Deposit>(&format!(
+        r#"
+        UPDATE lease_deposits
+        SET keys_returned_on = $2, exit_inventory_conforms = $3, return_deadline = $4,
+            status = 'awaiting_return', updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING {}
+        "#,
+        DEPOSIT_COLUMNS
+    ))
+    .bind(id)
+    .bind(payload.keys_returned_on)
+    .bind(payload.exit_inventory_conforms)
+    .bind(deadline)
+    .fetch_one(&db.pool)
+    .await?;
+
+    Ok(Json(deposit_detail(&db, deposit).await?))
+}
+
+/// POST /api/deposits/{id}/deductions
+/// Unpaid rent or charges may point at the receipt they settle, in which case
+/// the amount defaults to that receipt's outstanding balance and is recorded
+/// on the payments ledger against it, in the same transaction.
+async fn add_deduction(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateDepositDeduction>,
+) -> Result<(StatusCode, Json<DepositDetail>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let deposit = fetch_deposit_by_id(&db, id).await?;
+    ensure_lease_access(&db, deposit.lease_id, user_id).await?;
+    ensure_not_returned(&deposit)?;
+
+    if !DEDUCTION_CATEGORIES.contains(&payload.category.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Unknown deduction category '{}'",
+            payload.category
+        )));
+    }
+    let label = payload.label.trim();
+    if label.is_empty() {
+        return Err(AppError::Validation("Deduction label is required".to_string()));
+    }
+
+    let receipt_balance = match payload.receipt_id {
+        Some(receipt_id) => Some(
+            sqlx::query_scalar::<_, BigDecimal>(
+                "SELECT total_amount - amount_paid FROM receipts WHERE id = $1 AND lease_id = $2",
+            )
+            .bind(receipt_id)
+            .bind(deposit.lease_id)
+            .fetch_optional(&db.pool)
+            .await?
+            .ok_or_else(|| AppError::Validation("Receipt does not belong to this lease".to_string()))?,
+        ),
+        None => None,
+    };
+    let amount = payload
+        .amount
+        .or_else(|| receipt_balance.clone())
+        .ok_or_else(|| AppError::Validation("Deduction amount is required".to_string()))?;
+    if !amount.is_positive() {
+        return Err(AppError::Validation("Deduction amount must be positive".to_string()));
+    }
+
+    let credits_ledger = credits_ledger(&payload.category, payload.receipt_id);
+    if credits_ledger && receipt_balance.as_ref().is_some_and(|balance| &amount > balance) {
+        return Err(AppError::Validation(
+            "Deduction exceeds the outstanding balance of the receipt".to_string(),
+        ));
+    }
+
+    let mut tx = db.pool.begin().await?;
+
+    let payment_id = if credits_ledger {
+        Some(
+            sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO payments (lease_id, receipt_id, amount, payment_date, method, reference)
+                VALUES ($1, $2, $3, CURRENT_DATE, 'depot_garantie', $4)
+                RETURNING id
+                "#,
+            )
+            .bind(deposit.lease_id)
+            .bind(payload.receipt_id)
+            .bind(&amount)
+            .bind(format!("Retenue sur le dépôt de garantie : {}", label))
+            .fetch_one(&mut *tx)
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO deposit_deductions (deposit_id, category, label, amount, receipt_id, justification, payment_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(id)
+    .bind(&payload.category)
+    .bind(label)
+    .bind(&amount)
+    .bind(payload.receipt_id)
+    .bind(payload.justification.as_deref().map(str::trim).filter(|j| !j.is_empty()))
+    .bind(payment_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if payment_id.is_some() {
+        reconcile_lease(&db, deposit.lease_id, user_id).await?;
+    }
+
+    Ok((StatusCode::CREATED, Json(deposit_detail(&db, deposit).await?)))
+}
+
+/// DELETE /api/deposits/{id}/deductions/{deduction_id}
+async fn delete_deduction(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((id, deduction_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DepositDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let deposit = fetch_deposit_by_id(&db, id).await?;
+    ensure_lease_access(&db, deposit.lease_id, user_id).await?;
+    ensure_not_returned(&deposit)?;
+
+    let deduction_payment = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT payment_id FROM deposit_deductions WHERE id = $1 AND deposit_id = $2",
+    )
+    .bind(deduction_id)
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten();
+    if let Some(payment_id) = deduction_payment {
+        ensure_payment_removable(&db, payment_id).await?;
+    }
+
+    let mut tx = db.pool.begin().await?;
+    let payment_id = sqlx::query_scalar::<_, Option<Uuid>>(
+        "DELETE FROM deposit_deductions WHERE id = $1 AND deposit_id = $2 RETURNING payment_id",
+    )
+    .bind(deduction_id)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Deduction not found".to_string()))?;
+
+    // The period is owed again once nothing is kept from the deposit for it.
+    if let Some(payment_id) = payment_id {
+        sqlx::query("DELETE FROM payments WHERE id = $1")
+            .bind(payment_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    if payment_id.is_some() {
+        reconcile_lease(&db, deposit.lease_id, user_id).await?;
+    }
+
+    Ok(Json(deposit_detail(&db, deposit).await?))
+}
+
+/// POST /api/deposits/{id}/return
+/// Settle the deposit on the return date, including any late penalty, and
+/// freeze the settlement letter.
+async fn return_deposit(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReturnDeposit>,
+) -> Result<Json<DepositDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let deposit = fetch_deposit_by_id(&db, id).await?;
+    ensure_lease_access(&db, deposit.lease_id, user_id).await?;
+    ensure_not_returned(&deposit)?;
+
+    let Some(keys_returned_on) = deposit.keys_returned_on else {
+        return Err(AppError::Validation(
+            "Record the return of the keys before returning the deposit".to_string(),
+        ));
+    };
+    if payload.returned_on < keys_returned_on {
+        return Err(AppError::Validation(
+            "The deposit cannot be returned before the keys".to_string(),
+        ));
+    }
+
+    let deductions = fetch_deductions(&db, id).await?;
+    let (monthly_rent, _) = rent_in_force(&db, deposit.lease_id, payload.returned_on).await?;
+    let settlement = settle(
+        &deposit.amount,
+        &deductions,
+        deposit.return_deadline,
+        &monthly_rent,
+        payload.returned_on,
+    );
+
+    let lease = fetch_lease_by_id(&db, deposit.lease_id).await?;
+    let lease_snapshot = current_lease_snapshot(&db, deposit.lease_id, user_id).await?;
+    let mut parties = lease_snapshot.parties.clone();
+    if parties.lessees.is_empty() {
+        parties.lessees.push(LesseeParty {
+            full_name: parties.lessee_full_name.clone(),
+            address: parties.lessee_address.clone(),
+            email: parties.lessee_email.clone(),
+            birth_date: parties.lessee_birth_date,
+            birth_place: parties.lessee_birth_place.clone(),
+        });
+    }
+    let snapshot = DepositSnapshot {
+        id: Uuid::new_v4(),
+        deposit_id: id,
+        lease_id: deposit.lease_id,
+        template_version: CURRENT_DEPOSIT_TEMPLATE_VERSION.to_string(),
+        generated_at: Utc::now(),
+        language: "fr".to_string(),
+        parties,
+        property_address: lease_snapshot.property.address.clone(),
+        lease_start_date: lease.start_date,
+        keys_returned_on: deposit.keys_returned_on,
+        exit_inventory_conforms: deposit.exit_inventory_conforms,
+        deductions,
+        settlement,
+    };
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for deposit {}: {}", id, e);
+        AppError::Internal
+    })?;
+
+    let deposit = sqlx::query_as::<_, Deposit>(&format!(
+        r#"
+        UPDATE lease_deposits
+        SET status = 'returned', returned_on = $2, returned_amount = $3, late_penalty = $4,
+            snapshot = $5, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING {}
+        "#,
+        DEPOSIT_COLUMNS
+    ))
+    .bind(id)
+    .bind(payload.returned_on)
+    .bind(&snapshot.settlement.total_due_to_tenant)
+    .bind(&snapshot.settlement.late_penalty)
+    .bind(snapshot_json)
+    .fetch_one(&db.pool)
+    .await?;
+
+    Ok(Json(DepositDetail {
+        deposit,
+        deductions: snapshot.deductions,
+        settlement: snapshot.settlement,
+    }))
+}
+
+async fn load_snapshot(db: &Database, id: Uuid) -> Result<Option<DepositSnapshot>, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM lease_deposits WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten();
+
+    Ok(stored.and_then(|value| match serde_json::from_value::<DepositSnapshot>(value) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            tracing::warn!("Stored snapshot for deposit {} is invalid: {}", id, e);
+            None
+        }
+    }))
+}
+
+/// GET /api/deposits/{id}/letter
+/// The settlement letter, once the deposit is returned.
+async fn get_settlement_letter(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<LetterQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let deposit = fetch_deposit_by_id(&db, id).await?;
+    ensure_lease_access(&db, deposit.lease_id, user_id).await?;
+
+    let stored = match deposit.pdf_path.as_deref() {
+        Some(path) => tokio::fs::read(path)
+            .await
+            .map_err(|e| tracing::warn!("Stored PDF {} for deposit {} is unreadable ({}); re-rendering", path, id, e))
+            .ok(),
+        None => None,
+    };
+
+    let pdf_bytes = match stored {
+        Some(bytes) => bytes,
+        None => {
+            let snapshot = load_snapshot(&db, id).await?.ok_or_else(|| {
+                AppError::Validation("The settlement letter is issued when the deposit is returned".to_string())
+            })?;
+
+            let renderer = PdfRenderer::from_env().map_err(|e| {
+                tracing::error!("Failed to initialize PDF renderer: {}", e);
+                AppError::Internal
+            })?;
+            let bytes = renderer.generate_deposit_settlement_pdf(&snapshot).await.map_err(|e| {
+                tracing::error!("Deposit letter PDF generation failed for {}: {}", id, e);
+                AppError::BadRequest(format!("PDF generation failed: {}", e))
+            })?;
+
+            let storage_dir = receipts_storage_dir();
+            let pdf_path = storage_dir.join(format!("restitution_depot_{}.pdf", id));
+            tokio::fs::create_dir_all(&storage_dir).await.map_err(|e| {
+                tracing::error!("Failed to create receipt storage dir {:?}: {}", storage_dir, e);
+                AppError::Internal
+            })?;
+            tokio::fs::write(&pdf_path, &bytes).await.map_err(|e| {
+                tracing::error!("Failed to store deposit letter PDF {:?}: {}", pdf_path, e);
+                AppError::Internal
+            })?;
+            sqlx::query("UPDATE lease_deposits SET pdf_path = $1 WHERE id = $2")
+                .bind(pdf_path.to_string_lossy().to_string())
+                .bind(id)
+                .execute(&db.pool)
+                .await?;
+
+            bytes
+        }
+    };
+
+    let filename = format!("restitution_depot_{}.pdf", id);
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
@@ -0,0 +1,383 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
+};
+use bigdecimal::num_traits::Signed;
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::LesseeParty;
+use crate::models::deposit::DepositDeduction;
+use crate::models::inventory::{
+    CreateDegradationDeductions, InventoryComparison, InventorySnapshot, LeaseInventory, SaveLeaseInventory,
+    INVENTORY_KINDS,
+};
+use crate::routes::amendments::current_contract;
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, fetch_lease_by_id};
+use crate::routes::receipts::receipts_storage_dir;
+use crate::services::inventory::{deduction_label, degradations, find_degradation, missing_keys, validate};
+use crate::services::pdf_renderer::PdfRenderer;
+
+/// Current état des lieux template version recorded in snapshots.
+const CURRENT_INVENTORY_TEMPLATE_VERSION: &str = "etat-des-lieux-2026-10-17";
+
+const INVENTORY_COLUMNS: &str = r#"
+    id, lease_id, kind, performed_on, rooms, meters, keys, general_comment,
+    pdf_path, created_at, updated_at
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct InventoryPdfQuery {
+    inline: Option<bool>,
+}
+
+fn ensure_kind(kind: &str) -> Result<(), AppError> {
+    if INVENTORY_KINDS.contains(&kind) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "Unknown inventory '{}'; use entry or exit",
+            kind
+        )))
+    }
+}
+
+async fn fetch_inventory(db: &Database, lease_id: Uuid, kind: &str) -> Result<Option<LeaseInventory>, AppError> {
+    Ok(sqlx::query_as::<_, LeaseInventory>(&format!(
+        "SELECT {} FROM lease_inventories WHERE lease_id = $1 AND kind = $2",
+        INVENTORY_COLUMNS
+    ))
+    .bind(lease_id)
+    .bind(kind)
+    .fetch_optional(&db.pool)
+    .await?)
+}
+
+async fn compare(db: &Database, lease_id: Uuid) -> Result<(LeaseInventory, InventoryComparison), AppError> {
+    let entry = fetch_inventory(db, lease_id, "entry")
+        .await?
+        .ok_or_else(|| AppError::NotFound("No entry inventory for this lease".to_string()))?;
+    let exit = fetch_inventory(db, lease_id, "exit")
+        .await?
+        .ok_or_else(|| AppError::NotFound("No exit inventory for this lease".to_string()))?;
+
+    let degradations = degradations(&entry.rooms.0, &exit.rooms.0);
+    let missing_keys = missing_keys(&entry.keys.0, &exit.keys.0);
+    let comparison = InventoryComparison {
+        entry_id: entry.id,
+        exit_id: exit.id,
+        conforms: degradations.is_empty() && missing_keys.is_empty(),
+        degradations,
+        missing_keys,
+    };
+    Ok((exit, comparison))
+}
+
+/// GET /api/leases/{id}/inventories
+/// The entry and exit inventories recorded for the lease.
+pub async fn list_inventories(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<LeaseInventory>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let inventories = sqlx::query_as::<_, LeaseInventory>(&format!(
+        "SELECT {} FROM lease_inventories WHERE lease_id = $1 ORDER BY performed_on, kind",
+        INVENTORY_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(inventories))
+}
+
+/// PUT /api/leases/{id}/inventories/{kind}
+/// Record or replace the entry or exit inventory. Its document is rebuilt
+/// from the new content and rendered again on next download.
+pub async fn save_inventory(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((id, kind)): Path<(Uuid, String)>,
+    Json(payload): Json<SaveLeaseInventory>,
+) -> Result<Json<LeaseInventory>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    ensure_kind(&kind)?;
+    validate(&payload)?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    let other_kind = if kind == "entry" { "exit" } else { "entry" };
+    if let Some(other) = fetch_inventory(&db, id, other_kind).await? {
+        let out_of_order = if kind == "entry" {
+            payload.performed_on > other.performed_on
+        } else {
+            payload.performed_on < other.performed_on
+        };
+        if out_of_order {
+            return Err(AppError::Validation(format!(
+                "The exit inventory cannot predate the entry inventory of {}",
+                if kind == "entry" { payload.performed_on } else { other.performed_on }
+            )));
+        }
+    }
+
+    // The document names the parties as they stand on the day of the visit.
+    let contract = current_contract(&db, id, user_id, payload.performed_on.max(lease.start_date)).await?;
+    let general_comment = payload
+        .general_comment
+        .as_deref()
+        .map(str::trim)
+        .filter(|comment| !comment.is_empty());
+
+    let mut tx = db.pool.begin().await?;
+
+    let inventory = sqlx::query_as::<_, LeaseInventory>(&format!(
+        r#"
+        INSERT INTO lease_inventories (lease_id, kind, performed_on, rooms, meters, keys, general_comment)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (lease_id, kind) DO UPDATE SET
+            performed_on = EXCLUDED.performed_on,
+            rooms = EXCLUDED.rooms,
+            meters = EXCLUDED.meters,
+            keys = EXCLUDED.keys,
+            general_comment = EXCLUDED.general_comment,
+            pdf_path = NULL,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING {}
+        "#,
+        INVENTORY_COLUMNS
+    ))
+    .bind(id)
+    .bind(&kind)
+    .bind(payload.performed_on)
+    .bind(sqlx::types::Json(&payload.rooms))
+    .bind(sqlx::types::Json(&payload.meters))
+    .bind(sqlx::types::Json(&payload.keys))
+    .bind(general_comment)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let mut parties = contract.parties.clone();
+    if parties.lessees.is_empty() {
+        parties.lessees.push(LesseeParty {
+            full_name: parties.lessee_full_name.clone(),
+            address: parties.lessee_address.clone(),
+            email: parties.lessee_email.clone(),
+            birth_date: parties.lessee_birth_date,
+            birth_place: parties.lessee_birth_place.clone(),
+        });
+    }
+    let snapshot = InventorySnapshot {
+        id: Uuid::new_v4(),
+        inventory_id: inventory.id,
+        lease_id: id,
+        template_version: CURRENT_INVENTORY_TEMPLATE_VERSION.to_string(),
+        generated_at: Utc::now(),
+        language: "fr".to_string(),
+        kind: kind.clone(),
+        performed_on: payload.performed_on,
+        parties,
+        property_address: contract.property.address.clone(),
+        lease_start_date: lease.start_date,
+        rooms: payload.rooms,
+        meters: payload.meters,
+        keys: payload.keys,
+        general_comment: general_comment.map(str::to_string),
+    };
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for inventory {}: {}", inventory.id, e);
+        AppError::Internal
+    })?;
+    sqlx::query("UPDATE lease_inventories SET snapshot = $1 WHERE id = $2")
+        .bind(snapshot_json)
+        .bind(inventory.id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(inventory))
+}
+
+/// GET /api/leases/{id}/inventories/comparison
+/// Elements degraded or missing at exit and keys not returned.
+pub async fn compare_inventories(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InventoryComparison>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let (_, comparison) = compare(&db, id).await?;
+    Ok(Json(comparison))
+}
+
+/// POST /api/leases/{id}/inventories/comparison/deductions
+/// Charge the repair of degradations found at exit to the deposit, one
+/// "repairs" deduction per degradation, justified by the exit inventory.
+pub async fn create_degradation_deductions(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateDegradationDeductions>,
+) -> Result<(StatusCode, Json<Vec<DepositDeduction>>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    if payload.charges.is_empty() {
+        return Err(AppError::Validation("No degradation to charge".to_string()));
+    }
+    let (exit, comparison) = compare(&db, id).await?;
+
+    let deposit_id = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM lease_deposits WHERE lease_id = $1 AND status <> 'returned'",
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::Validation("No deposit awaiting settlement for this lease".to_string()))?;
+
+    let mut lines = Vec::with_capacity(payload.charges.len());
+    for charge in &payload.charges {
+        let degradation = find_degradation(&comparison.degradations, &charge.room, &charge.element)
+            .ok_or_else(|| {
+                AppError::Validation(format!(
+                    "'{}' in '{}' is not degraded according to the inventories",
+                    charge.element, charge.room
+                ))
+            })?;
+        if !charge.amount.is_positive() {
+            return Err(AppError::Validation("Deduction amount must be positive".to_string()));
+        }
+        let justification = match degradation.exit_comment.as_deref() {
+            Some(comment) => format!("État des lieux de sortie du {} : {}", exit.performed_on, comment),
+            None => format!("État des lieux de sortie du {}", exit.performed_on),
+        };
+        lines.push((deduction_label(degradation), &charge.amount, justification));
+    }
+
+    let mut tx = db.pool.begin().await?;
+    let mut deductions = Vec::with_capacity(lines.len());
+    for (label, amount, justification) in lines {
+        deductions.push(
+            sqlx::query_as::<_, DepositDeduction>(
+                r#"
+                INSERT INTO deposit_deductions (deposit_id, category, label, amount, justification)
+                VALUES ($1, 'repairs', $2, $3, $4)
+                RETURNING id, deposit_id, category, label, amount, receipt_id, justification, created_at
+                "#,
+            )
+            .bind(deposit_id)
+            .bind(label)
+            .bind(amount)
+            .bind(justification)
+            .fetch_one(&mut *tx)
+            .await?,
+        );
+    }
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(deductions)))
+}
+
+async fn load_inventory_snapshot(db: &Database, id: Uuid) -> Result<InventorySnapshot, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM lease_inventories WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten()
+    .ok_or_else(|| AppError::NotFound("Inventory document not found".to_string()))?;
+
+    serde_json::from_value::<InventorySnapshot>(stored).map_err(|e| {
+        tracing::error!("Stored snapshot for inventory {} is invalid: {}", id, e);
+        AppError::Internal
+    })
+}
+
+/// GET /api/leases/{id}/inventories/{kind}/pdf
+/// The état des lieux document, rendered from its snapshot on first access.
+pub async fn get_inventory_pdf(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((lease_id, kind)): Path<(Uuid, String)>,
+    Query(params): Query<InventoryPdfQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+    ensure_kind(&kind)?;
+    let inventory = fetch_inventory(&db, lease_id, &kind)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Inventory not found".to_string()))?;
+
+    let stored = match inventory.pdf_path.as_deref() {
+        Some(path) => tokio::fs::read(path)
+            .await
+            .map_err(|e| tracing::warn!("Stored PDF {} for inventory {} is unreadable ({}); re-rendering", path, inventory.id, e))
+            .ok(),
+        None => None,
+    };
+
+    let pdf_bytes = match stored {
+        Some(bytes) => bytes,
+        None => {
+            let snapshot = load_inventory_snapshot(&db, inventory.id).await?;
+            let renderer = PdfRenderer::from_env().map_err(|e| {
+                tracing::error!("Failed to initialize PDF renderer: {}", e);
+                AppError::Internal
+            })?;
+            let bytes = renderer.generate_inventory_pdf(&snapshot).await.map_err(|e| {
+                tracing::error!("Inventory PDF generation failed for {}: {}", inventory.id, e);
+                AppError::BadRequest(format!("PDF generation failed: {}", e))
+            })?;
+
+            let storage_dir = receipts_storage_dir();
+            let pdf_path = storage_dir.join(format!("etat_des_lieux_{}.pdf", inventory.id));
+            tokio::fs::create_dir_all(&storage_dir).await.map_err(|e| {
+                tracing::error!("Failed to create receipt storage dir {:?}: {}", storage_dir, e);
+                AppError::Internal
+            })?;
+            tokio::fs::write(&pdf_path, &bytes).await.map_err(|e| {
+                tracing::error!("Failed to store inventory PDF {:?}: {}", pdf_path, e);
+                AppError::Internal
+            })?;
+            sqlx::query("UPDATE lease_inventories SET pdf_path = $1 WHERE id = $2")
+                .bind(pdf_path.to_string_lossy().to_string())
+                .bind(inventory.id)
+                .execute(&db.pool)
+                .await?;
+
+            bytes
+        }
+    };
+
+    let filename = format!(
+        "etat_des_lieux_{}_{}.pdf",
+        if kind == "entry" { "entree" } else { "sortie" },
+        lease_id
+    );
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
@@ -0,0 +1,26 @@
+use axum::{Router, routing::get, extract::State, Json, http::HeaderMap};
+use crate::db::Database;
+use crate::error::AppError;
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::services::irl::IrlIndex;
+
+pub fn router() -> Router<Database> {
+    Router::new().route("/", get(list_irl_indices))
+}
+
+/// GET /api/irl
+/// The published IRL series, most recent quarter first.
+async fn list_irl_indices(
+    State(db): State<Database>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<IrlIndex>>, AppError> {
+    extract_user_id_from_headers(&headers)?;
+
+    let indices = sqlx::query_as::<_, IrlIndex>(
+        "SELECT year, quarter, value FROM irl_indices ORDER BY year DESC, quarter DESC",
+    )
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(indices))
+}
@@ -1,255 +1,234 @@
-use axum::{Router, routing::get, extract::{State, Path, Query}, Json, http::{StatusCode, HeaderMap, header}, response::Response, body::Body};
+use axum::{Router, routing::{get, post}, extract::{State, Path, Query}, Json, http::{StatusCode, HeaderMap, header}, response::Response, body::Body};
 use bigdecimal::BigDecimal;
-use chrono::NaiveDate;
+use quittance_compliance::furniture::{mandatory_furniture_label, missing_mandatory_furniture};
+use quittance_compliance::lease::lease_compliance_report;
+use quittance_compliance::report::{ComplianceReport, ComplianceViolation, SEVERITY_BLOCKING, SEVERITY_WARNING};
 use serde::Deserialize;
 use uuid::Uuid;
-use std::path::PathBuf;
 use crate::{
     db::Database,
-    models::lease::{Lease, CreateLease},
-    models::property::Property,
+    models::lease::{Lease, CreateLease, TransitionLease},
+    models::property::{Property, is_ancillary_property_type},
     models::tenant::Tenant,
     models::user::User,
     models::canonical_snapshot::CanonicalSnapshot,
-    services::pdf_renderer::PdfRenderer,
+    services::pdf_renderer::{PdfRenderer, legal_templates_dir},
+    services::rent_control::{
+        fill_reference_rents, lookup_reference_rents, rent_control_violations, DwellingFacts, RentControlLookup,
+    },
+    services::lease_lifecycle::{can_transition, is_billable, is_known_status, is_snapshot_frozen},
     error::AppError,
     routes::auth::extract_user_id_from_headers,
+    routes::amendments::{create_amendment, get_amendment_pdf, get_current_contract, list_amendments},
+    routes::snapshots::{diff_lease_snapshots, get_lease_snapshot_version, list_lease_snapshots},
+    routes::notices::{create_notice, get_notice_letter, list_notices},
+    routes::deadlines::{get_lease_deadlines, list_upcoming_deadlines},
+    routes::inventories::{
+        compare_inventories, create_degradation_deductions, get_inventory_pdf, list_inventories, save_inventory,
+    },
+    routes::rent_history::{
+        propose_rent_revision, confirm_rent_revision, list_rent_history, create_rent_period,
+        delete_rent_period, get_rent_schedule, sync_initial_rent_period,
+    },
 };
 
-/// Current legal template version used for new lease snapshots.
-const CURRENT_LEGAL_TEMPLATE_VERSION: &str = "2026-06-18";
+/// Current legal template versions used for new lease snapshots, one set per
+/// lease type.
+const CURRENT_FURNISHED_TEMPLATE_VERSION: &str = "2026-06-18";
+const CURRENT_UNFURNISHED_TEMPLATE_VERSION: &str = "bail-nu-2026-10-17";
+const CURRENT_ANCILLARY_TEMPLATE_VERSION: &str = "bail-annexe-2026-10-17";
+
+const LEASE_TYPES: [&str; 3] = ["furnished", "unfurnished", "ancillary"];
+
+fn legal_template_version(lease_type: &str) -> &'static str {
+    match lease_type {
+        "unfurnished" => CURRENT_UNFURNISHED_TEMPLATE_VERSION,
+        "ancillary" => CURRENT_ANCILLARY_TEMPLATE_VERSION,
+        _ => CURRENT_FURNISHED_TEMPLATE_VERSION,
+    }
+}
 
 #[derive(Debug, Deserialize)]
 struct LeaseQuery {
     property_id: Option<Uuid>,
 }
 
-fn str_is_blank(value: Option<&str>) -> bool {
-    value.map(|v| v.trim().is_empty()).unwrap_or(true)
-}
+/// Lease type of the payload. Parking, garage and storage properties only
+/// take an ancillary lease; dwellings default to their furnished flag.
+fn resolve_lease_type(data: &CreateLease, property: &PropertyLandlordFacts) -> Result<&'static str, AppError> {
+    let lease_type = match data.lease_type.as_deref() {
+        Some(value) => LEASE_TYPES
+            .iter()
+            .find(|lease_type| **lease_type == value)
+            .copied()
+            .ok_or_else(|| {
+                AppError::Validation("Lease type must be furnished, unfurnished or ancillary".to_string())
+            })?,
+        None if property.is_ancillary => "ancillary",
+        None if property.property_furnished => "furnished",
+        None => "unfurnished",
+    };
 
-fn dpe_rank(dpe_class: &str) -> Option<i32> {
-    match dpe_class.trim().to_ascii_uppercase().as_str() {
-        "A" => Some(1),
-        "B" => Some(2),
-        "C" => Some(3),
-        "D" => Some(4),
-        "E" => Some(5),
-        "F" => Some(6),
-        "G" => Some(7),
-        _ => None,
+    if property.is_ancillary != (lease_type == "ancillary") {
+        return Err(AppError::Validation(
+            "Parking, garage and storage properties require an ancillary lease, and only they can have one".to_string(),
+        ));
     }
+    Ok(lease_type)
 }
 
-fn min_allowed_dpe_rank(start_date: NaiveDate, is_dom_tom: bool) -> i32 {
-    if is_dom_tom {
-        if start_date >= NaiveDate::from_ymd_opt(2031, 1, 1).expect("valid date") {
-            5 // E
-        } else if start_date >= NaiveDate::from_ymd_opt(2028, 1, 1).expect("valid date") {
-            6 // F
-        } else {
-            7 // G (no threshold yet)
-        }
-    } else if start_date >= NaiveDate::from_ymd_opt(2034, 1, 1).expect("valid date") {
-        4 // D
-    } else if start_date >= NaiveDate::from_ymd_opt(2028, 1, 1).expect("valid date") {
-        5 // E
-    } else if start_date >= NaiveDate::from_ymd_opt(2025, 1, 1).expect("valid date") {
-        6 // F
-    } else {
-        7 // G
-    }
+/// Compliance report of the payload, with the reference rents re-checked
+/// against the rent-control arrêté of a dwelling.
+fn lease_report(
+    data: &CreateLease,
+    lease_type: &str,
+    landlord_is_legal_person: bool,
+    rent_control: Option<&RentControlLookup>,
+) -> ComplianceReport {
+    let mut report = lease_compliance_report(&data.compliance_terms(), lease_type, landlord_is_legal_person);
+    if lease_type != "ancillary" {
+        report.extend(rent_control_violations(data, rent_control));
+    }
+    report
 }
 
-fn validate_lease_payload(data: &CreateLease, property_is_furnished: bool) -> Result<(), AppError> {
-    let zero = BigDecimal::from(0);
-
-    if data.monthly_rent <= zero {
-        return Err(AppError::Validation("Monthly rent must be greater than 0".to_string()));
-    }
-    if data.charges < zero {
-        return Err(AppError::Validation("Charges cannot be negative".to_string()));
-    }
-    if data.deposit < zero {
-        return Err(AppError::Validation("Deposit cannot be negative".to_string()));
-    }
-
-    let max_deposit = &data.monthly_rent * BigDecimal::from(2);
-    if data.deposit > max_deposit {
-        return Err(AppError::Validation("Deposit cannot exceed 2x monthly rent excluding charges".to_string()));
-    }
-
-    if data.habitable_surface.as_ref().map(|v| v <= &zero).unwrap_or(true) {
-        return Err(AppError::Validation("Habitable surface is required and must be greater than 0".to_string()));
-    }
-
-    if data.main_room_count.unwrap_or(0) <= 0 {
-        return Err(AppError::Validation("Main room count is required and must be greater than 0".to_string()));
-    }
-
-    let lease_kind = data.lease_kind.clone().unwrap_or_else(|| "standard".to_string());
-    if lease_kind == "student" {
-        if data.duration_months != 9 {
-            return Err(AppError::Validation("Student lease duration must be exactly 9 months".to_string()));
-        }
-    } else if data.duration_months < 12 {
-        return Err(AppError::Validation("Standard furnished lease duration must be at least 12 months".to_string()));
-    }
-
-    let is_colocation = data.is_colocation.unwrap_or(false);
-    let tenant_count = data.tenant_ids.len();
-    if data.tenant_ids.is_empty() {
-        return Err(AppError::Validation("At least one tenant is required".to_string()));
-    }
-    {
-        // Reject duplicate tenants in the party set.
-        let mut seen = std::collections::HashSet::new();
-        if !data.tenant_ids.iter().all(|id| seen.insert(*id)) {
-            return Err(AppError::Validation("Duplicate tenants are not allowed".to_string()));
-        }
-    }
-    if is_colocation && tenant_count < 2 {
-        return Err(AppError::Validation("Colocation requires at least 2 tenants".to_string()));
-    }
-    if !is_colocation && tenant_count > 1 {
-        return Err(AppError::Validation("More than one tenant requires colocation mode".to_string()));
-    }
-
-    let dpe_class = data.dpe_class.as_deref().unwrap_or("");
-    if str_is_blank(Some(dpe_class)) {
-        return Err(AppError::Validation("DPE class is required".to_string()));
-    }
-
-    let dpe_rank = dpe_rank(dpe_class)
-        .ok_or_else(|| AppError::Validation("DPE class must be one of A, B, C, D, E, F, G".to_string()))?;
-    let is_dom_tom = data.is_dom_tom.unwrap_or(false);
-    let min_rank = min_allowed_dpe_rank(data.start_date, is_dom_tom);
-    if dpe_rank > min_rank {
-        return Err(AppError::Validation("DPE class is below the legal threshold for the lease date and territory".to_string()));
-    }
-
-    if !data.legal_notice_provided {
-        return Err(AppError::Validation("Legal notice must be provided".to_string()));
-    }
-
-    if !data.annex_dpe_provided.unwrap_or(false) {
-        return Err(AppError::Validation("DPE annex is required".to_string()));
-    }
-
-    if !data.annex_entry_inventory_provided.unwrap_or(false) {
-        return Err(AppError::Validation("Entry inventory annex is required".to_string()));
-    }
-
-    if property_is_furnished && !data.annex_furniture_inventory_provided.unwrap_or(false) {
-        return Err(AppError::Validation("Furniture inventory annex is required for furnished properties".to_string()));
-    }
-
-    let rent_controlled = data.rent_controlled.unwrap_or(false);
-    if rent_controlled {
-        if data.reference_rent.is_none() || data.reference_rent_majorated.is_none() {
-            return Err(AppError::Validation("Reference rent and majorated reference rent are required in rent-controlled areas".to_string()));
-        }
-
-        if data.rent_complement.as_ref().map(|v| v > &zero).unwrap_or(false)
-            && str_is_blank(data.rent_complement_justification.as_deref())
-        {
-            return Err(AppError::Validation("Rent complement justification is required when a rent complement is applied".to_string()));
-        }
-    }
-
-    let professional_mandate = data.professional_mandate.unwrap_or(false);
-    if professional_mandate {
-        let tenant_fee = data.agency_fee_tenant.as_ref().ok_or_else(|| {
-            AppError::Validation("Tenant agency fee is required when professional mandate applies".to_string())
-        })?;
-        let landlord_fee = data.agency_fee_landlord.as_ref().ok_or_else(|| {
-            AppError::Validation("Landlord agency fee is required when professional mandate applies".to_string())
-        })?;
-        if tenant_fee > landlord_fee {
-            return Err(AppError::Validation("Tenant agency fee cannot exceed landlord agency fee".to_string()));
-        }
-    }
-
-    if let Some(previous_departure_date) = data.previous_tenant_departure_date {
-        let signed_days = (data.start_date - previous_departure_date).num_days();
-        if (0..=548).contains(&signed_days) && data.previous_tenant_last_rent.is_none() {
-            return Err(AppError::Validation("Previous tenant last rent is required when previous departure was within 18 months".to_string()));
-        }
-    }
-
-    if let Some(custom_clauses) = data.custom_clauses.as_deref() {
-        let clauses = custom_clauses.to_ascii_lowercase();
-        let banned_patterns = [
-            "prélèvement automatique comme seul mode",
-            "prelevement automatique comme seul mode",
-            "interdiction d'heberger",
-            "interdiction d’héberger",
-            "frais de quittance",
-            "frais d'envoi de quittance",
-        ];
-        if banned_patterns.iter().any(|pattern| clauses.contains(pattern)) {
-            return Err(AppError::Validation("Custom clauses include legally prohibited terms".to_string()));
-        }
-    }
+/// Every compliance rule applied to the payload. Fails with the blocking
+/// violations; otherwise returns the report, which may still hold warnings.
+fn validate_lease_payload(
+    data: &CreateLease,
+    lease_type: &str,
+    landlord_is_legal_person: bool,
+    rent_control: Option<&RentControlLookup>,
+) -> Result<ComplianceReport, AppError> {
+    let report = lease_report(data, lease_type, landlord_is_legal_person, rent_control);
+    if report.blocking {
+        let messages: Vec<&str> = report
+            .violations
+            .iter()
+            .filter(|violation| violation.severity == SEVERITY_BLOCKING)
+            .map(|violation| violation.message.as_str())
+            .collect();
+        return Err(AppError::Validation(format!("Le bail n'est pas conforme : {}", messages.join(" "))));
+    }
+    Ok(report)
+}
 
-    // --- Identifiant fiscal du logement (IFL): mandatory except DOM-TOM ---
-    if !is_dom_tom && str_is_blank(data.identifiant_fiscal.as_deref()) {
-        return Err(AppError::Validation("Identifiant fiscal du logement is required (except DOM-TOM)".to_string()));
-    }
+/// Run the compliance rules on a stored lease again, as validation does on a
+/// payload: its property may have been reclassified or the reference rents
+/// reloaded since it was saved. Fails with the blocking violations.
+pub(crate) async fn ensure_lease_compliant(db: &Database, lease: &Lease) -> Result<ComplianceReport, AppError> {
+    let mut data = lease.as_payload();
+    let landlord = get_property_landlord_facts(db, lease.property_id).await?;
+    data.zone_tendue = Some(landlord.zone_tendue);
+    data.irl_value = reference_irl_value(db, &data).await?;
+    let rent_control = resolve_reference_rents(db, &mut data, &lease.lease_type).await?;
+    validate_lease_payload(&data, &lease.lease_type, landlord.is_legal_person, rent_control.as_ref())
+}
 
-    // --- Property characterisation (Section II): mandatory for issuance ---
-    if str_is_blank(data.habitat_type.as_deref()) {
-        return Err(AppError::Validation("Habitat type (collectif/individuel) is required".to_string()));
-    }
-    if str_is_blank(data.regime_juridique.as_deref()) {
-        return Err(AppError::Validation("Régime juridique (monopropriété/copropriété) is required".to_string()));
-    }
-    let construction_period = data.construction_period.as_deref().unwrap_or("");
-    if str_is_blank(Some(construction_period)) {
-        return Err(AppError::Validation("Période de construction is required".to_string()));
-    }
+/// Value of the lease's IRL reference quarter, which the relet cap indexes an
+/// unrevised previous rent with; `None` when no quarter is named or loaded.
+async fn reference_irl_value(db: &Database, data: &CreateLease) -> Result<Option<BigDecimal>, AppError> {
+    let (Some(quarter), Some(year)) = (data.irl_reference_quarter, data.irl_reference_year) else {
+        return Ok(None);
+    };
+    let value = sqlx::query_scalar::<_, BigDecimal>("SELECT value FROM irl_indices WHERE year = $1 AND quarter = $2")
+        .bind(year)
+        .bind(quarter)
+        .fetch_optional(&db.pool)
+        .await?;
+    Ok(value)
+}
 
-    // --- Rent must not exceed the majorated reference rent in encadrée zones ---
-    if rent_controlled {
-        if let Some(majorated) = data.reference_rent_majorated.as_ref() {
-            let has_justified_complement = data
-                .rent_complement
-                .as_ref()
-                .map(|v| v > &zero)
-                .unwrap_or(false)
-                && !str_is_blank(data.rent_complement_justification.as_deref());
-            if &data.monthly_rent > majorated && !has_justified_complement {
-                return Err(AppError::Validation(
-                    "Monthly rent exceeds the majorated reference rent in a rent-controlled zone without a justified complement".to_string(),
-                ));
-            }
-        }
-    }
+/// Reference rents of a rent-controlled dwelling from the loaded arrêtés.
+/// The payload's missing reference rents are filled in from them.
+async fn resolve_reference_rents(
+    db: &Database,
+    data: &mut CreateLease,
+    lease_type: &str,
+) -> Result<Option<RentControlLookup>, AppError> {
+    if !data.rent_controlled.unwrap_or(false) || lease_type == "ancillary" {
+        return Ok(None);
+    }
+    let facts = DwellingFacts {
+        property_id: data.property_id,
+        construction_period: data.construction_period.as_deref(),
+        main_room_count: data.main_room_count,
+        habitable_surface: data.habitable_surface.as_ref(),
+        furnished: lease_type == "furnished",
+        on: data.start_date,
+    };
+    let lookup = lookup_reference_rents(db, &facts).await?;
+    fill_reference_rents(data, lookup.as_ref());
+    Ok(lookup)
+}
 
-    // --- Mandatory annexes gated by property facts ---
-    if construction_period == "avant_1949" && !data.annex_lead_provided.unwrap_or(false) {
-        return Err(AppError::Validation("Lead diagnosis (Crep) annex is required for properties built before 1949".to_string()));
-    }
-    if data.electrical_installation_over_15y.unwrap_or(false) && !data.annex_electrical_provided.unwrap_or(false) {
-        return Err(AppError::Validation("Electrical diagnosis annex is required for installations over 15 years".to_string()));
-    }
-    if data.gas_installation_over_15y.unwrap_or(false) && !data.annex_gas_provided.unwrap_or(false) {
-        return Err(AppError::Validation("Gas diagnosis annex is required for installations over 15 years".to_string()));
-    }
-    if data.in_risk_zone.unwrap_or(false) && !data.annex_risk_provided.unwrap_or(false) {
-        return Err(AppError::Validation("État des risques (ERNT) annex is required in a risk zone".to_string()));
-    }
+/// Compliance warnings on the furniture of a furnished lease: every item of
+/// the décret 2015-981 minimum list that neither the linked furniture sets nor
+/// the free-text inventory mentions.
+async fn furniture_violations(
+    conn: &mut sqlx::PgConnection,
+    data: &CreateLease,
+    lease_type: &str,
+) -> Result<Vec<ComplianceViolation>, AppError> {
+    if lease_type != "furnished" {
+        return Ok(vec![]);
+    }
+    let rows = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT fi.category || ' ' || fi.name
+        FROM furniture_items fi
+        JOIN furniture_sets fs ON fs.id = fi.furniture_set_id
+        WHERE fs.property_id = $1 AND fs.id = ANY($2)
+        "#,
+    )
+    .bind(data.property_id)
+    .bind(&data.furniture_set_ids)
+    .fetch_all(&mut *conn)
+    .await?;
 
-    Ok(())
+    let descriptions = rows.iter().map(String::as_str).chain(data.furniture_inventory.as_deref());
+    Ok(missing_mandatory_furniture(descriptions)
+        .into_iter()
+        .filter_map(|code| {
+            mandatory_furniture_label(code).map(|label| ComplianceViolation {
+                code: format!("furniture.missing_{}", code),
+                severity: SEVERITY_WARNING.to_string(),
+                field: "furniture_set_ids".to_string(),
+                legal_reference: "Décret n° 2015-981 du 31 juillet 2015, art. 2".to_string(),
+                message: format!("Équipement obligatoire manquant : {}.", label.to_lowercase()),
+            })
+        })
+        .collect())
 }
 
 pub fn router() -> Router<Database> {
     Router::new()
         .route("/", get(list_leases).post(create_lease))
+    .route("/validate", post(validate_lease))
+    .route("/deadlines", get(list_upcoming_deadlines))
     .route("/:id", get(get_lease).put(update_lease).delete(delete_lease))
     .route("/:id/pdf", get(generate_lease_pdf))
     .route("/:id/preview", get(preview_lease_html))
     .route("/:id/snapshot", get(get_lease_snapshot))
+    .route("/:id/status", post(transition_lease))
+    .route("/:id/snapshots", get(list_lease_snapshots))
+    .route("/:id/snapshots/diff", get(diff_lease_snapshots))
+    .route("/:id/snapshots/:snapshot_id", get(get_lease_snapshot_version))
+    .route("/:id/contract", get(get_current_contract))
+    .route("/:id/amendments", get(list_amendments).post(create_amendment))
+    .route("/:id/amendments/:amendment_id/pdf", get(get_amendment_pdf))
+    .route("/:id/notices", get(list_notices).post(create_notice))
+    .route("/:id/notices/:notice_id/letter", get(get_notice_letter))
+    .route("/:id/deadlines", get(get_lease_deadlines))
+    .route("/:id/inventories", get(list_inventories))
+    .route("/:id/inventories/comparison", get(compare_inventories))
+    .route("/:id/inventories/comparison/deductions", post(create_degradation_deductions))
+    .route("/:id/inventories/:kind", axum::routing::put(save_inventory))
+    .route("/:id/inventories/:kind/pdf", get(get_inventory_pdf))
+    .route("/:id/rent-revision", get(propose_rent_revision).post(confirm_rent_revision))
+    .route("/:id/rent-history", get(list_rent_history).post(create_rent_period))
+    .route("/:id/rent-history/:entry_id", axum::routing::delete(delete_rent_period))
+    .route("/:id/rent-schedule", get(get_rent_schedule))
 }
 
 async fn ensure_property_access(db: &Database, property_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
@@ -334,7 +313,7 @@ async fn ensure_organization_landlord_complete(db: &Database, property_id: Uuid)
     Ok(())
 }
 
-async fn ensure_lease_access(db: &Database, lease_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+pub async fn ensure_lease_access(db: &Database, lease_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
     let exists = sqlx::query_scalar::<_, Option<bool>>(
         r#"
         SELECT EXISTS(
@@ -359,19 +338,45 @@ async fn ensure_lease_access(db: &Database, lease_id: Uuid, user_id: Uuid) -> Re
     }
 }
 
-async fn get_property_furnished(db: &Database, property_id: Uuid) -> Result<bool, AppError> {
-    let furnished = sqlx::query_scalar::<_, Option<bool>>(
-        "SELECT furnished FROM properties WHERE id = $1"
+/// Property and owner facts that select the applicable lease rules.
+pub(crate) struct PropertyLandlordFacts {
+    property_furnished: bool,
+    /// Parking, garage or storage let outside the residential regime.
+    is_ancillary: bool,
+    /// Owned by an organization other than a family SCI, which loi 89
+    /// treats like a natural person.
+    pub(crate) is_legal_person: bool,
+    /// The address lies in a commune of the zones tendues.
+    pub(crate) zone_tendue: bool,
+    /// The owner classified the property by hand.
+    pub(crate) zone_tendue_declared: Option<bool>,
+}
+
+pub(crate) async fn get_property_landlord_facts(db: &Database, property_id: Uuid) -> Result<PropertyLandlordFacts, AppError> {
+    let (property_type, property_furnished, is_legal_person, zone_tendue, zone_tendue_declared) = sqlx::query_as::<_, (String, bool, bool, bool, Option<bool>)>(
+        r#"
+        SELECT p.property_type, p.furnished, COALESCE(o.id IS NOT NULL AND NOT o.is_family_sci, FALSE), p.zone_tendue,
+               p.zone_tendue_declared
+        FROM properties p
+        LEFT JOIN organizations o ON o.id = p.organization_id
+        WHERE p.id = $1
+        "#,
     )
     .bind(property_id)
-    .fetch_one(&db.pool)
+    .fetch_optional(&db.pool)
     .await?
     .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", property_id)))?;
 
-    Ok(furnished)
+    Ok(PropertyLandlordFacts {
+        property_furnished,
+        is_ancillary: is_ancillary_property_type(&property_type),
+        is_legal_person,
+        zone_tendue,
+        zone_tendue_declared,
+    })
 }
 
-async fn fetch_lease_by_id(db: &Database, id: Uuid) -> Result<Lease, AppError> {
+pub async fn fetch_lease_by_id(db: &Database, id: Uuid) -> Result<Lease, AppError> {
     let lease = sqlx::query_as::<_, Lease>(
         r#"
         SELECT
@@ -392,6 +397,9 @@ async fn fetch_lease_by_id(db: &Database, id: Uuid) -> Result<Lease, AppError> {
             l.rent_revision,
             l.annual_charges_regularization,
             l.lease_kind,
+            l.lease_type,
+            l.mobility_reason,
+            l.mobility_justification,
             l.is_colocation,
             l.tenant_count,
             l.destination,
@@ -413,6 +421,9 @@ async fn fetch_lease_by_id(db: &Database, id: Uuid) -> Result<Lease, AppError> {
             l.rent_complement_justification,
             l.previous_tenant_departure_date,
             l.previous_tenant_last_rent,
+            l.previous_rent_revised_on,
+            l.previous_rent_irl,
+            l.comparable_rent,
             l.professional_mandate,
             l.agency_fee_tenant,
             l.agency_fee_landlord,
@@ -456,9 +467,18 @@ async fn fetch_lease_by_id(db: &Database, id: Uuid) -> Result<Lease, AppError> {
             l.works_amount,
             l.works_date,
             l.rent_revision_conditions,
+            l.irl_reference_quarter,
+            l.irl_reference_year,
+            l.dpe_date,
+            l.electrical_diagnostic_date,
+            l.gas_diagnostic_date,
+            l.last_renewed_on,
             l.compliance_status,
             l.compliance_errors,
+            l.compliance_rule_set,
             l.status,
+            l.signed_at,
+            l.status_changed_at,
             l.pdf_path,
             l.created_at,
             l.updated_at
@@ -500,49 +520,10 @@ async fn fetch_user_by_id(db: &Database, id: Uuid) -> Result<User, AppError> {
         .ok_or_else(|| AppError::NotFound(format!("User with id {} not found", id)))
 }
 
-/// Resolve the legal_templates directory path (overridable via env for deployment).
-fn legal_templates_dir() -> PathBuf {
-    std::env::var("LEGAL_TEMPLATES_DIR")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from("src/legal_templates"))
-}
-
-/// Resolve the wkhtmltopdf binary path.
-///
-/// Resolution order:
-/// 1. `WKHTMLTOPDF_PATH` env var (explicit override, used in Docker/dev).
-/// 2. Well-known default install locations (so a standard install works even
-///    when the binary is not on `PATH` and no env var is set).
-/// 3. Fall back to the bare name `wkhtmltopdf` (relies on `PATH`).
-fn resolve_wkhtmltopdf_path() -> String {
-    if let Ok(path) = std::env::var("WKHTMLTOPDF_PATH") {
-        if !path.trim().is_empty() {
-            return path;
-        }
-    }
-
-    const DEFAULT_LOCATIONS: &[&str] = &[
-        // Windows default install location
-        r"C:\Program Files\wkhtmltopdf\bin\wkhtmltopdf.exe",
-        r"C:\Program Files (x86)\wkhtmltopdf\bin\wkhtmltopdf.exe",
-        // Common Unix locations
-        "/usr/bin/wkhtmltopdf",
-        "/usr/local/bin/wkhtmltopdf",
-    ];
-
-    for candidate in DEFAULT_LOCATIONS {
-        if std::path::Path::new(candidate).exists() {
-            return candidate.to_string();
-        }
-    }
-
-    "wkhtmltopdf".to_string()
-}
-
 /// Build a canonical snapshot for a lease by loading its related entities.
 /// The landlord is the property owner; falls back to the requesting user for
 /// organization-owned properties without a direct owner.
-async fn build_snapshot_for_lease(
+pub async fn build_snapshot_for_lease(
     db: &Database,
     lease: &Lease,
     requesting_user_id: Uuid,
@@ -567,7 +548,7 @@ async fn build_snapshot_for_lease(
         &property,
         &tenants,
         &landlord,
-        CURRENT_LEGAL_TEMPLATE_VERSION.to_string(),
+        legal_template_version(&lease.lease_type).to_string(),
     );
 
     // For an organization-owned property, the bailleur is the organization
@@ -588,7 +569,8 @@ async fn build_snapshot_for_lease(
 
 /// Build the canonical snapshot for a lease and persist it to the
 /// `canonical_snapshot` column. Called on create/update so all renderings
-/// (preview, print, PDF) read identical, stable, versioned content.
+/// (preview, print, PDF) read identical, stable, versioned content. Every
+/// generation is also kept in `lease_snapshots`.
 async fn persist_snapshot_for_lease(
     db: &Database,
     lease_id: Uuid,
@@ -601,19 +583,37 @@ async fn persist_snapshot_for_lease(
         AppError::Internal
     })?;
 
+    let mut tx = db.pool.begin().await?;
+
     sqlx::query("UPDATE leases SET canonical_snapshot = $1 WHERE id = $2")
-        .bind(snapshot_json)
+        .bind(&snapshot_json)
         .bind(lease_id)
-        .execute(&db.pool)
+        .execute(&mut *tx)
         .await?;
 
+    sqlx::query(
+        r#"
+        INSERT INTO lease_snapshots (id, lease_id, legal_template_version, generated_at, snapshot)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(snapshot.id)
+    .bind(lease_id)
+    .bind(&snapshot.legal_template_version)
+    .bind(snapshot.generated_at)
+    .bind(&snapshot_json)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
     Ok(snapshot)
 }
 
 /// Load the persisted canonical snapshot for a lease. If none is stored
 /// (legacy rows created before snapshot persistence), rebuild it and persist
 /// it on demand so subsequent reads are stable.
-async fn load_or_build_snapshot(
+pub async fn load_or_build_snapshot(
     db: &Database,
     lease_id: Uuid,
     requesting_user_id: Uuid,
@@ -649,13 +649,7 @@ async fn generate_lease_pdf(
 
     let snapshot = load_or_build_snapshot(&db, id, user_id).await?;
 
-    let wkhtmltopdf_path = resolve_wkhtmltopdf_path();
-    let timeout_secs = std::env::var("PDF_GENERATION_TIMEOUT_SECS")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .unwrap_or(30);
-
-    let renderer = PdfRenderer::new(&legal_templates_dir(), wkhtmltopdf_path, timeout_secs)
+    let renderer = PdfRenderer::from_env()
         .map_err(|e| {
             tracing::error!("Failed to initialize PDF renderer: {}", e);
             AppError::Internal
@@ -793,16 +787,45 @@ async fn list_leases(
     Ok(Json(leases))
 }
 
+/// POST /api/leases/validate
+/// Dry run of the compliance rules on a lease payload: every blocking
+/// violation and warning at once, nothing saved.
+async fn validate_lease(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Json(mut data): Json<CreateLease>,
+) -> Result<Json<ComplianceReport>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_property_access(&db, data.property_id, user_id).await?;
+    ensure_tenants_access(&db, &data.tenant_ids, user_id).await?;
+    let landlord = get_property_landlord_facts(&db, data.property_id).await?;
+    let lease_type = resolve_lease_type(&data, &landlord)?;
+    data.zone_tendue = Some(landlord.zone_tendue);
+    data.irl_value = reference_irl_value(&db, &data).await?;
+
+    let rent_control = resolve_reference_rents(&db, &mut data, lease_type).await?;
+
+    let mut report = lease_report(&data, lease_type, landlord.is_legal_person, rent_control.as_ref());
+    let mut conn = db.pool.acquire().await?;
+    report.extend(furniture_violations(&mut conn, &data, lease_type).await?);
+
+    Ok(Json(report))
+}
+
 async fn create_lease(
     State(db): State<Database>,
     headers: HeaderMap,
-    Json(data): Json<CreateLease>,
+    Json(mut data): Json<CreateLease>,
 ) -> Result<(StatusCode, Json<Lease>), AppError> {
     let user_id = extract_user_id_from_headers(&headers)?;
     ensure_property_access(&db, data.property_id, user_id).await?;
     ensure_tenants_access(&db, &data.tenant_ids, user_id).await?;
-    let property_is_furnished = get_property_furnished(&db, data.property_id).await?;
-    validate_lease_payload(&data, property_is_furnished)?;
+    let landlord = get_property_landlord_facts(&db, data.property_id).await?;
+    let lease_type = resolve_lease_type(&data, &landlord)?;
+    data.zone_tendue = Some(landlord.zone_tendue);
+    data.irl_value = reference_irl_value(&db, &data).await?;
+    let rent_control = resolve_reference_rents(&db, &mut data, lease_type).await?;
+    let mut report = validate_lease_payload(&data, lease_type, landlord.is_legal_person, rent_control.as_ref())?;
     ensure_organization_landlord_complete(&db, data.property_id).await?;
 
     // Calculate end_date based on start_date + duration_months
@@ -824,8 +847,15 @@ async fn create_lease(
         }
     }
 
+    report.extend(furniture_violations(&mut tx, &data, lease_type).await?);
+
     let primary_furniture_set_id = data.furniture_set_ids.first().copied();
     let lease_kind = data.lease_kind.as_deref().unwrap_or("standard");
+    let (mobility_reason, mobility_justification) = if lease_kind == "mobility" {
+        (data.mobility_reason.clone(), data.mobility_justification.clone())
+    } else {
+        (None, None)
+    };
     let is_colocation = data.is_colocation.unwrap_or(false);
     let primary_tenant_id = *data.tenant_ids.first().expect("validated: at least one tenant");
     let tenant_count = data.tenant_ids.len() as i32;
@@ -865,7 +895,10 @@ async fn create_lease(
             annex_lead_provided, annex_electrical_provided, annex_gas_provided, annex_risk_provided,
             autres_parties, elements_equipement, privatifs_accessoires, parties_communes, tech_equipements,
             charges_settlement_mode, colocation_insurance_amount, works_nature, works_amount, works_date, rent_revision_conditions,
-            compliance_status, compliance_errors,
+            irl_reference_quarter, irl_reference_year, lease_type, mobility_reason, mobility_justification,
+            dpe_date, electrical_diagnostic_date, gas_diagnostic_date,
+            compliance_status, compliance_errors, compliance_rule_set,
+            previous_rent_revised_on, previous_rent_irl, comparable_rent,
             status
         )
         VALUES (
@@ -883,8 +916,11 @@ async fn create_lease(
             $58, $59, $60, $61,
             $62, $63, $64, $65, $66,
             $67, $68, $69, $70, $71, $72,
-            'compliant', '{}',
-            'active'
+            $73, $74, $75, $76, $77,
+            $78, $79, $80,
+            $81, $82, $83,
+            $84, $85, $86,
+            'draft'
         )
         RETURNING id
         "#
@@ -894,8 +930,8 @@ async fn create_lease(
     .bind(data.start_date)
     .bind(end_date)
     .bind(data.duration_months)
-    .bind(data.monthly_rent)
-    .bind(data.charges)
+    .bind(&data.monthly_rent)
+    .bind(&data.charges)
     .bind(data.deposit)
     .bind(data.rent_revision)
     .bind(data.annual_charges_regularization)
@@ -961,6 +997,20 @@ async fn create_lease(
     .bind(data.works_amount.clone())
     .bind(data.works_date)
     .bind(data.rent_revision_conditions.clone())
+    .bind(data.irl_reference_quarter)
+    .bind(data.irl_reference_year)
+    .bind(lease_type)
+    .bind(mobility_reason)
+    .bind(mobility_justification)
+    .bind(data.dpe_date)
+    .bind(data.electrical_diagnostic_date)
+    .bind(data.gas_diagnostic_date)
+    .bind(report.status())
+    .bind(sqlx::types::Json(&report.violations))
+    .bind(&report.rule_set_version)
+    .bind(data.previous_rent_revised_on)
+    .bind(data.previous_rent_irl.clone())
+    .bind(data.comparable_rent.clone())
     .fetch_one(&mut *tx)
     .await?;
 
@@ -986,6 +1036,8 @@ async fn create_lease(
         .await?;
     }
 
+    sync_initial_rent_period(&mut tx, lease_id, data.start_date, &data.monthly_rent, &data.charges).await?;
+
     tx.commit().await?;
 
     persist_snapshot_for_lease(&db, lease_id, user_id).await?;
@@ -1010,14 +1062,25 @@ async fn update_lease(
     State(db): State<Database>,
     headers: HeaderMap,
     Path(id): Path<Uuid>,
-    Json(data): Json<CreateLease>,
+    Json(mut data): Json<CreateLease>,
 ) -> Result<Json<Lease>, AppError> {
     let user_id = extract_user_id_from_headers(&headers)?;
     ensure_lease_access(&db, id, user_id).await?;
+    let status = fetch_lease_status(&db, id).await?;
+    if is_snapshot_frozen(&status) {
+        return Err(AppError::Validation(format!(
+            "A lease with status '{}' has been signed and can no longer be edited",
+            status
+        )));
+    }
     ensure_property_access(&db, data.property_id, user_id).await?;
     ensure_tenants_access(&db, &data.tenant_ids, user_id).await?;
-    let property_is_furnished = get_property_furnished(&db, data.property_id).await?;
-    validate_lease_payload(&data, property_is_furnished)?;
+    let landlord = get_property_landlord_facts(&db, data.property_id).await?;
+    let lease_type = resolve_lease_type(&data, &landlord)?;
+    data.zone_tendue = Some(landlord.zone_tendue);
+    data.irl_value = reference_irl_value(&db, &data).await?;
+    let rent_control = resolve_reference_rents(&db, &mut data, lease_type).await?;
+    let mut report = validate_lease_payload(&data, lease_type, landlord.is_legal_person, rent_control.as_ref())?;
     ensure_organization_landlord_complete(&db, data.property_id).await?;
 
     let end_date = data.start_date + chrono::Months::new(data.duration_months as u32);
@@ -1038,8 +1101,15 @@ async fn update_lease(
         }
     }
 
+    report.extend(furniture_violations(&mut tx, &data, lease_type).await?);
+
     let primary_furniture_set_id = data.furniture_set_ids.first().copied();
     let lease_kind = data.lease_kind.as_deref().unwrap_or("standard");
+    let (mobility_reason, mobility_justification) = if lease_kind == "mobility" {
+        (data.mobility_reason.clone(), data.mobility_justification.clone())
+    } else {
+        (None, None)
+    };
     let is_colocation = data.is_colocation.unwrap_or(false);
     let primary_tenant_id = *data.tenant_ids.first().expect("validated: at least one tenant");
     let tenant_count = data.tenant_ids.len() as i32;
@@ -1139,8 +1209,20 @@ async fn update_lease(
             works_amount = $71,
             works_date = $72,
             rent_revision_conditions = $73,
-            compliance_status = 'compliant',
-            compliance_errors = '{}',
+            irl_reference_quarter = $74,
+            irl_reference_year = $75,
+            lease_type = $76,
+            mobility_reason = $77,
+            mobility_justification = $78,
+            dpe_date = $79,
+            electrical_diagnostic_date = $80,
+            gas_diagnostic_date = $81,
+            compliance_status = $82,
+            compliance_errors = $83,
+            compliance_rule_set = $84,
+            previous_rent_revised_on = $85,
+            previous_rent_irl = $86,
+            comparable_rent = $87,
             updated_at = CURRENT_TIMESTAMP
         WHERE id = $1
         RETURNING id
@@ -1152,8 +1234,8 @@ async fn update_lease(
     .bind(data.start_date)
     .bind(end_date)
     .bind(data.duration_months)
-    .bind(data.monthly_rent)
-    .bind(data.charges)
+    .bind(&data.monthly_rent)
+    .bind(&data.charges)
     .bind(data.deposit)
     .bind(data.rent_revision)
     .bind(data.annual_charges_regularization)
@@ -1219,6 +1301,20 @@ async fn update_lease(
     .bind(data.works_amount.clone())
     .bind(data.works_date)
     .bind(data.rent_revision_conditions.clone())
+    .bind(data.irl_reference_quarter)
+    .bind(data.irl_reference_year)
+    .bind(lease_type)
+    .bind(mobility_reason)
+    .bind(mobility_justification)
+    .bind(data.dpe_date)
+    .bind(data.electrical_diagnostic_date)
+    .bind(data.gas_diagnostic_date)
+    .bind(report.status())
+    .bind(sqlx::types::Json(&report.violations))
+    .bind(&report.rule_set_version)
+    .bind(data.previous_rent_revised_on)
+    .bind(data.previous_rent_irl.clone())
+    .bind(data.comparable_rent.clone())
     .fetch_optional(&mut *tx)
     .await?
     .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", id)))?;
@@ -1255,6 +1351,8 @@ async fn update_lease(
         .await?;
     }
 
+    sync_initial_rent_period(&mut tx, updated_id, data.start_date, &data.monthly_rent, &data.charges).await?;
+
     tx.commit().await?;
 
     persist_snapshot_for_lease(&db, updated_id, user_id).await?;
@@ -1262,6 +1360,118 @@ async fn update_lease(
     Ok(Json(fetch_lease_by_id(&db, updated_id).await?))
 }
 
+pub(crate) async fn fetch_lease_status(db: &Database, id: Uuid) -> Result<String, AppError> {
+    sqlx::query_scalar::<_, String>("SELECT status FROM leases WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&db.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", id)))
+}
+
+/// Rent is only called and acknowledged while the lease is in force.
+pub(crate) async fn ensure_lease_billable(db: &Database, id: Uuid) -> Result<(), AppError> {
+    let status = fetch_lease_status(db, id).await?;
+    if !is_billable(&status) {
+        return Err(AppError::Validation(format!(
+            "Rent documents can only be issued for an active lease or during its notice period (status: '{}')",
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// POST /api/leases/{id}/status
+/// Move the lease along its lifecycle. Signing rebuilds the canonical snapshot
+/// one last time and freezes it: later renderings read the signed contract.
+async fn transition_lease(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(data): Json<TransitionLease>,
+) -> Result<Json<Lease>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    if !is_known_status(&data.status) {
+        return Err(AppError::Validation(format!("Unknown lease status '{}'", data.status)));
+    }
+    if data.status == "notice_given" {
+        return Err(AppError::Validation(
+            "Record the notice on the lease; it sets the end date and the status".to_string(),
+        ));
+    }
+    let current = fetch_lease_status(&db, id).await?;
+    if !can_transition(&current, &data.status) {
+        return Err(AppError::Validation(format!(
+            "A lease cannot move from '{}' to '{}'",
+            current, data.status
+        )));
+    }
+
+    if data.status == "active" {
+        let other_in_force = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM leases other
+                JOIN leases l ON l.property_id = other.property_id
+                WHERE l.id = $1 AND other.id <> $1
+                  AND other.status IN ('active', 'notice_given')
+            )
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await?;
+        if other_in_force {
+            return Err(AppError::Validation(
+                "Another lease is already in force for this property".to_string(),
+            ));
+        }
+    }
+
+    if data.status == "signed" {
+        // A lease cannot be signed in a state its validation would refuse.
+        ensure_lease_compliant(&db, &fetch_lease_by_id(&db, id).await?).await?;
+        persist_snapshot_for_lease(&db, id, user_id).await?;
+    }
+
+    let mut tx = db.pool.begin().await?;
+
+    // Guard on the current status so concurrent transitions cannot both apply.
+    let updated = sqlx::query(
+        r#"
+        UPDATE leases
+        SET status = $2,
+            signed_at = CASE WHEN $2 = 'signed' THEN CURRENT_TIMESTAMP ELSE signed_at END,
+            status_changed_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND status = $3
+        "#,
+    )
+    .bind(id)
+    .bind(&data.status)
+    .bind(&current)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(AppError::Validation("The lease status changed concurrently; reload and retry".to_string()));
+    }
+
+    // Going back to active withdraws the notice: the lease runs on.
+    if current == "notice_given" && data.status == "active" {
+        sqlx::query("UPDATE lease_notices SET status = 'withdrawn' WHERE lease_id = $1 AND status = 'active'")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(Json(fetch_lease_by_id(&db, id).await?))
+}
+
 async fn delete_lease(
     State(db): State<Database>,
     headers: HeaderMap,
@@ -1284,6 +1494,7 @@ async fn delete_lease(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::NaiveDate;
 
     fn base_payload() -> CreateLease {
         CreateLease {
@@ -1297,6 +1508,9 @@ mod tests {
             rent_revision: true,
             annual_charges_regularization: false,
             lease_kind: Some("standard".to_string()),
+            lease_type: None,
+            mobility_reason: None,
+            mobility_justification: None,
             is_colocation: Some(false),
             destination: Some("habitation".to_string()),
             habitable_surface: Some(BigDecimal::from(45)),
@@ -1317,6 +1531,9 @@ mod tests {
             rent_complement_justification: None,
             previous_tenant_departure_date: None,
             previous_tenant_last_rent: None,
+            previous_rent_revised_on: None,
+            previous_rent_irl: None,
+            comparable_rent: None,
             professional_mandate: Some(false),
             agency_fee_tenant: None,
             agency_fee_landlord: None,
@@ -1357,39 +1574,107 @@ mod tests {
             works_amount: None,
             works_date: None,
             rent_revision_conditions: None,
+            irl_reference_quarter: None,
+            irl_reference_year: None,
+            dpe_date: None,
+            electrical_diagnostic_date: None,
+            gas_diagnostic_date: None,
+            zone_tendue: None,
+            irl_value: None,
         }
     }
 
     #[test]
     fn accepts_valid_payload() {
         let payload = base_payload();
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn reports_every_violation_at_once() {
+        let mut payload = base_payload();
+        payload.deposit = BigDecimal::from(3000);
+        payload.legal_notice_provided = false;
+        payload.identifiant_fiscal = None;
+        payload.dpe_date = Some(NaiveDate::from_ymd_opt(2015, 3, 1).expect("valid date"));
+
+        let report = lease_compliance_report(&payload.compliance_terms(), "furnished", false);
+        let codes: Vec<&str> = report.violations.iter().map(|v| v.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            vec!["deposit.furnished_max", "notice.legal_information", "ifl.required", "dpe.expired"]
+        );
+        assert!(report.blocking);
+        assert_eq!(report.violations[0].field, "deposit");
+        assert_eq!(report.violations[0].legal_reference, "Loi n° 89-462 du 6 juillet 1989, art. 25-6");
+        assert_eq!(report.violations[3].severity, SEVERITY_WARNING);
+
+        let err = validate_lease_payload(&payload, "furnished", false, None).unwrap_err().to_string();
+        assert!(err.contains("deux mois de loyer") && err.contains("identifiant fiscal"));
+    }
+
+    #[test]
+    fn warnings_do_not_block_but_mark_the_lease_non_compliant() {
+        let mut payload = base_payload();
+        payload.dpe_date = Some(NaiveDate::from_ymd_opt(2015, 3, 1).expect("valid date"));
+        let report = validate_lease_payload(&payload, "furnished", false, None).expect("only warnings");
+        assert!(!report.blocking);
+        assert_eq!(report.status(), "non_compliant");
+
+        let report = validate_lease_payload(&base_payload(), "furnished", false, None).expect("valid");
+        assert_eq!(report.status(), "compliant");
+    }
+
     #[test]
     fn rejects_deposit_over_legal_limit() {
         let mut payload = base_payload();
         payload.deposit = BigDecimal::from(3000);
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn rejects_incomplete_or_invalid_irl_reference() {
+        let mut payload = base_payload();
+        payload.irl_reference_quarter = Some(3);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+
+        payload.irl_reference_year = Some(2025);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+
+        payload.irl_reference_quarter = Some(5);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+    }
+
     #[test]
     fn rejects_dpe_below_threshold() {
         let mut payload = base_payload();
         payload.start_date = NaiveDate::from_ymd_opt(2028, 2, 1).expect("valid date");
         payload.dpe_class = Some("F".to_string());
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn judges_the_lease_by_the_rules_in_force_on_its_start_date() {
+        let mut payload = base_payload();
+        payload.dpe_class = Some("G".to_string());
+        let report = lease_compliance_report(&payload.compliance_terms(), "furnished", false);
+        assert_eq!(report.rule_set_version, "regles-2025-01-01");
+        assert!(report.blocking);
+
+        payload.start_date = NaiveDate::from_ymd_opt(2024, 9, 1).expect("valid date");
+        let report = validate_lease_payload(&payload, "furnished", false, None).expect("G still lettable in 2024");
+        assert_eq!(report.rule_set_version, "regles-2014-03-27");
+    }
+
     #[test]
     fn rejects_student_duration_not_nine_months() {
         let mut payload = base_payload();
         payload.lease_kind = Some("student".to_string());
         payload.duration_months = 10;
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1399,7 +1684,7 @@ mod tests {
         payload.rent_controlled = Some(true);
         payload.reference_rent = None;
         payload.reference_rent_majorated = None;
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1409,7 +1694,7 @@ mod tests {
         payload.professional_mandate = Some(true);
         payload.agency_fee_tenant = Some(BigDecimal::from(400));
         payload.agency_fee_landlord = Some(BigDecimal::from(300));
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1417,7 +1702,7 @@ mod tests {
     fn rejects_prohibited_custom_clause() {
         let mut payload = base_payload();
         payload.custom_clauses = Some("Clause avec frais de quittance imposes".to_string());
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1426,60 +1711,116 @@ mod tests {
         let mut payload = base_payload();
         payload.is_dom_tom = Some(false);
         payload.identifiant_fiscal = None;
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
 
         payload.is_dom_tom = Some(true);
         // DOM-TOM: IFL not required, but DPE threshold differs — keep a compliant DPE class.
         payload.dpe_class = Some("A".to_string());
-        assert!(validate_lease_payload(&payload, true).is_ok());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
     }
 
     #[test]
     fn requires_property_characterisation() {
         let mut payload = base_payload();
         payload.habitat_type = None;
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
 
         let mut payload = base_payload();
         payload.regime_juridique = None;
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
 
         let mut payload = base_payload();
         payload.construction_period = None;
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
     }
 
     #[test]
     fn rejects_rent_above_majorated_reference_without_complement() {
         let mut payload = base_payload();
         payload.rent_controlled = Some(true);
-        payload.reference_rent = Some(BigDecimal::from(800));
-        payload.reference_rent_majorated = Some(BigDecimal::from(900));
+        payload.reference_rent = Some(BigDecimal::from(18));
+        payload.reference_rent_majorated = Some(BigDecimal::from(20));
         payload.monthly_rent = BigDecimal::from(1000);
         payload.rent_complement = None;
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
     }
 
     #[test]
     fn accepts_rent_above_majorated_with_justified_complement() {
         let mut payload = base_payload();
         payload.rent_controlled = Some(true);
-        payload.reference_rent = Some(BigDecimal::from(800));
-        payload.reference_rent_majorated = Some(BigDecimal::from(900));
+        payload.reference_rent = Some(BigDecimal::from(18));
+        payload.reference_rent_majorated = Some(BigDecimal::from(20));
         payload.monthly_rent = BigDecimal::from(1000);
         payload.rent_complement = Some(BigDecimal::from(100));
         payload.rent_complement_justification = Some("Vue exceptionnelle".to_string());
-        assert!(validate_lease_payload(&payload, true).is_ok());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
     }
 
     #[test]
     fn accepts_rent_within_majorated_reference() {
         let mut payload = base_payload();
         payload.rent_controlled = Some(true);
-        payload.reference_rent = Some(BigDecimal::from(800));
-        payload.reference_rent_majorated = Some(BigDecimal::from(1100));
+        payload.reference_rent = Some(BigDecimal::from(20));
+        payload.reference_rent_majorated = Some(BigDecimal::from(24));
+        payload.monthly_rent = BigDecimal::from(1000);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+    }
+
+    fn paris_lookup() -> RentControlLookup {
+        let reference = crate::services::rent_control::RentControlReference {
+            city: "paris".to_string(),
+            zone: "1".to_string(),
+            room_count: 2,
+            construction_period: "1989_2005".to_string(),
+            furnished: true,
+            valid_from: NaiveDate::from_ymd_opt(2025, 7, 1).expect("valid date"),
+            reference_rent: BigDecimal::from(20),
+            reference_rent_majorated: "27.5".parse().expect("valid amount"),
+            reference_rent_minorated: BigDecimal::from(14),
+        };
+        RentControlLookup::for_surface(reference, &BigDecimal::from(40))
+    }
+
+    #[test]
+    fn warns_when_reference_rents_differ_from_the_arrete() {
+        let mut payload = base_payload();
+        payload.rent_controlled = Some(true);
+        payload.reference_rent = Some(BigDecimal::from(2));
+        payload.reference_rent_majorated = "27.5".parse().ok();
+        let report = validate_lease_payload(&payload, "furnished", false, Some(&paris_lookup())).expect("only a warning");
+        let mismatch = report
+            .violations
+            .iter()
+            .find(|v| v.code == "rent_control.reference_mismatch")
+            .expect("mismatch reported");
+        assert_eq!(mismatch.severity, SEVERITY_WARNING);
+        assert!(mismatch.message.contains("20.00 €/m²"), "{}", mismatch.message);
+        assert!(!report.compliant);
+    }
+
+    #[test]
+    fn fills_missing_reference_rents_from_the_arrete() {
+        let mut payload = base_payload();
+        payload.rent_controlled = Some(true);
+        payload.reference_rent = None;
+        payload.reference_rent_majorated = None;
+        let lookup = paris_lookup();
+        fill_reference_rents(&mut payload, Some(&lookup));
+        assert_eq!(payload.reference_rent, Some(BigDecimal::from(20)));
+        let report = validate_lease_payload(&payload, "furnished", false, Some(&lookup)).expect("valid");
+        assert!(report.compliant);
+    }
+
+    #[test]
+    fn warns_when_no_arrete_covers_the_dwelling() {
+        let mut payload = base_payload();
+        payload.rent_controlled = Some(true);
+        payload.reference_rent = Some(BigDecimal::from(20));
+        payload.reference_rent_majorated = Some(BigDecimal::from(24));
         payload.monthly_rent = BigDecimal::from(1000);
-        assert!(validate_lease_payload(&payload, true).is_ok());
+        let report = validate_lease_payload(&payload, "furnished", false, None).expect("only a warning");
+        assert!(report.violations.iter().any(|v| v.code == "rent_control.reference_unverified"));
     }
 
     #[test]
@@ -1487,10 +1828,10 @@ mod tests {
         let mut payload = base_payload();
         payload.construction_period = Some("avant_1949".to_string());
         payload.annex_lead_provided = Some(false);
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
 
         payload.annex_lead_provided = Some(true);
-        assert!(validate_lease_payload(&payload, true).is_ok());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
     }
 
     #[test]
@@ -1498,40 +1839,142 @@ mod tests {
         let mut payload = base_payload();
         payload.electrical_installation_over_15y = Some(true);
         payload.annex_electrical_provided = Some(false);
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
 
         let mut payload = base_payload();
         payload.gas_installation_over_15y = Some(true);
         payload.annex_gas_provided = Some(false);
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
 
         let mut payload = base_payload();
         payload.in_risk_zone = Some(true);
         payload.annex_risk_provided = Some(false);
-        assert!(validate_lease_payload(&payload, true).is_err());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
     }
 
     #[test]
     fn omits_conditional_annexes_when_facts_absent() {
         // base_payload has no triggering facts → should pass.
         let payload = base_payload();
-        assert!(validate_lease_payload(&payload, true).is_ok());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
     }
 
     #[test]
     fn rejects_missing_furniture_annex_for_furnished_property() {
         let mut payload = base_payload();
         payload.annex_furniture_inventory_provided = Some(false);
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn applies_unfurnished_duration_and_deposit_rules() {
+        let mut payload = base_payload();
+        payload.annex_furniture_inventory_provided = Some(false);
+        payload.duration_months = 36;
+        payload.deposit = payload.monthly_rent.clone();
+        assert!(validate_lease_payload(&payload, "unfurnished", false, None).is_ok());
+        // An SCI that is not a family SCI must let for at least six years.
+        assert!(validate_lease_payload(&payload, "unfurnished", true, None).is_err());
+        payload.duration_months = 72;
+        assert!(validate_lease_payload(&payload, "unfurnished", true, None).is_ok());
+
+        payload.deposit = &payload.monthly_rent + BigDecimal::from(1);
+        assert!(validate_lease_payload(&payload, "unfurnished", true, None).is_err());
+
+        payload.deposit = payload.monthly_rent.clone();
+        payload.duration_months = 12;
+        assert!(validate_lease_payload(&payload, "unfurnished", false, None).is_err());
+    }
+
+    #[test]
+    fn rejects_unfurnished_student_lease_and_unknown_type() {
+        let mut payload = base_payload();
+        payload.lease_kind = Some("student".to_string());
+        payload.duration_months = 9;
+        assert!(validate_lease_payload(&payload, "unfurnished", false, None).is_err());
+
+        let dwelling = PropertyLandlordFacts {
+            property_furnished: false,
+            is_ancillary: false,
+            is_legal_person: false,
+            zone_tendue: false,
+            zone_tendue_declared: None,
+        };
+        payload.lease_type = Some("saisonnier".to_string());
+        assert!(resolve_lease_type(&payload, &dwelling).is_err());
+        payload.lease_type = None;
+        assert_eq!(resolve_lease_type(&payload, &dwelling).unwrap(), "unfurnished");
+    }
+
+    #[test]
+    fn validates_mobility_lease() {
+        let mut payload = base_payload();
+        payload.lease_kind = Some("mobility".to_string());
+        payload.duration_months = 6;
+        payload.deposit = BigDecimal::from(0);
+        payload.mobility_reason = Some("stage".to_string());
+        payload.mobility_justification = Some("Convention de stage du 1er juin au 30 novembre 2026".to_string());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+        assert!(validate_lease_payload(&payload, "unfurnished", false, None).is_err());
+
+        payload.duration_months = 11;
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+        payload.duration_months = 6;
+
+        payload.deposit = BigDecimal::from(500);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+        payload.deposit = BigDecimal::from(0);
+
+        payload.mobility_justification = Some("  ".to_string());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+        payload.mobility_justification = Some("Mutation au siège".to_string());
+        payload.mobility_reason = Some("vacances".to_string());
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+    }
+
+    #[test]
+    fn ancillary_lease_skips_residential_requirements() {
+        let garage = PropertyLandlordFacts {
+            property_furnished: false,
+            is_ancillary: true,
+            is_legal_person: false,
+            zone_tendue: false,
+            zone_tendue_declared: None,
+        };
+        let mut payload = base_payload();
+        assert_eq!(resolve_lease_type(&payload, &garage).unwrap(), "ancillary");
+        payload.lease_type = Some("unfurnished".to_string());
+        assert!(resolve_lease_type(&payload, &garage).is_err());
+
+        payload.lease_type = Some("ancillary".to_string());
+        payload.duration_months = 1;
+        payload.deposit = BigDecimal::from(3000);
+        payload.habitable_surface = None;
+        payload.dpe_class = None;
+        payload.identifiant_fiscal = None;
+        payload.legal_notice_provided = false;
+        payload.annex_dpe_provided = Some(false);
+        payload.annex_furniture_inventory_provided = Some(false);
+        assert!(validate_lease_payload(&payload, "ancillary", false, None).is_ok());
+
+        payload.lease_kind = Some("student".to_string());
+        assert!(validate_lease_payload(&payload, "ancillary", false, None).is_err());
+        payload.lease_kind = None;
+        payload.tenant_ids = vec![];
+        assert!(validate_lease_payload(&payload, "ancillary", false, None).is_err());
+
+        let dwelling = PropertyLandlordFacts { is_ancillary: false, ..garage };
+        payload.lease_type = Some("ancillary".to_string());
+        assert!(resolve_lease_type(&payload, &dwelling).is_err());
+    }
+
     #[test]
     fn rejects_colocation_without_multiple_tenants() {
         let mut payload = base_payload();
         payload.is_colocation = Some(true);
         payload.tenant_ids = vec![Uuid::new_v4()]; // only one tenant
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1540,7 +1983,7 @@ mod tests {
         let mut payload = base_payload();
         payload.is_colocation = Some(true);
         payload.tenant_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_ok());
     }
 
@@ -1549,7 +1992,7 @@ mod tests {
         let mut payload = base_payload();
         payload.is_colocation = Some(false);
         payload.tenant_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1559,7 +2002,7 @@ mod tests {
         let dup = Uuid::new_v4();
         payload.is_colocation = Some(true);
         payload.tenant_ids = vec![dup, dup];
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1567,7 +2010,7 @@ mod tests {
     fn rejects_missing_legal_notice_flag() {
         let mut payload = base_payload();
         payload.legal_notice_provided = false;
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
@@ -1579,10 +2022,103 @@ mod tests {
         payload.reference_rent_majorated = Some(BigDecimal::from(25));
         payload.rent_complement = Some(BigDecimal::from(100));
         payload.rent_complement_justification = None;
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 
+    fn zone_tendue_relet() -> CreateLease {
+        let mut payload = base_payload();
+        payload.zone_tendue = Some(true);
+        payload.previous_tenant_departure_date = NaiveDate::from_ymd_opt(2026, 3, 31);
+        payload.previous_tenant_last_rent = Some(BigDecimal::from(950));
+        payload
+    }
+
+    #[test]
+    fn caps_relet_rent_at_the_previous_rent_in_zone_tendue() {
+        let payload = zone_tendue_relet();
+        let err = validate_lease_payload(&payload, "furnished", false, None).unwrap_err().to_string();
+        assert!(err.contains("950.00 €"), "{}", err);
+
+        let mut outside = zone_tendue_relet();
+        outside.zone_tendue = Some(false);
+        assert!(validate_lease_payload(&outside, "furnished", false, None).is_ok());
+    }
+
+    #[test]
+    fn relet_cap_allows_a_share_of_improvement_works() {
+        // 6 000 € of works, at least six months of rent: 15 % a year is 75 € a month.
+        let mut payload = zone_tendue_relet();
+        payload.works_amount = Some(BigDecimal::from(6000));
+        payload.works_date = NaiveDate::from_ymd_opt(2026, 4, 15);
+        payload.monthly_rent = BigDecimal::from(1025);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+
+        payload.monthly_rent = BigDecimal::from(1030);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+
+        // Works below six months of rent do not lift the cap.
+        payload.works_amount = Some(BigDecimal::from(5000));
+        payload.monthly_rent = BigDecimal::from(1000);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+    }
+
+    #[test]
+    fn relet_cap_indexes_a_previous_rent_not_revised_for_a_year() {
+        // 950 € × 147 / 140 = 997.50 €.
+        let mut payload = zone_tendue_relet();
+        payload.previous_rent_irl = Some(BigDecimal::from(140));
+        payload.irl_value = Some(BigDecimal::from(147));
+        payload.monthly_rent = "997.50".parse().expect("valid amount");
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+
+        payload.previous_rent_revised_on = NaiveDate::from_ymd_opt(2025, 5, 1);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+
+        // Revised less than twelve months before the start: no indexation.
+        payload.previous_rent_revised_on = NaiveDate::from_ymd_opt(2025, 9, 1);
+        let err = validate_lease_payload(&payload, "furnished", false, None).unwrap_err().to_string();
+        assert!(err.contains("950.00 €"), "{}", err);
+
+        // Without the reference quarter's value the rent stays capped.
+        payload.previous_rent_revised_on = None;
+        payload.irl_value = None;
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+    }
+
+    #[test]
+    fn relet_cap_closes_half_the_gap_of_an_undervalued_rent() {
+        // Comparable dwellings let for 1 100 €: half the 150 € gap.
+        let mut payload = zone_tendue_relet();
+        payload.comparable_rent = Some(BigDecimal::from(1100));
+        payload.monthly_rent = BigDecimal::from(1025);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+
+        payload.monthly_rent = BigDecimal::from(1030);
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+
+        // Works and undervaluation do not add up: the larger one applies.
+        payload.works_amount = Some(BigDecimal::from(6000));
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_err());
+
+        // The gap is measured from the indexed previous rent.
+        payload.previous_rent_irl = Some(BigDecimal::from(140));
+        payload.irl_value = Some(BigDecimal::from(147));
+        payload.monthly_rent = "1048.75".parse().expect("valid amount");
+        assert!(validate_lease_payload(&payload, "furnished", false, None).is_ok());
+    }
+
+    #[test]
+    fn undervalued_rent_exception_does_not_apply_under_rent_control() {
+        let mut payload = zone_tendue_relet();
+        payload.comparable_rent = Some(BigDecimal::from(1100));
+        payload.rent_controlled = Some(true);
+        payload.reference_rent = Some(BigDecimal::from(20));
+        payload.reference_rent_majorated = Some(BigDecimal::from(30));
+        let report = lease_report(&payload, "furnished", false, None);
+        assert!(report.violations.iter().any(|v| v.code == "previous_rent.relet_cap"));
+    }
+
     #[test]
     fn rejects_missing_previous_tenant_rent_when_departure_recent() {
         let mut payload = base_payload();
@@ -1590,7 +2126,7 @@ mod tests {
             NaiveDate::from_ymd_opt(2025, 12, 1).expect("valid date")
         );
         payload.previous_tenant_last_rent = None;
-        let result = validate_lease_payload(&payload, true);
+        let result = validate_lease_payload(&payload, "furnished", false, None);
         assert!(result.is_err());
     }
 }
@@ -2,5 +2,16 @@ pub mod auth;
 pub mod properties;
 pub mod tenants;
 pub mod leases;
+pub mod amendments;
+pub mod snapshots;
+pub mod notices;
+pub mod deadlines;
+pub mod inventories;
 pub mod receipts;
+pub mod payments;
+pub mod rent_calls;
+pub mod rent_history;
+pub mod irl;
+pub mod regularisations;
+pub mod deposits;
 pub mod organizations;
@@ -0,0 +1,299 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::LesseeParty;
+use crate::models::lease_notice::{CreateLeaseNotice, LeaseNotice, NoticeSnapshot};
+use crate::routes::amendments::current_contract;
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, fetch_lease_by_id, get_property_landlord_facts};
+use crate::routes::receipts::receipts_storage_dir;
+use crate::services::lease_notice::{plan_notice, tenant_reduced_notice_reason, NoticeLease};
+use crate::services::pdf_renderer::PdfRenderer;
+
+/// Current congé template version recorded in snapshots.
+const CURRENT_NOTICE_TEMPLATE_VERSION: &str = "conge-2026-10-17";
+
+const NOTICE_COLUMNS: &str = r#"
+    id, lease_id, given_by, served_on, notice_months, effective_date, ground,
+    reduced_notice_reason, beneficiary_name, beneficiary_relationship, sale_price,
+    details, status, pdf_path, created_at
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct NoticeLetterQuery {
+    inline: Option<bool>,
+}
+
+async fn fetch_notice(db: &Database, lease_id: Uuid, id: Uuid) -> Result<LeaseNotice, AppError> {
+    sqlx::query_as::<_, LeaseNotice>(&format!(
+        "SELECT {} FROM lease_notices WHERE id = $1 AND lease_id = $2",
+        NOTICE_COLUMNS
+    ))
+    .bind(id)
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Notice not found".to_string()))
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// GET /api/leases/{id}/notices
+/// Every notice recorded on the lease, withdrawn ones included, newest first.
+pub async fn list_notices(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<LeaseNotice>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let notices = sqlx::query_as::<_, LeaseNotice>(&format!(
+        "SELECT {} FROM lease_notices WHERE lease_id = $1 ORDER BY served_on DESC, created_at DESC",
+        NOTICE_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(notices))
+}
+
+/// POST /api/leases/{id}/notices
+/// Record a congé on a lease in force. The lease moves to `notice_given` and
+/// is billed up to the computed effective date.
+pub async fn create_notice(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateLeaseNotice>,
+) -> Result<(StatusCode, Json<LeaseNotice>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    match lease.status.as_str() {
+        "active" => {}
+        "notice_given" => {
+            return Err(AppError::Validation(
+                "A notice is already running on this lease; withdraw it first".to_string(),
+            ))
+        }
+        _ => return Err(AppError::Validation("Notice can only be given on a lease in force".to_string())),
+    }
+
+    let landlord = get_property_landlord_facts(&db, lease.property_id).await?;
+    let notice_lease = NoticeLease {
+        lease_type: &lease.lease_type,
+        lease_kind: &lease.lease_kind,
+        start_date: lease.start_date,
+        duration_months: lease.duration_months,
+        landlord_is_legal_person: landlord.is_legal_person,
+        zone_tendue: landlord.zone_tendue,
+        zone_tendue_declared: landlord.zone_tendue_declared,
+    };
+    let terms = plan_notice(&notice_lease, &payload)?;
+
+    // Keep only the fields that apply to who gives notice.
+    let by_landlord = payload.given_by == "landlord";
+    let reduced_notice_reason = if !by_landlord && lease.lease_type == "unfurnished" {
+        tenant_reduced_notice_reason(&notice_lease, payload.reduced_notice_reason.as_deref())?.map(str::to_string)
+    } else {
+        None
+    };
+    let ground = payload.ground.clone().filter(|_| by_landlord);
+    let is_reprise = ground.as_deref() == Some("reprise");
+    let beneficiary_name = trimmed(payload.beneficiary_name.clone()).filter(|_| is_reprise);
+    let beneficiary_relationship = trimmed(payload.beneficiary_relationship.clone()).filter(|_| is_reprise);
+    let sale_price = payload.sale_price.clone().filter(|_| ground.as_deref() == Some("vente"));
+    let details = trimmed(payload.details.clone()).filter(|_| by_landlord);
+
+    // The letter names the parties as they stand on the day it is served.
+    let contract = current_contract(&db, id, user_id, payload.served_on).await?;
+
+    let mut tx = db.pool.begin().await?;
+
+    let notice = sqlx::query_as::<_, LeaseNotice>(&format!(
+        r#"
+        INSERT INTO lease_notices (
+            lease_id, given_by, served_on, notice_months, effective_date, ground,
+            reduced_notice_reason, beneficiary_name, beneficiary_relationship, sale_price, details
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING {}
+        "#,
+        NOTICE_COLUMNS
+    ))
+    .bind(id)
+    .bind(&payload.given_by)
+    .bind(payload.served_on)
+    .bind(terms.notice_months)
+    .bind(terms.effective_date)
+    .bind(&ground)
+    .bind(&reduced_notice_reason)
+    .bind(&beneficiary_name)
+    .bind(&beneficiary_relationship)
+    .bind(&sale_price)
+    .bind(&details)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE leases
+        SET status = 'notice_given',
+            status_changed_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND status = 'active'
+        "#,
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        return Err(AppError::Validation("The lease status changed concurrently; reload and retry".to_string()));
+    }
+
+    let mut parties = contract.parties.clone();
+    if parties.lessees.is_empty() {
+        parties.lessees.push(LesseeParty {
+            full_name: parties.lessee_full_name.clone(),
+            address: parties.lessee_address.clone(),
+            email: parties.lessee_email.clone(),
+            birth_date: parties.lessee_birth_date,
+            birth_place: parties.lessee_birth_place.clone(),
+        });
+    }
+    let snapshot = NoticeSnapshot {
+        id: Uuid::new_v4(),
+        notice_id: notice.id,
+        lease_id: id,
+        template_version: CURRENT_NOTICE_TEMPLATE_VERSION.to_string(),
+        generated_at: Utc::now(),
+        language: "fr".to_string(),
+        given_by: notice.given_by.clone(),
+        parties,
+        property_address: contract.property.address.clone(),
+        lease_type: lease.lease_type.clone(),
+        lease_start_date: lease.start_date,
+        served_on: notice.served_on,
+        notice_months: notice.notice_months,
+        effective_date: notice.effective_date,
+        ground,
+        reduced_notice_reason,
+        beneficiary_name,
+        beneficiary_relationship,
+        sale_price,
+        details,
+    };
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for notice {}: {}", notice.id, e);
+        AppError::Internal
+    })?;
+    sqlx::query("UPDATE lease_notices SET snapshot = $1 WHERE id = $2")
+        .bind(snapshot_json)
+        .bind(notice.id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(notice)))
+}
+
+async fn load_notice_snapshot(db: &Database, id: Uuid) -> Result<NoticeSnapshot, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM lease_notices WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten()
+    .ok_or_else(|| AppError::NotFound("Notice letter not found".to_string()))?;
+
+    serde_json::from_value::<NoticeSnapshot>(stored).map_err(|e| {
+        tracing::error!("Stored snapshot for notice {} is invalid: {}", id, e);
+        AppError::Internal
+    })
+}
+
+/// GET /api/leases/{id}/notices/{notice_id}/letter
+/// The congé letter, rendered from its frozen snapshot on first access.
+pub async fn get_notice_letter(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((lease_id, notice_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<NoticeLetterQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+    let notice = fetch_notice(&db, lease_id, notice_id).await?;
+
+    let stored = match notice.pdf_path.as_deref() {
+        Some(path) => tokio::fs::read(path)
+            .await
+            .map_err(|e| tracing::warn!("Stored PDF {} for notice {} is unreadable ({}); re-rendering", path, notice_id, e))
+            .ok(),
+        None => None,
+    };
+
+    let pdf_bytes = match stored {
+        Some(bytes) => bytes,
+        None => {
+            let snapshot = load_notice_snapshot(&db, notice_id).await?;
+            let renderer = PdfRenderer::from_env().map_err(|e| {
+                tracing::error!("Failed to initialize PDF renderer: {}", e);
+                AppError::Internal
+            })?;
+            let bytes = renderer.generate_notice_pdf(&snapshot).await.map_err(|e| {
+                tracing::error!("Notice PDF generation failed for {}: {}", notice_id, e);
+                AppError::BadRequest(format!("PDF generation failed: {}", e))
+            })?;
+
+            let storage_dir = receipts_storage_dir();
+            let pdf_path = storage_dir.join(format!("conge_{}.pdf", notice_id));
+            tokio::fs::create_dir_all(&storage_dir).await.map_err(|e| {
+                tracing::error!("Failed to create receipt storage dir {:?}: {}", storage_dir, e);
+                AppError::Internal
+            })?;
+            tokio::fs::write(&pdf_path, &bytes).await.map_err(|e| {
+                tracing::error!("Failed to store notice PDF {:?}: {}", pdf_path, e);
+                AppError::Internal
+            })?;
+            sqlx::query("UPDATE lease_notices SET pdf_path = $1 WHERE id = $2")
+                .bind(pdf_path.to_string_lossy().to_string())
+                .bind(notice_id)
+                .execute(&db.pool)
+                .await?;
+
+            bytes
+        }
+    };
+
+    let filename = format!("conge_{}_{}.pdf", notice.served_on, lease_id);
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
@@ -0,0 +1,599 @@
+use axum::{
+    Router,
+    routing::get,
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap},
+};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::payment::{CreatePayment, Payment, PAYMENT_METHODS};
+use crate::models::receipt::Receipt;
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::ensure_lease_access;
+use crate::routes::receipts::{current_lease_snapshot, persist_receipt_snapshot};
+use crate::services::ledger::{allocate, Allocation, DuePeriod, LedgerOutcome, LedgerPayment, PeriodBalance};
+
+pub fn router() -> Router<Database> {
+    Router::new()
+        .route("/", get(list_payments).post(create_payment))
+        .route("/:id", axum::routing::delete(delete_payment))
+        .route("/lease/:lease_id/ledger", get(get_lease_ledger))
+}
+
+#[derive(Debug, Deserialize)]
+struct PaymentQuery {
+    lease_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+struct LeaseLedger {
+    lease_id: Uuid,
+    payments: Vec<Payment>,
+    #[serde(flatten)]
+    outcome: LedgerOutcome,
+}
+
+fn validate_payment(payload: &CreatePayment) -> Result<(), AppError> {
+    if payload.amount <= BigDecimal::zero() {
+        return Err(AppError::Validation("Payment amount must be positive".to_string()));
+    }
+    if !PAYMENT_METHODS.contains(&payload.method.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Payment method must be one of: {}",
+            PAYMENT_METHODS.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Receipt status matching a period's settlement.
+fn receipt_status_for(settlement: &str, emailed: bool) -> &'static str {
+    match settlement {
+        "settled" => "paid",
+        "partial" => "partially_paid",
+        _ if emailed => "sent",
+        _ => "generated",
+    }
+}
+
+async fn fetch_lease_receipts(db: &Database, lease_id: Uuid) -> Result<Vec<Receipt>, AppError> {
+    let receipts = sqlx::query_as::<_, Receipt>(
+        r#"
+        SELECT id, lease_id, period_month, period_year,
+               base_rent, charges, total_amount,
+               payment_date, status, amount_paid, email_sent_at, pdf_path,
+               created_at, updated_at
+        FROM receipts
+        WHERE lease_id = $1
+        ORDER BY period_year, period_month
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(receipts)
+}
+
+async fn fetch_lease_payments(db: &Database, lease_id: Uuid) -> Result<Vec<Payment>, AppError> {
+    let payments = sqlx::query_as::<_, Payment>(
+        r#"
+        SELECT id, lease_id, payer_tenant_id, receipt_id, amount, payment_date,
+               method, reference, created_at, updated_at
+        FROM payments
+        WHERE lease_id = $1
+        ORDER BY payment_date DESC, created_at DESC
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(payments)
+}
+
+/// Allocations already recorded for the lease's payments.
+async fn fetch_lease_allocations(db: &Database, lease_id: Uuid) -> Result<Vec<Allocation>, AppError> {
+    let rows = sqlx::query_as::<_, (Uuid, Uuid, BigDecimal)>(
+        r#"
+        SELECT a.payment_id, a.receipt_id, a.amount
+        FROM payment_allocations a
+        JOIN payments p ON p.id = a.payment_id
+        WHERE p.lease_id = $1
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(payment_id, receipt_id, amount)| Allocation { payment_id, receipt_id, amount })
+        .collect())
+}
+
+fn compute_ledger(receipts: &[Receipt], payments: &[Payment], recorded: &[Allocation]) -> LedgerOutcome {
+    let periods: Vec<DuePeriod> = receipts
+        .iter()
+        .map(|r| DuePeriod {
+            receipt_id: r.id,
+            period_year: r.period_year,
+            period_month: r.period_month,
+            amount_due: r.total_amount.clone(),
+        })
+        .collect();
+    let ledger_payments: Vec<LedgerPayment> = payments
+        .iter()
+        .map(|p| LedgerPayment {
+            id: p.id,
+            amount: p.amount.clone(),
+            payment_date: p.payment_date,
+            receipt_id: p.receipt_id,
+        })
+        .collect();
+
+    allocate(&periods, &ledger_payments, recorded)
+}
+
+/// Date of the last payment allocated to each receipt: the payment that
+/// completed a settled period, or the latest one towards a partial period.
+fn last_payment_dates(outcome: &LedgerOutcome, payments: &[Payment]) -> HashMap<Uuid, NaiveDate> {
+    let mut dates: HashMap<Uuid, NaiveDate> = HashMap::new();
+    for allocation in &outcome.allocations {
+        let Some(payment) = payments.iter().find(|p| p.id == allocation.payment_id) else {
+            continue;
+        };
+        dates
+            .entry(allocation.receipt_id)
+            .and_modify(|date| *date = (*date).max(payment.payment_date))
+            .or_insert(payment.payment_date);
+    }
+    dates
+}
+
+/// The status a receipt moves to given its balance on the ledger and its last
+/// payment, or `None` when it stays as it is. A paid receipt is final: its
+/// quittance was issued, so it is never downgraded or re-dated.
+fn settlement_update(
+    receipt: &Receipt,
+    balance: &PeriodBalance,
+    paid_on: Option<NaiveDate>,
+) -> Option<&'static str> {
+    if receipt.status == "paid" {
+        return None;
+    }
+    let status = receipt_status_for(&balance.settlement, receipt.email_sent_at.is_some());
+    let unchanged = receipt.amount_paid == balance.amount_paid
+        && receipt.status == status
+        && paid_on.is_none_or(|date| date == receipt.payment_date);
+    (!unchanged).then_some(status)
+}
+
+/// Allocate the payments of a lease that are not yet allocated to the
+/// receipts still owing, and persist the result: the new
+/// `payment_allocations`, each open receipt's `amount_paid`, status and
+/// `payment_date` (the last allocated payment, or the due date when nothing
+/// is allocated), and the link from each avis d'échéance to the receipt that
+/// closes it. Recorded allocations are never moved.
+/// An open receipt whose settlement changed gets a fresh snapshot and loses
+/// its stored PDF, since it now entitles the tenants to a different document;
+/// a paid receipt keeps the quittance issued for it.
+pub(crate) async fn reconcile_lease(
+    db: &Database,
+    lease_id: Uuid,
+    requesting_user_id: Uuid,
+) -> Result<LedgerOutcome, AppError> {
+    let receipts = fetch_lease_receipts(db, lease_id).await?;
+    let payments = fetch_lease_payments(db, lease_id).await?;
+    let recorded = fetch_lease_allocations(db, lease_id).await?;
+    let outcome = compute_ledger(&receipts, &payments, &recorded);
+    let paid_on = last_payment_dates(&outcome, &payments);
+
+    let mut tx = db.pool.begin().await?;
+
+    for allocation in outcome.allocations.iter().filter(|a| !recorded.contains(a)) {
+        sqlx::query(
+            r#"
+            INSERT INTO payment_allocations (payment_id, receipt_id, amount) VALUES ($1, $2, $3)
+            ON CONFLICT (payment_id, receipt_id) DO UPDATE SET amount = EXCLUDED.amount
+            "#,
+        )
+        .bind(allocation.payment_id)
+        .bind(allocation.receipt_id)
+        .bind(&allocation.amount)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let mut changed = Vec::new();
+    for balance in &outcome.periods {
+        let Some(receipt) = receipts.iter().find(|r| r.id == balance.receipt_id) else {
+            continue;
+        };
+        let payment_date = paid_on.get(&receipt.id).copied();
+        let Some(status) = settlement_update(receipt, balance, payment_date) else {
+            continue;
+        };
+
+        let updated = sqlx::query_as::<_, Receipt>(
+            r#"
+            UPDATE receipts
+            SET amount_paid = $2,
+                status = $3,
+                payment_date = COALESCE($4, due_date),
+                pdf_path = NULL,
+                pdf_data = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status <> 'paid'
+            RETURNING id, lease_id, period_month, period_year,
+                      base_rent, charges, total_amount,
+                      payment_date, status, amount_paid, email_sent_at, pdf_path,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(receipt.id)
+        .bind(&balance.amount_paid)
+        .bind(status)
+        .bind(payment_date)
+        .fetch_optional(&mut *tx)
+        .await?;
+        changed.extend(updated);
+    }
+
+    // An avis d'échéance is closed by the receipt of its period once that
+    // period is fully paid.
+    sqlx::query(
+        r#"
+        UPDATE rent_calls rc
+        SET receipt_id = r.id,
+            status = CASE
+                WHEN r.id IS NOT NULL THEN 'settled'
+                WHEN rc.email_sent_at IS NOT NULL THEN 'sent'
+                ELSE 'issued'
+            END,
+            updated_at = CURRENT_TIMESTAMP
+        FROM rent_calls current
+        LEFT JOIN receipts r
+          ON r.lease_id = current.lease_id
+         AND r.period_month = current.period_month
+         AND r.period_year = current.period_year
+         AND r.status = 'paid'
+        WHERE rc.id = current.id
+          AND rc.lease_id = $1
+          AND rc.receipt_id IS DISTINCT FROM r.id
+        "#,
+    )
+    .bind(lease_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if !changed.is_empty() {
+        let lease_snapshot = current_lease_snapshot(db, lease_id, requesting_user_id).await?;
+        for receipt in &changed {
+            persist_receipt_snapshot(db, &lease_snapshot, receipt).await?;
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Reject removing a payment that settled a period whose quittance was
+/// issued: the quittance attests that payment.
+pub(crate) async fn ensure_payment_removable(db: &Database, payment_id: Uuid) -> Result<(), AppError> {
+    let settles_quittance = sqlx::query_scalar::<_, Option<bool>>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1
+            FROM payment_allocations a
+            JOIN receipts r ON r.id = a.receipt_id
+            WHERE a.payment_id = $1 AND r.status = 'paid'
+        )
+        "#,
+    )
+    .bind(payment_id)
+    .fetch_one(&db.pool)
+    .await?
+    .unwrap_or(false);
+
+    if settles_quittance {
+        return Err(AppError::Conflict(
+            "This payment settles a period whose quittance was issued; it cannot be removed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject a payer who is not a tenant of the lease.
+async fn ensure_payer_on_lease(db: &Database, lease_id: Uuid, tenant_id: Uuid) -> Result<(), AppError> {
+    let on_lease = sqlx::query_scalar::<_, Option<bool>>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM lease_tenants WHERE lease_id = $1 AND tenant_id = $2
+            UNION
+            SELECT 1 FROM leases WHERE id = $1 AND tenant_id = $2
+        )
+        "#,
+    )
+    .bind(lease_id)
+    .bind(tenant_id)
+    .fetch_one(&db.pool)
+    .await?
+    .unwrap_or(false);
+
+    if !on_lease {
+        return Err(AppError::Validation("Payer is not a tenant of this lease".to_string()));
+    }
+    Ok(())
+}
+
+async fn ensure_receipt_on_lease(db: &Database, lease_id: Uuid, receipt_id: Uuid) -> Result<(), AppError> {
+    let on_lease = sqlx::query_scalar::<_, Option<bool>>(
+        "SELECT EXISTS(SELECT 1 FROM receipts WHERE id = $1 AND lease_id = $2)",
+    )
+    .bind(receipt_id)
+    .bind(lease_id)
+    .fetch_one(&db.pool)
+    .await?
+    .unwrap_or(false);
+
+    if !on_lease {
+        return Err(AppError::Validation("Receipt does not belong to this lease".to_string()));
+    }
+    Ok(())
+}
+
+/// Record a payment and allocate it on the lease's ledger. Shared by the
+/// payments endpoint and the receipt "mark paid" shortcut.
+pub(crate) async fn record_payment(
+    db: &Database,
+    payload: &CreatePayment,
+    requesting_user_id: Uuid,
+) -> Result<Payment, AppError> {
+    validate_payment(payload)?;
+    if let Some(tenant_id) = payload.payer_tenant_id {
+        ensure_payer_on_lease(db, payload.lease_id, tenant_id).await?;
+    }
+    if let Some(receipt_id) = payload.receipt_id {
+        ensure_receipt_on_lease(db, payload.lease_id, receipt_id).await?;
+    }
+
+    let payment = sqlx::query_as::<_, Payment>(
+        r#"
+        INSERT INTO payments (lease_id, payer_tenant_id, receipt_id, amount, payment_date, method, reference)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, lease_id, payer_tenant_id, receipt_id, amount, payment_date,
+                  method, reference, created_at, updated_at
+        "#,
+    )
+    .bind(payload.lease_id)
+    .bind(payload.payer_tenant_id)
+    .bind(payload.receipt_id)
+    .bind(&payload.amount)
+    .bind(payload.payment_date)
+    .bind(&payload.method)
+    .bind(&payload.reference)
+    .fetch_one(&db.pool)
+    .await?;
+
+    reconcile_lease(db, payload.lease_id, requesting_user_id).await?;
+
+    Ok(payment)
+}
+
+/// GET /api/payments?lease_id=
+async fn list_payments(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Query(params): Query<PaymentQuery>,
+) -> Result<Json<Vec<Payment>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, params.lease_id, user_id).await?;
+
+    Ok(Json(fetch_lease_payments(&db, params.lease_id).await?))
+}
+
+/// POST /api/payments
+async fn create_payment(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Json(payload): Json<CreatePayment>,
+) -> Result<(StatusCode, Json<Payment>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, payload.lease_id, user_id).await?;
+
+    let payment = record_payment(&db, &payload, user_id).await?;
+    Ok((StatusCode::CREATED, Json(payment)))
+}
+
+/// DELETE /api/payments/{id}
+async fn delete_payment(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let (lease_id, method) =
+        sqlx::query_as::<_, (Uuid, String)>("SELECT lease_id, method FROM payments WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&db.pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Payment not found".to_string()))?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+    if method == "depot_garantie" {
+        return Err(AppError::Validation(
+            "This payment was kept from the deposit; remove the deposit deduction instead".to_string(),
+        ));
+    }
+    ensure_payment_removable(&db, id).await?;
+
+    sqlx::query("DELETE FROM payments WHERE id = $1")
+        .bind(id)
+        .execute(&db.pool)
+        .await?;
+
+    reconcile_lease(&db, lease_id, user_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/payments/lease/{lease_id}/ledger
+/// Per-period balances, arrears and credit for a lease.
+async fn get_lease_ledger(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(lease_id): Path<Uuid>,
+) -> Result<Json<LeaseLedger>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+
+    let receipts = fetch_lease_receipts(&db, lease_id).await?;
+    let payments = fetch_lease_payments(&db, lease_id).await?;
+    let recorded = fetch_lease_allocations(&db, lease_id).await?;
+    let outcome = compute_ledger(&receipts, &payments, &recorded);
+
+    Ok(Json(LeaseLedger { lease_id, payments, outcome }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    fn payload(amount: &str, method: &str) -> CreatePayment {
+        CreatePayment {
+            lease_id: Uuid::new_v4(),
+            payer_tenant_id: None,
+            receipt_id: None,
+            amount: BigDecimal::from_str(amount).unwrap(),
+            payment_date: NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(),
+            method: method.to_string(),
+            reference: None,
+        }
+    }
+
+    #[test]
+    fn accepts_supported_methods() {
+        for method in PAYMENT_METHODS {
+            assert!(validate_payment(&payload("700", method)).is_ok());
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_method_and_non_positive_amount() {
+        assert!(validate_payment(&payload("700", "bitcoin")).is_err());
+        assert!(validate_payment(&payload("700", "autre")).is_err());
+        assert!(validate_payment(&payload("0", "virement")).is_err());
+        assert!(validate_payment(&payload("-10", "virement")).is_err());
+    }
+
+    #[test]
+    fn receipt_status_follows_settlement() {
+        assert_eq!(receipt_status_for("settled", false), "paid");
+        assert_eq!(receipt_status_for("partial", true), "partially_paid");
+        assert_eq!(receipt_status_for("unpaid", true), "sent");
+        assert_eq!(receipt_status_for("unpaid", false), "generated");
+    }
+
+    fn d(day: u32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn receipt(month: i32, total: i64) -> Receipt {
+        Receipt {
+            id: Uuid::new_v4(),
+            lease_id: Uuid::nil(),
+            period_month: month,
+            period_year: 2026,
+            base_rent: BigDecimal::from(total),
+            charges: BigDecimal::zero(),
+            total_amount: BigDecimal::from(total),
+            payment_date: d(1, month as u32),
+            status: "generated".to_string(),
+            amount_paid: BigDecimal::zero(),
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn payment(amount: i64, payment_date: NaiveDate) -> Payment {
+        Payment {
+            id: Uuid::new_v4(),
+            lease_id: Uuid::nil(),
+            payer_tenant_id: None,
+            receipt_id: None,
+            amount: BigDecimal::from(amount),
+            payment_date,
+            method: "virement".to_string(),
+            reference: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn payment_date_is_the_payment_completing_the_settlement() {
+        let september = receipt(9, 700);
+        let october = receipt(10, 700);
+        // Two colocataires pay their halves of September on different days;
+        // the second transfer also covers part of October.
+        let payments = vec![payment(350, d(3, 9)), payment(600, d(12, 9))];
+        let outcome = compute_ledger(&[september.clone(), october.clone()], &payments, &[]);
+        let dates = last_payment_dates(&outcome, &payments);
+
+        assert_eq!(dates.get(&september.id), Some(&d(12, 9)));
+        assert_eq!(dates.get(&october.id), Some(&d(12, 9)));
+    }
+
+    #[test]
+    fn unpaid_receipt_has_no_payment_date() {
+        let october = receipt(10, 700);
+        let outcome = compute_ledger(std::slice::from_ref(&october), &[], &[]);
+        assert!(last_payment_dates(&outcome, &[]).is_empty());
+    }
+
+    #[test]
+    fn backfilled_earlier_period_leaves_the_issued_quittance_alone() {
+        let mut october = receipt(10, 700);
+        let payments = vec![payment(700, d(5, 10))];
+        let issued = compute_ledger(std::slice::from_ref(&october), &payments, &[]);
+        october.status = "paid".to_string();
+        october.amount_paid = BigDecimal::from(700);
+        october.payment_date = d(5, 10);
+
+        // September is added after October's quittance went out.
+        let september = receipt(9, 700);
+        let outcome = compute_ledger(&[september.clone(), october.clone()], &payments, &issued.allocations);
+        assert_eq!(outcome.allocations, issued.allocations);
+        let dates = last_payment_dates(&outcome, &payments);
+        for (receipt, balance) in [&september, &october].into_iter().zip(&outcome.periods) {
+            assert_eq!(balance.receipt_id, receipt.id);
+            assert_eq!(settlement_update(receipt, balance, dates.get(&receipt.id).copied()), None);
+        }
+        assert_eq!(outcome.arrears, BigDecimal::from(700));
+    }
+
+    #[test]
+    fn paid_receipt_is_never_downgraded() {
+        let mut october = receipt(10, 700);
+        october.status = "paid".to_string();
+        october.amount_paid = BigDecimal::from(700);
+        let outcome = compute_ledger(std::slice::from_ref(&october), &[], &[]);
+        assert_eq!(outcome.periods[0].settlement, "unpaid");
+        assert_eq!(settlement_update(&october, &outcome.periods[0], None), None);
+
+        let partial = receipt(11, 700);
+        let outcome = compute_ledger(std::slice::from_ref(&partial), &[payment(300, d(5, 11))], &[]);
+        assert_eq!(settlement_update(&partial, &outcome.periods[0], Some(d(5, 11))), Some("partially_paid"));
+    }
+}
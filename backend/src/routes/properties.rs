@@ -1,10 +1,13 @@
 use axum::{
     Router,
     routing::get,
-    extract::{State, Path},
+    extract::{State, Path, Query},
     Json,
     http::{StatusCode, HeaderMap},
 };
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
 use uuid::Uuid;
 use crate::{
     db::Database,
@@ -20,6 +23,8 @@ use crate::{
             UpdateFurnitureItem,
         },
     },
+    services::rent_control::{lookup_reference_rents, DwellingFacts, RentControlLookup},
+    services::zone_tendue::classify_property,
     error::AppError,
     routes::auth::extract_user_id_from_headers,
 };
@@ -28,6 +33,7 @@ pub fn router() -> Router<Database> {
     Router::new()
         .route("/", get(list_properties).post(create_property))
         .route("/:id", get(get_property).put(update_property).delete(delete_property))
+        .route("/:id/rent-control-reference", get(get_rent_control_reference))
         .route("/:id/furniture-sets", get(list_furniture_sets).post(create_furniture_set))
         .route("/:id/furniture-sets/:set_id", get(get_furniture_set).put(update_furniture_set).delete(delete_furniture_set))
         .route("/:id/furniture-sets/:set_id/items", axum::routing::post(create_furniture_item))
@@ -66,7 +72,7 @@ async fn list_properties(
     let properties = sqlx::query_as::<_, Property>(
         r#"
         SELECT DISTINCT p.id, p.user_id, p.organization_id, p.address, p.property_type,
-               p.furnished, p.surface_area, p.rooms, p.max_occupants, p.description,
+               p.furnished, p.surface_area, p.rooms, p.max_occupants, p.description, p.rent_control_zone, p.zone_tendue, p.zone_tendue_declared,
                p.created_at, p.updated_at
         FROM properties p
         LEFT JOIN organization_members om ON p.organization_id = om.organization_id
@@ -93,12 +99,16 @@ async fn create_property(
     } else {
         (Some(user_id), None)
     };
+    let zone_tendue = classify_property(&db, &data.address, data.zone_tendue_declared).await?;
 
     let property = sqlx::query_as::<_, Property>(
         r#"
-        INSERT INTO properties (user_id, organization_id, address, property_type, furnished, surface_area, rooms, max_occupants, description)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-        RETURNING id, user_id, organization_id, address, property_type, furnished, surface_area, rooms, max_occupants, description, created_at, updated_at
+        INSERT INTO properties (
+            user_id, organization_id, address, property_type, furnished, surface_area, rooms, max_occupants,
+            description, rent_control_zone, zone_tendue, zone_tendue_declared
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING id, user_id, organization_id, address, property_type, furnished, surface_area, rooms, max_occupants, description, rent_control_zone, zone_tendue, zone_tendue_declared, created_at, updated_at
         "#,
     )
     .bind(owner_user_id)
@@ -110,6 +120,9 @@ async fn create_property(
     .bind(data.rooms)
     .bind(data.max_occupants)
     .bind(data.description)
+    .bind(data.rent_control_zone)
+    .bind(zone_tendue)
+    .bind(data.zone_tendue_declared)
     .fetch_one(&db.pool)
     .await?;
 
@@ -126,7 +139,7 @@ async fn get_property(
     let property = sqlx::query_as::<_, Property>(
         r#"
         SELECT p.id, p.user_id, p.organization_id, p.address, p.property_type,
-               p.furnished, p.surface_area, p.rooms, p.max_occupants, p.description,
+               p.furnished, p.surface_area, p.rooms, p.max_occupants, p.description, p.rent_control_zone, p.zone_tendue, p.zone_tendue_declared,
                p.created_at, p.updated_at
         FROM properties p
         LEFT JOIN organization_members om ON p.organization_id = om.organization_id
@@ -158,14 +171,16 @@ async fn update_property(
     } else {
         (Some(user_id), None)
     };
+    let zone_tendue = classify_property(&db, &data.address, data.zone_tendue_declared).await?;
 
     let property = sqlx::query_as::<_, Property>(
         r#"
         UPDATE properties
         SET user_id = $1, organization_id = $2, address = $3, property_type = $4, furnished = $5,
-            surface_area = $6, rooms = $7, max_occupants = $8, description = $9, updated_at = CURRENT_TIMESTAMP
-        WHERE id = $10
-        RETURNING id, user_id, organization_id, address, property_type, furnished, surface_area, rooms, max_occupants, description, created_at, updated_at
+            surface_area = $6, rooms = $7, max_occupants = $8, description = $9, rent_control_zone = $10,
+            zone_tendue = $11, zone_tendue_declared = $12, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $13
+        RETURNING id, user_id, organization_id, address, property_type, furnished, surface_area, rooms, max_occupants, description, rent_control_zone, zone_tendue, zone_tendue_declared, created_at, updated_at
         "#,
     )
     .bind(owner_user_id)
@@ -177,6 +192,9 @@ async fn update_property(
     .bind(data.rooms)
     .bind(data.max_occupants)
     .bind(data.description)
+    .bind(data.rent_control_zone)
+    .bind(zone_tendue)
+    .bind(data.zone_tendue_declared)
     .bind(id)
     .fetch_one(&db.pool)
     .await?;
@@ -201,6 +219,43 @@ pub async fn delete_property(
     Ok(Json(()))
 }
 
+#[derive(Debug, Deserialize)]
+struct RentControlReferenceQuery {
+    construction_period: String,
+    main_room_count: i32,
+    habitable_surface: Option<BigDecimal>,
+    furnished: bool,
+    start_date: NaiveDate,
+}
+
+/// GET /api/properties/:id/rent-control-reference
+/// Reference rents of the dwelling from the arrêté in force on the start
+/// date, for the lease form to fill in.
+async fn get_rent_control_reference(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(query): Query<RentControlReferenceQuery>,
+) -> Result<Json<RentControlLookup>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+
+    ensure_property_access(&db, id, user_id).await?;
+
+    let facts = DwellingFacts {
+        property_id: id,
+        construction_period: Some(&query.construction_period),
+        main_room_count: Some(query.main_room_count),
+        habitable_surface: query.habitable_surface.as_ref(),
+        furnished: query.furnished,
+        on: query.start_date,
+    };
+    let lookup = lookup_reference_rents(&db, &facts).await?.ok_or_else(|| {
+        AppError::NotFound("No reference rent covers this dwelling in the loaded arrêtés".to_string())
+    })?;
+
+    Ok(Json(lookup))
+}
+
 async fn list_furniture_sets(
     State(db): State<Database>,
     headers: HeaderMap,
@@ -3,20 +3,43 @@ use axum::{
     routing::get,
     extract::{Path, Query, State},
     Json,
-    http::StatusCode,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
 };
-use bigdecimal::{BigDecimal, num_traits::Signed};
+use bigdecimal::num_traits::Signed;
 use chrono::{Datelike, NaiveDate, Utc};
 use serde::Deserialize;
+use std::path::PathBuf;
 use uuid::Uuid;
 use crate::db::Database;
 use crate::error::AppError;
-use crate::models::receipt::{Receipt, CreateReceipt};
+use crate::models::canonical_snapshot::CanonicalSnapshot;
+use crate::models::payment::CreatePayment;
+use crate::models::receipt::{Receipt, CreateReceipt, MarkReceiptPaid};
+use crate::models::receipt_snapshot::ReceiptSnapshot;
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{build_snapshot_for_lease, ensure_lease_access, ensure_lease_billable, fetch_lease_by_id};
+use crate::routes::payments::{reconcile_lease, record_payment};
+use crate::routes::rent_history::rent_periods;
+use crate::models::rent_history::amounts_over;
+use crate::services::mailer::{MailAttachment, Mailer, OutgoingMail};
+use crate::services::pdf_renderer::{PdfRenderer, format_amount, period_label};
+
+/// Current quittance template version recorded in newly issued receipt snapshots.
+const CURRENT_RECEIPT_TEMPLATE_VERSION: &str = "quittance-2026-10-17";
+
+/// Current template version for reçus issued on partially paid periods.
+const CURRENT_RECU_TEMPLATE_VERSION: &str = "recu-2026-10-17";
 
 pub fn router() -> Router<Database> {
     Router::new()
         .route("/", get(list_receipts).post(create_receipt))
         .route("/:id", get(get_receipt).delete(delete_receipt))
+        .route("/:id/pdf", get(get_receipt_pdf))
+        .route("/:id/mark-paid", axum::routing::post(mark_receipt_paid))
+        .route("/:id/send", axum::routing::post(send_receipt))
+        .route("/send-month", axum::routing::post(send_month_receipts))
         .route("/lease/:lease_id/regenerate", axum::routing::post(regenerate_receipts))
 }
 
@@ -25,6 +48,42 @@ struct ReceiptQuery {
     lease_id: Option<Uuid>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ReceiptPdfQuery {
+    /// Serve the PDF for in-browser preview instead of as a download.
+    inline: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendMonthPayload {
+    period_month: i32,
+    period_year: i32,
+    /// Also resend receipts already emailed for that month.
+    resend: Option<bool>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct EmailDelivery {
+    tenant_email: String,
+    status: String,
+    error_message: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct SendReceiptResult {
+    receipt: Receipt,
+    deliveries: Vec<EmailDelivery>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct SendMonthResult {
+    sent_count: usize,
+    logged_count: usize,
+    failed_count: usize,
+    skipped_count: usize,
+    results: Vec<SendReceiptResult>,
+}
+
 #[derive(Debug, Deserialize)]
 struct RegenerateReceiptsPayload {
     purge_existing: Option<bool>,
@@ -67,14 +126,340 @@ fn next_month(date: NaiveDate) -> NaiveDate {
     }
 }
 
-fn prorated_amount(amount: &BigDecimal, covered_days: i64, days_in_month: i64) -> BigDecimal {
-    if covered_days <= 0 || days_in_month <= 0 {
-        return BigDecimal::from(0);
+/// Directory where rendered PDFs other than quittances are stored
+/// (overridable via env).
+pub(crate) fn receipts_storage_dir() -> PathBuf {
+    std::env::var("RECEIPTS_STORAGE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("storage/receipts"))
+}
+
+async fn fetch_receipt_by_id(db: &Database, id: Uuid) -> Result<Receipt, AppError> {
+    sqlx::query_as::<_, Receipt>(
+        r#"
+        SELECT id, lease_id, period_month, period_year,
+               base_rent, charges, total_amount,
+               payment_date, status, amount_paid, email_sent_at, pdf_path,
+               created_at, updated_at
+        FROM receipts
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Receipt not found".to_string()))
+}
+
+/// Build the lease parties as they stand today, for freezing into receipt snapshots.
+pub(crate) async fn current_lease_snapshot(
+    db: &Database,
+    lease_id: Uuid,
+    requesting_user_id: Uuid,
+) -> Result<CanonicalSnapshot, AppError> {
+    let lease = fetch_lease_by_id(db, lease_id).await?;
+    build_snapshot_for_lease(db, &lease, requesting_user_id).await
+}
+
+/// Template version for the document a receipt currently entitles the
+/// tenants to: a reçu while the period is only partially paid.
+fn template_version_for(receipt: &Receipt) -> &'static str {
+    match ReceiptSnapshot::document_kind_for(receipt) {
+        "recu" => CURRENT_RECU_TEMPLATE_VERSION,
+        _ => CURRENT_RECEIPT_TEMPLATE_VERSION,
+    }
+}
+
+/// Freeze the quittance content of a receipt into its `snapshot` column.
+/// Called when a receipt is issued or its settlement changes; every later
+/// render reads this snapshot, never the live landlord/tenant/property rows.
+pub(crate) async fn persist_receipt_snapshot(
+    db: &Database,
+    lease_snapshot: &CanonicalSnapshot,
+    receipt: &Receipt,
+) -> Result<ReceiptSnapshot, AppError> {
+    let snapshot = ReceiptSnapshot::from_lease_snapshot(
+        lease_snapshot,
+        receipt,
+        template_version_for(receipt).to_string(),
+    )
+    .ok_or_else(|| AppError::Validation("Invalid receipt period".to_string()))?;
+
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for receipt {}: {}", receipt.id, e);
+        AppError::Internal
+    })?;
+
+    sqlx::query("UPDATE receipts SET snapshot = $1 WHERE id = $2")
+        .bind(snapshot_json)
+        .bind(receipt.id)
+        .execute(&db.pool)
+        .await?;
+
+    Ok(snapshot)
+}
+
+/// Load the persisted snapshot for a receipt. Receipts issued before snapshots
+/// existed get one frozen on first access so later renders stay stable.
+async fn load_or_build_receipt_snapshot(
+    db: &Database,
+    receipt: &Receipt,
+    requesting_user_id: Uuid,
+) -> Result<ReceiptSnapshot, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM receipts WHERE id = $1",
+    )
+    .bind(receipt.id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten();
+
+    if let Some(value) = stored {
+        if let Ok(snapshot) = serde_json::from_value::<ReceiptSnapshot>(value) {
+            return Ok(snapshot);
+        }
+        tracing::warn!("Stored snapshot for receipt {} is invalid; rebuilding", receipt.id);
+    }
+
+    let lease_snapshot = current_lease_snapshot(db, receipt.lease_id, requesting_user_id).await?;
+    persist_receipt_snapshot(db, &lease_snapshot, receipt).await
+}
+
+/// Return the stored quittance (or reçu) PDF for a receipt, rendering and
+/// storing it in `receipts.pdf_data` on first access. The stored bytes are
+/// what every later preview and download serves, so all copies of a quittance
+/// are byte-identical whatever happens to the server's disk. Unpaid periods
+/// have no document: a quittance attests a payment.
+async fn load_or_render_receipt_pdf(
+    db: &Database,
+    receipt: &Receipt,
+    requesting_user_id: Uuid,
+) -> Result<Vec<u8>, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<Vec<u8>>>("SELECT pdf_data FROM receipts WHERE id = $1")
+        .bind(receipt.id)
+        .fetch_optional(&db.pool)
+        .await?
+        .flatten();
+    if let Some(bytes) = stored {
+        return Ok(bytes);
+    }
+
+    // Documents issued before the bytes moved to the database.
+    if let Some(path) = receipt.pdf_path.as_deref() {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => {
+                store_receipt_pdf(db, receipt.id, &bytes).await?;
+                return Ok(bytes);
+            }
+            Err(e) => tracing::warn!("Stored PDF {} for receipt {} is unreadable ({}); re-rendering", path, receipt.id, e),
+        }
+    }
+
+    let snapshot = load_or_build_receipt_snapshot(db, receipt, requesting_user_id).await?;
+    if snapshot.document_kind == "unpaid" {
+        return Err(AppError::Validation(
+            "No payment recorded for this period; a quittance or reçu can only be issued once rent is received".to_string(),
+        ));
     }
 
-    let covered = BigDecimal::from(covered_days);
-    let month_days = BigDecimal::from(days_in_month);
-    ((amount * covered) / month_days).with_scale(2)
+    let renderer = PdfRenderer::from_env().map_err(|e| {
+        tracing::error!("Failed to initialize PDF renderer: {}", e);
+        AppError::Internal
+    })?;
+
+    let pdf_bytes = renderer
+        .generate_quittance_pdf(&snapshot)
+        .await
+        .map_err(|e| {
+            tracing::error!("Quittance PDF generation failed for receipt {}: {}", receipt.id, e);
+            AppError::BadRequest(format!("PDF generation failed: {}", e))
+        })?;
+
+    store_receipt_pdf(db, receipt.id, &pdf_bytes).await?;
+    Ok(pdf_bytes)
+}
+
+async fn store_receipt_pdf(db: &Database, receipt_id: Uuid, pdf_bytes: &[u8]) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE receipts SET pdf_data = $1, pdf_path = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+    )
+    .bind(pdf_bytes)
+    .bind(receipt_id)
+    .execute(&db.pool)
+    .await?;
+    Ok(())
+}
+
+/// Email addresses of every tenant on the lease (colocataires included).
+pub(crate) async fn lease_recipient_emails(db: &Database, lease_id: Uuid) -> Result<Vec<String>, AppError> {
+    let emails = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT DISTINCT t.email
+        FROM tenants t
+        WHERE t.id IN (
+            SELECT lt.tenant_id FROM lease_tenants lt WHERE lt.lease_id = $1
+            UNION
+            SELECT l.tenant_id FROM leases l WHERE l.id = $1
+        )
+          AND t.email IS NOT NULL
+          AND t.email <> ''
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(emails)
+}
+
+fn quittance_email(snapshot: &ReceiptSnapshot, to: &str, pdf_bytes: &[u8]) -> OutgoingMail {
+    let period = period_label(snapshot.period.month, snapshot.period.year);
+    let (document, subject, amount_line) = if snapshot.document_kind == "recu" {
+        (
+            "le reçu de paiement partiel du loyer",
+            "Reçu de paiement partiel",
+            format!(
+                "Montant reçu : {} €\nReste dû : {} €",
+                format_amount(&snapshot.amounts.amount_paid),
+                format_amount(&snapshot.amounts.balance_due),
+            ),
+        )
+    } else {
+        (
+            "la quittance de loyer",
+            "Quittance de loyer",
+            format!("Montant total : {} €", format_amount(&snapshot.amounts.total_amount)),
+        )
+    };
+    let body = format!(
+        "Bonjour,\n\n\
+         Veuillez trouver ci-joint {} pour la période de {} \
+         concernant le logement situé {}.\n\n\
+         {}\n\n\
+         Cordialement,\n{}",
+        document,
+        period,
+        snapshot.property_address,
+        amount_line,
+        snapshot.parties.landlord_full_name,
+    );
+
+    OutgoingMail {
+        to: to.to_string(),
+        subject: format!("{} - {}", subject, period),
+        body,
+        attachments: vec![MailAttachment {
+            filename: format!(
+                "{}_{}_{:02}.pdf",
+                snapshot.document_kind, snapshot.period.year, snapshot.period.month
+            ),
+            content_type: "application/pdf".to_string(),
+            content: pdf_bytes.to_vec(),
+        }],
+    }
+}
+
+/// Email the quittance PDF to every tenant on the lease, logging each
+/// attempt in `email_logs`. The receipt is marked sent once at least one
+/// delivery succeeds over SMTP; messages only logged by the file or stdout
+/// transport leave it unsent.
+async fn deliver_receipt(
+    db: &Database,
+    mailer: &Mailer,
+    receipt: &Receipt,
+    requesting_user_id: Uuid,
+) -> Result<SendReceiptResult, AppError> {
+    let recipients = lease_recipient_emails(db, receipt.lease_id).await?;
+    if recipients.is_empty() {
+        return Err(AppError::Validation(
+            "No tenant on this lease has an email address".to_string(),
+        ));
+    }
+
+    let pdf_bytes = load_or_render_receipt_pdf(db, receipt, requesting_user_id).await?;
+    let snapshot = load_or_build_receipt_snapshot(db, receipt, requesting_user_id).await?;
+
+    let mut deliveries = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        let mail = quittance_email(&snapshot, &recipient, &pdf_bytes);
+
+        let log_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO email_logs (receipt_id, tenant_email, subject, status)
+            VALUES ($1, $2, $3, 'pending')
+            RETURNING id
+            "#,
+        )
+        .bind(receipt.id)
+        .bind(&recipient)
+        .bind(&mail.subject)
+        .fetch_one(&db.pool)
+        .await?;
+
+        match mailer.send(&mail).await {
+            Ok(()) => {
+                let status = mailer.delivery_status();
+                sqlx::query(
+                    "UPDATE email_logs SET status = $2, sent_at = CURRENT_TIMESTAMP WHERE id = $1",
+                )
+                .bind(log_id)
+                .bind(status)
+                .execute(&db.pool)
+                .await?;
+                deliveries.push(EmailDelivery {
+                    tenant_email: recipient,
+                    status: status.to_string(),
+                    error_message: None,
+                });
+            }
+            Err(e) => {
+                tracing::warn!("Failed to email receipt {} to {}: {}", receipt.id, recipient, e);
+                sqlx::query(
+                    "UPDATE email_logs SET status = 'failed', error_message = $2 WHERE id = $1",
+                )
+                .bind(log_id)
+                .bind(e.to_string())
+                .execute(&db.pool)
+                .await?;
+                deliveries.push(EmailDelivery {
+                    tenant_email: recipient,
+                    status: "failed".to_string(),
+                    error_message: Some(e.to_string()),
+                });
+            }
+        }
+    }
+
+    let receipt = if deliveries.iter().any(|d| d.status == "sent") {
+        // Sending never regresses a (partially) paid receipt.
+        sqlx::query_as::<_, Receipt>(
+            r#"
+            UPDATE receipts
+            SET email_sent_at = CURRENT_TIMESTAMP,
+                status = CASE WHEN status IN ('paid', 'partially_paid') THEN status ELSE 'sent' END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING id, lease_id, period_month, period_year,
+                      base_rent, charges, total_amount,
+                      payment_date, status, amount_paid, email_sent_at, pdf_path,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(receipt.id)
+        .fetch_one(&db.pool)
+        .await?
+    } else {
+        fetch_receipt_by_id(db, receipt.id).await?
+    };
+
+    Ok(SendReceiptResult { receipt, deliveries })
+}
+
+pub(crate) fn mailer_from_env() -> Result<Mailer, AppError> {
+    Mailer::from_env().map_err(|e| {
+        tracing::error!("Failed to initialize mailer: {}", e);
+        AppError::Internal
+    })
 }
 
 async fn list_receipts(
@@ -83,37 +468,31 @@ async fn list_receipts(
 ) -> Result<Json<Vec<Receipt>>, AppError> {
     let receipts = if let Some(lease_id) = params.lease_id {
         // Filter by lease_id
-        sqlx::query_as!(
-            Receipt,
+        sqlx::query_as::<_, Receipt>(
             r#"
-            SELECT id, lease_id, period_month, period_year, 
-                   base_rent as "base_rent!: BigDecimal", 
-                   charges as "charges!: BigDecimal", 
-                   total_amount as "total_amount!: BigDecimal",
-                   payment_date, status, email_sent_at, pdf_path, 
+            SELECT id, lease_id, period_month, period_year,
+                   base_rent, charges, total_amount,
+                   payment_date, status, amount_paid, email_sent_at, pdf_path,
                    created_at, updated_at
             FROM receipts
             WHERE lease_id = $1
             ORDER BY period_year DESC, period_month DESC
             "#,
-            lease_id
         )
+        .bind(lease_id)
         .fetch_all(&db.pool)
         .await?
     } else {
         // List all receipts (for admin view)
-        sqlx::query_as!(
-            Receipt,
+        sqlx::query_as::<_, Receipt>(
             r#"
-            SELECT id, lease_id, period_month, period_year, 
-                   base_rent as "base_rent!: BigDecimal", 
-                   charges as "charges!: BigDecimal", 
-                   total_amount as "total_amount!: BigDecimal",
-                   payment_date, status, email_sent_at, pdf_path, 
+            SELECT id, lease_id, period_month, period_year,
+                   base_rent, charges, total_amount,
+                   payment_date, status, amount_paid, email_sent_at, pdf_path,
                    created_at, updated_at
             FROM receipts
             ORDER BY period_year DESC, period_month DESC
-            "#
+            "#,
         )
         .fetch_all(&db.pool)
         .await?
@@ -124,8 +503,11 @@ async fn list_receipts(
 
 async fn create_receipt(
     State(db): State<Database>,
+    headers: HeaderMap,
     Json(payload): Json<CreateReceipt>,
 ) -> Result<Json<Receipt>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+
     // Validate period_month is between 1 and 12
     if payload.period_month < 1 || payload.period_month > 12 {
         return Err(AppError::Validation("Period month must be between 1 and 12".to_string()));
@@ -136,9 +518,15 @@ async fn create_receipt(
         return Err(AppError::Validation("Rent amounts cannot be negative".to_string()));
     }
 
-    // Check if lease exists and period overlaps lease dates
+    // Check if lease exists and period overlaps lease dates; a notice ends
+    // the lease on its effective date.
     let lease_dates = sqlx::query_as::<_, (NaiveDate, Option<NaiveDate>)>(
-        "SELECT start_date, end_date FROM leases WHERE id = $1"
+        r#"
+        SELECT l.start_date, COALESCE(n.effective_date, l.end_date)
+        FROM leases l
+        LEFT JOIN lease_notices n ON n.lease_id = l.id AND n.status = 'active'
+        WHERE l.id = $1
+        "#,
     )
     .bind(payload.lease_id)
     .fetch_optional(&db.pool)
@@ -147,6 +535,8 @@ async fn create_receipt(
     let Some(lease_dates) = lease_dates else {
         return Err(AppError::NotFound("Lease not found".to_string()));
     };
+    ensure_lease_access(&db, payload.lease_id, user_id).await?;
+    ensure_lease_billable(&db, payload.lease_id).await?;
 
     let (period_start, period_end) = month_bounds(payload.period_year, payload.period_month)?;
     let lease_start = lease_dates.0;
@@ -161,21 +551,50 @@ async fn create_receipt(
         ));
     }
 
-    // Upsert for the selected period to avoid duplicate-period errors.
+    let already_issued = || {
+        AppError::Conflict(format!(
+            "A document was already issued or paid for {}/{}; it cannot be replaced",
+            payload.period_month, payload.period_year
+        ))
+    };
+    let existing = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT pdf_data IS NOT NULL OR pdf_path IS NOT NULL OR email_sent_at IS NOT NULL OR amount_paid > 0
+        FROM receipts
+        WHERE lease_id = $1 AND period_month = $2 AND period_year = $3
+        "#,
+    )
+    .bind(payload.lease_id)
+    .bind(payload.period_month)
+    .bind(payload.period_year)
+    .fetch_optional(&db.pool)
+    .await?;
+    if existing == Some(true) {
+        return Err(already_issued());
+    }
+
+    // Upsert for the selected period to avoid duplicate-period errors. A
+    // period whose document went out or that received a payment is never
+    // replaced; the guard repeats the check above against a concurrent issue.
     let receipt = sqlx::query_as::<_, Receipt>(
         r#"
-        INSERT INTO receipts (lease_id, period_month, period_year, base_rent, charges, payment_date, status)
-        VALUES ($1, $2, $3, $4, $5, $6, 'generated')
+        INSERT INTO receipts (lease_id, period_month, period_year, base_rent, charges, payment_date, due_date, status)
+        VALUES ($1, $2, $3, $4, $5, $6, $6, 'generated')
         ON CONFLICT (lease_id, period_month, period_year)
         DO UPDATE SET
             base_rent = EXCLUDED.base_rent,
             charges = EXCLUDED.charges,
             payment_date = EXCLUDED.payment_date,
-            status = 'generated',
+            due_date = EXCLUDED.due_date,
+            snapshot = NULL,
             updated_at = CURRENT_TIMESTAMP
+        WHERE receipts.pdf_data IS NULL
+          AND receipts.pdf_path IS NULL
+          AND receipts.email_sent_at IS NULL
+          AND receipts.amount_paid = 0
         RETURNING id, lease_id, period_month, period_year,
                   base_rent, charges, total_amount,
-                  payment_date, status, email_sent_at, pdf_path,
+                  payment_date, status, amount_paid, email_sent_at, pdf_path,
                   created_at, updated_at
         "#,
     )
@@ -185,73 +604,77 @@ async fn create_receipt(
     .bind(payload.base_rent)
     .bind(payload.charges)
     .bind(payload.payment_date)
-    .fetch_one(&db.pool)
-    .await?;
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(already_issued)?;
+
+    // Freeze the snapshot when the receipt is first issued. A replaced one
+    // was never issued: it gets its snapshot on first render or settlement.
+    if existing.is_none() {
+        let lease_snapshot = current_lease_snapshot(&db, receipt.lease_id, user_id).await?;
+        persist_receipt_snapshot(&db, &lease_snapshot, &receipt).await?;
+    }
+
+    // Payments already on the ledger (or tenant credit) may settle this period.
+    reconcile_lease(&db, receipt.lease_id, user_id).await?;
 
-    Ok(Json(receipt))
+    fetch_receipt_by_id(&db, receipt.id).await.map(Json)
 }
 
 async fn get_receipt(
     State(db): State<Database>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Receipt>, AppError> {
-    let receipt = sqlx::query_as!(
-        Receipt,
-        r#"
-        SELECT id, lease_id, period_month, period_year, 
-               base_rent as "base_rent!: BigDecimal", 
-               charges as "charges!: BigDecimal", 
-               total_amount as "total_amount!: BigDecimal",
-               payment_date, status, email_sent_at, pdf_path, 
-               created_at, updated_at
-        FROM receipts
-        WHERE id = $1
-        "#,
-        id
-    )
-    .fetch_optional(&db.pool)
-    .await?;
-
-    receipt
-        .map(Json)
-        .ok_or_else(|| AppError::NotFound("Receipt not found".to_string()))
+    fetch_receipt_by_id(&db, id).await.map(Json)
 }
 
 async fn delete_receipt(
     State(db): State<Database>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
-    let result = sqlx::query("DELETE FROM receipts WHERE id = $1")
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let receipt = fetch_receipt_by_id(&db, id).await?;
+    ensure_lease_access(&db, receipt.lease_id, user_id).await?;
+
+    sqlx::query("DELETE FROM receipts WHERE id = $1")
         .bind(id)
         .execute(&db.pool)
         .await?;
 
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Receipt not found".to_string()));
-    }
+    // Payments allocated to the deleted period go back to the ledger.
+    reconcile_lease(&db, receipt.lease_id, user_id).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 async fn regenerate_receipts(
     State(db): State<Database>,
+    headers: HeaderMap,
     Path(lease_id): Path<Uuid>,
     Json(payload): Json<RegenerateReceiptsPayload>,
 ) -> Result<Json<RegenerateReceiptsResult>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
     let purge_existing = payload.purge_existing.unwrap_or(false);
 
-    let lease = sqlx::query_as::<_, (NaiveDate, Option<NaiveDate>, BigDecimal, BigDecimal)>(
-        "SELECT start_date, end_date, monthly_rent, charges FROM leases WHERE id = $1",
+    // Receipts stop at the notice's effective date, prorating the last month.
+    let lease = sqlx::query_as::<_, (NaiveDate, Option<NaiveDate>)>(
+        r#"
+        SELECT l.start_date, COALESCE(n.effective_date, l.end_date)
+        FROM leases l
+        LEFT JOIN lease_notices n ON n.lease_id = l.id AND n.status = 'active'
+        WHERE l.id = $1
+        "#,
     )
     .bind(lease_id)
     .fetch_optional(&db.pool)
     .await?
     .ok_or_else(|| AppError::NotFound("Lease not found".to_string()))?;
+    ensure_lease_access(&db, lease_id, user_id).await?;
+    ensure_lease_billable(&db, lease_id).await?;
 
     let lease_start = lease.0;
     let lease_end_opt = lease.1;
-    let monthly_rent = lease.2;
-    let monthly_charges = lease.3;
 
     let today = Utc::now().date_naive();
     let current_month_start = first_day_of_month(today);
@@ -272,6 +695,7 @@ async fn regenerate_receipts(
         }));
     }
 
+    let rent_periods = rent_periods(&db, lease_id).await?;
     let mut tx = db.pool.begin().await?;
 
     let deleted_count = if purge_existing {
@@ -288,7 +712,7 @@ async fn regenerate_receipts(
         0
     };
 
-    let mut created_count = 0usize;
+    let mut created_ids = Vec::new();
     let mut cursor = first_day_of_month(lease_start);
 
     while cursor <= effective_end {
@@ -303,16 +727,17 @@ async fn regenerate_receipts(
         let covered_end = if capped_lease_end < period_end { capped_lease_end } else { period_end };
 
         if covered_start <= covered_end {
-            let covered_days = (covered_end - covered_start).num_days() + 1;
+            // Amounts in force at the time, not today's: a revision never
+            // rewrites the months before it.
             let days_in_month = (period_end - period_start).num_days() + 1;
-            let base_rent = prorated_amount(&monthly_rent, covered_days, days_in_month);
-            let charges = prorated_amount(&monthly_charges, covered_days, days_in_month);
+            let (base_rent, charges) = amounts_over(&rent_periods, covered_start, covered_end, days_in_month);
 
-            let inserted = sqlx::query(
+            let inserted = sqlx::query_scalar::<_, Uuid>(
                 r#"
-                INSERT INTO receipts (lease_id, period_month, period_year, base_rent, charges, payment_date, status)
-                VALUES ($1, $2, $3, $4, $5, $6, 'generated')
+                INSERT INTO receipts (lease_id, period_month, period_year, base_rent, charges, payment_date, due_date, status)
+                VALUES ($1, $2, $3, $4, $5, $6, $6, 'generated')
                 ON CONFLICT (lease_id, period_month, period_year) DO NOTHING
+                RETURNING id
                 "#,
             )
             .bind(lease_id)
@@ -321,22 +746,35 @@ async fn regenerate_receipts(
             .bind(base_rent)
             .bind(charges)
             .bind(period_end)
-            .execute(&mut *tx)
+            .fetch_optional(&mut *tx)
             .await?;
 
-            if inserted.rows_affected() > 0 {
-                created_count += 1;
+            if let Some(id) = inserted {
+                created_ids.push(id);
             }
         }
 
         cursor = next_month(cursor);
     }
 
+    tx.commit().await?;
+
+    // Freeze snapshots for the newly issued receipts.
+    if !created_ids.is_empty() {
+        let lease_snapshot = current_lease_snapshot(&db, lease_id, user_id).await?;
+        for id in &created_ids {
+            let receipt = fetch_receipt_by_id(&db, *id).await?;
+            persist_receipt_snapshot(&db, &lease_snapshot, &receipt).await?;
+        }
+    }
+
+    reconcile_lease(&db, lease_id, user_id).await?;
+
     let receipts = sqlx::query_as::<_, Receipt>(
         r#"
         SELECT id, lease_id, period_month, period_year,
                base_rent, charges, total_amount,
-               payment_date, status, email_sent_at, pdf_path,
+               payment_date, status, amount_paid, email_sent_at, pdf_path,
                created_at, updated_at
         FROM receipts
         WHERE lease_id = $1
@@ -347,14 +785,251 @@ async fn regenerate_receipts(
     .bind(lease_id)
     .bind(effective_end.year())
     .bind(effective_end.month() as i32)
-    .fetch_all(&mut *tx)
+    .fetch_all(&db.pool)
     .await?;
 
-    tx.commit().await?;
-
     Ok(Json(RegenerateReceiptsResult {
-        created_count,
+        created_count: created_ids.len(),
         deleted_count,
         receipts,
     }))
 }
+
+/// POST /api/receipts/{id}/mark-paid
+/// Record a payment of the outstanding balance, designated to this period,
+/// so the receipt becomes a quittance.
+async fn mark_receipt_paid(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<MarkReceiptPaid>,
+) -> Result<Json<Receipt>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let existing = fetch_receipt_by_id(&db, id).await?;
+    ensure_lease_access(&db, existing.lease_id, user_id).await?;
+
+    // A settled period keeps the quittance already issued for it.
+    let outstanding = &existing.total_amount - &existing.amount_paid;
+    if outstanding.is_positive() {
+        if let Some(payment_date) = payload.payment_date {
+            sqlx::query("UPDATE receipts SET payment_date = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1")
+                .bind(id)
+                .bind(payment_date)
+                .execute(&db.pool)
+                .await?;
+        }
+
+        let payment = CreatePayment {
+            lease_id: existing.lease_id,
+            payer_tenant_id: payload.payer_tenant_id,
+            receipt_id: Some(existing.id),
+            amount: outstanding,
+            payment_date: payload.payment_date.unwrap_or(existing.payment_date),
+            method: payload.method.unwrap_or_else(|| "virement".to_string()),
+            reference: None,
+        };
+        record_payment(&db, &payment, user_id).await?;
+    }
+
+    fetch_receipt_by_id(&db, id).await.map(Json)
+}
+
+/// GET /api/receipts/{id}/pdf
+/// Serve the quittance PDF rendered server-side from the versioned receipt
+/// template. `?inline=true` serves the same bytes for in-browser preview.
+async fn get_receipt_pdf(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ReceiptPdfQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let receipt = fetch_receipt_by_id(&db, id).await?;
+    ensure_lease_access(&db, receipt.lease_id, user_id).await?;
+
+    let pdf_bytes = load_or_render_receipt_pdf(&db, &receipt, user_id).await?;
+
+    // Same naming as the emailed attachment: a partial payment gets a reçu.
+    let filename = format!(
+        "{}_{}_{:02}_{}.pdf",
+        ReceiptSnapshot::document_kind_for(&receipt),
+        receipt.period_year,
+        receipt.period_month,
+        receipt.id
+    );
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
+
+/// POST /api/receipts/{id}/send
+/// Email the quittance PDF to every tenant on the lease.
+async fn send_receipt(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SendReceiptResult>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let receipt = fetch_receipt_by_id(&db, id).await?;
+    ensure_lease_access(&db, receipt.lease_id, user_id).await?;
+
+    let mailer = mailer_from_env()?;
+    let result = deliver_receipt(&db, &mailer, &receipt, user_id).await?;
+
+    if result.deliveries.iter().all(|d| d.status == "failed") {
+        return Err(AppError::BadRequest(format!(
+            "Email delivery failed for all {} recipient(s)",
+            result.deliveries.len()
+        )));
+    }
+
+    Ok(Json(result))
+}
+
+/// POST /api/receipts/send-month
+/// Email every receipt of a month across the leases the user can access.
+/// Receipts already emailed are skipped unless `resend` is set.
+async fn send_month_receipts(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Json(payload): Json<SendMonthPayload>,
+) -> Result<Json<SendMonthResult>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    if payload.period_month < 1 || payload.period_month > 12 {
+        return Err(AppError::Validation("Period month must be between 1 and 12".to_string()));
+    }
+    let resend = payload.resend.unwrap_or(false);
+
+    let receipts = sqlx::query_as::<_, Receipt>(
+        r#"
+        SELECT r.id, r.lease_id, r.period_month, r.period_year,
+               r.base_rent, r.charges, r.total_amount,
+               r.payment_date, r.status, r.amount_paid, r.email_sent_at, r.pdf_path,
+               r.created_at, r.updated_at
+        FROM receipts r
+        JOIN leases l ON r.lease_id = l.id
+        JOIN properties p ON l.property_id = p.id
+        WHERE r.period_month = $1
+          AND r.period_year = $2
+          AND (
+              p.user_id = $3
+              OR EXISTS (
+                  SELECT 1 FROM organization_members om
+                  WHERE om.organization_id = p.organization_id AND om.user_id = $3
+              )
+          )
+        ORDER BY r.created_at
+        "#,
+    )
+    .bind(payload.period_month)
+    .bind(payload.period_year)
+    .bind(user_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let mailer = mailer_from_env()?;
+    let mut result = SendMonthResult {
+        sent_count: 0,
+        logged_count: 0,
+        failed_count: 0,
+        skipped_count: 0,
+        results: Vec::new(),
+    };
+
+    for receipt in receipts {
+        if receipt.email_sent_at.is_some() && !resend {
+            result.skipped_count += 1;
+            continue;
+        }
+
+        match deliver_receipt(&db, &mailer, &receipt, user_id).await {
+            Ok(delivered) => {
+                if delivered.deliveries.iter().any(|d| d.status == "sent") {
+                    result.sent_count += 1;
+                } else if delivered.deliveries.iter().any(|d| d.status == "logged") {
+                    result.logged_count += 1;
+                } else {
+                    result.failed_count += 1;
+                }
+                result.results.push(delivered);
+            }
+            Err(AppError::Validation(msg)) => {
+                tracing::warn!("Skipping receipt {}: {}", receipt.id, msg);
+                result.skipped_count += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    fn make_snapshot() -> ReceiptSnapshot {
+        let mut lease = CanonicalSnapshot::new(Uuid::new_v4(), "2026-06-18".to_string());
+        lease.parties.landlord_full_name = "Jean Dupont".to_string();
+        lease.parties.lessee_full_name = "Marie Martin".to_string();
+        lease.property.address = "1 rue de Paris".to_string();
+        lease.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        lease.lease_terms.end_date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let receipt = Receipt {
+            id: Uuid::new_v4(),
+            lease_id: lease.lease_id,
+            period_month: 10,
+            period_year: 2026,
+            base_rent: BigDecimal::from_str("650").unwrap(),
+            charges: BigDecimal::from_str("50").unwrap(),
+            total_amount: BigDecimal::from_str("700").unwrap(),
+            amount_paid: BigDecimal::from_str("700").unwrap(),
+            payment_date: NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(),
+            status: "generated".to_string(),
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        };
+        ReceiptSnapshot::from_lease_snapshot(&lease, &receipt, CURRENT_RECEIPT_TEMPLATE_VERSION.to_string())
+            .unwrap()
+    }
+
+    #[test]
+    fn quittance_email_attaches_pdf_with_period_subject() {
+        let mail = quittance_email(&make_snapshot(), "marie@example.com", b"%PDF");
+        assert_eq!(mail.to, "marie@example.com");
+        assert_eq!(mail.subject, "Quittance de loyer - Octobre 2026");
+        assert!(mail.body.contains("1 rue de Paris"));
+        assert!(mail.body.contains("700,00 €"));
+        assert!(mail.body.ends_with("Jean Dupont"));
+        assert_eq!(mail.attachments.len(), 1);
+        assert_eq!(mail.attachments[0].filename, "quittance_2026_10.pdf");
+        assert_eq!(mail.attachments[0].content, b"%PDF".to_vec());
+    }
+
+    #[test]
+    fn recu_email_states_amount_received_and_balance() {
+        let mut snapshot = make_snapshot();
+        snapshot.document_kind = "recu".to_string();
+        snapshot.amounts.amount_paid = BigDecimal::from_str("300").unwrap();
+        snapshot.amounts.balance_due = BigDecimal::from_str("400").unwrap();
+        let mail = quittance_email(&snapshot, "marie@example.com", b"%PDF");
+        assert_eq!(mail.subject, "Reçu de paiement partiel - Octobre 2026");
+        assert!(mail.body.contains("Montant reçu : 300,00 €"));
+        assert!(mail.body.contains("Reste dû : 400,00 €"));
+        assert_eq!(mail.attachments[0].filename, "recu_2026_10.pdf");
+    }
+}
@@ -0,0 +1,603 @@
+use axum::{
+    Router,
+    routing::{get, post},
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
+};
+use bigdecimal::{BigDecimal, num_traits::Signed};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::LesseeParty;
+use crate::models::charges_regularisation::{
+    ChargesRegularisation, CreateRegularisation, CreateRegularisationExpense, RegularisationDetail,
+    RegularisationExpense, RegularisationSnapshot, RegularisationStatement,
+};
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, fetch_lease_by_id};
+use crate::routes::receipts::{current_lease_snapshot, receipts_storage_dir};
+use crate::services::charges_regularisation::{category_label, compute_statement, occupancy_in_year};
+use crate::services::pdf_renderer::PdfRenderer;
+
+/// Current regularisation statement template version recorded in snapshots.
+const CURRENT_REGULARISATION_TEMPLATE_VERSION: &str = "regularisation-charges-2026-10-17";
+
+pub fn router() -> Router<Database> {
+    Router::new()
+        .route("/", get(list_regularisations).post(create_regularisation))
+        .route("/:id", get(get_regularisation).delete(delete_regularisation))
+        .route("/:id/expenses", post(add_expense))
+        .route("/:id/expenses/:expense_id", axum::routing::delete(delete_expense))
+        .route("/:id/finalize", post(finalize_regularisation))
+        .route("/:id/pdf", get(get_regularisation_pdf))
+}
+
+#[derive(Debug, Deserialize)]
+struct RegularisationQuery {
+    lease_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegularisationPdfQuery {
+    inline: Option<bool>,
+}
+
+const REGULARISATION_COLUMNS: &str = r#"
+    id, lease_id, year, occupancy_start, occupancy_end, total_expenses, tenant_share,
+    provisions_collected, balance, status, rent_call_id, pdf_path, finalized_at,
+    created_at, updated_at
+"#;
+
+const EXPENSE_COLUMNS: &str = r#"
+    id, regularisation_id, category, label, amount, covered_start, covered_end, created_at
+"#;
+
+/// Only leases paying monthly provisions are regularised; a forfait is final.
+fn ensure_provision_mode(settlement_mode: Option<&str>, annual_regularization: bool) -> Result<(), AppError> {
+    match settlement_mode {
+        Some("forfait") => Err(AppError::Validation(
+            "Charges paid as a forfait are not regularised".to_string(),
+        )),
+        Some("provisions") | Some("regularisation") | Some("régularisation") => Ok(()),
+        _ if annual_regularization => Ok(()),
+        _ => Err(AppError::Validation(
+            "This lease does not provide for an annual charges regularisation".to_string(),
+        )),
+    }
+}
+
+async fn fetch_regularisation_by_id(db: &Database, id: Uuid) -> Result<ChargesRegularisation, AppError> {
+    sqlx::query_as::<_, ChargesRegularisation>(&format!(
+        "SELECT {} FROM charges_regularisations WHERE id = $1",
+        REGULARISATION_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Charges regularisation not found".to_string()))
+}
+
+async fn fetch_expenses(db: &Database, regularisation_id: Uuid) -> Result<Vec<RegularisationExpense>, AppError> {
+    let expenses = sqlx::query_as::<_, RegularisationExpense>(&format!(
+        "SELECT {} FROM charges_regularisation_expenses WHERE regularisation_id = $1 ORDER BY category, created_at",
+        EXPENSE_COLUMNS
+    ))
+    .bind(regularisation_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(expenses)
+}
+
+/// Charges provisions called on the receipts of the year.
+async fn provisions_for_year(db: &Database, lease_id: Uuid, year: i32) -> Result<BigDecimal, AppError> {
+    let total = sqlx::query_scalar::<_, BigDecimal>(
+        "SELECT COALESCE(SUM(charges), 0) FROM receipts WHERE lease_id = $1 AND period_year = $2",
+    )
+    .bind(lease_id)
+    .bind(year)
+    .fetch_one(&db.pool)
+    .await?;
+
+    Ok(total)
+}
+
+/// Names of the lease's tenants, primary first.
+async fn lease_tenant_names(db: &Database, lease_id: Uuid) -> Result<Vec<String>, AppError> {
+    let names = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT t.name
+        FROM lease_tenants lt
+        JOIN tenants t ON t.id = lt.tenant_id
+        WHERE lt.lease_id = $1
+        ORDER BY lt.position
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(names)
+}
+
+/// Recompute a draft from its expenses and the provisions on receipts, and
+/// store the totals on the row. Finalized regularisations return the
+/// statement frozen in their snapshot instead.
+async fn refresh_regularisation(
+    db: &Database,
+    regularisation: ChargesRegularisation,
+) -> Result<RegularisationDetail, AppError> {
+    let expenses = fetch_expenses(db, regularisation.id).await?;
+
+    if regularisation.status != "draft" {
+        if let Some(snapshot) = load_snapshot(db, regularisation.id).await? {
+            return Ok(RegularisationDetail {
+                regularisation,
+                expenses,
+                statement: snapshot.statement,
+            });
+        }
+    }
+
+    let lease = fetch_lease_by_id(db, regularisation.lease_id).await?;
+    let colocataires = if lease.is_colocation {
+        lease_tenant_names(db, lease.id).await?
+    } else {
+        Vec::new()
+    };
+    let provisions = provisions_for_year(db, lease.id, regularisation.year).await?;
+    let statement = compute_statement(
+        regularisation.year,
+        (regularisation.occupancy_start, regularisation.occupancy_end),
+        &expenses,
+        &provisions,
+        &colocataires,
+    );
+
+    let regularisation = if regularisation.status == "draft" {
+        sqlx::query_as::<_, ChargesRegularisation>(&format!(
+            r#"
+            UPDATE charges_regularisations
+            SET total_expenses = $2, tenant_share = $3, provisions_collected = $4,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            REGULARISATION_COLUMNS
+        ))
+        .bind(regularisation.id)
+        .bind(&statement.total_expenses)
+        .bind(&statement.tenant_share)
+        .bind(&statement.provisions_collected)
+        .fetch_one(&db.pool)
+        .await?
+    } else {
+        regularisation
+    };
+
+    Ok(RegularisationDetail { regularisation, expenses, statement })
+}
+
+async fn load_snapshot(db: &Database, id: Uuid) -> Result<Option<RegularisationSnapshot>, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM charges_regularisations WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten();
+
+    Ok(stored.and_then(|value| match serde_json::from_value::<RegularisationSnapshot>(value) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            tracing::warn!("Stored snapshot for regularisation {} is invalid: {}", id, e);
+            None
+        }
+    }))
+}
+
+fn build_snapshot(
+    lease: &crate::models::canonical_snapshot::CanonicalSnapshot,
+    regularisation: &ChargesRegularisation,
+    statement: RegularisationStatement,
+) -> RegularisationSnapshot {
+    let mut parties = lease.parties.clone();
+    if parties.lessees.is_empty() {
+        parties.lessees.push(LesseeParty {
+            full_name: parties.lessee_full_name.clone(),
+            address: parties.lessee_address.clone(),
+            email: parties.lessee_email.clone(),
+            birth_date: parties.lessee_birth_date,
+            birth_place: parties.lessee_birth_place.clone(),
+        });
+    }
+
+    RegularisationSnapshot {
+        id: Uuid::new_v4(),
+        regularisation_id: regularisation.id,
+        lease_id: regularisation.lease_id,
+        template_version: CURRENT_REGULARISATION_TEMPLATE_VERSION.to_string(),
+        generated_at: Utc::now(),
+        language: "fr".to_string(),
+        parties,
+        property_address: lease.property.address.clone(),
+        statement,
+    }
+}
+
+fn ensure_draft(regularisation: &ChargesRegularisation) -> Result<(), AppError> {
+    if regularisation.status != "draft" {
+        return Err(AppError::Validation(
+            "This regularisation is finalized and can no longer be changed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Finalized balances not yet carried by an avis d'échéance; the next notice
+/// issued for the lease adds them as its regularisation line.
+pub(crate) async fn pending_regularisation_balance(
+    db: &Database,
+    lease_id: Uuid,
+) -> Result<(BigDecimal, Vec<Uuid>), AppError> {
+    let pending = sqlx::query_as::<_, (Uuid, BigDecimal)>(
+        "SELECT id, balance FROM charges_regularisations WHERE lease_id = $1 AND status = 'finalized' AND rent_call_id IS NULL",
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let total = pending.iter().fold(BigDecimal::from(0), |acc, (_, balance)| acc + balance);
+    Ok((total, pending.into_iter().map(|(id, _)| id).collect()))
+}
+
+/// Mark regularisations as carried by the given avis d'échéance.
+pub(crate) async fn mark_regularisations_applied(
+    db: &Database,
+    regularisation_ids: &[Uuid],
+    rent_call_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE charges_regularisations
+        SET status = 'applied', rent_call_id = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = ANY($1)
+        "#,
+    )
+    .bind(regularisation_ids)
+    .bind(rent_call_id)
+    .execute(&db.pool)
+    .await?;
+
+    Ok(())
+}
+
+/// GET /api/regularisations?lease_id=
+async fn list_regularisations(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Query(params): Query<RegularisationQuery>,
+) -> Result<Json<Vec<ChargesRegularisation>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, params.lease_id, user_id).await?;
+
+    let regularisations = sqlx::query_as::<_, ChargesRegularisation>(&format!(
+        "SELECT {} FROM charges_regularisations WHERE lease_id = $1 ORDER BY year DESC",
+        REGULARISATION_COLUMNS
+    ))
+    .bind(params.lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(regularisations))
+}
+
+/// POST /api/regularisations
+/// Open the regularisation of a calendar year for a lease paying provisions.
+async fn create_regularisation(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateRegularisation>,
+) -> Result<(StatusCode, Json<RegularisationDetail>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, payload.lease_id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, payload.lease_id).await?;
+
+    ensure_provision_mode(lease.charges_settlement_mode.as_deref(), lease.annual_charges_regularization)?;
+    let (occupancy_start, occupancy_end) = occupancy_in_year(lease.start_date, lease.end_date, payload.year)
+        .ok_or_else(|| {
+            AppError::Validation(format!("The lease did not run during {}", payload.year))
+        })?;
+
+    let regularisation = sqlx::query_as::<_, ChargesRegularisation>(&format!(
+        r#"
+        INSERT INTO charges_regularisations (lease_id, year, occupancy_start, occupancy_end)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (lease_id, year) DO NOTHING
+        RETURNING {}
+        "#,
+        REGULARISATION_COLUMNS
+    ))
+    .bind(lease.id)
+    .bind(payload.year)
+    .bind(occupancy_start)
+    .bind(occupancy_end)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::Validation(format!("{} is already being regularised for this lease", payload.year))
+    })?;
+
+    let detail = refresh_regularisation(&db, regularisation).await?;
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// GET /api/regularisations/{id}
+async fn get_regularisation(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RegularisationDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let regularisation = fetch_regularisation_by_id(&db, id).await?;
+    ensure_lease_access(&db, regularisation.lease_id, user_id).await?;
+
+    Ok(Json(refresh_regularisation(&db, regularisation).await?))
+}
+
+/// DELETE /api/regularisations/{id}
+/// Drafts and finalized statements not yet on an avis d'échéance can be dropped.
+async fn delete_regularisation(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let regularisation = fetch_regularisation_by_id(&db, id).await?;
+    ensure_lease_access(&db, regularisation.lease_id, user_id).await?;
+
+    if regularisation.status == "applied" {
+        return Err(AppError::Validation(
+            "This regularisation is already on an avis d'échéance".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM charges_regularisations WHERE id = $1")
+        .bind(id)
+        .execute(&db.pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/regularisations/{id}/expenses
+async fn add_expense(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateRegularisationExpense>,
+) -> Result<(StatusCode, Json<RegularisationDetail>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let regularisation = fetch_regularisation_by_id(&db, id).await?;
+    ensure_lease_access(&db, regularisation.lease_id, user_id).await?;
+    ensure_draft(&regularisation)?;
+
+    if category_label(&payload.category).is_none() {
+        return Err(AppError::Validation(format!(
+            "'{}' is not a recoverable charge category of décret 87-713",
+            payload.category
+        )));
+    }
+    let label = payload.label.trim();
+    if label.is_empty() {
+        return Err(AppError::Validation("Expense label is required".to_string()));
+    }
+    if payload.amount.is_negative() {
+        return Err(AppError::Validation("Expense amount cannot be negative".to_string()));
+    }
+
+    let year_start = NaiveDate::from_ymd_opt(regularisation.year, 1, 1)
+        .ok_or_else(|| AppError::Validation("Invalid regularisation year".to_string()))?;
+    let year_end = NaiveDate::from_ymd_opt(regularisation.year, 12, 31)
+        .ok_or_else(|| AppError::Validation("Invalid regularisation year".to_string()))?;
+    let covered_start = payload.covered_start.unwrap_or(year_start);
+    let covered_end = payload.covered_end.unwrap_or(year_end);
+    if covered_start > covered_end {
+        return Err(AppError::Validation(
+            "Expense period must start before it ends".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO charges_regularisation_expenses
+            (regularisation_id, category, label, amount, covered_start, covered_end)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(id)
+    .bind(&payload.category)
+    .bind(label)
+    .bind(&payload.amount)
+    .bind(covered_start)
+    .bind(covered_end)
+    .execute(&db.pool)
+    .await?;
+
+    let detail = refresh_regularisation(&db, regularisation).await?;
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// DELETE /api/regularisations/{id}/expenses/{expense_id}
+async fn delete_expense(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((id, expense_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RegularisationDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let regularisation = fetch_regularisation_by_id(&db, id).await?;
+    ensure_lease_access(&db, regularisation.lease_id, user_id).await?;
+    ensure_draft(&regularisation)?;
+
+    let deleted = sqlx::query(
+        "DELETE FROM charges_regularisation_expenses WHERE id = $1 AND regularisation_id = $2",
+    )
+    .bind(expense_id)
+    .bind(id)
+    .execute(&db.pool)
+    .await?
+    .rows_affected();
+    if deleted == 0 {
+        return Err(AppError::NotFound("Expense not found".to_string()));
+    }
+
+    Ok(Json(refresh_regularisation(&db, regularisation).await?))
+}
+
+/// POST /api/regularisations/{id}/finalize
+/// Freeze the statement; its balance goes on the next avis d'échéance.
+async fn finalize_regularisation(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RegularisationDetail>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let regularisation = fetch_regularisation_by_id(&db, id).await?;
+    ensure_lease_access(&db, regularisation.lease_id, user_id).await?;
+    ensure_draft(&regularisation)?;
+
+    let detail = refresh_regularisation(&db, regularisation).await?;
+    if detail.expenses.is_empty() {
+        return Err(AppError::Validation(
+            "Record the year's expenses before finalizing".to_string(),
+        ));
+    }
+
+    let lease_snapshot = current_lease_snapshot(&db, detail.regularisation.lease_id, user_id).await?;
+    let snapshot = build_snapshot(&lease_snapshot, &detail.regularisation, detail.statement.clone());
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for regularisation {}: {}", id, e);
+        AppError::Internal
+    })?;
+
+    let regularisation = sqlx::query_as::<_, ChargesRegularisation>(&format!(
+        r#"
+        UPDATE charges_regularisations
+        SET status = 'finalized', snapshot = $2, finalized_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING {}
+        "#,
+        REGULARISATION_COLUMNS
+    ))
+    .bind(id)
+    .bind(snapshot_json)
+    .fetch_one(&db.pool)
+    .await?;
+
+    Ok(Json(RegularisationDetail {
+        regularisation,
+        expenses: detail.expenses,
+        statement: snapshot.statement,
+    }))
+}
+
+/// GET /api/regularisations/{id}/pdf
+async fn get_regularisation_pdf(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<RegularisationPdfQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let regularisation = fetch_regularisation_by_id(&db, id).await?;
+    ensure_lease_access(&db, regularisation.lease_id, user_id).await?;
+
+    let pdf_bytes = match regularisation.pdf_path.as_deref() {
+        Some(path) => match tokio::fs::read(path).await {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                tracing::warn!("Stored PDF {} for regularisation {} is unreadable ({}); re-rendering", path, id, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let pdf_bytes = match pdf_bytes {
+        Some(bytes) => bytes,
+        None => {
+            // Drafts render a fresh statement and are never stored.
+            let snapshot = match load_snapshot(&db, id).await? {
+                Some(snapshot) => snapshot,
+                None => {
+                    let detail = refresh_regularisation(&db, regularisation.clone()).await?;
+                    let lease_snapshot = current_lease_snapshot(&db, regularisation.lease_id, user_id).await?;
+                    build_snapshot(&lease_snapshot, &detail.regularisation, detail.statement)
+                }
+            };
+
+            let renderer = PdfRenderer::from_env().map_err(|e| {
+                tracing::error!("Failed to initialize PDF renderer: {}", e);
+                AppError::Internal
+            })?;
+            let bytes = renderer.generate_regularisation_pdf(&snapshot).await.map_err(|e| {
+                tracing::error!("Regularisation PDF generation failed for {}: {}", id, e);
+                AppError::BadRequest(format!("PDF generation failed: {}", e))
+            })?;
+
+            if regularisation.status != "draft" {
+                let storage_dir = receipts_storage_dir();
+                let pdf_path = storage_dir.join(format!("regularisation_charges_{}.pdf", id));
+                tokio::fs::create_dir_all(&storage_dir).await.map_err(|e| {
+                    tracing::error!("Failed to create receipt storage dir {:?}: {}", storage_dir, e);
+                    AppError::Internal
+                })?;
+                tokio::fs::write(&pdf_path, &bytes).await.map_err(|e| {
+                    tracing::error!("Failed to store regularisation PDF {:?}: {}", pdf_path, e);
+                    AppError::Internal
+                })?;
+                sqlx::query("UPDATE charges_regularisations SET pdf_path = $1 WHERE id = $2")
+                    .bind(pdf_path.to_string_lossy().to_string())
+                    .bind(id)
+                    .execute(&db.pool)
+                    .await?;
+            }
+
+            bytes
+        }
+    };
+
+    let filename = format!("regularisation_charges_{}_{}.pdf", regularisation.year, id);
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_provision_leases_are_regularised() {
+        assert!(ensure_provision_mode(Some("provisions"), false).is_ok());
+        assert!(ensure_provision_mode(None, true).is_ok());
+        assert!(ensure_provision_mode(Some("forfait"), true).is_err());
+        assert!(ensure_provision_mode(None, false).is_err());
+    }
+}
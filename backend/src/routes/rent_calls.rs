@@ -0,0 +1,619 @@
+use axum::{
+    Router,
+    routing::get,
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap, header},
+    response::Response,
+    body::Body,
+};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::CanonicalSnapshot;
+use crate::models::rent_call::{CreateRentCall, RentCall};
+use crate::models::rent_call_snapshot::{ArrearsLine, RentCallSnapshot};
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, ensure_lease_billable};
+use crate::routes::receipts::{
+    current_lease_snapshot, lease_recipient_emails, mailer_from_env, receipts_storage_dir,
+};
+use crate::services::mailer::{MailAttachment, Mailer, OutgoingMail};
+use crate::services::pdf_renderer::{PdfRenderer, format_amount, period_label};
+use crate::routes::regularisations::{mark_regularisations_applied, pending_regularisation_balance};
+use crate::routes::rent_history::rent_periods;
+use crate::services::receipt_scheduler::{period_charge, PeriodCharge};
+
+/// Current avis d'échéance template version recorded in new rent call snapshots.
+const CURRENT_RENT_CALL_TEMPLATE_VERSION: &str = "avis-echeance-2026-10-17";
+
+pub fn router() -> Router<Database> {
+    Router::new()
+        .route("/", get(list_rent_calls).post(create_rent_call))
+        .route("/:id", get(get_rent_call))
+        .route("/:id/pdf", get(get_rent_call_pdf))
+        .route("/:id/send", axum::routing::post(send_rent_call))
+}
+
+#[derive(Debug, Deserialize)]
+struct RentCallQuery {
+    lease_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+struct RentCallPdfQuery {
+    inline: Option<bool>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct RentCallDelivery {
+    tenant_email: String,
+    status: String,
+    error_message: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct SendRentCallResult {
+    rent_call: RentCall,
+    deliveries: Vec<RentCallDelivery>,
+}
+
+const RENT_CALL_COLUMNS: &str = r#"
+    id, lease_id, period_month, period_year, due_date,
+    base_rent, charges, regularisation, arrears, total_due,
+    status, receipt_id, email_sent_at, pdf_path, created_at, updated_at
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct RentCallLeaseTerms {
+    start_date: NaiveDate,
+    /// Lease end, or the effective date of a running notice.
+    end_date: Option<NaiveDate>,
+    rent_payment_timing: String,
+}
+
+async fn fetch_rent_call_by_id(db: &Database, id: Uuid) -> Result<RentCall, AppError> {
+    sqlx::query_as::<_, RentCall>(&format!("SELECT {} FROM rent_calls WHERE id = $1", RENT_CALL_COLUMNS))
+        .bind(id)
+        .fetch_optional(&db.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Avis d'échéance not found".to_string()))
+}
+
+/// Earlier periods still owed as of `as_of`. Only periods already due count:
+/// a period whose due date has not passed is not yet in arrears.
+async fn arrears_before(
+    db: &Database,
+    lease_id: Uuid,
+    period_year: i32,
+    period_month: i32,
+    as_of: NaiveDate,
+) -> Result<Vec<ArrearsLine>, AppError> {
+    let lines = sqlx::query_as::<_, ArrearsLine>(
+        r#"
+        SELECT period_month, period_year, total_amount - amount_paid AS balance
+        FROM receipts
+        WHERE lease_id = $1
+          AND (period_year * 100 + period_month) < ($2 * 100 + $3)
+          AND amount_paid < total_amount
+          AND due_date < $4
+        ORDER BY period_year, period_month
+        "#,
+    )
+    .bind(lease_id)
+    .bind(period_year)
+    .bind(period_month)
+    .bind(as_of)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(lines)
+}
+
+fn total_arrears(lines: &[ArrearsLine]) -> BigDecimal {
+    lines.iter().fold(BigDecimal::zero(), |acc, line| acc + &line.balance)
+}
+
+/// Freeze the notice content into the rent call's `snapshot` column.
+async fn persist_rent_call_snapshot(
+    db: &Database,
+    lease_snapshot: &CanonicalSnapshot,
+    rent_call: &RentCall,
+    arrears_lines: Vec<ArrearsLine>,
+) -> Result<RentCallSnapshot, AppError> {
+    let snapshot = RentCallSnapshot::from_lease_snapshot(
+        lease_snapshot,
+        rent_call,
+        arrears_lines,
+        CURRENT_RENT_CALL_TEMPLATE_VERSION.to_string(),
+    )
+    .ok_or_else(|| AppError::Validation("Invalid rent call period".to_string()))?;
+
+    let snapshot_json = serde_json::to_value(&snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for rent call {}: {}", rent_call.id, e);
+        AppError::Internal
+    })?;
+
+    sqlx::query("UPDATE rent_calls SET snapshot = $1 WHERE id = $2")
+        .bind(snapshot_json)
+        .bind(rent_call.id)
+        .execute(&db.pool)
+        .await?;
+
+    Ok(snapshot)
+}
+
+/// Issue the avis d'échéance of one period. Amounts follow the lease terms
+/// prorated to the leased days; arrears are the earlier periods already due
+/// and unpaid on `issued_on`; finalized charges regularisations not yet
+/// called make up the regularisation line. Returns `None` when the period
+/// already has a notice, so repeated runs never issue it twice. Without a user to resolve
+/// the landlord, the snapshot is frozen on first access instead.
+pub(crate) async fn issue_rent_call(
+    db: &Database,
+    lease_id: Uuid,
+    period_start: NaiveDate,
+    issued_on: NaiveDate,
+    requesting_user_id: Option<Uuid>,
+) -> Result<Option<RentCall>, AppError> {
+    let terms = sqlx::query_as::<_, RentCallLeaseTerms>(
+        r#"
+        SELECT l.start_date, COALESCE(n.effective_date, l.end_date) AS end_date, l.rent_payment_timing
+        FROM leases l
+        LEFT JOIN lease_notices n ON n.lease_id = l.id AND n.status = 'active'
+        WHERE l.id = $1
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Lease not found".to_string()))?;
+    ensure_lease_billable(db, lease_id).await?;
+
+    let rent_periods = rent_periods(db, lease_id).await?;
+    let PeriodCharge { base_rent, charges, due_date } = period_charge(
+        terms.start_date,
+        terms.end_date,
+        &rent_periods,
+        &terms.rent_payment_timing,
+        period_start,
+    )
+    .ok_or_else(|| {
+        AppError::Validation(format!(
+            "Period {}/{} is outside lease dates",
+            period_start.month(),
+            period_start.year()
+        ))
+    })?;
+
+    let period_month = period_start.month() as i32;
+    let period_year = period_start.year();
+    let arrears_lines = arrears_before(db, lease_id, period_year, period_month, issued_on).await?;
+    let (regularisation, regularisation_ids) = pending_regularisation_balance(db, lease_id).await?;
+
+    let inserted = sqlx::query_as::<_, RentCall>(&format!(
+        r#"
+        INSERT INTO rent_calls (lease_id, period_month, period_year, due_date, base_rent, charges, arrears, regularisation)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (lease_id, period_month, period_year) DO NOTHING
+        RETURNING {}
+        "#,
+        RENT_CALL_COLUMNS
+    ))
+    .bind(lease_id)
+    .bind(period_month)
+    .bind(period_year)
+    .bind(due_date)
+    .bind(base_rent)
+    .bind(charges)
+    .bind(total_arrears(&arrears_lines))
+    .bind(regularisation)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    if let Some(rent_call) = &inserted {
+        if !regularisation_ids.is_empty() {
+            mark_regularisations_applied(db, &regularisation_ids, rent_call.id).await?;
+        }
+    }
+
+    if let (Some(rent_call), Some(user_id)) = (&inserted, requesting_user_id) {
+        let lease_snapshot = current_lease_snapshot(db, lease_id, user_id).await?;
+        persist_rent_call_snapshot(db, &lease_snapshot, rent_call, arrears_lines).await?;
+    }
+
+    Ok(inserted)
+}
+
+async fn load_or_build_rent_call_snapshot(
+    db: &Database,
+    rent_call: &RentCall,
+    requesting_user_id: Uuid,
+) -> Result<RentCallSnapshot, AppError> {
+    let stored = sqlx::query_scalar::<_, Option<serde_json::Value>>(
+        "SELECT snapshot FROM rent_calls WHERE id = $1",
+    )
+    .bind(rent_call.id)
+    .fetch_optional(&db.pool)
+    .await?
+    .flatten();
+
+    if let Some(value) = stored {
+        if let Ok(snapshot) = serde_json::from_value::<RentCallSnapshot>(value) {
+            return Ok(snapshot);
+        }
+        tracing::warn!("Stored snapshot for rent call {} is invalid; rebuilding", rent_call.id);
+    }
+
+    // Rebuild the arrears as they stood when the notice was issued.
+    let issued_on = rent_call
+        .created_at
+        .map(|at| at.date_naive())
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let arrears_lines = arrears_before(
+        db,
+        rent_call.lease_id,
+        rent_call.period_year,
+        rent_call.period_month,
+        issued_on,
+    )
+    .await?;
+    let lease_snapshot = current_lease_snapshot(db, rent_call.lease_id, requesting_user_id).await?;
+    persist_rent_call_snapshot(db, &lease_snapshot, rent_call, arrears_lines).await
+}
+
+async fn load_or_render_rent_call_pdf(
+    db: &Database,
+    rent_call: &RentCall,
+    requesting_user_id: Uuid,
+) -> Result<Vec<u8>, AppError> {
+    if let Some(path) = rent_call.pdf_path.as_deref() {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => return Ok(bytes),
+            Err(e) => tracing::warn!("Stored PDF {} for rent call {} is unreadable ({}); re-rendering", path, rent_call.id, e),
+        }
+    }
+
+    let snapshot = load_or_build_rent_call_snapshot(db, rent_call, requesting_user_id).await?;
+
+    let renderer = PdfRenderer::from_env().map_err(|e| {
+        tracing::error!("Failed to initialize PDF renderer: {}", e);
+        AppError::Internal
+    })?;
+
+    let pdf_bytes = renderer
+        .generate_rent_call_pdf(&snapshot)
+        .await
+        .map_err(|e| {
+            tracing::error!("Avis d'échéance PDF generation failed for rent call {}: {}", rent_call.id, e);
+            AppError::BadRequest(format!("PDF generation failed: {}", e))
+        })?;
+
+    let storage_dir = receipts_storage_dir();
+    let pdf_path = storage_dir.join(format!("avis_echeance_{}.pdf", rent_call.id));
+    tokio::fs::create_dir_all(&storage_dir).await.map_err(|e| {
+        tracing::error!("Failed to create receipt storage dir {:?}: {}", storage_dir, e);
+        AppError::Internal
+    })?;
+    tokio::fs::write(&pdf_path, &pdf_bytes).await.map_err(|e| {
+        tracing::error!("Failed to store avis d'échéance PDF {:?}: {}", pdf_path, e);
+        AppError::Internal
+    })?;
+
+    sqlx::query("UPDATE rent_calls SET pdf_path = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2")
+        .bind(pdf_path.to_string_lossy().to_string())
+        .bind(rent_call.id)
+        .execute(&db.pool)
+        .await?;
+
+    Ok(pdf_bytes)
+}
+
+fn rent_call_email(snapshot: &RentCallSnapshot, to: &str, pdf_bytes: &[u8]) -> OutgoingMail {
+    let period = period_label(snapshot.period.month, snapshot.period.year);
+    let body = format!(
+        "Bonjour,\n\n\
+         Veuillez trouver ci-joint l'avis d'échéance pour la période de {} \
+         concernant le logement situé {}.\n\n\
+         Montant à régler : {} €\n\n\
+         Cordialement,\n{}",
+        period,
+        snapshot.property_address,
+        format_amount(&snapshot.amounts.total_due),
+        snapshot.parties.landlord_full_name,
+    );
+
+    OutgoingMail {
+        to: to.to_string(),
+        subject: format!("Avis d'échéance - {}", period),
+        body,
+        attachments: vec![MailAttachment {
+            filename: format!(
+                "avis_echeance_{}_{:02}.pdf",
+                snapshot.period.year, snapshot.period.month
+            ),
+            content_type: "application/pdf".to_string(),
+            content: pdf_bytes.to_vec(),
+        }],
+    }
+}
+
+/// Email the notice to every tenant on the lease, logging each attempt in
+/// `email_logs`.
+async fn deliver_rent_call(
+    db: &Database,
+    mailer: &Mailer,
+    rent_call: &RentCall,
+    requesting_user_id: Uuid,
+) -> Result<SendRentCallResult, AppError> {
+    let recipients = lease_recipient_emails(db, rent_call.lease_id).await?;
+    if recipients.is_empty() {
+        return Err(AppError::Validation(
+            "No tenant on this lease has an email address".to_string(),
+        ));
+    }
+
+    let pdf_bytes = load_or_render_rent_call_pdf(db, rent_call, requesting_user_id).await?;
+    let snapshot = load_or_build_rent_call_snapshot(db, rent_call, requesting_user_id).await?;
+
+    let mut deliveries = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        let mail = rent_call_email(&snapshot, &recipient, &pdf_bytes);
+
+        let log_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO email_logs (rent_call_id, tenant_email, subject, status)
+            VALUES ($1, $2, $3, 'pending')
+            RETURNING id
+            "#,
+        )
+        .bind(rent_call.id)
+        .bind(&recipient)
+        .bind(&mail.subject)
+        .fetch_one(&db.pool)
+        .await?;
+
+        match mailer.send(&mail).await {
+            Ok(()) => {
+                let status = mailer.delivery_status();
+                sqlx::query(
+                    "UPDATE email_logs SET status = $2, sent_at = CURRENT_TIMESTAMP WHERE id = $1",
+                )
+                .bind(log_id)
+                .bind(status)
+                .execute(&db.pool)
+                .await?;
+                deliveries.push(RentCallDelivery {
+                    tenant_email: recipient,
+                    status: status.to_string(),
+                    error_message: None,
+                });
+            }
+            Err(e) => {
+                tracing::warn!("Failed to email rent call {} to {}: {}", rent_call.id, recipient, e);
+                sqlx::query(
+                    "UPDATE email_logs SET status = 'failed', error_message = $2 WHERE id = $1",
+                )
+                .bind(log_id)
+                .bind(e.to_string())
+                .execute(&db.pool)
+                .await?;
+                deliveries.push(RentCallDelivery {
+                    tenant_email: recipient,
+                    status: "failed".to_string(),
+                    error_message: Some(e.to_string()),
+                });
+            }
+        }
+    }
+
+    let rent_call = if deliveries.iter().any(|d| d.status == "sent") {
+        sqlx::query_as::<_, RentCall>(&format!(
+            r#"
+            UPDATE rent_calls
+            SET email_sent_at = CURRENT_TIMESTAMP,
+                status = CASE WHEN status = 'settled' THEN status ELSE 'sent' END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            RENT_CALL_COLUMNS
+        ))
+        .bind(rent_call.id)
+        .fetch_one(&db.pool)
+        .await?
+    } else {
+        fetch_rent_call_by_id(db, rent_call.id).await?
+    };
+
+    Ok(SendRentCallResult { rent_call, deliveries })
+}
+
+/// GET /api/rent-calls?lease_id=
+async fn list_rent_calls(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Query(params): Query<RentCallQuery>,
+) -> Result<Json<Vec<RentCall>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, params.lease_id, user_id).await?;
+
+    let rent_calls = sqlx::query_as::<_, RentCall>(&format!(
+        "SELECT {} FROM rent_calls WHERE lease_id = $1 ORDER BY period_year DESC, period_month DESC",
+        RENT_CALL_COLUMNS
+    ))
+    .bind(params.lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(rent_calls))
+}
+
+/// POST /api/rent-calls
+/// Issue the avis d'échéance of a period; returns the existing notice if the
+/// period already has one.
+async fn create_rent_call(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateRentCall>,
+) -> Result<Json<RentCall>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    if payload.period_month < 1 || payload.period_month > 12 {
+        return Err(AppError::Validation("Period month must be between 1 and 12".to_string()));
+    }
+    ensure_lease_access(&db, payload.lease_id, user_id).await?;
+
+    let period_start = NaiveDate::from_ymd_opt(payload.period_year, payload.period_month as u32, 1)
+        .ok_or_else(|| AppError::Validation("Invalid rent call period".to_string()))?;
+
+    let issued = issue_rent_call(
+        &db,
+        payload.lease_id,
+        period_start,
+        Utc::now().date_naive(),
+        Some(user_id),
+    )
+    .await?;
+
+    let rent_call = match issued {
+        Some(rent_call) => rent_call,
+        None => sqlx::query_as::<_, RentCall>(&format!(
+            "SELECT {} FROM rent_calls WHERE lease_id = $1 AND period_month = $2 AND period_year = $3",
+            RENT_CALL_COLUMNS
+        ))
+        .bind(payload.lease_id)
+        .bind(payload.period_month)
+        .bind(payload.period_year)
+        .fetch_one(&db.pool)
+        .await?,
+    };
+
+    Ok(Json(rent_call))
+}
+
+/// GET /api/rent-calls/{id}
+async fn get_rent_call(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RentCall>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let rent_call = fetch_rent_call_by_id(&db, id).await?;
+    ensure_lease_access(&db, rent_call.lease_id, user_id).await?;
+
+    Ok(Json(rent_call))
+}
+
+/// GET /api/rent-calls/{id}/pdf
+async fn get_rent_call_pdf(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<RentCallPdfQuery>,
+) -> Result<Response, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let rent_call = fetch_rent_call_by_id(&db, id).await?;
+    ensure_lease_access(&db, rent_call.lease_id, user_id).await?;
+
+    let pdf_bytes = load_or_render_rent_call_pdf(&db, &rent_call, user_id).await?;
+
+    let filename = format!(
+        "avis_echeance_{}_{:02}_{}.pdf",
+        rent_call.period_year, rent_call.period_month, rent_call.id
+    );
+    let disposition = if params.inline.unwrap_or(false) { "inline" } else { "attachment" };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}\"", disposition, filename),
+        )
+        .body(Body::from(pdf_bytes))
+        .map_err(|e| {
+            tracing::error!("Failed to build PDF response: {}", e);
+            AppError::Internal
+        })
+}
+
+/// POST /api/rent-calls/{id}/send
+async fn send_rent_call(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SendRentCallResult>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    let rent_call = fetch_rent_call_by_id(&db, id).await?;
+    ensure_lease_access(&db, rent_call.lease_id, user_id).await?;
+
+    let mailer = mailer_from_env()?;
+    let result = deliver_rent_call(&db, &mailer, &rent_call, user_id).await?;
+
+    if result.deliveries.iter().all(|d| d.status == "failed") {
+        return Err(AppError::BadRequest(format!(
+            "Email delivery failed for all {} recipient(s)",
+            result.deliveries.len()
+        )));
+    }
+
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn rent_call_email_states_amount_due() {
+        let mut lease = CanonicalSnapshot::new(Uuid::new_v4(), "2026-06-18".to_string());
+        lease.parties.landlord_full_name = "Jean Dupont".to_string();
+        lease.parties.lessee_full_name = "Marie Martin".to_string();
+        lease.property.address = "1 rue de Paris".to_string();
+        lease.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        lease.lease_terms.end_date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let rent_call = RentCall {
+            id: Uuid::new_v4(),
+            lease_id: lease.lease_id,
+            period_month: 11,
+            period_year: 2026,
+            due_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            base_rent: BigDecimal::from_str("650").unwrap(),
+            charges: BigDecimal::from_str("50").unwrap(),
+            regularisation: BigDecimal::zero(),
+            arrears: BigDecimal::zero(),
+            total_due: BigDecimal::from_str("700").unwrap(),
+            status: "issued".to_string(),
+            receipt_id: None,
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let snapshot = RentCallSnapshot::from_lease_snapshot(
+            &lease,
+            &rent_call,
+            Vec::new(),
+            CURRENT_RENT_CALL_TEMPLATE_VERSION.to_string(),
+        )
+        .unwrap();
+
+        let mail = rent_call_email(&snapshot, "marie@example.com", b"%PDF");
+        assert_eq!(mail.subject, "Avis d'échéance - Novembre 2026");
+        assert!(mail.body.contains("Montant à régler : 700,00 €"));
+        assert_eq!(mail.attachments[0].filename, "avis_echeance_2026_11.pdf");
+    }
+
+    #[test]
+    fn total_arrears_sums_outstanding_periods() {
+        let lines = vec![
+            ArrearsLine { period_month: 8, period_year: 2026, balance: BigDecimal::from(150) },
+            ArrearsLine { period_month: 9, period_year: 2026, balance: BigDecimal::from(700) },
+        ];
+        assert_eq!(total_arrears(&lines), BigDecimal::from(850));
+        assert_eq!(total_arrears(&[]), BigDecimal::zero());
+    }
+}
@@ -0,0 +1,402 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+    http::{StatusCode, HeaderMap},
+};
+use bigdecimal::{BigDecimal, num_traits::Signed};
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::lease::Lease;
+use crate::models::rent_history::{
+    amounts_over, in_force_on, CreateRentPeriod, RentHistoryEntry, RentPeriod, RentRevisionRequest,
+    RentScheduleLine, RentScheduleQuery,
+};
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::{ensure_lease_access, fetch_lease_by_id};
+use crate::services::irl::{mid_lease_increase_error, propose_revision, RevisionInput, RevisionProposal};
+
+const RENT_HISTORY_COLUMNS: &str = r#"
+    id, lease_id, effective_date, monthly_rent, charges, reason, previous_rent,
+    irl_year, irl_quarter, irl_value, previous_irl_value, note, created_at
+"#;
+
+/// Rent periods of a lease, oldest first. Leases predating the history fall
+/// back to their signed amounts from the start date.
+pub(crate) async fn rent_periods(db: &Database, lease_id: Uuid) -> Result<Vec<RentPeriod>, AppError> {
+    let periods = sqlx::query_as::<_, RentPeriod>(
+        r#"
+        SELECT effective_date, monthly_rent, charges FROM lease_rent_history
+        WHERE lease_id = $1
+        ORDER BY effective_date
+        "#,
+    )
+    .bind(lease_id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    if !periods.is_empty() {
+        return Ok(periods);
+    }
+
+    let initial = sqlx::query_as::<_, RentPeriod>(
+        "SELECT start_date AS effective_date, monthly_rent, charges FROM leases WHERE id = $1",
+    )
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", lease_id)))?;
+
+    Ok(vec![initial])
+}
+
+/// Rent and charges in force on `on`.
+pub(crate) async fn rent_in_force(
+    db: &Database,
+    lease_id: Uuid,
+    on: NaiveDate,
+) -> Result<(BigDecimal, BigDecimal), AppError> {
+    let periods = rent_periods(db, lease_id).await?;
+    in_force_on(&periods, on)
+        .map(|period| (period.monthly_rent.clone(), period.charges.clone()))
+        .ok_or_else(|| AppError::NotFound(format!("Lease with id {} not found", lease_id)))
+}
+
+/// Keep the lease's 'initial' period in line with its signed start date and
+/// amounts. Called in the transaction that creates or updates the lease.
+pub(crate) async fn sync_initial_rent_period(
+    conn: &mut PgConnection,
+    lease_id: Uuid,
+    start_date: NaiveDate,
+    monthly_rent: &BigDecimal,
+    charges: &BigDecimal,
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM lease_rent_history WHERE lease_id = $1 AND reason = 'initial'")
+        .bind(lease_id)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO lease_rent_history (lease_id, effective_date, monthly_rent, charges, reason)
+        VALUES ($1, $2, $3, $4, 'initial')
+        ON CONFLICT (lease_id, effective_date) DO NOTHING
+        "#,
+    )
+    .bind(lease_id)
+    .bind(start_date)
+    .bind(monthly_rent)
+    .bind(charges)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn build_revision_proposal(
+    db: &Database,
+    lease: &Lease,
+    requested_on: NaiveDate,
+) -> Result<RevisionProposal, AppError> {
+    if !lease.rent_revision {
+        return Err(AppError::Validation("This lease has no rent revision clause".to_string()));
+    }
+    let (Some(reference_quarter), Some(reference_year)) =
+        (lease.irl_reference_quarter, lease.irl_reference_year)
+    else {
+        return Err(AppError::Validation(
+            "The lease does not name an IRL reference quarter".to_string(),
+        ));
+    };
+
+    // An avenant raising the rent uses up the revision of its year.
+    let last_revision_date = sqlx::query_scalar::<_, Option<NaiveDate>>(
+        r#"
+        SELECT MAX(effective_date) FROM lease_rent_history
+        WHERE lease_id = $1
+          AND (reason = 'irl_revision' OR (reason = 'amendment' AND monthly_rent > previous_rent))
+        "#,
+    )
+    .bind(lease.id)
+    .fetch_one(&db.pool)
+    .await?;
+
+    let indices = sqlx::query_as::<_, (i32, BigDecimal)>(
+        "SELECT year, value FROM irl_indices WHERE quarter = $1",
+    )
+    .bind(reference_quarter)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let (current_rent, _) = rent_in_force(db, lease.id, requested_on).await?;
+    let input = RevisionInput {
+        start_date: lease.start_date,
+        reference_quarter,
+        reference_year,
+        current_rent,
+        dpe_class: lease.dpe_class.clone(),
+        is_dom_tom: lease.is_dom_tom,
+        last_revision_date,
+        requested_on,
+    };
+
+    propose_revision(&input, |year, _| {
+        indices.iter().find(|(y, _)| *y == year).map(|(_, value)| value.clone())
+    })
+    .map_err(|e| AppError::Validation(e.to_string()))
+}
+
+/// Refuse a rent raised during the lease beyond the IRL revision open on
+/// `effective_date` (loi du 6 juillet 1989, art. 17-1).
+pub(crate) async fn ensure_rent_increase_allowed(
+    db: &Database,
+    lease: &Lease,
+    effective_date: NaiveDate,
+    new_rent: &BigDecimal,
+) -> Result<(), AppError> {
+    let (current_rent, _) = rent_in_force(db, lease.id, effective_date).await?;
+    if new_rent <= &current_rent {
+        return Ok(());
+    }
+    let revision = match build_revision_proposal(db, lease, effective_date).await {
+        Ok(proposal) => Ok(proposal),
+        Err(AppError::Validation(reason)) => Err(reason),
+        Err(e) => return Err(e),
+    };
+    match mid_lease_increase_error(&current_rent, new_rent, revision.as_ref().map_err(String::as_str)) {
+        Some(message) => Err(AppError::Validation(message)),
+        None => Ok(()),
+    }
+}
+
+/// GET /api/leases/{id}/rent-revision?requested_on=
+/// Propose the IRL revision the landlord may claim on that date.
+pub async fn propose_rent_revision(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<RentRevisionRequest>,
+) -> Result<Json<RevisionProposal>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    let requested_on = params.requested_on.unwrap_or_else(|| Utc::now().date_naive());
+    build_revision_proposal(&db, &lease, requested_on).await.map(Json)
+}
+
+/// POST /api/leases/{id}/rent-revision
+/// Confirm the proposed revision and record it in the rent history, from
+/// which later receipts take their amount.
+pub async fn confirm_rent_revision(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RentRevisionRequest>,
+) -> Result<(StatusCode, Json<RentHistoryEntry>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    let requested_on = payload.requested_on.unwrap_or_else(|| Utc::now().date_naive());
+    let proposal = build_revision_proposal(&db, &lease, requested_on).await?;
+    if proposal.frozen {
+        return Err(AppError::Validation(
+            proposal.freeze_reason.unwrap_or_else(|| "Rent revision is frozen".to_string()),
+        ));
+    }
+
+    let (_, charges) = rent_in_force(&db, lease.id, proposal.effective_date).await?;
+    let entry = sqlx::query_as::<_, RentHistoryEntry>(&format!(
+        r#"
+        INSERT INTO lease_rent_history (
+            lease_id, effective_date, monthly_rent, charges, reason, previous_rent,
+            irl_year, irl_quarter, irl_value, previous_irl_value
+        )
+        VALUES ($1, $2, $3, $4, 'irl_revision', $5, $6, $7, $8, $9)
+        RETURNING {}
+        "#,
+        RENT_HISTORY_COLUMNS
+    ))
+    .bind(lease.id)
+    .bind(proposal.effective_date)
+    .bind(&proposal.proposed_rent)
+    .bind(charges)
+    .bind(&proposal.current_rent)
+    .bind(proposal.new_index.year)
+    .bind(proposal.new_index.quarter)
+    .bind(&proposal.new_index.value)
+    .bind(&proposal.previous_index.value)
+    .fetch_one(&db.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// GET /api/leases/{id}/rent-history
+pub async fn list_rent_history(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<RentHistoryEntry>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let entries = sqlx::query_as::<_, RentHistoryEntry>(&format!(
+        "SELECT {} FROM lease_rent_history WHERE lease_id = $1 ORDER BY effective_date",
+        RENT_HISTORY_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(entries))
+}
+
+/// POST /api/leases/{id}/rent-history
+/// Record a manual change of rent or charges from a given date. Receipts
+/// already issued keep their amounts; regenerate them to apply the change.
+pub async fn create_rent_period(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateRentPeriod>,
+) -> Result<(StatusCode, Json<RentHistoryEntry>), AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    if !payload.monthly_rent.is_positive() || payload.charges.is_negative() {
+        return Err(AppError::Validation(
+            "Rent must be positive and charges cannot be negative".to_string(),
+        ));
+    }
+    if payload.effective_date <= lease.start_date {
+        return Err(AppError::Validation(
+            "A rent change must take effect after the lease start date".to_string(),
+        ));
+    }
+    if lease.end_date.is_some_and(|end| payload.effective_date > end) {
+        return Err(AppError::Validation(
+            "A rent change cannot take effect after the lease end date".to_string(),
+        ));
+    }
+
+    let (previous_rent, _) = rent_in_force(&db, id, payload.effective_date).await?;
+    let entry = sqlx::query_as::<_, RentHistoryEntry>(&format!(
+        r#"
+        INSERT INTO lease_rent_history (
+            lease_id, effective_date, monthly_rent, charges, reason, previous_rent, note
+        )
+        VALUES ($1, $2, $3, $4, 'adjustment', $5, $6)
+        ON CONFLICT (lease_id, effective_date) DO NOTHING
+        RETURNING {}
+        "#,
+        RENT_HISTORY_COLUMNS
+    ))
+    .bind(id)
+    .bind(payload.effective_date)
+    .bind(&payload.monthly_rent)
+    .bind(&payload.charges)
+    .bind(previous_rent)
+    .bind(payload.note.as_deref().map(str::trim).filter(|note| !note.is_empty()))
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::Validation(format!(
+            "The rent already changes on {}",
+            payload.effective_date
+        ))
+    })?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// DELETE /api/leases/{id}/rent-history/{entry_id}
+/// The initial period follows the lease itself and cannot be removed.
+pub async fn delete_rent_period(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((id, entry_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let reason = sqlx::query_scalar::<_, String>(
+        "SELECT reason FROM lease_rent_history WHERE id = $1 AND lease_id = $2",
+    )
+    .bind(entry_id)
+    .bind(id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Rent history entry not found".to_string()))?;
+
+    if reason == "initial" {
+        return Err(AppError::Validation(
+            "The initial rent is set on the lease itself".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM lease_rent_history WHERE id = $1")
+        .bind(entry_id)
+        .execute(&db.pool)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/leases/{id}/rent-schedule?year=
+/// Month-by-month amounts owed over a calendar year from the rent history,
+/// next to what was actually invoiced: the figures the accountant needs.
+pub async fn get_rent_schedule(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<RentScheduleQuery>,
+) -> Result<Json<Vec<RentScheduleLine>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+    let lease = fetch_lease_by_id(&db, id).await?;
+
+    let periods = rent_periods(&db, id).await?;
+    let invoiced = sqlx::query_as::<_, (i32, BigDecimal)>(
+        "SELECT period_month, total_amount FROM receipts WHERE lease_id = $1 AND period_year = $2",
+    )
+    .bind(id)
+    .bind(params.year)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let first_month = NaiveDate::from_ymd_opt(params.year, 1, 1)
+        .ok_or_else(|| AppError::Validation("Invalid year".to_string()))?;
+    let lines = (0..12)
+        .filter_map(|offset| {
+            let month_start = first_month + Months::new(offset);
+            let month_end = (month_start + Months::new(1)).pred_opt()?;
+            let covered_start = month_start.max(lease.start_date);
+            let covered_end = lease.end_date.map_or(month_end, |end| month_end.min(end));
+            if covered_start > covered_end {
+                return None;
+            }
+
+            let days_in_month = (month_end - month_start).num_days() + 1;
+            let (base_rent, charges) = amounts_over(&periods, covered_start, covered_end, days_in_month);
+            let period_month = month_start.month() as i32;
+            Some(RentScheduleLine {
+                period_month,
+                period_year: params.year,
+                total_amount: &base_rent + &charges,
+                base_rent,
+                charges,
+                invoiced_amount: invoiced
+                    .iter()
+                    .find(|(month, _)| *month == period_month)
+                    .map(|(_, total)| total.clone()),
+            })
+        })
+        .collect();
+
+    Ok(Json(lines))
+}
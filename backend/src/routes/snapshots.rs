@@ -0,0 +1,118 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+    http::HeaderMap,
+};
+use chrono::{Days, NaiveDate};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::canonical_snapshot::{LeaseSnapshotVersion, SnapshotDiff};
+use crate::routes::auth::extract_user_id_from_headers;
+use crate::routes::leases::ensure_lease_access;
+use crate::services::snapshot_diff::section_diff;
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotListQuery {
+    /// Only versions generated on or before this date; the last one is the
+    /// contract as it stood that day.
+    pub as_of: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotDiffQuery {
+    pub from: Uuid,
+    pub to: Uuid,
+}
+
+async fn fetch_version(db: &Database, lease_id: Uuid, id: Uuid) -> Result<(LeaseSnapshotVersion, serde_json::Value), AppError> {
+    let version = sqlx::query_as::<_, LeaseSnapshotVersion>(
+        r#"
+        SELECT id, lease_id, legal_template_version, generated_at
+        FROM lease_snapshots
+        WHERE id = $1 AND lease_id = $2
+        "#,
+    )
+    .bind(id)
+    .bind(lease_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Snapshot {} not found for this lease", id)))?;
+
+    let snapshot = sqlx::query_scalar::<_, serde_json::Value>("SELECT snapshot FROM lease_snapshots WHERE id = $1")
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await?;
+
+    Ok((version, snapshot))
+}
+
+/// GET /api/leases/{id}/snapshots?as_of=
+/// Every snapshot generated for the lease, oldest first.
+pub async fn list_lease_snapshots(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SnapshotListQuery>,
+) -> Result<Json<Vec<LeaseSnapshotVersion>>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    // Versions generated at any time of day `as_of` count.
+    let before = params
+        .as_of
+        .and_then(|date| date.checked_add_days(Days::new(1)))
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc());
+
+    let versions = sqlx::query_as::<_, LeaseSnapshotVersion>(
+        r#"
+        SELECT id, lease_id, legal_template_version, generated_at
+        FROM lease_snapshots
+        WHERE lease_id = $1 AND ($2::TIMESTAMPTZ IS NULL OR generated_at < $2)
+        ORDER BY generated_at, created_at
+        "#,
+    )
+    .bind(id)
+    .bind(before)
+    .fetch_all(&db.pool)
+    .await?;
+
+    Ok(Json(versions))
+}
+
+/// GET /api/leases/{id}/snapshots/{snapshot_id}
+/// A past snapshot exactly as it was stored.
+pub async fn get_lease_snapshot_version(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path((id, snapshot_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let (_, snapshot) = fetch_version(&db, id, snapshot_id).await?;
+    Ok(Json(snapshot))
+}
+
+/// GET /api/leases/{id}/snapshots/diff?from=&to=
+/// Field-by-field differences between two snapshot versions, by section.
+pub async fn diff_lease_snapshots(
+    State(db): State<Database>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SnapshotDiffQuery>,
+) -> Result<Json<SnapshotDiff>, AppError> {
+    let user_id = extract_user_id_from_headers(&headers)?;
+    ensure_lease_access(&db, id, user_id).await?;
+
+    let (from, before) = fetch_version(&db, id, params.from).await?;
+    let (to, after) = fetch_version(&db, id, params.to).await?;
+
+    Ok(Json(SnapshotDiff {
+        from,
+        to,
+        sections: section_diff(&before, &after),
+    }))
+}
@@ -0,0 +1,246 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero, num_traits::Signed};
+use chrono::NaiveDate;
+
+use crate::models::charges_regularisation::{
+    CategoryTotal, ColocataireShare, RegularisationExpense, RegularisationStatement, StatementLine,
+};
+
+/// Categories of recoverable charges listed in the annex to décret n° 87-713
+/// du 26 août 1987.
+pub const RECOVERABLE_CATEGORIES: [(&str, &str); 8] = [
+    ("ascenseurs", "Ascenseurs et monte-charge"),
+    ("eau_chauffage_collectif", "Eau froide, eau chaude et chauffage collectif"),
+    ("installations_individuelles", "Installations individuelles"),
+    ("parties_communes", "Parties communes intérieures au bâtiment"),
+    ("espaces_exterieurs", "Espaces extérieurs au bâtiment"),
+    ("hygiene", "Hygiène"),
+    ("equipements_divers", "Équipements divers du bâtiment"),
+    ("impositions_redevances", "Impositions et redevances"),
+];
+
+pub fn category_label(category: &str) -> Option<&'static str> {
+    RECOVERABLE_CATEGORIES
+        .iter()
+        .find(|(code, _)| *code == category)
+        .map(|(_, label)| *label)
+}
+
+/// Days of `year` during which the lease was running, or `None` if none.
+pub fn occupancy_in_year(
+    lease_start: NaiveDate,
+    lease_end: Option<NaiveDate>,
+    year: i32,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let year_start = NaiveDate::from_ymd_opt(year, 1, 1)?;
+    let year_end = NaiveDate::from_ymd_opt(year, 12, 31)?;
+    let start = year_start.max(lease_start);
+    let end = lease_end.map_or(year_end, |end| year_end.min(end));
+    (start <= end).then_some((start, end))
+}
+
+fn days_between(start: NaiveDate, end: NaiveDate) -> i64 {
+    (end - start).num_days() + 1
+}
+
+/// Part of an expense covering `covered_start..=covered_end` that falls
+/// within the occupancy, prorated by days.
+pub fn prorated_share(
+    amount: &BigDecimal,
+    covered_start: NaiveDate,
+    covered_end: NaiveDate,
+    occupancy: (NaiveDate, NaiveDate),
+) -> BigDecimal {
+    let overlap_start = covered_start.max(occupancy.0);
+    let overlap_end = covered_end.min(occupancy.1);
+    if overlap_start > overlap_end || covered_start > covered_end {
+        return BigDecimal::zero();
+    }
+
+    let overlap = BigDecimal::from(days_between(overlap_start, overlap_end));
+    let covered = BigDecimal::from(days_between(covered_start, covered_end));
+    (amount * overlap / covered).with_scale_round(2, RoundingMode::HalfUp)
+}
+
+/// Split `total` into `parts` equal shares to the cent; leftover cents go to
+/// the first shares so the parts always add up to the total.
+pub fn split_evenly(total: &BigDecimal, parts: usize) -> Vec<BigDecimal> {
+    if parts == 0 {
+        return Vec::new();
+    }
+
+    let cents = (total * BigDecimal::from(100)).with_scale_round(0, RoundingMode::HalfUp);
+    let count = BigDecimal::from(parts as i64);
+    let base = (&cents / &count).with_scale_round(0, RoundingMode::Down);
+    let remainder = &cents - &base * &count;
+    let step = if remainder.is_negative() { BigDecimal::from(-1) } else { BigDecimal::from(1) };
+    let mut extra = remainder.abs();
+
+    (0..parts)
+        .map(|_| {
+            let mut share = base.clone();
+            if !extra.is_zero() {
+                share += &step;
+                extra -= BigDecimal::from(1);
+            }
+            (share / BigDecimal::from(100)).with_scale(2)
+        })
+        .collect()
+}
+
+/// Build the statement of a regularisation: each expense prorated to the
+/// occupancy, totals per category, the balance against the provisions and,
+/// for a colocation, each colocataire's share.
+pub fn compute_statement(
+    year: i32,
+    occupancy: (NaiveDate, NaiveDate),
+    expenses: &[RegularisationExpense],
+    provisions_collected: &BigDecimal,
+    colocataires: &[String],
+) -> RegularisationStatement {
+    let lines: Vec<StatementLine> = expenses
+        .iter()
+        .map(|expense| StatementLine {
+            category: expense.category.clone(),
+            category_label: category_label(&expense.category)
+                .unwrap_or(expense.category.as_str())
+                .to_string(),
+            label: expense.label.clone(),
+            amount: expense.amount.clone(),
+            covered_start: expense.covered_start,
+            covered_end: expense.covered_end,
+            tenant_share: prorated_share(
+                &expense.amount,
+                expense.covered_start,
+                expense.covered_end,
+                occupancy,
+            ),
+        })
+        .collect();
+
+    let category_totals = RECOVERABLE_CATEGORIES
+        .iter()
+        .filter_map(|(code, label)| {
+            let in_category: Vec<&StatementLine> =
+                lines.iter().filter(|line| line.category == *code).collect();
+            if in_category.is_empty() {
+                return None;
+            }
+            Some(CategoryTotal {
+                category: code.to_string(),
+                category_label: label.to_string(),
+                amount: in_category.iter().fold(BigDecimal::zero(), |acc, line| acc + &line.amount),
+                tenant_share: in_category
+                    .iter()
+                    .fold(BigDecimal::zero(), |acc, line| acc + &line.tenant_share),
+            })
+        })
+        .collect();
+
+    let total_expenses = lines.iter().fold(BigDecimal::zero(), |acc, line| acc + &line.amount);
+    let tenant_share = lines.iter().fold(BigDecimal::zero(), |acc, line| acc + &line.tenant_share);
+    let balance = &tenant_share - provisions_collected;
+
+    let colocataire_shares = if colocataires.len() > 1 {
+        let shares = split_evenly(&tenant_share, colocataires.len());
+        let provisions = split_evenly(provisions_collected, colocataires.len());
+        colocataires
+            .iter()
+            .zip(shares)
+            .zip(provisions)
+            .map(|((name, share), provision)| ColocataireShare {
+                tenant_name: name.clone(),
+                balance: &share - &provision,
+                tenant_share: share,
+                provisions_collected: provision,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    RegularisationStatement {
+        year,
+        occupancy_start: occupancy.0,
+        occupancy_end: occupancy.1,
+        occupancy_days: days_between(occupancy.0, occupancy.1),
+        lines,
+        category_totals,
+        total_expenses,
+        tenant_share,
+        provisions_collected: provisions_collected.clone(),
+        balance,
+        colocataire_shares,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn expense(category: &str, amount: &str) -> RegularisationExpense {
+        RegularisationExpense {
+            id: Uuid::new_v4(),
+            regularisation_id: Uuid::new_v4(),
+            category: category.to_string(),
+            label: "Facture".to_string(),
+            amount: BigDecimal::from_str(amount).unwrap(),
+            covered_start: d(2025, 1, 1),
+            covered_end: d(2025, 12, 31),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn prorates_expenses_to_occupancy_days() {
+        // Moved in on 1 July 2025: 184 of 365 days.
+        let occupancy = occupancy_in_year(d(2025, 7, 1), None, 2025).unwrap();
+        assert_eq!(occupancy, (d(2025, 7, 1), d(2025, 12, 31)));
+
+        let expenses = vec![expense("ascenseurs", "365.00"), expense("hygiene", "730.00")];
+        let statement = compute_statement(
+            2025,
+            occupancy,
+            &expenses,
+            &BigDecimal::from_str("600.00").unwrap(),
+            &[],
+        );
+
+        assert_eq!(statement.occupancy_days, 184);
+        assert_eq!(statement.total_expenses, BigDecimal::from_str("1095.00").unwrap());
+        assert_eq!(statement.tenant_share, BigDecimal::from_str("552.00").unwrap());
+        assert_eq!(statement.balance, BigDecimal::from_str("-48.00").unwrap());
+        assert_eq!(statement.category_totals.len(), 2);
+        assert!(statement.colocataire_shares.is_empty());
+        assert!(occupancy_in_year(d(2026, 1, 1), None, 2025).is_none());
+    }
+
+    #[test]
+    fn splits_colocation_balance_to_the_cent() {
+        let occupancy = (d(2025, 1, 1), d(2025, 12, 31));
+        let statement = compute_statement(
+            2025,
+            occupancy,
+            &[expense("eau_chauffage_collectif", "1000.00")],
+            &BigDecimal::from_str("900.00").unwrap(),
+            &["Alice".to_string(), "Bruno".to_string(), "Chloé".to_string()],
+        );
+
+        let shares: Vec<BigDecimal> =
+            statement.colocataire_shares.iter().map(|share| share.tenant_share.clone()).collect();
+        assert_eq!(shares[0], BigDecimal::from_str("333.34").unwrap());
+        assert_eq!(shares[2], BigDecimal::from_str("333.33").unwrap());
+        let total_balance = statement
+            .colocataire_shares
+            .iter()
+            .fold(BigDecimal::zero(), |acc, share| acc + &share.balance);
+        assert_eq!(total_balance, statement.balance);
+        assert_eq!(category_label("hygiene"), Some("Hygiène"));
+        assert_eq!(category_label("travaux"), None);
+    }
+}
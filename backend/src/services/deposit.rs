@@ -0,0 +1,179 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{Months, NaiveDate};
+use uuid::Uuid;
+
+use crate::models::deposit::{DepositDeduction, DepositSettlement};
+
+pub const DEDUCTION_CATEGORIES: [&str; 5] =
+    ["repairs", "unpaid_rent", "unpaid_charges", "charges_provision", "other"];
+
+/// Whether a deduction pays a period out of the deposit: unpaid rent or
+/// charges pointing at the receipt they settle. Those are recorded on the
+/// payments ledger so the period is not owed twice.
+pub fn credits_ledger(category: &str, receipt_id: Option<Uuid>) -> bool {
+    matches!(category, "unpaid_rent" | "unpaid_charges") && receipt_id.is_some()
+}
+
+/// Loi du 6 juillet 1989, art. 22: the deposit is returned within one month
+/// of the keys being handed back when the exit inventory matches the entry
+/// one, two months otherwise.
+pub fn return_deadline(keys_returned_on: NaiveDate, exit_inventory_conforms: bool) -> Option<NaiveDate> {
+    let months = if exit_inventory_conforms { 1 } else { 2 };
+    keys_returned_on.checked_add_months(Months::new(months))
+}
+
+/// Months started since the deadline on `on`; zero when not late.
+pub fn months_late(deadline: NaiveDate, on: NaiveDate) -> i32 {
+    let mut months = 0;
+    while deadline
+        .checked_add_months(Months::new(months as u32))
+        .is_some_and(|threshold| threshold < on)
+    {
+        months += 1;
+    }
+    months
+}
+
+/// Late return costs 10% of the monthly rent (hors charges) for each month
+/// started after the deadline.
+pub fn late_penalty(monthly_rent: &BigDecimal, months_late: i32) -> BigDecimal {
+    (monthly_rent * BigDecimal::from(months_late) / BigDecimal::from(10))
+        .with_scale_round(2, RoundingMode::HalfUp)
+}
+
+/// Settle the deposit on `on`: deductions, amount left to return and any
+/// penalty for returning it after the deadline.
+pub fn settle(
+    amount_held: &BigDecimal,
+    deductions: &[DepositDeduction],
+    deadline: Option<NaiveDate>,
+    monthly_rent: &BigDecimal,
+    on: NaiveDate,
+) -> DepositSettlement {
+    let total_deductions = deductions
+        .iter()
+        .fold(BigDecimal::zero(), |acc, deduction| acc + &deduction.amount);
+    let remaining = amount_held - &total_deductions;
+    let (amount_to_return, tenant_balance_due) = if remaining < BigDecimal::zero() {
+        (BigDecimal::zero(), -remaining)
+    } else {
+        (remaining, BigDecimal::zero())
+    };
+
+    let months_late = deadline.map_or(0, |deadline| months_late(deadline, on));
+    let late_penalty = late_penalty(monthly_rent, months_late);
+
+    DepositSettlement {
+        amount_held: amount_held.clone(),
+        total_deductions,
+        total_due_to_tenant: &amount_to_return + &late_penalty,
+        amount_to_return,
+        tenant_balance_due,
+        return_deadline: deadline,
+        months_late,
+        late_penalty,
+        settled_on: on,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ledger::{allocate, DuePeriod, LedgerPayment};
+    use std::str::FromStr;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn deduction(amount: &str) -> DepositDeduction {
+        DepositDeduction {
+            id: Uuid::new_v4(),
+            deposit_id: Uuid::new_v4(),
+            category: "repairs".to_string(),
+            label: "Remplacement moquette".to_string(),
+            amount: BigDecimal::from_str(amount).unwrap(),
+            receipt_id: None,
+            justification: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn deadline_depends_on_exit_inventory() {
+        assert_eq!(return_deadline(d(2026, 1, 31), true), Some(d(2026, 2, 28)));
+        assert_eq!(return_deadline(d(2026, 3, 15), false), Some(d(2026, 5, 15)));
+    }
+
+    #[test]
+    fn penalty_counts_every_started_month() {
+        let deadline = d(2026, 4, 15);
+        assert_eq!(months_late(deadline, d(2026, 4, 15)), 0);
+        assert_eq!(months_late(deadline, d(2026, 4, 16)), 1);
+        assert_eq!(months_late(deadline, d(2026, 5, 15)), 1);
+        assert_eq!(months_late(deadline, d(2026, 5, 16)), 2);
+
+        let settlement = settle(
+            &BigDecimal::from(800),
+            &[deduction("150.00")],
+            Some(deadline),
+            &BigDecimal::from(750),
+            d(2026, 5, 20),
+        );
+        assert_eq!(settlement.amount_to_return, BigDecimal::from_str("650.00").unwrap());
+        assert_eq!(settlement.late_penalty, BigDecimal::from_str("150.00").unwrap());
+        assert_eq!(settlement.total_due_to_tenant, BigDecimal::from_str("800.00").unwrap());
+    }
+
+    #[test]
+    fn deductions_beyond_the_deposit_remain_owed() {
+        let settlement = settle(
+            &BigDecimal::from(500),
+            &[deduction("420.00"), deduction("180.00")],
+            Some(d(2026, 4, 15)),
+            &BigDecimal::from(500),
+            d(2026, 4, 1),
+        );
+        assert_eq!(settlement.amount_to_return, BigDecimal::zero());
+        assert_eq!(settlement.tenant_balance_due, BigDecimal::from_str("100.00").unwrap());
+        assert_eq!(settlement.late_penalty, BigDecimal::from_str("0.00").unwrap());
+    }
+
+    #[test]
+    fn only_receipt_linked_rent_deductions_credit_the_ledger() {
+        let receipt = Some(Uuid::new_v4());
+        assert!(credits_ledger("unpaid_rent", receipt));
+        assert!(credits_ledger("unpaid_charges", receipt));
+        assert!(!credits_ledger("unpaid_rent", None));
+        assert!(!credits_ledger("repairs", receipt));
+    }
+
+    #[test]
+    fn deduction_clears_the_arrears_it_settles() {
+        let receipt_id = Uuid::new_v4();
+        let periods = vec![DuePeriod {
+            receipt_id,
+            period_year: 2026,
+            period_month: 9,
+            amount_due: BigDecimal::from(700),
+        }];
+        let tenant_payment = LedgerPayment {
+            id: Uuid::new_v4(),
+            amount: BigDecimal::from(400),
+            payment_date: d(2026, 9, 5),
+            receipt_id: None,
+        };
+        assert_eq!(allocate(&periods, std::slice::from_ref(&tenant_payment), &[]).arrears, BigDecimal::from(300));
+
+        // The 300 € kept from the deposit is credited against September.
+        let deduction = LedgerPayment {
+            id: Uuid::new_v4(),
+            amount: BigDecimal::from(300),
+            payment_date: d(2026, 10, 20),
+            receipt_id: Some(receipt_id),
+        };
+        let outcome = allocate(&periods, &[tenant_payment, deduction], &[]);
+        assert_eq!(outcome.arrears, BigDecimal::zero());
+        assert_eq!(outcome.credit, BigDecimal::zero());
+    }
+}
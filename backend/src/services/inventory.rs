@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+
+use crate::error::AppError;
+use crate::models::inventory::{
+    condition_label, InventoryDegradation, InventoryRoom, KeyHandover, KeyShortfall, SaveLeaseInventory,
+    CONDITION_RATINGS,
+};
+
+/// Position in the condition grid, 0 being the best.
+fn condition_rank(condition: &str) -> Option<usize> {
+    CONDITION_RATINGS.iter().position(|(code, _)| *code == condition)
+}
+
+/// Rooms, elements and keys are matched between entry and exit by name,
+/// ignoring case and surrounding spaces.
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Check the inventory is complete enough to be signed and compared.
+pub fn validate(inventory: &SaveLeaseInventory) -> Result<(), AppError> {
+    if inventory.rooms.is_empty() {
+        return Err(AppError::Validation("An inventory lists at least one room".to_string()));
+    }
+
+    let mut rooms = HashSet::new();
+    for room in &inventory.rooms {
+        if room.name.trim().is_empty() {
+            return Err(AppError::Validation("Every room needs a name".to_string()));
+        }
+        if !rooms.insert(normalize(&room.name)) {
+            return Err(AppError::Validation(format!("Room '{}' is listed twice", room.name.trim())));
+        }
+        let mut elements = HashSet::new();
+        for element in &room.elements {
+            if element.name.trim().is_empty() {
+                return Err(AppError::Validation(format!(
+                    "Every element of room '{}' needs a name",
+                    room.name.trim()
+                )));
+            }
+            if !elements.insert(normalize(&element.name)) {
+                return Err(AppError::Validation(format!(
+                    "Element '{}' is listed twice in room '{}'",
+                    element.name.trim(),
+                    room.name.trim()
+                )));
+            }
+            if condition_label(&element.condition).is_none() {
+                return Err(AppError::Validation(format!(
+                    "Unknown condition '{}' for '{}'; use neuf, bon, moyen or mauvais",
+                    element.condition,
+                    element.name.trim()
+                )));
+            }
+        }
+    }
+
+    for meter in &inventory.meters {
+        if meter.meter.trim().is_empty() || meter.reading.trim().is_empty() {
+            return Err(AppError::Validation("Every meter needs a name and a reading".to_string()));
+        }
+    }
+    for key in &inventory.keys {
+        if key.label.trim().is_empty() || key.count < 0 {
+            return Err(AppError::Validation(
+                "Every key needs a label and a count that is not negative".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Elements in a worse condition at exit than at entry, and elements of the
+/// entry inventory missing at exit. Normal wear shows as the same rating and
+/// is not a degradation; elements only listed at exit are ignored.
+pub fn degradations(entry: &[InventoryRoom], exit: &[InventoryRoom]) -> Vec<InventoryDegradation> {
+    let mut found = Vec::new();
+    for entry_room in entry {
+        let exit_room = exit.iter().find(|room| normalize(&room.name) == normalize(&entry_room.name));
+        for entry_element in &entry_room.elements {
+            let exit_element = exit_room.and_then(|room| {
+                room.elements
+                    .iter()
+                    .find(|element| normalize(&element.name) == normalize(&entry_element.name))
+            });
+            let worse = match exit_element {
+                Some(exit_element) => {
+                    condition_rank(&exit_element.condition) > condition_rank(&entry_element.condition)
+                }
+                None => true,
+            };
+            if worse {
+                found.push(InventoryDegradation {
+                    room: entry_room.name.trim().to_string(),
+                    element: entry_element.name.trim().to_string(),
+                    entry_condition: entry_element.condition.clone(),
+                    exit_condition: exit_element.map(|element| element.condition.clone()),
+                    exit_comment: exit_element.and_then(|element| element.comment.clone()),
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Keys handed over at entry and not all returned at exit.
+pub fn missing_keys(entry: &[KeyHandover], exit: &[KeyHandover]) -> Vec<KeyShortfall> {
+    entry
+        .iter()
+        .filter_map(|handed| {
+            let returned = exit
+                .iter()
+                .filter(|key| normalize(&key.label) == normalize(&handed.label))
+                .map(|key| key.count)
+                .sum::<i32>();
+            (returned < handed.count).then(|| KeyShortfall {
+                label: handed.label.trim().to_string(),
+                handed_over: handed.count,
+                returned,
+            })
+        })
+        .collect()
+}
+
+/// The degradation a repair charge refers to, by room and element name.
+pub fn find_degradation<'a>(
+    degradations: &'a [InventoryDegradation],
+    room: &str,
+    element: &str,
+) -> Option<&'a InventoryDegradation> {
+    degradations
+        .iter()
+        .find(|d| normalize(&d.room) == normalize(room) && normalize(&d.element) == normalize(element))
+}
+
+/// Deduction line for a repaired degradation, as it reads on the deposit
+/// settlement letter.
+pub fn deduction_label(degradation: &InventoryDegradation) -> String {
+    let entry = condition_label(&degradation.entry_condition).unwrap_or_default();
+    let exit = degradation
+        .exit_condition
+        .as_deref()
+        .and_then(condition_label)
+        .unwrap_or("manquant");
+    format!(
+        "{} — {} : {} à l'entrée, {} à la sortie",
+        degradation.room,
+        degradation.element,
+        entry.to_lowercase(),
+        exit.to_lowercase()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::inventory::{InventoryElement, MeterReading};
+    use chrono::NaiveDate;
+
+    fn element(name: &str, condition: &str) -> InventoryElement {
+        InventoryElement { name: name.to_string(), condition: condition.to_string(), comment: None }
+    }
+
+    fn room(name: &str, elements: Vec<InventoryElement>) -> InventoryRoom {
+        InventoryRoom { name: name.to_string(), elements, comment: None }
+    }
+
+    fn inventory(rooms: Vec<InventoryRoom>) -> SaveLeaseInventory {
+        SaveLeaseInventory {
+            performed_on: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            rooms,
+            meters: vec![MeterReading {
+                meter: "Électricité".to_string(),
+                serial_number: None,
+                reading: "12345".to_string(),
+            }],
+            keys: vec![KeyHandover { label: "Porte d'entrée".to_string(), count: 2 }],
+            general_comment: None,
+        }
+    }
+
+    #[test]
+    fn validates_names_conditions_and_duplicates() {
+        let valid = inventory(vec![room("Séjour", vec![element("Sol", "bon"), element("Murs", "neuf")])]);
+        assert!(validate(&valid).is_ok());
+
+        assert!(validate(&inventory(vec![])).is_err());
+        assert!(validate(&inventory(vec![room("Séjour", vec![element("Sol", "abîmé")])])).is_err());
+        assert!(validate(&inventory(vec![room("Séjour", vec![element("Sol", "bon"), element(" sol ", "bon")])])).is_err());
+        assert!(validate(&inventory(vec![room("Cuisine", vec![]), room("cuisine", vec![])])).is_err());
+
+        let mut negative_keys = valid;
+        negative_keys.keys[0].count = -1;
+        assert!(validate(&negative_keys).is_err());
+    }
+
+    #[test]
+    fn flags_worse_and_missing_elements_only() {
+        let entry = vec![
+            room("Séjour", vec![element("Sol", "bon"), element("Murs", "bon"), element("Rideaux", "neuf")]),
+            room("Cuisine", vec![element("Évier", "moyen")]),
+        ];
+        let mut exit = vec![
+            room("séjour", vec![element("Sol", "mauvais"), element("Murs", "bon")]),
+            room("Cuisine", vec![element("Évier", "moyen"), element("Four", "mauvais")]),
+        ];
+        exit[0].elements[0].comment = Some("Parquet rayé".to_string());
+
+        let found = degradations(&entry, &exit);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].element, "Sol");
+        assert_eq!(found[0].exit_condition.as_deref(), Some("mauvais"));
+        assert_eq!(found[0].exit_comment.as_deref(), Some("Parquet rayé"));
+        assert_eq!(found[1].element, "Rideaux");
+        assert_eq!(found[1].exit_condition, None);
+        assert_eq!(deduction_label(&found[1]), "Séjour — Rideaux : neuf ou très bon état à l'entrée, manquant à la sortie");
+        assert!(find_degradation(&found, "SÉJOUR", "sol").is_some());
+        assert!(find_degradation(&found, "Séjour", "Murs").is_none());
+    }
+
+    #[test]
+    fn counts_unreturned_keys() {
+        let entry = vec![
+            KeyHandover { label: "Porte d'entrée".to_string(), count: 2 },
+            KeyHandover { label: "Boîte aux lettres".to_string(), count: 1 },
+        ];
+        let exit = vec![KeyHandover { label: "porte d'entrée".to_string(), count: 1 }];
+        let shortfalls = missing_keys(&entry, &exit);
+        assert_eq!(shortfalls.len(), 2);
+        assert_eq!(shortfalls[0], KeyShortfall { label: "Porte d'entrée".to_string(), handed_over: 2, returned: 1 });
+    }
+}
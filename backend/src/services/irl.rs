@@ -0,0 +1,446 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::db::Database;
+use crate::error::AppError;
+
+#[derive(Debug, Error)]
+pub enum IrlError {
+    #[error("Cannot read IRL file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid IRL data on line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("Invalid IRL JSON: {0}")]
+    Json(String),
+}
+
+/// One quarterly IRL value published by INSEE.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow, Serialize, Deserialize)]
+pub struct IrlIndex {
+    pub year: i32,
+    pub quarter: i32,
+    pub value: BigDecimal,
+}
+
+/// Resolve the IRL data file (overridable via `IRL_DATA_FILE`).
+pub fn irl_data_file() -> PathBuf {
+    std::env::var("IRL_DATA_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("data/irl.csv"))
+}
+
+/// Parse a quarter written as `2`, `T2` or `Q2`.
+fn parse_quarter(raw: &str) -> Option<i32> {
+    let digits = raw.trim().trim_start_matches(['T', 't', 'Q', 'q']);
+    digits.parse::<i32>().ok().filter(|q| (1..=4).contains(q))
+}
+
+/// Parse `year,quarter,value` rows. A header row is skipped; `;` separators
+/// and decimal commas (as in INSEE downloads) are accepted.
+pub fn parse_irl_csv(content: &str) -> Result<Vec<IrlIndex>, IrlError> {
+    let mut indices = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let separator = if line.contains(';') { ';' } else { ',' };
+        let fields: Vec<&str> = line.split(separator).map(str::trim).collect();
+        if number == 0 && fields.first().is_some_and(|f| f.parse::<i32>().is_err()) {
+            continue;
+        }
+        let parse_error = |message: &str| IrlError::Parse { line: number + 1, message: message.to_string() };
+        if fields.len() < 3 {
+            return Err(parse_error("expected year, quarter and value"));
+        }
+
+        let year = fields[0].parse::<i32>().map_err(|_| parse_error("invalid year"))?;
+        let quarter = parse_quarter(fields[1]).ok_or_else(|| parse_error("quarter must be 1 to 4"))?;
+        let value = BigDecimal::from_str(&fields[2].replace(',', "."))
+            .map_err(|_| parse_error("invalid index value"))?;
+        indices.push(IrlIndex { year, quarter, value });
+    }
+    Ok(indices)
+}
+
+/// Parse a JSON array of `{ "year", "quarter", "value" }` objects.
+pub fn parse_irl_json(content: &str) -> Result<Vec<IrlIndex>, IrlError> {
+    let indices: Vec<IrlIndex> =
+        serde_json::from_str(content).map_err(|e| IrlError::Json(e.to_string()))?;
+    if let Some(bad) = indices.iter().find(|i| !(1..=4).contains(&i.quarter)) {
+        return Err(IrlError::Json(format!("quarter {} of {} must be 1 to 4", bad.quarter, bad.year)));
+    }
+    Ok(indices)
+}
+
+/// Load IRL values from a `.csv` or `.json` file.
+pub fn load_irl_file(path: &Path) -> Result<Vec<IrlIndex>, IrlError> {
+    let content = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => parse_irl_json(&content),
+        _ => parse_irl_csv(&content),
+    }
+}
+
+/// Upsert IRL values into `irl_indices`. Returns the number of rows written.
+pub async fn sync_irl_table(db: &Database, indices: &[IrlIndex]) -> Result<usize, AppError> {
+    for index in indices {
+        sqlx::query(
+            r#"
+            INSERT INTO irl_indices (year, quarter, value)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (year, quarter)
+            DO UPDATE SET value = EXCLUDED.value, updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(index.year)
+        .bind(index.quarter)
+        .bind(&index.value)
+        .execute(&db.pool)
+        .await?;
+    }
+    Ok(indices.len())
+}
+
+/// Load the IRL data file into the database at startup. A missing or invalid
+/// file is logged, not fatal: revisions then report the missing index.
+pub async fn sync_from_data_file(db: &Database) {
+    let path = irl_data_file();
+    match load_irl_file(&path) {
+        Ok(indices) => match sync_irl_table(db, &indices).await {
+            Ok(count) => tracing::info!("Loaded {} IRL values from {:?}", count, path),
+            Err(e) => tracing::error!("Failed to store IRL values: {}", e),
+        },
+        Err(e) => tracing::warn!("IRL values not loaded from {:?}: {}", path, e),
+    }
+}
+
+/// Rent freeze for housing rated F or G (loi Climat et résilience, art. 159):
+/// in force since 24 August 2022 in mainland France, 1 July 2024 overseas.
+pub fn dpe_freeze_applies(dpe_class: Option<&str>, is_dom_tom: bool, on: NaiveDate) -> bool {
+    let in_force_from = if is_dom_tom {
+        NaiveDate::from_ymd_opt(2024, 7, 1)
+    } else {
+        NaiveDate::from_ymd_opt(2022, 8, 24)
+    }
+    .expect("valid date");
+    matches!(dpe_class, Some("F") | Some("G")) && on >= in_force_from
+}
+
+/// Lease facts needed to propose a revision.
+#[derive(Debug, Clone)]
+pub struct RevisionInput {
+    pub start_date: NaiveDate,
+    pub reference_quarter: i32,
+    pub reference_year: i32,
+    pub current_rent: BigDecimal,
+    pub dpe_class: Option<String>,
+    pub is_dom_tom: bool,
+    /// Effective date of the last IRL revision recorded for the lease.
+    pub last_revision_date: Option<NaiveDate>,
+    /// Date the landlord asks for the revision.
+    pub requested_on: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionProposal {
+    /// Anniversary the revision belongs to.
+    pub anniversary_date: NaiveDate,
+    /// Date from which the revised rent applies.
+    pub effective_date: NaiveDate,
+    pub previous_index: IrlIndex,
+    pub new_index: IrlIndex,
+    pub current_rent: BigDecimal,
+    pub proposed_rent: BigDecimal,
+    /// Requested after the anniversary: applies from the request, not retroactively.
+    pub late: bool,
+    /// Earlier anniversaries never revised; those revisions are lost.
+    pub forfeited_revisions: i32,
+    /// DPE F/G freeze: the rent cannot be revised.
+    pub frozen: bool,
+    pub freeze_reason: Option<String>,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RevisionError {
+    #[error("The lease has not reached its first anniversary")]
+    NoAnniversaryYet,
+
+    #[error("Rent already revised for the anniversary of {0}")]
+    AlreadyRevised(NaiveDate),
+
+    #[error("IRL value for T{quarter} {year} is not available")]
+    MissingIndex { year: i32, quarter: i32 },
+}
+
+/// Propose the annual IRL revision (loi du 6 juillet 1989, art. 17-1).
+///
+/// The revision belongs to the latest anniversary on or before the request.
+/// The new rent is the rent in force times the ratio of the lease's reference
+/// quarter index to the same quarter a year earlier. A landlord has one year
+/// after an anniversary to claim its revision, and a late claim only applies
+/// from the request date, so anniversaries skipped earlier are forfeited and
+/// never compounded.
+pub fn propose_revision(
+    input: &RevisionInput,
+    index_for: impl Fn(i32, i32) -> Option<BigDecimal>,
+) -> Result<RevisionProposal, RevisionError> {
+    let years = anniversaries_between(input.start_date, input.requested_on);
+    if years == 0 {
+        return Err(RevisionError::NoAnniversaryYet);
+    }
+    let anniversary_date = anniversary(input.start_date, years);
+
+    if let Some(last) = input.last_revision_date {
+        if last >= anniversary_date {
+            return Err(RevisionError::AlreadyRevised(anniversary_date));
+        }
+    }
+
+    let last_revised_year = input
+        .last_revision_date
+        .map(|last| anniversaries_between(input.start_date, last))
+        .unwrap_or(0);
+    let forfeited_revisions = (years - last_revised_year - 1).max(0);
+
+    let new_year = input.reference_year + years;
+    let previous_year = new_year - 1;
+    let lookup = |year: i32| {
+        index_for(year, input.reference_quarter)
+            .map(|value| IrlIndex { year, quarter: input.reference_quarter, value })
+            .ok_or(RevisionError::MissingIndex { year, quarter: input.reference_quarter })
+    };
+    let new_index = lookup(new_year)?;
+    let previous_index = lookup(previous_year)?;
+
+    let late = input.requested_on > anniversary_date;
+    let effective_date = if late { input.requested_on } else { anniversary_date };
+
+    let frozen = dpe_freeze_applies(input.dpe_class.as_deref(), input.is_dom_tom, effective_date);
+    let proposed_rent = if frozen {
+        input.current_rent.clone()
+    } else {
+        (&input.current_rent * &new_index.value / &previous_index.value)
+            .with_scale_round(2, RoundingMode::HalfUp)
+    };
+
+    Ok(RevisionProposal {
+        anniversary_date,
+        effective_date,
+        previous_index,
+        new_index,
+        current_rent: input.current_rent.clone(),
+        proposed_rent,
+        late,
+        forfeited_revisions,
+        frozen,
+        freeze_reason: frozen.then(|| {
+            "Logement classé F ou G au DPE : le loyer ne peut pas être révisé (loi n°2021-1104, art. 159)".to_string()
+        }),
+    })
+}
+
+/// Why raising the rent from `current_rent` to `new_rent` during the lease
+/// breaks art. 17-1, given the revision open on that date or the reason none
+/// is. A lower or unchanged rent is always allowed; a higher one only up to
+/// the IRL-revised rent, and not at all under the DPE freeze.
+pub fn mid_lease_increase_error(
+    current_rent: &BigDecimal,
+    new_rent: &BigDecimal,
+    revision: Result<&RevisionProposal, &str>,
+) -> Option<String> {
+    if new_rent <= current_rent {
+        return None;
+    }
+    match revision {
+        Err(reason) => Some(format!(
+            "The rent can only rise during the lease through the annual IRL revision: {}",
+            reason
+        )),
+        Ok(proposal) if proposal.frozen => proposal.freeze_reason.clone(),
+        Ok(proposal) if new_rent > &proposal.proposed_rent => Some(format!(
+            "The new rent exceeds the IRL-revised rent of {} €",
+            proposal.proposed_rent
+        )),
+        Ok(_) => None,
+    }
+}
+
+/// Next anniversary of `start` on or after `on`: the date from which the next
+/// revision can be claimed.
+pub fn next_revision_date(start: NaiveDate, on: NaiveDate) -> NaiveDate {
+    let reached = on.pred_opt().map_or(0, |day| anniversaries_between(start, day));
+    anniversary(start, reached + 1)
+}
+
+fn anniversary(start: NaiveDate, years: i32) -> NaiveDate {
+    // 29 February leases fall back to 28 February in common years.
+    start
+        .checked_add_months(Months::new(12 * years as u32))
+        .unwrap_or(start)
+}
+
+/// Number of anniversaries of `start` reached on or before `on`.
+fn anniversaries_between(start: NaiveDate, on: NaiveDate) -> i32 {
+    if on < start {
+        return 0;
+    }
+    let mut years = on.year() - start.year();
+    if years > 0 && anniversary(start, years) > on {
+        years -= 1;
+    }
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn dec(s: &str) -> BigDecimal {
+        BigDecimal::from_str(s).unwrap()
+    }
+
+    fn table() -> Vec<IrlIndex> {
+        parse_irl_csv("year,quarter,value\n2023,3,141.03\n2024,3,144.51\n2025,3,145.77\n").unwrap()
+    }
+
+    fn lookup(year: i32, quarter: i32) -> Option<BigDecimal> {
+        table()
+            .into_iter()
+            .find(|i| i.year == year && i.quarter == quarter)
+            .map(|i| i.value)
+    }
+
+    fn input(requested_on: NaiveDate) -> RevisionInput {
+        RevisionInput {
+            start_date: d(2024, 1, 15),
+            reference_quarter: 3,
+            reference_year: 2023,
+            current_rent: dec("800.00"),
+            dpe_class: Some("D".to_string()),
+            is_dom_tom: false,
+            last_revision_date: None,
+            requested_on,
+        }
+    }
+
+    #[test]
+    fn parses_csv_with_header_semicolons_and_decimal_commas() {
+        let indices = parse_irl_csv("Année;Trimestre;IRL\n2024;T2;145,17\n").unwrap();
+        assert_eq!(indices, vec![IrlIndex { year: 2024, quarter: 2, value: dec("145.17") }]);
+        assert!(parse_irl_csv("2024,5,145.17").is_err());
+    }
+
+    #[test]
+    fn bundled_data_file_has_every_quarter() {
+        let indices = load_irl_file(Path::new("data/irl.csv")).unwrap();
+        for pair in indices.windows(2) {
+            let next = if pair[0].quarter == 4 { (pair[0].year + 1, 1) } else { (pair[0].year, pair[0].quarter + 1) };
+            assert_eq!((pair[1].year, pair[1].quarter), next);
+        }
+        let latest = indices.last().unwrap();
+        assert_eq!((latest.year, latest.quarter, latest.value.clone()), (2025, 3, dec("145.77")));
+    }
+
+    #[test]
+    fn parses_json_array() {
+        let indices = parse_irl_json(r#"[{"year": 2024, "quarter": 3, "value": "144.51"}]"#).unwrap();
+        assert_eq!(indices[0].value, dec("144.51"));
+        assert!(parse_irl_json(r#"[{"year": 2024, "quarter": 0, "value": "1"}]"#).is_err());
+    }
+
+    #[test]
+    fn revises_on_anniversary_with_reference_quarter() {
+        let proposal = propose_revision(&input(d(2025, 1, 15)), lookup).unwrap();
+        assert_eq!(proposal.anniversary_date, d(2025, 1, 15));
+        assert_eq!(proposal.effective_date, d(2025, 1, 15));
+        assert_eq!(proposal.previous_index.year, 2023);
+        assert_eq!(proposal.new_index.year, 2024);
+        // 800 × 144.51 / 141.03
+        assert_eq!(proposal.proposed_rent, dec("819.74"));
+        assert!(!proposal.late);
+    }
+
+    #[test]
+    fn late_request_applies_from_request_date_without_catch_up() {
+        let proposal = propose_revision(&input(d(2026, 3, 1)), lookup).unwrap();
+        assert_eq!(proposal.anniversary_date, d(2026, 1, 15));
+        assert_eq!(proposal.effective_date, d(2026, 3, 1));
+        assert!(proposal.late);
+        // The 2025 revision was never claimed: only one year of variation.
+        assert_eq!(proposal.forfeited_revisions, 1);
+        assert_eq!(proposal.new_index.year, 2025);
+        assert_eq!(proposal.previous_index.year, 2024);
+    }
+
+    #[test]
+    fn next_revision_date_is_the_coming_anniversary() {
+        assert_eq!(next_revision_date(d(2024, 1, 15), d(2024, 3, 1)), d(2025, 1, 15));
+        assert_eq!(next_revision_date(d(2024, 1, 15), d(2025, 1, 15)), d(2025, 1, 15));
+        assert_eq!(next_revision_date(d(2024, 1, 15), d(2025, 1, 16)), d(2026, 1, 15));
+    }
+
+    #[test]
+    fn dpe_g_housing_rent_is_frozen() {
+        let mut frozen = input(d(2025, 1, 15));
+        frozen.dpe_class = Some("G".to_string());
+        let proposal = propose_revision(&frozen, lookup).unwrap();
+        assert!(proposal.frozen);
+        assert_eq!(proposal.proposed_rent, dec("800.00"));
+        assert!(proposal.freeze_reason.is_some());
+    }
+
+    #[test]
+    fn freeze_applies_later_overseas() {
+        assert!(dpe_freeze_applies(Some("F"), false, d(2023, 1, 1)));
+        assert!(!dpe_freeze_applies(Some("F"), true, d(2023, 1, 1)));
+        assert!(dpe_freeze_applies(Some("F"), true, d(2024, 7, 1)));
+        assert!(!dpe_freeze_applies(Some("E"), false, d(2025, 1, 1)));
+    }
+
+    #[test]
+    fn rejects_before_anniversary_repeat_and_missing_index() {
+        assert_eq!(
+            propose_revision(&input(d(2024, 12, 31)), lookup).unwrap_err(),
+            RevisionError::NoAnniversaryYet
+        );
+
+        let mut revised = input(d(2025, 6, 1));
+        revised.last_revision_date = Some(d(2025, 1, 15));
+        assert_eq!(
+            propose_revision(&revised, lookup).unwrap_err(),
+            RevisionError::AlreadyRevised(d(2025, 1, 15))
+        );
+
+        assert_eq!(
+            propose_revision(&input(d(2027, 1, 15)), lookup).unwrap_err(),
+            RevisionError::MissingIndex { year: 2026, quarter: 3 }
+        );
+    }
+
+    #[test]
+    fn mid_lease_increase_is_capped_by_the_irl_revision() {
+        let proposal = propose_revision(&input(d(2025, 1, 15)), lookup).unwrap();
+        let current = dec("800.00");
+
+        assert_eq!(mid_lease_increase_error(&current, &dec("750.00"), Err("no clause")), None);
+        assert_eq!(mid_lease_increase_error(&current, &proposal.proposed_rent, Ok(&proposal)), None);
+        assert!(mid_lease_increase_error(&current, &dec("850.00"), Ok(&proposal)).is_some());
+        assert!(mid_lease_increase_error(&current, &dec("810.00"), Err("no anniversary yet")).is_some());
+
+        let mut frozen = input(d(2025, 1, 15));
+        frozen.dpe_class = Some("G".to_string());
+        let frozen = propose_revision(&frozen, lookup).unwrap();
+        assert!(mid_lease_increase_error(&current, &dec("801.00"), Ok(&frozen)).is_some());
+    }
+}
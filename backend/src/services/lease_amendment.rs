@@ -0,0 +1,292 @@
+use serde_json::{Map, Value};
+
+use crate::error::AppError;
+use crate::models::canonical_snapshot::{CanonicalSnapshot, SnapshotChange};
+use crate::models::lease_amendment::AmendmentChangeLine;
+use crate::services::snapshot_diff::diff;
+
+/// Snapshot sections an avenant may change. Identity, template version and
+/// compliance metadata belong to the signed contract and stay as signed.
+pub const AMENDABLE_SECTIONS: [&str; 9] = [
+    "parties",
+    "property",
+    "lease_terms",
+    "financial_terms",
+    "professional_mandate",
+    "works",
+    "diagnostics",
+    "previous_tenancy",
+    "lease_sections",
+];
+
+/// Contract fields the lease itself keeps and that drive billing, renewal and
+/// the compliance regime. They follow the signed lease and are never changed
+/// by an avenant; a new term or regime needs a new lease.
+pub const FIXED_TERM_FIELDS: [&str; 7] = [
+    "lease_terms.lease_kind",
+    "lease_terms.lease_type",
+    "lease_terms.start_date",
+    "lease_terms.duration_months",
+    "lease_terms.end_date",
+    "financial_terms.rent_payment_frequency",
+    "financial_terms.rent_payment_timing",
+];
+
+/// RFC 7386 JSON merge patch: objects merge key by key, `null` removes a key
+/// and any other value replaces the target wholesale (arrays included).
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let target = target.as_object_mut().expect("just made an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Set every changed field to its amended value.
+pub fn apply_changes(target: &mut Value, changes: &[SnapshotChange]) {
+    for change in changes {
+        let mut node = &mut *target;
+        for segment in change.path.split('.') {
+            if !node.is_object() {
+                *node = Value::Object(Map::new());
+            }
+            node = node
+                .as_object_mut()
+                .expect("just made an object")
+                .entry(segment.to_string())
+                .or_insert(Value::Null);
+        }
+        *node = change.after.clone();
+    }
+}
+
+fn to_value(snapshot: &CanonicalSnapshot) -> Result<Value, AppError> {
+    serde_json::to_value(snapshot).map_err(|e| {
+        tracing::error!("snapshot serialization failed for lease {}: {}", snapshot.lease_id, e);
+        AppError::Internal
+    })
+}
+
+/// Apply an avenant's merge patch to the contract as it currently stands and
+/// return the amended contract with the field-level changes.
+pub fn amend(
+    contract: &CanonicalSnapshot,
+    patch: &Value,
+) -> Result<(CanonicalSnapshot, Vec<SnapshotChange>), AppError> {
+    let Some(sections) = patch.as_object() else {
+        return Err(AppError::Validation("Amendment changes must be a JSON object".to_string()));
+    };
+    if let Some(section) = sections.keys().find(|key| !AMENDABLE_SECTIONS.contains(&key.as_str())) {
+        return Err(AppError::Validation(format!(
+            "Section '{}' of the contract cannot be amended",
+            section
+        )));
+    }
+
+    let before = to_value(contract)?;
+    let mut after = before.clone();
+    merge_patch(&mut after, patch);
+    let amended = serde_json::from_value::<CanonicalSnapshot>(after.clone()).map_err(|e| {
+        AppError::Validation(format!("Amendment changes do not fit the contract: {}", e))
+    })?;
+
+    let changes = diff(&before, &after);
+    if changes.is_empty() {
+        return Err(AppError::Validation("The amendment does not change the contract".to_string()));
+    }
+    if let Some(change) = changes.iter().find(|change| FIXED_TERM_FIELDS.contains(&change.path.as_str())) {
+        return Err(AppError::Validation(format!(
+            "'{}' follows the lease itself and cannot be changed by an amendment",
+            change.path
+        )));
+    }
+    Ok((amended, changes))
+}
+
+/// The contract in force: the signed snapshot with the given amendments
+/// applied in order.
+pub fn contract_view<'a>(
+    signed: &CanonicalSnapshot,
+    amendments: impl IntoIterator<Item = &'a [SnapshotChange]>,
+) -> Result<CanonicalSnapshot, AppError> {
+    let mut contract = to_value(signed)?;
+    for changes in amendments {
+        apply_changes(&mut contract, changes);
+    }
+    serde_json::from_value(contract).map_err(|e| {
+        tracing::error!("amended contract of lease {} is invalid: {}", signed.lease_id, e);
+        AppError::Internal
+    })
+}
+
+fn field_label(path: &str) -> String {
+    let label = match path {
+        "parties.lessees" => "Locataires",
+        "parties.lessee_full_name" => "Locataire principal",
+        "lease_terms.is_colocation" => "Colocation",
+        "lease_terms.tenant_count" => "Nombre de locataires",
+        "lease_terms.end_date" => "Date de fin du bail",
+        "lease_terms.duration_months" => "Durée du bail (mois)",
+        "financial_terms.monthly_rent" => "Loyer mensuel hors charges",
+        "financial_terms.charges_monthly" => "Charges mensuelles",
+        "financial_terms.deposit_amount" => "Dépôt de garantie",
+        "financial_terms.rent_payment_period" => "Période de paiement",
+        "property.privatifs_accessoires" => "Locaux et équipements accessoires à usage privatif",
+        "property.autres_parties" => "Autres parties du logement",
+        "property.elements_equipement" => "Éléments d'équipement du logement",
+        "lease_sections.section_x_custom.text" => "Clauses particulières",
+        _ => return path.to_string(),
+    };
+    label.to_string()
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "—".to_string(),
+        Value::Bool(true) => "Oui".to_string(),
+        Value::Bool(false) => "Non".to_string(),
+        Value::String(text) if text.is_empty() => "—".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        // Lessee lists read as the parties' names.
+        Value::Array(items) if items.iter().all(|item| item.get("full_name").is_some()) => items
+            .iter()
+            .filter_map(|item| item.get("full_name").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Human-readable lines for the avenant document.
+pub fn change_lines(changes: &[SnapshotChange]) -> Vec<AmendmentChangeLine> {
+    changes
+        .iter()
+        .map(|change| AmendmentChangeLine {
+            label: field_label(&change.path),
+            before: display_value(&change.before),
+            after: display_value(&change.after),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn signed_contract() -> CanonicalSnapshot {
+        let mut snapshot = CanonicalSnapshot::new(Uuid::new_v4(), "2026-06-18".to_string());
+        snapshot.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        snapshot.financial_terms.monthly_rent = "850.00".to_string();
+        snapshot.financial_terms.charges_monthly = "50.00".to_string();
+        snapshot
+    }
+
+    #[test]
+    fn merge_patch_replaces_removes_and_merges() {
+        let mut target = json!({"a": {"b": 1, "c": 2}, "d": [1, 2]});
+        merge_patch(&mut target, &json!({"a": {"b": 3, "c": null}, "d": [3]}));
+        assert_eq!(target, json!({"a": {"b": 3}, "d": [3]}));
+    }
+
+    #[test]
+    fn amend_records_field_level_changes() {
+        let contract = signed_contract();
+        let (amended, changes) = amend(
+            &contract,
+            &json!({"financial_terms": {"monthly_rent": "900.00"}}),
+        )
+        .unwrap();
+
+        assert_eq!(amended.financial_terms.monthly_rent, "900.00");
+        assert_eq!(
+            changes,
+            vec![SnapshotChange {
+                path: "financial_terms.monthly_rent".to_string(),
+                before: json!("850.00"),
+                after: json!("900.00"),
+            }]
+        );
+    }
+
+    #[test]
+    fn amend_rejects_signed_metadata_and_no_op_patches() {
+        let contract = signed_contract();
+        assert!(amend(&contract, &json!({"legal_template_version": "x"})).is_err());
+        assert!(amend(&contract, &json!({"compliance": {"compliance_status": "compliant"}})).is_err());
+        assert!(amend(&contract, &json!({"financial_terms": {"monthly_rent": "850.00"}})).is_err());
+        assert!(amend(&contract, &json!(["financial_terms"])).is_err());
+    }
+
+    #[test]
+    fn amend_rejects_changes_that_break_the_contract_shape() {
+        let contract = signed_contract();
+        assert!(amend(&contract, &json!({"lease_terms": {"start_date": "not a date"}})).is_err());
+    }
+
+    #[test]
+    fn amend_rejects_term_fields_the_lease_keeps() {
+        let contract = signed_contract();
+        assert!(amend(&contract, &json!({"lease_terms": {"duration_months": 48}})).is_err());
+        assert!(amend(&contract, &json!({"lease_terms": {"end_date": "2031-12-31"}})).is_err());
+        assert!(amend(&contract, &json!({"financial_terms": {"rent_payment_timing": "a_terme_echu"}})).is_err());
+        assert!(amend(&contract, &json!({"lease_terms": {"is_colocation": true}})).is_ok());
+    }
+
+    #[test]
+    fn contract_view_replays_amendments_in_order() {
+        let signed = signed_contract();
+        let (first, first_changes) =
+            amend(&signed, &json!({"financial_terms": {"monthly_rent": "900.00"}})).unwrap();
+        let (_, second_changes) = amend(
+            &first,
+            &json!({
+                "financial_terms": {"charges_monthly": "60.00"},
+                "property": {"privatifs_accessoires": "Place de parking n°12"}
+            }),
+        )
+        .unwrap();
+
+        let view = contract_view(&signed, [first_changes.as_slice(), second_changes.as_slice()]).unwrap();
+        assert_eq!(view.financial_terms.monthly_rent, "900.00");
+        assert_eq!(view.financial_terms.charges_monthly, "60.00");
+        assert_eq!(view.property.privatifs_accessoires.as_deref(), Some("Place de parking n°12"));
+        assert_eq!(view.id, signed.id);
+
+        let unamended = contract_view(&signed, std::iter::empty()).unwrap();
+        assert_eq!(unamended.financial_terms.monthly_rent, "850.00");
+    }
+
+    #[test]
+    fn change_lines_read_as_french_labels_and_party_names() {
+        let lines = change_lines(&[
+            SnapshotChange {
+                path: "parties.lessees".to_string(),
+                before: json!([{"full_name": "Jean Dupont"}]),
+                after: json!([{"full_name": "Jean Dupont"}, {"full_name": "Marie Curie"}]),
+            },
+            SnapshotChange {
+                path: "lease_terms.is_colocation".to_string(),
+                before: json!(false),
+                after: json!(true),
+            },
+        ]);
+        assert_eq!(lines[0].label, "Locataires");
+        assert_eq!(lines[0].after, "Jean Dupont, Marie Curie");
+        assert_eq!(lines[1].before, "Non");
+        assert_eq!(lines[1].after, "Oui");
+    }
+}
@@ -0,0 +1,93 @@
+/// Lease lifecycle, in order. `terminated` and `archived` are end states.
+pub const LEASE_STATUSES: [&str; 7] = [
+    "draft",
+    "ready_for_signature",
+    "signed",
+    "active",
+    "notice_given",
+    "terminated",
+    "archived",
+];
+
+/// Statuses during which rent is due and receipts or rent calls may be issued.
+pub const BILLABLE_STATUSES: [&str; 2] = ["active", "notice_given"];
+
+pub fn is_known_status(status: &str) -> bool {
+    LEASE_STATUSES.contains(&status)
+}
+
+/// Transitions accepted by the server. A lease ready for signature can go
+/// back to draft for corrections; once signed it only moves forward.
+pub fn can_transition(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        ("draft", "ready_for_signature")
+            | ("ready_for_signature", "draft")
+            | ("ready_for_signature", "signed")
+            | ("signed", "active")
+            | ("signed", "terminated")
+            | ("active", "notice_given")
+            | ("active", "terminated")
+            | ("notice_given", "active")
+            | ("notice_given", "terminated")
+            | ("terminated", "archived")
+    )
+}
+
+/// From signature onwards the canonical snapshot is the contract the parties
+/// agreed to: the lease terms can no longer be edited in place.
+pub fn is_snapshot_frozen(status: &str) -> bool {
+    !matches!(status, "draft" | "ready_for_signature")
+}
+
+pub fn is_billable(status: &str) -> bool {
+    BILLABLE_STATUSES.contains(&status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_the_signature_path() {
+        let path = ["draft", "ready_for_signature", "signed", "active", "notice_given", "terminated", "archived"];
+        for pair in path.windows(2) {
+            assert!(can_transition(pair[0], pair[1]), "{} -> {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn rejects_skipping_signature_or_going_back_after_it() {
+        assert!(!can_transition("draft", "signed"));
+        assert!(!can_transition("draft", "active"));
+        assert!(!can_transition("signed", "draft"));
+        assert!(!can_transition("active", "ready_for_signature"));
+        assert!(!can_transition("terminated", "active"));
+        assert!(!can_transition("archived", "terminated"));
+        assert!(!can_transition("active", "active"));
+    }
+
+    #[test]
+    fn allows_withdrawing_a_notice_and_correcting_before_signature() {
+        assert!(can_transition("notice_given", "active"));
+        assert!(can_transition("ready_for_signature", "draft"));
+    }
+
+    #[test]
+    fn freezes_snapshot_from_signature() {
+        assert!(!is_snapshot_frozen("draft"));
+        assert!(!is_snapshot_frozen("ready_for_signature"));
+        for status in ["signed", "active", "notice_given", "terminated", "archived"] {
+            assert!(is_snapshot_frozen(status), "{}", status);
+        }
+    }
+
+    #[test]
+    fn bills_only_active_and_notice_periods() {
+        assert!(is_billable("active"));
+        assert!(is_billable("notice_given"));
+        for status in ["draft", "ready_for_signature", "signed", "terminated", "archived"] {
+            assert!(!is_billable(status), "{}", status);
+        }
+    }
+}
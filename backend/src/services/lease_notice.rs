@@ -0,0 +1,365 @@
+use chrono::{Months, NaiveDate};
+
+use crate::error::AppError;
+use crate::models::lease_notice::{
+    landlord_notice_ground_label, reduced_notice_reason_label, CreateLeaseNotice,
+};
+
+/// Lease facts the notice rules depend on.
+pub struct NoticeLease<'a> {
+    pub lease_type: &'a str,
+    pub lease_kind: &'a str,
+    pub start_date: NaiveDate,
+    pub duration_months: i32,
+    pub landlord_is_legal_person: bool,
+    /// The property lies in a zone tendue.
+    pub zone_tendue: bool,
+    /// Classification the owner set by hand, if any.
+    pub zone_tendue_declared: Option<bool>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NoticeTerms {
+    pub notice_months: i32,
+    pub effective_date: NaiveDate,
+}
+
+/// Tenant's notice: one month for a furnished lease (art. 25-8), three for an
+/// unfurnished one unless one of the art. 15-I situations applies.
+pub fn tenant_notice_months(lease_type: &str, reduced_notice_reason: Option<&str>) -> i32 {
+    match (lease_type, reduced_notice_reason) {
+        ("unfurnished", None) => 3,
+        _ => 1,
+    }
+}
+
+/// Landlord's notice before the term: six months for an unfurnished lease,
+/// three for a furnished one; the code civil lease states one month.
+pub fn landlord_notice_months(lease_type: &str) -> i32 {
+    match lease_type {
+        "unfurnished" => 6,
+        "ancillary" => 1,
+        _ => 3,
+    }
+}
+
+/// Length of each tacit renewal (loi du 6 juillet 1989): three years for an
+/// unfurnished lease, six when the landlord is a legal person other than a
+/// family SCI (art. 10), one year for a furnished lease (art. 25-7).
+pub fn renewal_months(lease_type: &str, landlord_is_legal_person: bool) -> i32 {
+    match (lease_type, landlord_is_legal_person) {
+        ("unfurnished", true) => 72,
+        ("unfurnished", false) => 36,
+        _ => 12,
+    }
+}
+
+/// End of the term running on `on`: the first term ends `duration_months`
+/// after the start date and each renewal `renewal_months` after the previous
+/// end; returns the first of those ends that falls after `on`.
+pub fn term_end_after(
+    start_date: NaiveDate,
+    duration_months: i32,
+    renewal_months: i32,
+    on: NaiveDate,
+) -> Option<NaiveDate> {
+    let first_term = u32::try_from(duration_months).ok().filter(|months| *months > 0)?;
+    let renewal = u32::try_from(renewal_months).ok().filter(|months| *months > 0)?;
+    let mut renewals = 0;
+    loop {
+        // Counted from the start date so month-end starts do not drift.
+        let end = start_date.checked_add_months(Months::new(first_term + renewal * renewals))?;
+        if end > on {
+            return Some(end);
+        }
+        renewals += 1;
+    }
+}
+
+fn is_blank(value: Option<&str>) -> bool {
+    value.is_none_or(|v| v.trim().is_empty())
+}
+
+fn add_months(date: NaiveDate, months: i32) -> Result<NaiveDate, AppError> {
+    date.checked_add_months(Months::new(months as u32))
+        .ok_or_else(|| AppError::Validation("Notice date is out of range".to_string()))
+}
+
+/// Reason reducing a tenant's notice: the one the tenant gives, or the zone
+/// tendue when the property's classification puts it in one. The owner's
+/// declaration wins over the list; a zone tendue reason is refused for a
+/// property classified outside them, so an owner whose commune is missing
+/// from the list declares it in a zone tendue.
+pub fn tenant_reduced_notice_reason<'a>(
+    lease: &NoticeLease,
+    requested: Option<&'a str>,
+) -> Result<Option<&'a str>, AppError> {
+    let zone_tendue = lease.zone_tendue_declared.unwrap_or(lease.zone_tendue);
+    match requested {
+        Some(reason) if reduced_notice_reason_label(reason).is_none() => {
+            Err(AppError::Validation(format!("Unknown reduced notice reason '{}'", reason)))
+        }
+        Some("zone_tendue") if !zone_tendue => Err(AppError::Validation(
+            "The property is not in a zone tendue; the notice cannot be reduced on that ground".to_string(),
+        )),
+        None if zone_tendue => Ok(Some("zone_tendue")),
+        reason => Ok(reason),
+    }
+}
+
+/// Validate a notice against the lease and compute its notice period and the
+/// date the lease ends.
+pub fn plan_notice(lease: &NoticeLease, notice: &CreateLeaseNotice) -> Result<NoticeTerms, AppError> {
+    if notice.served_on < lease.start_date {
+        return Err(AppError::Validation(
+            "A notice cannot be served before the lease start date".to_string(),
+        ));
+    }
+
+    match notice.given_by.as_str() {
+        "tenant" => {
+            let reason = tenant_reduced_notice_reason(lease, notice.reduced_notice_reason.as_deref())?;
+            let notice_months = tenant_notice_months(lease.lease_type, reason);
+            Ok(NoticeTerms {
+                notice_months,
+                effective_date: add_months(notice.served_on, notice_months)?,
+            })
+        }
+        "landlord" => plan_landlord_notice(lease, notice),
+        other => Err(AppError::Validation(format!(
+            "A notice is given by the tenant or the landlord, not '{}'",
+            other
+        ))),
+    }
+}
+
+fn plan_landlord_notice(lease: &NoticeLease, notice: &CreateLeaseNotice) -> Result<NoticeTerms, AppError> {
+    if matches!(lease.lease_kind, "student" | "mobility") {
+        return Err(AppError::Validation(
+            "This lease ends at its term without renewal; the landlord gives no notice".to_string(),
+        ));
+    }
+
+    let notice_months = landlord_notice_months(lease.lease_type);
+    // The code civil lease runs for an indefinite term after its first one.
+    if lease.lease_type == "ancillary" {
+        return Ok(NoticeTerms {
+            notice_months,
+            effective_date: add_months(notice.served_on, notice_months)?,
+        });
+    }
+
+    let ground = notice.ground.as_deref().unwrap_or_default();
+    if landlord_notice_ground_label(ground).is_none() {
+        return Err(AppError::Validation(
+            "A landlord notice must state its ground: vente, reprise or motif_legitime".to_string(),
+        ));
+    }
+    match ground {
+        "reprise" => {
+            if lease.landlord_is_legal_person {
+                return Err(AppError::Validation(
+                    "Only a natural person or a family SCI can give notice to take the dwelling back".to_string(),
+                ));
+            }
+            if is_blank(notice.beneficiary_name.as_deref()) || is_blank(notice.beneficiary_relationship.as_deref()) {
+                return Err(AppError::Validation(
+                    "A notice for reprise must name the beneficiary and their relationship to the landlord".to_string(),
+                ));
+            }
+        }
+        "vente" => {
+            // The tenant of an unfurnished dwelling has a right of first refusal:
+            // the notice is the offer of sale and states its price.
+            let priced = notice.sale_price.as_ref().is_some_and(|price| price > &bigdecimal::BigDecimal::from(0));
+            if lease.lease_type == "unfurnished" && !priced {
+                return Err(AppError::Validation(
+                    "A notice for sale of an unfurnished dwelling must state the sale price".to_string(),
+                ));
+            }
+        }
+        _ => {
+            if is_blank(notice.details.as_deref()) {
+                return Err(AppError::Validation(
+                    "A notice for a legitimate and serious reason must state that reason".to_string(),
+                ));
+            }
+        }
+    }
+
+    let renewal = renewal_months(lease.lease_type, lease.landlord_is_legal_person);
+    let term_end = term_end_after(lease.start_date, lease.duration_months, renewal, notice.served_on)
+        .ok_or_else(|| AppError::Validation("The lease term cannot be computed".to_string()))?;
+    let latest = term_end
+        .checked_sub_months(Months::new(notice_months as u32))
+        .ok_or_else(|| AppError::Validation("Notice date is out of range".to_string()))?;
+    if notice.served_on > latest {
+        let next_term_end = term_end_after(lease.start_date, lease.duration_months, renewal, term_end)
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+        return Err(AppError::Validation(format!(
+            "A landlord notice must be received at least {} months before the term ending on {} (by {}); it can now only take effect on {}",
+            notice_months, term_end, latest, next_term_end
+        )));
+    }
+
+    Ok(NoticeTerms {
+        notice_months,
+        effective_date: term_end,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn lease(lease_type: &'static str, duration_months: i32) -> NoticeLease<'static> {
+        NoticeLease {
+            lease_type,
+            lease_kind: "standard",
+            start_date: d(2026, 1, 1),
+            duration_months,
+            landlord_is_legal_person: false,
+            zone_tendue: false,
+            zone_tendue_declared: None,
+        }
+    }
+
+    fn notice(given_by: &str, served_on: NaiveDate) -> CreateLeaseNotice {
+        CreateLeaseNotice {
+            given_by: given_by.to_string(),
+            served_on,
+            ground: None,
+            reduced_notice_reason: None,
+            beneficiary_name: None,
+            beneficiary_relationship: None,
+            sale_price: None,
+            details: None,
+        }
+    }
+
+    #[test]
+    fn tenant_notice_periods_follow_lease_type() {
+        let served = d(2026, 3, 15);
+        let furnished = plan_notice(&lease("furnished", 12), &notice("tenant", served)).unwrap();
+        assert_eq!(furnished, NoticeTerms { notice_months: 1, effective_date: d(2026, 4, 15) });
+
+        let unfurnished = plan_notice(&lease("unfurnished", 36), &notice("tenant", served)).unwrap();
+        assert_eq!(unfurnished, NoticeTerms { notice_months: 3, effective_date: d(2026, 6, 15) });
+
+        let mut reduced = notice("tenant", served);
+        reduced.reduced_notice_reason = Some("mutation".to_string());
+        let reduced = plan_notice(&lease("unfurnished", 36), &reduced).unwrap();
+        assert_eq!(reduced.notice_months, 1);
+    }
+
+    #[test]
+    fn zone_tendue_reduction_follows_the_property_classification() {
+        let served = d(2026, 3, 15);
+        let tendue = NoticeLease { zone_tendue: true, ..lease("unfurnished", 36) };
+        assert_eq!(plan_notice(&tendue, &notice("tenant", served)).unwrap().notice_months, 1);
+        assert_eq!(tenant_reduced_notice_reason(&tendue, None).unwrap(), Some("zone_tendue"));
+
+        // Unlisted and undeclared: the ground is refused.
+        let mut claimed = notice("tenant", served);
+        claimed.reduced_notice_reason = Some("zone_tendue".to_string());
+        assert!(plan_notice(&lease("unfurnished", 36), &claimed).is_err());
+
+        // The owner's declaration wins over the list either way.
+        let declared_inside = NoticeLease { zone_tendue_declared: Some(true), ..lease("unfurnished", 36) };
+        assert_eq!(plan_notice(&declared_inside, &claimed).unwrap().notice_months, 1);
+        assert_eq!(tenant_reduced_notice_reason(&declared_inside, None).unwrap(), Some("zone_tendue"));
+
+        let declared_outside = NoticeLease { zone_tendue: true, zone_tendue_declared: Some(false), ..lease("unfurnished", 36) };
+        assert!(plan_notice(&declared_outside, &claimed).is_err());
+        assert_eq!(tenant_reduced_notice_reason(&declared_outside, None).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_unknown_reduction_and_early_notice() {
+        let mut unknown = notice("tenant", d(2026, 3, 15));
+        unknown.reduced_notice_reason = Some("convenance".to_string());
+        assert!(plan_notice(&lease("unfurnished", 36), &unknown).is_err());
+        assert!(plan_notice(&lease("furnished", 12), &notice("tenant", d(2025, 12, 1))).is_err());
+        assert!(plan_notice(&lease("furnished", 12), &notice("agency", d(2026, 3, 1))).is_err());
+    }
+
+    #[test]
+    fn term_end_counts_renewed_terms() {
+        assert_eq!(term_end_after(d(2026, 1, 1), 12, 12, d(2026, 5, 1)), Some(d(2027, 1, 1)));
+        assert_eq!(term_end_after(d(2026, 1, 1), 12, 12, d(2027, 1, 1)), Some(d(2028, 1, 1)));
+        assert_eq!(term_end_after(d(2026, 1, 1), 36, 36, d(2030, 6, 1)), Some(d(2032, 1, 1)));
+        assert_eq!(term_end_after(d(2026, 1, 1), 0, 12, d(2026, 6, 1)), None);
+    }
+
+    #[test]
+    fn renewals_follow_the_statutory_length_not_the_first_term() {
+        // Furnished lease signed for two years renews one year at a time.
+        let furnished = renewal_months("furnished", false);
+        assert_eq!(term_end_after(d(2026, 1, 1), 24, furnished, d(2027, 6, 1)), Some(d(2028, 1, 1)));
+        assert_eq!(term_end_after(d(2026, 1, 1), 24, furnished, d(2028, 1, 1)), Some(d(2029, 1, 1)));
+
+        // Unfurnished lease of a legal person: six years, renewed for six.
+        let company = renewal_months("unfurnished", true);
+        assert_eq!(term_end_after(d(2026, 1, 1), 72, company, d(2032, 1, 1)), Some(d(2038, 1, 1)));
+
+        // A natural person's unfurnished lease renews for three years.
+        let private = renewal_months("unfurnished", false);
+        assert_eq!(term_end_after(d(2026, 1, 1), 36, private, d(2029, 1, 1)), Some(d(2032, 1, 1)));
+    }
+
+    #[test]
+    fn landlord_notice_takes_effect_at_term_end_when_served_in_time() {
+        let mut sale = notice("landlord", d(2026, 9, 30));
+        sale.ground = Some("vente".to_string());
+        let terms = plan_notice(&lease("furnished", 12), &sale).unwrap();
+        assert_eq!(terms, NoticeTerms { notice_months: 3, effective_date: d(2027, 1, 1) });
+
+        // Less than three months before the anniversary.
+        sale.served_on = d(2026, 10, 15);
+        let err = plan_notice(&lease("furnished", 12), &sale).unwrap_err();
+        assert!(err.to_string().contains("2028-01-01"));
+    }
+
+    #[test]
+    fn landlord_grounds_are_validated() {
+        let unfurnished = lease("unfurnished", 36);
+        let served = d(2028, 3, 1);
+
+        assert!(plan_notice(&unfurnished, &notice("landlord", served)).is_err());
+
+        let mut sale = notice("landlord", served);
+        sale.ground = Some("vente".to_string());
+        assert!(plan_notice(&unfurnished, &sale).is_err());
+        sale.sale_price = Some(BigDecimal::from(250_000));
+        assert_eq!(plan_notice(&unfurnished, &sale).unwrap().effective_date, d(2029, 1, 1));
+
+        let mut reprise = notice("landlord", served);
+        reprise.ground = Some("reprise".to_string());
+        assert!(plan_notice(&unfurnished, &reprise).is_err());
+        reprise.beneficiary_name = Some("Claire Martin".to_string());
+        reprise.beneficiary_relationship = Some("Fille du bailleur".to_string());
+        assert!(plan_notice(&unfurnished, &reprise).is_ok());
+        let company = NoticeLease { landlord_is_legal_person: true, ..lease("unfurnished", 72) };
+        assert!(plan_notice(&company, &reprise).is_err());
+
+        let mut legitimate = notice("landlord", served);
+        legitimate.ground = Some("motif_legitime".to_string());
+        assert!(plan_notice(&unfurnished, &legitimate).is_err());
+        legitimate.details = Some("Retards de paiement répétés".to_string());
+        assert!(plan_notice(&unfurnished, &legitimate).is_ok());
+    }
+
+    #[test]
+    fn landlord_cannot_give_notice_on_non_renewable_leases() {
+        let mobility = NoticeLease { lease_kind: "mobility", ..lease("furnished", 6) };
+        let mut sale = notice("landlord", d(2026, 2, 1));
+        sale.ground = Some("vente".to_string());
+        assert!(plan_notice(&mobility, &sale).is_err());
+    }
+}
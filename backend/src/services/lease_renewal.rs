@@ -0,0 +1,368 @@
+use chrono::{Months, NaiveDate};
+use quittance_compliance::rules::dpe_expiry;
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::lease::Lease;
+use crate::models::lease_deadline::{LeaseDeadline, LeaseDeadlines};
+use crate::services::irl::{dpe_freeze_applies, next_revision_date};
+use crate::services::lease_notice::{landlord_notice_months, renewal_months, term_end_after};
+
+/// The electricity and gas diagnostics are valid six years when the dwelling
+/// is let.
+pub const INSTALLATION_DIAGNOSTIC_VALIDITY_YEARS: u32 = 6;
+
+/// Lease facts the term and deadline rules depend on.
+pub struct TermFacts<'a> {
+    pub lease_kind: &'a str,
+    pub lease_type: &'a str,
+    pub status: &'a str,
+    pub start_date: NaiveDate,
+    pub duration_months: i32,
+    pub end_date: Option<NaiveDate>,
+    /// Owned by an organization other than a family SCI: unfurnished
+    /// renewals then last six years.
+    pub landlord_is_legal_person: bool,
+    /// Effective date of the running notice, if any.
+    pub notice_effective_date: Option<NaiveDate>,
+    pub rent_revision: bool,
+    pub dpe_class: Option<&'a str>,
+    pub is_dom_tom: bool,
+    pub dpe_date: Option<NaiveDate>,
+    /// Only set when the installation is over 15 years old and the
+    /// diagnostic is required.
+    pub electrical_diagnostic_date: Option<NaiveDate>,
+    pub gas_diagnostic_date: Option<NaiveDate>,
+}
+
+impl<'a> TermFacts<'a> {
+    pub fn from_lease(
+        lease: &'a Lease,
+        landlord_is_legal_person: bool,
+        notice_effective_date: Option<NaiveDate>,
+    ) -> Self {
+        TermFacts {
+            lease_kind: &lease.lease_kind,
+            lease_type: &lease.lease_type,
+            status: &lease.status,
+            start_date: lease.start_date,
+            duration_months: lease.duration_months,
+            end_date: lease.end_date,
+            landlord_is_legal_person,
+            notice_effective_date,
+            rent_revision: lease.rent_revision,
+            dpe_class: lease.dpe_class.as_deref(),
+            is_dom_tom: lease.is_dom_tom,
+            dpe_date: lease.dpe_date,
+            electrical_diagnostic_date: lease
+                .electrical_diagnostic_date
+                .filter(|_| lease.electrical_installation_over_15y),
+            gas_diagnostic_date: lease.gas_diagnostic_date.filter(|_| lease.gas_installation_over_15y),
+        }
+    }
+
+    fn renewal_months(&self) -> i32 {
+        renewal_months(self.lease_type, self.landlord_is_legal_person)
+    }
+}
+
+/// Student and mobility leases end at their term (art. 25-9 and 25-14);
+/// standard leases renew tacitly for the statutory renewal length. Ancillary
+/// leases fall under the code civil, where a lease left running past its
+/// term continues without a fixed term (art. 1738) rather than renewing.
+pub fn is_renewable(lease_kind: &str, lease_type: &str) -> bool {
+    lease_kind == "standard" && lease_type != "ancillary"
+}
+
+/// The end date an active renewable lease has once tacitly renewed on
+/// `today`, or `None` when it does not renew (term not reached, notice
+/// given, non-renewable kind).
+pub fn renewed_end_date(facts: &TermFacts, today: NaiveDate) -> Option<NaiveDate> {
+    if !is_renewable(facts.lease_kind, facts.lease_type) || facts.status != "active" || facts.notice_effective_date.is_some() {
+        return None;
+    }
+    let end_date = facts.end_date?;
+    if end_date > today {
+        return None;
+    }
+    term_end_after(facts.start_date, facts.duration_months, facts.renewal_months(), today)
+}
+
+fn add_years(date: NaiveDate, years: u32) -> NaiveDate {
+    date.checked_add_months(Months::new(12 * years)).unwrap_or(date)
+}
+
+/// Every upcoming or overdue deadline of the lease, computed in one place.
+pub fn lease_deadlines(lease_id: Uuid, facts: &TermFacts, today: NaiveDate) -> LeaseDeadlines {
+    let renewable = is_renewable(facts.lease_kind, facts.lease_type);
+    let ancillary = facts.lease_type == "ancillary";
+    let term_end = facts
+        .notice_effective_date
+        .or_else(|| renewed_end_date(facts, today))
+        .or(facts.end_date);
+
+    let mut deadlines = Vec::new();
+    let mut push = |kind: &str, date: NaiveDate, label: String| {
+        deadlines.push(LeaseDeadline {
+            kind: kind.to_string(),
+            date,
+            label,
+            days_remaining: (date - today).num_days(),
+        });
+    };
+
+    if let Some(end) = term_end {
+        if facts.notice_effective_date.is_some() {
+            push("notice_end", end, "Fin du bail suite au congé".to_string());
+        } else if renewable {
+            push("term_end", end, "Terme du bail, reconduit tacitement sauf congé".to_string());
+        } else if ancillary {
+            push("term_end", end, "Terme du bail, prolongé sans durée fixe sauf congé".to_string());
+        } else {
+            push("term_end", end, "Fin du bail, sans reconduction".to_string());
+        }
+
+        // The landlord's window to give notice before the term; once missed,
+        // the next one is before the following term.
+        if renewable && facts.notice_effective_date.is_none() {
+            let months = Months::new(landlord_notice_months(facts.lease_type) as u32);
+            let mut target = end;
+            let mut deadline = target.checked_sub_months(months);
+            if deadline.is_some_and(|date| date < today) {
+                if let Some(next) = term_end_after(facts.start_date, facts.duration_months, facts.renewal_months(), target) {
+                    target = next;
+                    deadline = next.checked_sub_months(months);
+                }
+            }
+            if let Some(deadline) = deadline {
+                push(
+                    "landlord_notice",
+                    deadline,
+                    format!("Dernier jour pour délivrer un congé bailleur avant le terme du {}", target),
+                );
+            }
+        }
+    }
+
+    if facts.rent_revision {
+        let revision = next_revision_date(facts.start_date, today);
+        let within_lease = (renewable || ancillary) && facts.notice_effective_date.is_none()
+            || term_end.is_some_and(|end| revision < end);
+        if within_lease && !dpe_freeze_applies(facts.dpe_class, facts.is_dom_tom, revision) {
+            push("irl_revision", revision, "Révision annuelle du loyer (IRL)".to_string());
+        }
+    }
+
+    if let Some(performed_on) = facts.dpe_date {
+        push("dpe_expiry", dpe_expiry(performed_on), "Fin de validité du DPE".to_string());
+    }
+    if let Some(performed_on) = facts.electrical_diagnostic_date {
+        push(
+            "electrical_diagnostic_expiry",
+            add_years(performed_on, INSTALLATION_DIAGNOSTIC_VALIDITY_YEARS),
+            "Fin de validité du diagnostic électricité".to_string(),
+        );
+    }
+    if let Some(performed_on) = facts.gas_diagnostic_date {
+        push(
+            "gas_diagnostic_expiry",
+            add_years(performed_on, INSTALLATION_DIAGNOSTIC_VALIDITY_YEARS),
+            "Fin de validité du diagnostic gaz".to_string(),
+        );
+    }
+
+    deadlines.sort_by_key(|deadline| deadline.date);
+
+    LeaseDeadlines {
+        lease_id,
+        renewable,
+        term_end,
+        ended_without_renewal: !renewable && !ancillary && facts.end_date.is_some_and(|end| end <= today),
+        deadlines,
+    }
+}
+
+/// Tacitly renew every active standard lease whose term has been reached:
+/// roll `end_date` to the end of the new term. Ancillary leases past their
+/// term continue without one, so their `end_date` is cleared. Returns the
+/// number of leases renewed.
+pub async fn renew_due_leases(db: &Database, today: NaiveDate) -> Result<usize, AppError> {
+    let due = sqlx::query_as::<_, (Uuid, String, NaiveDate, i32, NaiveDate, bool)>(
+        r#"
+        SELECT l.id, l.lease_type, l.start_date, l.duration_months, l.end_date,
+               COALESCE(o.id IS NOT NULL AND NOT o.is_family_sci, FALSE)
+        FROM leases l
+        JOIN properties p ON p.id = l.property_id
+        LEFT JOIN organizations o ON o.id = p.organization_id
+        WHERE l.status = 'active' AND l.lease_kind = 'standard' AND l.end_date <= $1
+        "#,
+    )
+    .bind(today)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let mut renewed = 0;
+    for (id, lease_type, start_date, duration_months, end_date, landlord_is_legal_person) in due {
+        let (new_end, renewed_on) = if is_renewable("standard", &lease_type) {
+            let renewal = renewal_months(&lease_type, landlord_is_legal_person);
+            let Some(new_end) = term_end_after(start_date, duration_months, renewal, today) else {
+                continue;
+            };
+            let renewed_on = new_end.checked_sub_months(Months::new(renewal as u32)).unwrap_or(end_date);
+            (Some(new_end), renewed_on)
+        } else {
+            (None, end_date)
+        };
+        // Guard on the end date read above so a concurrent run renews once.
+        renewed += sqlx::query(
+            r#"
+            UPDATE leases
+            SET end_date = $2, last_renewed_on = $3, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = 'active' AND end_date = $4
+            "#,
+        )
+        .bind(id)
+        .bind(new_end)
+        .bind(renewed_on)
+        .bind(end_date)
+        .execute(&db.pool)
+        .await?
+        .rows_affected() as usize;
+    }
+
+    Ok(renewed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    fn facts(lease_kind: &'static str, lease_type: &'static str, duration_months: i32) -> TermFacts<'static> {
+        let start_date = d(2026, 1, 1);
+        TermFacts {
+            lease_kind,
+            lease_type,
+            status: "active",
+            start_date,
+            duration_months,
+            end_date: start_date.checked_add_months(Months::new(duration_months as u32)),
+            landlord_is_legal_person: false,
+            notice_effective_date: None,
+            rent_revision: true,
+            dpe_class: Some("D"),
+            is_dom_tom: false,
+            dpe_date: None,
+            electrical_diagnostic_date: None,
+            gas_diagnostic_date: None,
+        }
+    }
+
+    fn find<'a>(deadlines: &'a LeaseDeadlines, kind: &str) -> Option<&'a LeaseDeadline> {
+        deadlines.deadlines.iter().find(|deadline| deadline.kind == kind)
+    }
+
+    #[test]
+    fn standard_lease_rolls_to_the_next_term() {
+        let furnished = facts("standard", "furnished", 12);
+        assert_eq!(renewed_end_date(&furnished, d(2026, 12, 31)), None);
+        assert_eq!(renewed_end_date(&furnished, d(2027, 1, 1)), Some(d(2028, 1, 1)));
+        // A missed run still lands on the term running today.
+        assert_eq!(renewed_end_date(&furnished, d(2028, 3, 1)), Some(d(2029, 1, 1)));
+    }
+
+    #[test]
+    fn furnished_two_year_lease_renews_one_year_at_a_time() {
+        let furnished = facts("standard", "furnished", 24);
+        assert_eq!(renewed_end_date(&furnished, d(2028, 1, 1)), Some(d(2029, 1, 1)));
+        assert_eq!(renewed_end_date(&furnished, d(2029, 1, 1)), Some(d(2030, 1, 1)));
+
+        let deadlines = lease_deadlines(Uuid::nil(), &furnished, d(2028, 2, 1));
+        assert_eq!(deadlines.term_end, Some(d(2029, 1, 1)));
+        assert_eq!(find(&deadlines, "landlord_notice").unwrap().date, d(2028, 10, 1));
+    }
+
+    #[test]
+    fn sci_unfurnished_lease_renews_for_six_years() {
+        let mut company = facts("standard", "unfurnished", 72);
+        company.landlord_is_legal_person = true;
+        assert_eq!(renewed_end_date(&company, d(2032, 1, 1)), Some(d(2038, 1, 1)));
+
+        // Missing the notice window before the first term moves it six years on.
+        let late = lease_deadlines(Uuid::nil(), &company, d(2031, 8, 1));
+        assert_eq!(find(&late, "landlord_notice").unwrap().date, d(2037, 7, 1));
+    }
+
+    #[test]
+    fn ancillary_lease_continues_without_renewing() {
+        assert!(!is_renewable("standard", "ancillary"));
+        let parking = facts("standard", "ancillary", 12);
+        assert_eq!(renewed_end_date(&parking, d(2027, 2, 1)), None);
+
+        let deadlines = lease_deadlines(Uuid::nil(), &parking, d(2027, 2, 1));
+        assert!(!deadlines.renewable);
+        assert!(!deadlines.ended_without_renewal);
+        assert!(find(&deadlines, "landlord_notice").is_none());
+        assert_eq!(find(&deadlines, "irl_revision").unwrap().date, d(2028, 1, 1));
+    }
+
+    #[test]
+    fn non_renewable_or_noticed_leases_do_not_roll() {
+        let student = facts("student", "furnished", 9);
+        assert_eq!(renewed_end_date(&student, d(2026, 12, 1)), None);
+
+        let mut noticed = facts("standard", "furnished", 12);
+        noticed.notice_effective_date = Some(d(2027, 1, 1));
+        assert_eq!(renewed_end_date(&noticed, d(2027, 1, 1)), None);
+
+        let mut draft = facts("standard", "furnished", 12);
+        draft.status = "draft";
+        assert_eq!(renewed_end_date(&draft, d(2027, 6, 1)), None);
+    }
+
+    #[test]
+    fn student_lease_is_flagged_at_its_nine_month_end() {
+        let student = facts("student", "furnished", 9);
+        let before = lease_deadlines(Uuid::nil(), &student, d(2026, 9, 1));
+        assert!(!before.ended_without_renewal);
+        assert_eq!(before.term_end, Some(d(2026, 10, 1)));
+        assert_eq!(find(&before, "term_end").unwrap().days_remaining, 30);
+        assert!(find(&before, "landlord_notice").is_none());
+        // No anniversary falls within a nine-month lease.
+        assert!(find(&before, "irl_revision").is_none());
+
+        let after = lease_deadlines(Uuid::nil(), &student, d(2026, 10, 1));
+        assert!(after.ended_without_renewal);
+    }
+
+    #[test]
+    fn landlord_notice_window_moves_to_the_next_term_once_missed() {
+        let unfurnished = facts("standard", "unfurnished", 36);
+        let early = lease_deadlines(Uuid::nil(), &unfurnished, d(2028, 3, 1));
+        assert_eq!(find(&early, "landlord_notice").unwrap().date, d(2028, 7, 1));
+
+        let late = lease_deadlines(Uuid::nil(), &unfurnished, d(2028, 8, 1));
+        assert_eq!(find(&late, "landlord_notice").unwrap().date, d(2031, 7, 1));
+        assert_eq!(find(&late, "irl_revision").unwrap().date, d(2029, 1, 1));
+    }
+
+    #[test]
+    fn deadlines_include_diagnostics_and_skip_frozen_revisions() {
+        let mut lease = facts("standard", "furnished", 12);
+        lease.dpe_class = Some("G");
+        lease.dpe_date = Some(d(2019, 5, 1));
+        lease.electrical_diagnostic_date = Some(d(2022, 2, 1));
+        let deadlines = lease_deadlines(Uuid::nil(), &lease, d(2026, 6, 1));
+
+        assert!(find(&deadlines, "irl_revision").is_none());
+        let dpe = find(&deadlines, "dpe_expiry").unwrap();
+        assert_eq!(dpe.date, d(2024, 12, 31));
+        assert!(dpe.days_remaining < 0);
+        assert_eq!(find(&deadlines, "electrical_diagnostic_expiry").unwrap().date, d(2028, 2, 1));
+        assert!(deadlines.deadlines.windows(2).all(|pair| pair[0].date <= pair[1].date));
+    }
+}
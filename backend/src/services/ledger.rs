@@ -0,0 +1,297 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A period the tenants owe rent for (one receipt row).
+#[derive(Debug, Clone)]
+pub struct DuePeriod {
+    pub receipt_id: Uuid,
+    pub period_year: i32,
+    pub period_month: i32,
+    pub amount_due: BigDecimal,
+}
+
+/// A payment received for the lease.
+#[derive(Debug, Clone)]
+pub struct LedgerPayment {
+    pub id: Uuid,
+    pub amount: BigDecimal,
+    pub payment_date: NaiveDate,
+    /// Period the payer designated, if any.
+    pub receipt_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Allocation {
+    pub payment_id: Uuid,
+    pub receipt_id: Uuid,
+    pub amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodBalance {
+    pub receipt_id: Uuid,
+    pub period_year: i32,
+    pub period_month: i32,
+    pub amount_due: BigDecimal,
+    pub amount_paid: BigDecimal,
+    pub balance: BigDecimal,
+    /// "settled", "partial" or "unpaid".
+    pub settlement: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerOutcome {
+    pub periods: Vec<PeriodBalance>,
+    pub allocations: Vec<Allocation>,
+    pub total_due: BigDecimal,
+    pub total_paid: BigDecimal,
+    /// Amount still owed across all periods.
+    pub arrears: BigDecimal,
+    /// Amount received but not yet allocated to any period (tenant credit).
+    pub credit: BigDecimal,
+}
+
+/// Settlement state of a period given what it owes and what it received.
+pub fn settlement(amount_due: &BigDecimal, amount_paid: &BigDecimal) -> &'static str {
+    if amount_paid >= amount_due {
+        "settled"
+    } else if amount_paid > &BigDecimal::zero() {
+        "partial"
+    } else {
+        "unpaid"
+    }
+}
+
+/// Allocate payments against due periods.
+///
+/// Allocations already recorded stay as they are: only what each payment has
+/// not yet settled is allocated, and only to periods still owing, so a period
+/// added later never takes a payment away from one it already settled.
+/// A payment first settles the period its payer designated (article 1342-10
+/// of the Code civil lets the debtor choose which debt is paid); anything left
+/// over, and undesignated payments, go to the oldest unpaid period first.
+/// Payments are applied in date order; what cannot be allocated is credit.
+pub fn allocate(periods: &[DuePeriod], payments: &[LedgerPayment], recorded: &[Allocation]) -> LedgerOutcome {
+    let mut periods: Vec<DuePeriod> = periods.to_vec();
+    periods.sort_by_key(|p| (p.period_year, p.period_month));
+
+    let mut payments: Vec<LedgerPayment> = payments.to_vec();
+    payments.sort_by_key(|p| (p.payment_date, p.id));
+
+    let mut remaining: Vec<BigDecimal> = periods
+        .iter()
+        .map(|p| {
+            recorded
+                .iter()
+                .filter(|a| a.receipt_id == p.receipt_id)
+                .fold(p.amount_due.clone(), |left, a| left - &a.amount)
+        })
+        .collect();
+    let mut leftovers: Vec<BigDecimal> = payments
+        .iter()
+        .map(|p| {
+            recorded
+                .iter()
+                .filter(|a| a.payment_id == p.id)
+                .fold(p.amount.clone(), |left, a| left - &a.amount)
+        })
+        .collect();
+    let mut allocations: Vec<Allocation> = recorded.to_vec();
+
+    let mut apply = |payment_id: Uuid, index: usize, left: &mut BigDecimal, remaining: &mut [BigDecimal]| {
+        let amount = if *left < remaining[index] { left.clone() } else { remaining[index].clone() };
+        if amount <= BigDecimal::zero() {
+            return;
+        }
+        *left -= &amount;
+        remaining[index] -= &amount;
+        let receipt_id = periods[index].receipt_id;
+        match allocations
+            .iter_mut()
+            .find(|a| a.payment_id == payment_id && a.receipt_id == receipt_id)
+        {
+            Some(existing) => existing.amount += amount,
+            None => allocations.push(Allocation { payment_id, receipt_id, amount }),
+        }
+    };
+
+    // Designated periods first.
+    for (payment, left) in payments.iter().zip(leftovers.iter_mut()) {
+        if let Some(index) = payment
+            .receipt_id
+            .and_then(|id| periods.iter().position(|p| p.receipt_id == id))
+        {
+            apply(payment.id, index, left, &mut remaining);
+        }
+    }
+
+    // Then oldest period first.
+    for (payment, left) in payments.iter().zip(leftovers.iter_mut()) {
+        for index in 0..remaining.len() {
+            if *left <= BigDecimal::zero() {
+                break;
+            }
+            apply(payment.id, index, left, &mut remaining);
+        }
+    }
+
+    let period_balances: Vec<PeriodBalance> = periods
+        .iter()
+        .zip(remaining.iter())
+        .map(|(period, left)| {
+            let amount_paid = &period.amount_due - left;
+            PeriodBalance {
+                receipt_id: period.receipt_id,
+                period_year: period.period_year,
+                period_month: period.period_month,
+                amount_due: period.amount_due.clone(),
+                settlement: settlement(&period.amount_due, &amount_paid).to_string(),
+                amount_paid,
+                balance: left.clone(),
+            }
+        })
+        .collect();
+
+    let total_due = periods.iter().fold(BigDecimal::zero(), |acc, p| acc + &p.amount_due);
+    let total_paid = payments.iter().fold(BigDecimal::zero(), |acc, p| acc + &p.amount);
+    let arrears = remaining.iter().fold(BigDecimal::zero(), |acc, r| acc + r);
+    let credit = leftovers.iter().fold(BigDecimal::zero(), |acc, l| acc + l);
+
+    LedgerOutcome {
+        periods: period_balances,
+        allocations,
+        total_due,
+        total_paid,
+        arrears,
+        credit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(s: &str) -> BigDecimal {
+        BigDecimal::from_str(s).unwrap()
+    }
+
+    fn period(month: i32, due: &str) -> DuePeriod {
+        DuePeriod {
+            receipt_id: Uuid::new_v4(),
+            period_year: 2026,
+            period_month: month,
+            amount_due: dec(due),
+        }
+    }
+
+    fn payment(day: u32, amount: &str, receipt_id: Option<Uuid>) -> LedgerPayment {
+        LedgerPayment {
+            id: Uuid::new_v4(),
+            amount: dec(amount),
+            payment_date: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+            receipt_id,
+        }
+    }
+
+    #[test]
+    fn full_payment_settles_period() {
+        let periods = vec![period(10, "700.00")];
+        let outcome = allocate(&periods, &[payment(5, "700.00", None)], &[]);
+        assert_eq!(outcome.periods[0].settlement, "settled");
+        assert_eq!(outcome.arrears, dec("0"));
+        assert_eq!(outcome.credit, dec("0"));
+    }
+
+    #[test]
+    fn half_payment_leaves_partial_period() {
+        let periods = vec![period(10, "700.00")];
+        let outcome = allocate(&periods, &[payment(5, "350.00", None)], &[]);
+        assert_eq!(outcome.periods[0].settlement, "partial");
+        assert_eq!(outcome.periods[0].amount_paid, dec("350.00"));
+        assert_eq!(outcome.periods[0].balance, dec("350.00"));
+    }
+
+    #[test]
+    fn undesignated_payment_settles_oldest_period_first() {
+        let periods = vec![period(10, "700.00"), period(9, "700.00")];
+        let outcome = allocate(&periods, &[payment(5, "1000.00", None)], &[]);
+        // Sorted by period: September first.
+        assert_eq!(outcome.periods[0].period_month, 9);
+        assert_eq!(outcome.periods[0].settlement, "settled");
+        assert_eq!(outcome.periods[1].settlement, "partial");
+        assert_eq!(outcome.periods[1].amount_paid, dec("300.00"));
+        assert_eq!(outcome.allocations.len(), 2);
+    }
+
+    #[test]
+    fn designated_payment_goes_to_chosen_period() {
+        let september = period(9, "700.00");
+        let october = period(10, "700.00");
+        let outcome = allocate(
+            &[september.clone(), october.clone()],
+            &[payment(5, "700.00", Some(october.receipt_id))],
+            &[],
+        );
+        assert_eq!(outcome.periods[0].settlement, "unpaid");
+        assert_eq!(outcome.periods[1].settlement, "settled");
+    }
+
+    #[test]
+    fn overpayment_becomes_credit() {
+        let periods = vec![period(10, "700.00")];
+        let outcome = allocate(&periods, &[payment(5, "800.00", None)], &[]);
+        assert_eq!(outcome.credit, dec("100.00"));
+        assert_eq!(outcome.total_paid, dec("800.00"));
+    }
+
+    #[test]
+    fn colocataire_shares_add_up_to_settlement() {
+        let periods = vec![period(10, "900.00")];
+        let outcome = allocate(
+            &periods,
+            &[payment(3, "450.00", None), payment(6, "450.00", None)],
+            &[],
+        );
+        assert_eq!(outcome.periods[0].settlement, "settled");
+        assert_eq!(outcome.allocations.len(), 2);
+    }
+
+    #[test]
+    fn earlier_period_added_later_keeps_recorded_allocations() {
+        let october = period(10, "700.00");
+        let paid = payment(5, "700.00", None);
+        let settled = allocate(std::slice::from_ref(&october), std::slice::from_ref(&paid), &[]);
+        assert_eq!(settled.periods[0].settlement, "settled");
+
+        // September is backfilled once October's quittance is out: the
+        // October payment stays with October.
+        let september = period(9, "700.00");
+        let outcome = allocate(&[september.clone(), october.clone()], std::slice::from_ref(&paid), &settled.allocations);
+        assert_eq!(outcome.periods[0].settlement, "unpaid");
+        assert_eq!(outcome.periods[1].settlement, "settled");
+        assert_eq!(outcome.allocations, settled.allocations);
+
+        // A new payment goes to the period still owing.
+        let next = payment(20, "700.00", None);
+        let outcome = allocate(&[september, october], &[paid, next.clone()], &settled.allocations);
+        assert_eq!(outcome.periods[0].settlement, "settled");
+        assert_eq!(outcome.allocations.len(), 2);
+        assert_eq!(outcome.allocations[1].payment_id, next.id);
+        assert_eq!(outcome.credit, dec("0"));
+    }
+
+    #[test]
+    fn partial_allocation_is_completed_by_the_rest_of_the_payment() {
+        let september = period(9, "700.00");
+        let october = period(10, "700.00");
+        let paid = payment(5, "1000.00", None);
+        let recorded = vec![Allocation { payment_id: paid.id, receipt_id: october.receipt_id, amount: dec("700.00") }];
+        let outcome = allocate(&[september, october], &[paid], &recorded);
+        assert_eq!(outcome.periods[0].amount_paid, dec("300.00"));
+        assert_eq!(outcome.periods[1].settlement, "settled");
+        assert_eq!(outcome.credit, dec("0"));
+    }
+}
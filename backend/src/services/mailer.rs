@@ -0,0 +1,276 @@
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::path::PathBuf;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+
+#[derive(Debug, Error)]
+pub enum MailError {
+    #[error("Mail configuration error: {0}")]
+    Config(String),
+
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Failed to build message: {0}")]
+    Build(String),
+
+    #[error("Failed to send message: {0}")]
+    Send(String),
+}
+
+pub type MailResult<T> = Result<T, MailError>;
+
+/// Where outgoing mail goes. SMTP in production; file and stdout transports
+/// let development and tests inspect messages without a mail server.
+pub enum MailTransport {
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    File(AsyncFileTransport<Tokio1Executor>),
+    Stdout,
+}
+
+/// File attached to an outgoing message.
+pub struct MailAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+/// Plain-text message with optional attachments, addressed to one recipient.
+pub struct OutgoingMail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+    pub attachments: Vec<MailAttachment>,
+}
+
+pub struct Mailer {
+    transport: MailTransport,
+    from: Mailbox,
+}
+
+impl Mailer {
+    pub fn new(transport: MailTransport, from: &str) -> MailResult<Self> {
+        let from = from
+            .parse::<Mailbox>()
+            .map_err(|e| MailError::InvalidAddress(format!("{}: {}", from, e)))?;
+        Ok(Mailer { transport, from })
+    }
+
+    /// Create a mailer from the environment.
+    ///
+    /// `MAIL_TRANSPORT` selects `smtp`, `file` or `stdout`; the last two never
+    /// reach the tenant. Left unset, it is `smtp` when `SMTP_HOST` is set and
+    /// `stdout` otherwise, so an instance without a mail server still runs. SMTP reads
+    /// `SMTP_HOST`, `SMTP_PORT`, `SMTP_USERNAME`, `SMTP_PASSWORD` and
+    /// `SMTP_TLS` (`starttls` default, `tls` or `none`); the file transport
+    /// writes `.eml` files to `MAIL_FILE_DIR`. The sender is `MAIL_FROM`.
+    pub fn from_env() -> MailResult<Self> {
+        let from = std::env::var("MAIL_FROM")
+            .unwrap_or_else(|_| "Quittance <no-reply@localhost>".to_string());
+
+        let selected = default_transport(
+            std::env::var("MAIL_TRANSPORT").ok(),
+            std::env::var("SMTP_HOST").is_ok(),
+        );
+        let transport = match selected.as_str() {
+            "smtp" => MailTransport::Smtp(smtp_transport_from_env()?),
+            "file" => {
+                let dir = std::env::var("MAIL_FILE_DIR")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|_| PathBuf::from("storage/mail"));
+                std::fs::create_dir_all(&dir)
+                    .map_err(|e| MailError::Config(format!("Cannot create {:?}: {}", dir, e)))?;
+                MailTransport::File(AsyncFileTransport::<Tokio1Executor>::new(dir))
+            }
+            "stdout" => MailTransport::Stdout,
+            other => {
+                return Err(MailError::Config(format!("Unknown MAIL_TRANSPORT '{}'", other)));
+            }
+        };
+
+        Self::new(transport, &from)
+    }
+
+    /// Whether sent messages actually reach their recipient. Only SMTP does;
+    /// the file and stdout transports merely record the message.
+    pub fn delivers(&self) -> bool {
+        matches!(self.transport, MailTransport::Smtp(_))
+    }
+
+    /// `email_logs` status for a message the transport accepted: `sent` when
+    /// it went out over SMTP, `logged` when it was only written locally.
+    pub fn delivery_status(&self) -> &'static str {
+        if self.delivers() { "sent" } else { "logged" }
+    }
+
+    /// Build the MIME message for an outgoing mail.
+    pub fn build_message(&self, mail: &OutgoingMail) -> MailResult<Message> {
+        let to = mail
+            .to
+            .parse::<Mailbox>()
+            .map_err(|e| MailError::InvalidAddress(format!("{}: {}", mail.to, e)))?;
+
+        let mut body = MultiPart::mixed().singlepart(SinglePart::plain(mail.body.clone()));
+        for attachment in &mail.attachments {
+            let content_type = ContentType::parse(&attachment.content_type)
+                .map_err(|e| MailError::Build(format!("{}: {}", attachment.content_type, e)))?;
+            body = body.singlepart(
+                Attachment::new(attachment.filename.clone())
+                    .body(attachment.content.clone(), content_type),
+            );
+        }
+
+        Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(mail.subject.clone())
+            .multipart(body)
+            .map_err(|e| MailError::Build(e.to_string()))
+    }
+
+    /// Send one message through the configured transport.
+    pub async fn send(&self, mail: &OutgoingMail) -> MailResult<()> {
+        let message = self.build_message(mail)?;
+
+        match &self.transport {
+            MailTransport::Smtp(transport) => {
+                transport
+                    .send(message)
+                    .await
+                    .map_err(|e| MailError::Send(e.to_string()))?;
+            }
+            MailTransport::File(transport) => {
+                transport
+                    .send(message)
+                    .await
+                    .map_err(|e| MailError::Send(e.to_string()))?;
+            }
+            MailTransport::Stdout => {
+                let mut stdout = tokio::io::stdout();
+                stdout
+                    .write_all(&message.formatted())
+                    .await
+                    .map_err(|e| MailError::Send(e.to_string()))?;
+                stdout
+                    .write_all(b"\n")
+                    .await
+                    .map_err(|e| MailError::Send(e.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The transport named by `MAIL_TRANSPORT`, or the one implied by whether an
+/// SMTP server is configured when the variable is unset.
+fn default_transport(selected: Option<String>, smtp_host_set: bool) -> String {
+    selected.unwrap_or_else(|| if smtp_host_set { "smtp" } else { "stdout" }.to_string())
+}
+
+fn smtp_transport_from_env() -> MailResult<AsyncSmtpTransport<Tokio1Executor>> {
+    let host = std::env::var("SMTP_HOST")
+        .map_err(|_| MailError::Config("SMTP_HOST must be set for the smtp transport".to_string()))?;
+
+    let mut builder = match std::env::var("SMTP_TLS")
+        .unwrap_or_else(|_| "starttls".to_string())
+        .as_str()
+    {
+        "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+            .map_err(|e| MailError::Config(e.to_string()))?,
+        "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&host)
+            .map_err(|e| MailError::Config(e.to_string()))?,
+        "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host),
+        other => return Err(MailError::Config(format!("Unknown SMTP_TLS '{}'", other))),
+    };
+
+    if let Some(port) = std::env::var("SMTP_PORT").ok().and_then(|p| p.parse::<u16>().ok()) {
+        builder = builder.port(port);
+    }
+
+    if let (Ok(username), Ok(password)) = (std::env::var("SMTP_USERNAME"), std::env::var("SMTP_PASSWORD")) {
+        builder = builder.credentials(Credentials::new(username, password));
+    }
+
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_mail() -> OutgoingMail {
+        OutgoingMail {
+            to: "marie.martin@example.com".to_string(),
+            subject: "Quittance de loyer - Octobre 2026".to_string(),
+            body: "Bonjour,".to_string(),
+            attachments: vec![MailAttachment {
+                filename: "quittance_2026_10.pdf".to_string(),
+                content_type: "application/pdf".to_string(),
+                content: b"%PDF-1.4 test".to_vec(),
+            }],
+        }
+    }
+
+    #[test]
+    fn builds_message_with_pdf_attachment() {
+        let mailer = Mailer::new(MailTransport::Stdout, "Jean Dupont <jean@example.com>").unwrap();
+        let message = mailer.build_message(&sample_mail()).expect("build ok");
+        let formatted = String::from_utf8_lossy(&message.formatted()).to_string();
+        assert!(formatted.contains("To: marie.martin@example.com"));
+        assert!(formatted.contains("application/pdf"));
+        assert!(formatted.contains("quittance_2026_10.pdf"));
+    }
+
+    #[test]
+    fn rejects_invalid_recipient() {
+        let mailer = Mailer::new(MailTransport::Stdout, "jean@example.com").unwrap();
+        let mut mail = sample_mail();
+        mail.to = "not-an-address".to_string();
+        assert!(matches!(mailer.build_message(&mail), Err(MailError::InvalidAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn file_transport_writes_eml() {
+        let dir = std::env::temp_dir().join(format!("quittance-mail-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mailer = Mailer::new(
+            MailTransport::File(AsyncFileTransport::<Tokio1Executor>::new(&dir)),
+            "jean@example.com",
+        )
+        .unwrap();
+
+        mailer.send(&sample_mail()).await.expect("send ok");
+
+        let written: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(written.len(), 1);
+        assert_eq!(mailer.delivery_status(), "logged");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn falls_back_to_stdout_without_smtp_host() {
+        assert_eq!(default_transport(None, false), "stdout");
+        assert_eq!(default_transport(None, true), "smtp");
+        assert_eq!(default_transport(Some("smtp".to_string()), false), "smtp");
+        assert_eq!(default_transport(Some("file".to_string()), true), "file");
+    }
+
+    #[tokio::test]
+    async fn only_smtp_counts_as_delivered() {
+        let stdout = Mailer::new(MailTransport::Stdout, "jean@example.com").unwrap();
+        assert!(!stdout.delivers());
+        assert_eq!(stdout.delivery_status(), "logged");
+
+        let smtp = Mailer::new(
+            MailTransport::Smtp(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous("localhost").build()),
+            "jean@example.com",
+        )
+        .unwrap();
+        assert!(smtp.delivers());
+        assert_eq!(smtp.delivery_status(), "sent");
+    }
+}
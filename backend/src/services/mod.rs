@@ -1 +1,15 @@
 pub mod pdf_renderer;
+pub mod mailer;
+pub mod receipt_scheduler;
+pub mod ledger;
+pub mod irl;
+pub mod rent_control;
+pub mod zone_tendue;
+pub mod charges_regularisation;
+pub mod deposit;
+pub mod lease_lifecycle;
+pub mod lease_amendment;
+pub mod snapshot_diff;
+pub mod lease_notice;
+pub mod lease_renewal;
+pub mod inventory;
@@ -1,7 +1,17 @@
-use crate::models::canonical_snapshot::CanonicalSnapshot;
+use crate::models::canonical_snapshot::{CanonicalSnapshot, PartiesSection};
+use crate::models::receipt_snapshot::ReceiptSnapshot;
+use crate::models::rent_call_snapshot::RentCallSnapshot;
+use crate::models::charges_regularisation::RegularisationSnapshot;
+use crate::models::deposit::DepositSnapshot;
+use crate::models::lease_amendment::AmendmentSnapshot;
+use crate::models::lease_notice::{landlord_notice_ground_label, reduced_notice_reason_label, NoticeSnapshot};
+use crate::models::inventory::{condition_label, InventorySnapshot};
+use bigdecimal::{BigDecimal, Zero, num_traits::Signed};
+use chrono::{Datelike, NaiveDate};
+use quittance_compliance::lease::mobility_reason_label;
 use serde_json::json;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::fs;
 use thiserror::Error;
@@ -121,6 +131,71 @@ impl TemplateCache {
         };
         format!("<strong>{}</strong>{}, demeurant à {}", full_name, birth, address)
     }
+
+    /// Render the bailleur designation, branching on natural vs legal person (SCI).
+    fn render_landlord_block(p: &PartiesSection) -> String {
+        if p.landlord_kind == "legal" {
+            let capital = p
+                .landlord_capital_social
+                .as_deref()
+                .map(|c| format!(" au capital de {} €", c))
+                .unwrap_or_default();
+            let rcs = match (p.landlord_rcs_city.as_deref(), p.landlord_registration_number.as_deref()) {
+                (Some(city), Some(num)) if !city.is_empty() && !num.is_empty() => {
+                    format!(", immatriculée au RCS de {} sous le numéro {}", city, num)
+                }
+                _ => String::new(),
+            };
+            let representative = match (
+                p.landlord_representative_name.as_deref(),
+                p.landlord_representative_role.as_deref(),
+            ) {
+                (Some(name), Some(role)) if !name.is_empty() && !role.is_empty() => {
+                    format!(", représentée par {} en qualité de {}", name, role)
+                }
+                (Some(name), _) if !name.is_empty() => format!(", représentée par {}", name),
+                _ => String::new(),
+            };
+            let family = if p.landlord_is_family_sci {
+                " (SCI constituée entre parents et alliés jusqu'au quatrième degré inclus)"
+            } else {
+                ""
+            };
+            let legal_form = p.landlord_legal_form.clone().unwrap_or_default();
+            format!(
+                "La société <strong>{}</strong>, {}{}, dont le siège social est situé {}{}{}{}, agissant en qualité de bailleur (personne morale).",
+                p.landlord_full_name, legal_form, capital, p.landlord_address, rcs, representative, family
+            )
+        } else {
+            format!(
+                "<strong>{}</strong>, demeurant à {}",
+                p.landlord_full_name, p.landlord_address
+            )
+        }
+    }
+
+    /// Render the bailleur signature line. An SCI signs via its représentant.
+    fn render_landlord_signature(p: &PartiesSection) -> String {
+        if p.landlord_kind == "legal" {
+            let rep = p
+                .landlord_representative_name
+                .clone()
+                .unwrap_or_else(|| p.landlord_full_name.clone());
+            let role = p
+                .landlord_representative_role
+                .clone()
+                .unwrap_or_else(|| "Gérant".to_string());
+            format!(
+                "<div class=\"signature-line\">Le bailleur<br>{}<br>représentée par {} ({})</div>",
+                p.landlord_full_name, rep, role
+            )
+        } else {
+            format!(
+                "<div class=\"signature-line\">Le bailleur<br>{}</div>",
+                p.landlord_full_name
+            )
+        }
+    }
     
     /// Generate full HTML document from canonical snapshot
     pub fn render_full_html(&self, snapshot: &CanonicalSnapshot) -> TemplateResult<String> {
@@ -327,68 +402,10 @@ impl TemplateCache {
         };
 
         // --- Landlord designation: branch on natural vs legal person (SCI) ---
-        let landlord_block = if snapshot.parties.landlord_kind == "legal" {
-            let p = &snapshot.parties;
-            let capital = p
-                .landlord_capital_social
-                .as_deref()
-                .map(|c| format!(" au capital de {} €", c))
-                .unwrap_or_default();
-            let rcs = match (p.landlord_rcs_city.as_deref(), p.landlord_registration_number.as_deref()) {
-                (Some(city), Some(num)) if !city.is_empty() && !num.is_empty() => {
-                    format!(", immatriculée au RCS de {} sous le numéro {}", city, num)
-                }
-                _ => String::new(),
-            };
-            let representative = match (
-                p.landlord_representative_name.as_deref(),
-                p.landlord_representative_role.as_deref(),
-            ) {
-                (Some(name), Some(role)) if !name.is_empty() && !role.is_empty() => {
-                    format!(", représentée par {} en qualité de {}", name, role)
-                }
-                (Some(name), _) if !name.is_empty() => format!(", représentée par {}", name),
-                _ => String::new(),
-            };
-            let family = if p.landlord_is_family_sci {
-                " (SCI constituée entre parents et alliés jusqu'au quatrième degré inclus)"
-            } else {
-                ""
-            };
-            let legal_form = p.landlord_legal_form.clone().unwrap_or_default();
-            format!(
-                "La société <strong>{}</strong>, {}{}, dont le siège social est situé {}{}{}{}, agissant en qualité de bailleur (personne morale).",
-                p.landlord_full_name, legal_form, capital, p.landlord_address, rcs, representative, family
-            )
-        } else {
-            format!(
-                "<strong>{}</strong>, demeurant à {}",
-                snapshot.parties.landlord_full_name, snapshot.parties.landlord_address
-            )
-        };
+        let landlord_block = Self::render_landlord_block(&snapshot.parties);
 
         // Landlord signature line: SCI signs via its représentant.
-        let landlord_signature = if snapshot.parties.landlord_kind == "legal" {
-            let rep = snapshot
-                .parties
-                .landlord_representative_name
-                .clone()
-                .unwrap_or_else(|| snapshot.parties.landlord_full_name.clone());
-            let role = snapshot
-                .parties
-                .landlord_representative_role
-                .clone()
-                .unwrap_or_else(|| "Gérant".to_string());
-            format!(
-                "<div class=\"signature-line\">Le bailleur<br>{}<br>représentée par {} ({})</div>",
-                snapshot.parties.landlord_full_name, rep, role
-            )
-        } else {
-            format!(
-                "<div class=\"signature-line\">Le bailleur<br>{}</div>",
-                snapshot.parties.landlord_full_name
-            )
-        };
+        let landlord_signature = Self::render_landlord_signature(&snapshot.parties);
 
         // Build context object with all snapshot fields
         let context = json!({
@@ -415,6 +432,12 @@ impl TemplateCache {
             "duration_months": snapshot.lease_terms.duration_months,
             "end_date": snapshot.lease_terms.end_date.to_string(),
             "lease_kind": snapshot.lease_terms.lease_kind,
+            "mobility_reason_label": snapshot
+                .lease_terms
+                .mobility_reason
+                .as_deref()
+                .map(|reason| mobility_reason_label(reason).unwrap_or(reason)),
+            "mobility_justification": snapshot.lease_terms.mobility_justification,
             "auto_renewal": snapshot.lease_terms.auto_renewal,
             "is_colocation": snapshot.lease_terms.is_colocation,
             "monthly_rent": snapshot.financial_terms.monthly_rent,
@@ -453,15 +476,40 @@ impl TemplateCache {
             "rent_revision_conditions_block": rent_revision_conditions_block,
             "works_block": works_block,
             "colocation_insurance_block": colocation_insurance_block,
+            "premises_label": premises_label(&snapshot.property.property_type),
         });
-        
+
+        // Add watermark if non-compliant
+        let watermark = if snapshot.compliance.compliance_status != "compliant" {
+            "<div class=\"draft-watermark\">PROJET / NON CONFORME</div>"
+        } else {
+            ""
+        };
+
+        // The civil-code lease of ancillary premises is a single template
+        // without the residential sections.
+        if snapshot.lease_terms.lease_type == "ancillary" {
+            let layout = self.render_section(version, "layout", &context)?;
+            return Ok(layout.replace("{{watermark_placeholder}}", watermark));
+        }
+
         // Render each section
         let section_i = self.render_section(version, "section_i_parties", &context)?;
         let section_ii = self.render_section(version, "section_ii_property", &context)?;
-        let section_iii = self.render_section(version, "section_iii_duration", &context)?;
+        // The bail mobilité has its own duration and guarantees sections.
+        let is_mobility = snapshot.lease_terms.lease_kind == "mobility";
+        let section_iii = if is_mobility {
+            self.render_section(version, "section_iii_duration_mobility", &context)?
+        } else {
+            self.render_section(version, "section_iii_duration", &context)?
+        };
         let section_iv = self.render_section(version, "section_iv_financial", &context)?;
         let section_v = self.render_section(version, "section_v_works", &context)?;
-        let section_vi = self.render_section(version, "section_vi_guarantees", &context)?;
+        let section_vi = if is_mobility {
+            self.render_section(version, "section_vi_guarantees_mobility", &context)?
+        } else {
+            self.render_section(version, "section_vi_guarantees", &context)?
+        };
         let section_vii = if snapshot.lease_terms.is_colocation {
             self.render_section(version, "section_vii_solidarity", &context)?
         } else {
@@ -476,13 +524,6 @@ impl TemplateCache {
         let section_x = self.render_section(version, "section_x_custom", &context)?;
         let section_xi = self.render_section(version, "section_xi_annexes", &context)?;
         
-        // Add watermark if non-compliant
-        let watermark = if snapshot.compliance.compliance_status != "compliant" {
-            "<div class=\"draft-watermark\">PROJET / NON CONFORME</div>"
-        } else {
-            ""
-        };
-        
         // Load layout and inject sections
         let mut layout = self.render_section(version, "layout", &context)?;
         layout = layout.replace("{{section_i_parties}}", &section_i);
@@ -500,6 +541,653 @@ impl TemplateCache {
         
         Ok(layout)
     }
+
+    /// Generate the quittance (or reçu, for a partial payment) HTML from a
+    /// frozen receipt snapshot, using the template version recorded in it.
+    pub fn render_quittance_html(&self, snapshot: &ReceiptSnapshot) -> TemplateResult<String> {
+        let lessee_names = snapshot.lessee_names();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+
+        let context = json!({
+            "period_label": period_label(snapshot.period.month, snapshot.period.year),
+            "landlord_block": Self::render_landlord_block(&snapshot.parties),
+            "landlord_signature": Self::render_landlord_signature(&snapshot.parties),
+            "lessees_block": lessees_block,
+            "lessee_names": join_names(&lessee_names),
+            "property_address": snapshot.property_address,
+            "covered_from": format_date_fr(snapshot.period.covered_from),
+            "covered_to": format_date_fr(snapshot.period.covered_to),
+            "base_rent": format_amount(&snapshot.amounts.base_rent),
+            "charges": format_amount(&snapshot.amounts.charges),
+            "total_amount": format_amount(&snapshot.amounts.total_amount),
+            "amount_paid": format_amount(&snapshot.amounts.amount_paid),
+            "balance_due": format_amount(&snapshot.amounts.balance_due),
+            "payment_date": format_date_fr(snapshot.payment_date),
+            "issued_on": format_date_fr(snapshot.payment_date),
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+
+    /// Generate the avis d'échéance HTML from a frozen rent call snapshot.
+    pub fn render_rent_call_html(&self, snapshot: &RentCallSnapshot) -> TemplateResult<String> {
+        let lessee_names = snapshot.lessee_names();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+
+        let regularisation_row = if snapshot.amounts.regularisation.is_zero() {
+            String::new()
+        } else {
+            format!(
+                "<tr><td>Régularisation annuelle des charges</td><td class=\"amount\">{} €</td></tr>",
+                format_amount(&snapshot.amounts.regularisation)
+            )
+        };
+        let arrears_rows = snapshot
+            .arrears_lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "<tr><td>Solde impayé — {}</td><td class=\"amount\">{} €</td></tr>",
+                    period_label(line.period_month, line.period_year),
+                    format_amount(&line.balance)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let arrears_sentence = if snapshot.amounts.arrears.is_zero() {
+            String::new()
+        } else {
+            format!(
+                ", dont <strong>{} €</strong> restant dus sur les périodes antérieures",
+                format_amount(&snapshot.amounts.arrears)
+            )
+        };
+
+        let context = json!({
+            "period_label": period_label(snapshot.period.month, snapshot.period.year),
+            "landlord_block": Self::render_landlord_block(&snapshot.parties),
+            "landlord_signature": Self::render_landlord_signature(&snapshot.parties),
+            "lessees_block": lessees_block,
+            "lessee_names": join_names(&lessee_names),
+            "property_address": snapshot.property_address,
+            "covered_from": format_date_fr(snapshot.period.covered_from),
+            "covered_to": format_date_fr(snapshot.period.covered_to),
+            "due_date": format_date_fr(snapshot.due_date),
+            "base_rent": format_amount(&snapshot.amounts.base_rent),
+            "charges": format_amount(&snapshot.amounts.charges),
+            "regularisation_row": regularisation_row,
+            "arrears_rows": arrears_rows,
+            "arrears_sentence": arrears_sentence,
+            "total_due": format_amount(&snapshot.amounts.total_due),
+            "issued_on": format_date_fr(snapshot.generated_at.date_naive()),
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+
+    /// Generate the charges regularisation statement HTML from its snapshot.
+    pub fn render_regularisation_html(&self, snapshot: &RegularisationSnapshot) -> TemplateResult<String> {
+        let statement = &snapshot.statement;
+        let lessee_names: Vec<String> =
+            snapshot.parties.lessees.iter().map(|l| l.full_name.clone()).collect();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+
+        let expense_rows = statement
+            .lines
+            .iter()
+            .map(|line| {
+                format!(
+                    "<tr><td>{}</td><td>{}</td><td>{} – {}</td><td class=\"amount\">{} €</td><td class=\"amount\">{} €</td></tr>",
+                    line.category_label,
+                    line.label,
+                    format_date_fr(line.covered_start),
+                    format_date_fr(line.covered_end),
+                    format_amount(&line.amount),
+                    format_amount(&line.tenant_share)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let category_rows = statement
+            .category_totals
+            .iter()
+            .map(|total| {
+                format!(
+                    "<tr><td>{}</td><td class=\"amount\">{} €</td></tr>",
+                    total.category_label,
+                    format_amount(&total.tenant_share)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let (balance_label, settlement_sentence) = if statement.balance.is_negative() {
+            (
+                "Trop-perçu à rembourser au locataire",
+                "Ce trop-perçu sera déduit du prochain avis d'échéance.",
+            )
+        } else if statement.balance.is_zero() {
+            ("Solde", "Les provisions versées couvrent exactement les charges de l'année.")
+        } else {
+            (
+                "Complément dû par le locataire",
+                "Ce complément sera ajouté au prochain avis d'échéance.",
+            )
+        };
+
+        let colocataire_block = if statement.colocataire_shares.is_empty() {
+            String::new()
+        } else {
+            let rows = statement
+                .colocataire_shares
+                .iter()
+                .map(|share| {
+                    format!(
+                        "<tr><td>{}</td><td class=\"amount\">{} €</td><td class=\"amount\">{} €</td><td class=\"amount\">{} €</td></tr>",
+                        share.tenant_name,
+                        format_amount(&share.tenant_share),
+                        format_amount(&share.provisions_collected),
+                        format_amount(&share.balance)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!(
+                "<div class=\"section-title\">Répartition entre colocataires</div>\n\
+                 <table><tr><th>Colocataire</th><th>Charges réelles</th><th>Provisions</th><th>Solde</th></tr>\n{}\n</table>",
+                rows
+            )
+        };
+
+        let context = json!({
+            "year": statement.year,
+            "landlord_block": Self::render_landlord_block(&snapshot.parties),
+            "landlord_signature": Self::render_landlord_signature(&snapshot.parties),
+            "lessees_block": lessees_block,
+            "lessee_names": join_names(&lessee_names),
+            "property_address": snapshot.property_address,
+            "occupancy_start": format_date_fr(statement.occupancy_start),
+            "occupancy_end": format_date_fr(statement.occupancy_end),
+            "occupancy_days": statement.occupancy_days,
+            "expense_rows": expense_rows,
+            "category_rows": category_rows,
+            "total_expenses": format_amount(&statement.total_expenses),
+            "tenant_share": format_amount(&statement.tenant_share),
+            "provisions_collected": format_amount(&statement.provisions_collected),
+            "balance_label": balance_label,
+            "balance_amount": format_amount(&statement.balance.abs()),
+            "colocataire_block": colocataire_block,
+            "settlement_sentence": settlement_sentence,
+            "issued_on": format_date_fr(snapshot.generated_at.date_naive()),
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+
+    /// Generate the deposit settlement letter HTML from its snapshot.
+    pub fn render_deposit_settlement_html(&self, snapshot: &DepositSnapshot) -> TemplateResult<String> {
+        let settlement = &snapshot.settlement;
+        let lessee_names: Vec<String> =
+            snapshot.parties.lessees.iter().map(|l| l.full_name.clone()).collect();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+
+        let deduction_rows = snapshot
+            .deductions
+            .iter()
+            .map(|deduction| {
+                let justification = deduction
+                    .justification
+                    .as_deref()
+                    .filter(|j| !j.is_empty())
+                    .map(|j| format!(" <em>({})</em>", j))
+                    .unwrap_or_default();
+                format!(
+                    "<tr><td>Retenue : {}{}</td><td class=\"amount\">- {} €</td></tr>",
+                    deduction.label,
+                    justification,
+                    format_amount(&deduction.amount)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let penalty_row = if settlement.late_penalty.is_zero() {
+            String::new()
+        } else {
+            format!(
+                "<tr><td>Majoration pour restitution tardive ({} mois commencé(s))</td><td class=\"amount\">{} €</td></tr>",
+                settlement.months_late,
+                format_amount(&settlement.late_penalty)
+            )
+        };
+        let inventory_sentence = match snapshot.exit_inventory_conforms {
+            Some(true) => "L'état des lieux de sortie est conforme à l'état des lieux d'entrée.",
+            Some(false) => "L'état des lieux de sortie fait apparaître des différences avec l'état des lieux d'entrée.",
+            None => "",
+        };
+        let settlement_sentence = if settlement.tenant_balance_due.is_zero() {
+            format!(
+                "La somme de <strong>{} €</strong> est restituée le {}.",
+                format_amount(&settlement.total_due_to_tenant),
+                format_date_fr(settlement.settled_on)
+            )
+        } else {
+            format!(
+                "Les retenues excèdent le dépôt de garantie : la somme de <strong>{} €</strong> reste due au bailleur.",
+                format_amount(&settlement.tenant_balance_due)
+            )
+        };
+        let optional_date = |date: Option<NaiveDate>| date.map(format_date_fr).unwrap_or_else(|| "—".to_string());
+
+        let context = json!({
+            "landlord_block": Self::render_landlord_block(&snapshot.parties),
+            "landlord_signature": Self::render_landlord_signature(&snapshot.parties),
+            "lessees_block": lessees_block,
+            "property_address": snapshot.property_address,
+            "lease_start_date": format_date_fr(snapshot.lease_start_date),
+            "keys_returned_on": optional_date(snapshot.keys_returned_on),
+            "return_deadline": optional_date(settlement.return_deadline),
+            "inventory_sentence": inventory_sentence,
+            "amount_held": format_amount(&settlement.amount_held),
+            "deduction_rows": deduction_rows,
+            "total_deductions": format_amount(&settlement.total_deductions),
+            "penalty_row": penalty_row,
+            "total_due_to_tenant": format_amount(&settlement.total_due_to_tenant),
+            "settlement_sentence": settlement_sentence,
+            "issued_on": format_date_fr(snapshot.generated_at.date_naive()),
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+
+    /// Generate the avenant HTML from its snapshot.
+    pub fn render_amendment_html(&self, snapshot: &AmendmentSnapshot) -> TemplateResult<String> {
+        let lessee_names: Vec<String> =
+            snapshot.parties.lessees.iter().map(|l| l.full_name.clone()).collect();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        let change_rows = snapshot
+            .changes
+            .iter()
+            .map(|line| {
+                format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    line.label, line.before, line.after
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let signed_on = snapshot
+            .signed_at
+            .map(|signed_at| format!(", signé le {}", format_date_fr(signed_at.date_naive())))
+            .unwrap_or_default();
+        let lessee_signatures = lessee_names
+            .iter()
+            .map(|name| format!("<div class=\"signature-line\">{}<br>Le locataire</div>", name))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let context = json!({
+            "sequence_number": snapshot.sequence_number,
+            "landlord_block": Self::render_landlord_block(&snapshot.parties),
+            "landlord_signature": Self::render_landlord_signature(&snapshot.parties),
+            "lessees_block": lessees_block,
+            "lessee_signatures": lessee_signatures,
+            "property_address": snapshot.property_address,
+            "lease_start_date": format_date_fr(snapshot.lease_start_date),
+            "signed_on": signed_on,
+            "summary": snapshot.summary,
+            "change_rows": change_rows,
+            "effective_date": format_date_fr(snapshot.effective_date),
+            "issued_on": format_date_fr(snapshot.generated_at.date_naive()),
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+
+    /// Render the congé letter. The sender is the party giving notice; the
+    /// body states the notice period, the end date and, for a landlord, the
+    /// ground the notice rests on.
+    pub fn render_notice_html(&self, snapshot: &NoticeSnapshot) -> TemplateResult<String> {
+        let lessee_names: Vec<String> =
+            snapshot.parties.lessees.iter().map(|l| l.full_name.clone()).collect();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        let lessee_signatures = lessee_names
+            .iter()
+            .map(|name| format!("<div class=\"signature-line\">{}<br>Le locataire</div>", name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let by_tenant = snapshot.given_by == "tenant";
+        let names = join_names(&lessee_names);
+        let lease_reference = format!(
+            "en vertu du bail ayant pris effet le {}",
+            format_date_fr(snapshot.lease_start_date)
+        );
+        let period = format!(
+            "Le délai de préavis de {} mois court à compter de la réception de la présente lettre, le {}. \
+             Le bail prendra fin le <strong>{}</strong>.",
+            snapshot.notice_months,
+            format_date_fr(snapshot.served_on),
+            format_date_fr(snapshot.effective_date)
+        );
+
+        let mut paragraphs = Vec::new();
+        let (title, subtitle, legal_notice) = if by_tenant {
+            paragraphs.push(format!(
+                "Par la présente, {}, locataire(s) du logement désigné ci-dessus {}, donne(nt) congé de ce logement.",
+                names, lease_reference
+            ));
+            paragraphs.push(period);
+            if let Some(label) = snapshot.reduced_notice_reason.as_deref().and_then(reduced_notice_reason_label) {
+                paragraphs.push(format!(
+                    "Le préavis est réduit à un mois en application de l'article 15-I de la loi du 6 juillet 1989 : {}. \
+                     Le justificatif correspondant est joint à la présente.",
+                    label.to_lowercase()
+                ));
+            }
+            let legal_notice = match snapshot.lease_type.as_str() {
+                "unfurnished" => "Article 15-I de la loi n° 89-462 du 6 juillet 1989 : le locataire peut donner congé à tout moment, \
+                    moyennant un préavis de trois mois, réduit à un mois dans les cas prévus par la loi.",
+                "ancillary" => "Article 1736 du code civil : le congé est donné en observant les délais fixés par le bail.",
+                _ => "Article 25-8 de la loi n° 89-462 du 6 juillet 1989 : le locataire peut donner congé à tout moment, \
+                    moyennant un préavis d'un mois.",
+            };
+            ("CONGÉ DONNÉ PAR LE LOCATAIRE", "Lettre recommandée avec accusé de réception".to_string(), legal_notice)
+        } else {
+            paragraphs.push(format!(
+                "Par la présente, le bailleur donne congé à {} du logement désigné ci-dessus, loué {}.",
+                names, lease_reference
+            ));
+            paragraphs.push(period);
+            match snapshot.ground.as_deref() {
+                Some("vente") => {
+                    paragraphs.push("Ce congé est fondé sur la décision de vendre le logement.".to_string());
+                    if let Some(price) = &snapshot.sale_price {
+                        paragraphs.push(if snapshot.lease_type == "unfurnished" {
+                            format!(
+                                "Conformément à l'article 15-II de la loi du 6 juillet 1989, le présent congé vaut offre de vente \
+                                 au profit du locataire, au prix de <strong>{} €</strong>. Cette offre est valable pendant \
+                                 les deux premiers mois du délai de préavis.",
+                                format_amount(price)
+                            )
+                        } else {
+                            format!("Le prix de vente envisagé est de {} €.", format_amount(price))
+                        });
+                    }
+                }
+                Some("reprise") => paragraphs.push(format!(
+                    "Ce congé est fondé sur la reprise du logement pour y habiter, au bénéfice de {} ({}).",
+                    snapshot.beneficiary_name.as_deref().unwrap_or_default(),
+                    snapshot.beneficiary_relationship.as_deref().unwrap_or_default()
+                )),
+                Some("motif_legitime") => paragraphs.push(format!(
+                    "Ce congé est fondé sur un motif légitime et sérieux : {}",
+                    snapshot.details.as_deref().unwrap_or_default()
+                )),
+                _ => {}
+            }
+            let legal_notice = match snapshot.lease_type.as_str() {
+                "unfurnished" => "Article 15 de la loi n° 89-462 du 6 juillet 1989 : le congé du bailleur est délivré au moins six mois \
+                    avant le terme du bail et justifié par sa décision de reprendre ou de vendre le logement, ou par un \
+                    motif légitime et sérieux. Une notice d'information relative aux obligations du bailleur et aux voies \
+                    de recours du locataire est jointe au présent congé.",
+                "ancillary" => "Article 1736 du code civil : le congé est donné en observant les délais fixés par le bail.",
+                _ => "Article 25-8 de la loi n° 89-462 du 6 juillet 1989 : le congé du bailleur est délivré au moins trois mois \
+                    avant le terme du bail et motivé par sa décision de reprendre ou de vendre le logement, ou par un \
+                    motif légitime et sérieux.",
+            };
+            let subtitle = snapshot
+                .ground
+                .as_deref()
+                .and_then(landlord_notice_ground_label)
+                .map_or_else(|| "Lettre recommandée avec accusé de réception".to_string(), |label| format!("Motif : {}", label));
+            ("CONGÉ DONNÉ PAR LE BAILLEUR", subtitle, legal_notice)
+        };
+
+        let landlord_block = Self::render_landlord_block(&snapshot.parties);
+        let (sender_block, recipient_block, sender_signature) = if by_tenant {
+            (lessees_block, landlord_block, lessee_signatures)
+        } else {
+            (landlord_block, lessees_block, Self::render_landlord_signature(&snapshot.parties))
+        };
+
+        let context = json!({
+            "title": title,
+            "subtitle": subtitle,
+            "sender_block": sender_block,
+            "recipient_block": recipient_block,
+            "property_address": snapshot.property_address,
+            "body": paragraphs.iter().map(|p| format!("<p>{}</p>", p)).collect::<Vec<_>>().join("\n"),
+            "legal_notice": legal_notice,
+            "issued_on": format_date_fr(snapshot.generated_at.date_naive()),
+            "sender_signature": sender_signature,
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+    /// Generate the état des lieux HTML from its snapshot: one table per room,
+    /// then meter readings and keys.
+    pub fn render_inventory_html(&self, snapshot: &InventorySnapshot) -> TemplateResult<String> {
+        let lessee_names: Vec<String> =
+            snapshot.parties.lessees.iter().map(|l| l.full_name.clone()).collect();
+        let lessees_block = lessee_names
+            .iter()
+            .map(|name| format!("<strong>{}</strong>", name))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        let lessee_signatures = lessee_names
+            .iter()
+            .map(|name| format!("<div class=\"signature-line\">{}<br>Le locataire</div>", name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let by_entry = snapshot.kind == "entry";
+
+        let room_tables = snapshot
+            .rooms
+            .iter()
+            .map(|room| {
+                let rows = room
+                    .elements
+                    .iter()
+                    .map(|element| {
+                        format!(
+                            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                            element.name,
+                            condition_label(&element.condition).unwrap_or(&element.condition),
+                            element.comment.as_deref().unwrap_or_default()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let comment = room
+                    .comment
+                    .as_deref()
+                    .filter(|c| !c.is_empty())
+                    .map(|c| format!("<div class=\"section-content\"><p><em>{}</em></p></div>", c))
+                    .unwrap_or_default();
+                format!(
+                    "<div class=\"section-title\">{}</div>\n<table>\n<tr><th>Élément</th><th>État</th><th>Observations</th></tr>\n{}\n</table>\n{}",
+                    room.name, rows, comment
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let meters_table = if snapshot.meters.is_empty() {
+            "<div class=\"section-content\"><p>Aucun relevé.</p></div>".to_string()
+        } else {
+            let rows = snapshot
+                .meters
+                .iter()
+                .map(|meter| {
+                    format!(
+                        "<tr><td>{}</td><td>{}</td><td class=\"amount\">{}</td></tr>",
+                        meter.meter,
+                        meter.serial_number.as_deref().unwrap_or("—"),
+                        meter.reading
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!(
+                "<table>\n<tr><th>Compteur</th><th>N° de série</th><th>Index</th></tr>\n{}\n</table>",
+                rows
+            )
+        };
+        let keys_table = if snapshot.keys.is_empty() {
+            "<div class=\"section-content\"><p>Aucune clé.</p></div>".to_string()
+        } else {
+            let rows = snapshot
+                .keys
+                .iter()
+                .map(|key| format!("<tr><td>{}</td><td class=\"amount\">{}</td></tr>", key.label, key.count))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("<table>\n<tr><th>Désignation</th><th>Nombre</th></tr>\n{}\n</table>", rows)
+        };
+        let general_comment = snapshot
+            .general_comment
+            .as_deref()
+            .map(|c| {
+                format!(
+                    "<div class=\"section-title\">Observations générales</div>\n<div class=\"section-content\"><p>{}</p></div>",
+                    c
+                )
+            })
+            .unwrap_or_default();
+
+        let context = json!({
+            "title": if by_entry { "ÉTAT DES LIEUX D'ENTRÉE" } else { "ÉTAT DES LIEUX DE SORTIE" },
+            "performed_on": format_date_fr(snapshot.performed_on),
+            "lease_start_date": format_date_fr(snapshot.lease_start_date),
+            "landlord_block": Self::render_landlord_block(&snapshot.parties),
+            "landlord_signature": Self::render_landlord_signature(&snapshot.parties),
+            "lessees_block": lessees_block,
+            "lessee_signatures": lessee_signatures,
+            "property_address": snapshot.property_address,
+            "room_tables": room_tables,
+            "meters_table": meters_table,
+            "keys_direction": if by_entry { "remis au locataire" } else { "restitués au bailleur" },
+            "keys_table": keys_table,
+            "general_comment": general_comment,
+        });
+
+        self.render_section(&snapshot.template_version, "layout", &context)
+    }
+}
+
+const FRENCH_MONTHS: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin",
+    "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+];
+
+/// "octobre 2026" → "Octobre 2026"
+pub(crate) fn period_label(month: i32, year: i32) -> String {
+    let name = usize::try_from(month - 1)
+        .ok()
+        .and_then(|i| FRENCH_MONTHS.get(i))
+        .copied()
+        .unwrap_or("");
+    let mut chars = name.chars();
+    let capitalised = match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    };
+    format!("{} {}", capitalised, year)
+}
+
+/// What an ancillary lease lets, as named in its documents.
+fn premises_label(property_type: &str) -> &'static str {
+    match property_type {
+        "parking" => "un emplacement de stationnement",
+        "garage" => "un garage",
+        "storage" => "un local de stockage",
+        _ => "des locaux",
+    }
+}
+
+/// Format a date the French way, e.g. "1er octobre 2026".
+fn format_date_fr(date: NaiveDate) -> String {
+    let day = if date.day() == 1 { "1er".to_string() } else { date.day().to_string() };
+    format!("{} {} {}", day, FRENCH_MONTHS[date.month0() as usize], date.year())
+}
+
+/// Format an amount with two decimals and a decimal comma, e.g. "850,00".
+pub(crate) fn format_amount(amount: &BigDecimal) -> String {
+    amount
+        .with_scale_round(2, bigdecimal::RoundingMode::HalfUp)
+        .to_string()
+        .replace('.', ",")
+}
+
+/// "A", "A et B", "A, B et C"
+fn join_names(names: &[String]) -> String {
+    match names.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} et {}", rest.join(", "), last),
+    }
+}
+
+/// Resolve the legal_templates directory path (overridable via env for deployment).
+pub fn legal_templates_dir() -> PathBuf {
+    std::env::var("LEGAL_TEMPLATES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("src/legal_templates"))
+}
+
+/// Resolve the wkhtmltopdf binary path.
+///
+/// Resolution order:
+/// 1. `WKHTMLTOPDF_PATH` env var (explicit override, used in Docker/dev).
+/// 2. Well-known default install locations (so a standard install works even
+///    when the binary is not on `PATH` and no env var is set).
+/// 3. Fall back to the bare name `wkhtmltopdf` (relies on `PATH`).
+pub fn resolve_wkhtmltopdf_path() -> String {
+    if let Ok(path) = std::env::var("WKHTMLTOPDF_PATH") {
+        if !path.trim().is_empty() {
+            return path;
+        }
+    }
+
+    const DEFAULT_LOCATIONS: &[&str] = &[
+        // Windows default install location
+        r"C:\Program Files\wkhtmltopdf\bin\wkhtmltopdf.exe",
+        r"C:\Program Files (x86)\wkhtmltopdf\bin\wkhtmltopdf.exe",
+        // Common Unix locations
+        "/usr/bin/wkhtmltopdf",
+        "/usr/local/bin/wkhtmltopdf",
+    ];
+
+    for candidate in DEFAULT_LOCATIONS {
+        if Path::new(candidate).exists() {
+            return candidate.to_string();
+        }
+    }
+
+    "wkhtmltopdf".to_string()
 }
 
 /// PDF renderer using wkhtmltopdf
@@ -522,6 +1210,17 @@ impl PdfRenderer {
         })
     }
 
+    /// Create a renderer from the environment: `LEGAL_TEMPLATES_DIR`,
+    /// `WKHTMLTOPDF_PATH` and `PDF_GENERATION_TIMEOUT_SECS`.
+    pub fn from_env() -> TemplateResult<Self> {
+        let timeout_secs = std::env::var("PDF_GENERATION_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(30);
+
+        Self::new(&legal_templates_dir(), resolve_wkhtmltopdf_path(), timeout_secs)
+    }
+
     /// Render the canonical lease HTML from a snapshot.
     /// This is the single source of truth shared by the on-screen preview and the PDF.
     pub fn render_html(&self, snapshot: &CanonicalSnapshot) -> TemplateResult<String> {
@@ -532,7 +1231,53 @@ impl PdfRenderer {
     pub async fn generate_pdf(&self, snapshot: &CanonicalSnapshot) -> TemplateResult<Vec<u8>> {
         // Render HTML from snapshot
         let html = self.template_cache.render_full_html(snapshot)?;
-        
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the quittance PDF from a frozen receipt snapshot.
+    pub async fn generate_quittance_pdf(&self, snapshot: &ReceiptSnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_quittance_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the avis d'échéance PDF from a frozen rent call snapshot.
+    pub async fn generate_rent_call_pdf(&self, snapshot: &RentCallSnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_rent_call_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the charges regularisation statement PDF from its snapshot.
+    pub async fn generate_regularisation_pdf(&self, snapshot: &RegularisationSnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_regularisation_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the deposit settlement letter PDF from its snapshot.
+    pub async fn generate_deposit_settlement_pdf(&self, snapshot: &DepositSnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_deposit_settlement_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the avenant PDF from its snapshot.
+    pub async fn generate_amendment_pdf(&self, snapshot: &AmendmentSnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_amendment_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the congé letter PDF from its snapshot.
+    pub async fn generate_notice_pdf(&self, snapshot: &NoticeSnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_notice_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Generate the état des lieux PDF from its snapshot.
+    pub async fn generate_inventory_pdf(&self, snapshot: &InventorySnapshot) -> TemplateResult<Vec<u8>> {
+        let html = self.template_cache.render_inventory_html(snapshot)?;
+        self.html_to_pdf(&html)
+    }
+
+    /// Convert a rendered HTML document to PDF bytes with wkhtmltopdf.
+    fn html_to_pdf(&self, html: &str) -> TemplateResult<Vec<u8>> {
         // Call wkhtmltopdf to convert HTML to PDF.
         // Global options (margins, page size, encoding) MUST come before the
         // input/output arguments, otherwise wkhtmltopdf errors with
@@ -656,6 +1401,56 @@ mod tests {
         assert!(html.contains("Animaux autorisés."));
     }
 
+    #[test]
+    fn mobility_lease_renders_its_section_variants() {
+        let cache = load_cache();
+        let mut snapshot = make_snapshot(false, "compliant", None);
+        snapshot.lease_terms.lease_kind = "mobility".to_string();
+        snapshot.lease_terms.mobility_reason = Some("mutation_professionnelle".to_string());
+        snapshot.lease_terms.mobility_justification = Some("Mutation à Lyon".to_string());
+        let html = cache.render_full_html(&snapshot).expect("render ok");
+        assert!(html.contains("bail mobilité régi par les dispositions du"));
+        assert!(html.contains("Mutation professionnelle"));
+        assert!(html.contains("Mutation à Lyon"));
+        assert!(html.contains("garantie Visale"));
+        assert!(!html.contains("deux mois de loyer hors charges"));
+
+        let standard = cache
+            .render_full_html(&make_snapshot(false, "compliant", None))
+            .expect("render ok");
+        assert!(!standard.contains("Visale"));
+    }
+
+    #[test]
+    fn ancillary_lease_renders_civil_code_contract() {
+        let cache = load_cache();
+        let mut snapshot = make_snapshot(false, "compliant", None);
+        snapshot.legal_template_version = "bail-annexe-2026-10-17".to_string();
+        snapshot.lease_terms.lease_type = "ancillary".to_string();
+        snapshot.property.property_type = "garage".to_string();
+        snapshot.financial_terms.monthly_rent = "90".to_string();
+        let html = cache.render_full_html(&snapshot).expect("render ok");
+        assert!(html.contains("un garage"));
+        assert!(html.contains("code civil"));
+        assert!(html.contains("90 €"));
+        assert!(html.contains("Clause résolutoire obligatoire."));
+        assert!(!html.contains("DPE"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn unfurnished_template_omits_furniture_inventory() {
+        let cache = load_cache();
+        let mut snapshot = make_snapshot(false, "compliant", None);
+        snapshot.legal_template_version = "bail-nu-2026-10-17".to_string();
+        snapshot.lease_terms.lease_type = "unfurnished".to_string();
+        let html = cache.render_full_html(&snapshot).expect("render ok");
+        assert!(html.contains("LOGEMENT NU"));
+        assert!(html.contains("un mois de loyer hors charges"));
+        assert!(!html.contains("Inventaire du mobilier"));
+        assert!(html.contains("CLAUSE RÉSOLUTOIRE"));
+    }
+
     #[test]
     fn preview_and_pdf_share_same_html_source() {
         // The PdfRenderer.render_html method and the PDF generation path both call
@@ -799,5 +1594,362 @@ mod tests {
         assert!(!html.contains("immatriculée au RCS"));
         assert!(!html.contains("en qualité de"));
     }
+
+    fn make_receipt_snapshot(lease: &CanonicalSnapshot, month: i32, year: i32) -> ReceiptSnapshot {
+        use crate::models::receipt::Receipt;
+        use bigdecimal::BigDecimal;
+        use std::str::FromStr;
+        let receipt = Receipt {
+            id: Uuid::new_v4(),
+            lease_id: Uuid::new_v4(),
+            period_month: month,
+            period_year: year,
+            base_rent: BigDecimal::from_str("650.00").unwrap(),
+            charges: BigDecimal::from_str("50.5").unwrap(),
+            total_amount: BigDecimal::from_str("700.50").unwrap(),
+            amount_paid: BigDecimal::from_str("700.50").unwrap(),
+            payment_date: NaiveDate::from_ymd_opt(year, month as u32, 5).unwrap(),
+            status: "generated".to_string(),
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        };
+        ReceiptSnapshot::from_lease_snapshot(lease, &receipt, "quittance-2026-10-17".to_string())
+            .expect("valid period")
+    }
+
+    #[test]
+    fn quittance_renders_period_amounts_and_address() {
+        let cache = load_cache();
+        let mut s = make_snapshot(false, "compliant", None);
+        s.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        s.lease_terms.end_date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let html = cache
+            .render_quittance_html(&make_receipt_snapshot(&s, 10, 2026))
+            .expect("render ok");
+        assert!(html.contains("Octobre 2026"));
+        assert!(html.contains("650,00"));
+        assert!(html.contains("50,50"));
+        assert!(html.contains("700,50"));
+        assert!(html.contains("1 rue de Paris"));
+        assert!(html.contains("<strong>1er octobre 2026</strong> au <strong>31 octobre 2026</strong>"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn quittance_covers_only_leased_days_in_first_month() {
+        let cache = load_cache();
+        let mut s = make_snapshot(false, "compliant", None);
+        s.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        s.lease_terms.end_date = NaiveDate::from_ymd_opt(2027, 10, 14).unwrap();
+        let html = cache
+            .render_quittance_html(&make_receipt_snapshot(&s, 10, 2026))
+            .expect("render ok");
+        assert!(html.contains("<strong>15 octobre 2026</strong> au <strong>31 octobre 2026</strong>"));
+    }
+
+    #[test]
+    fn quittance_lists_every_colocataire() {
+        use crate::models::canonical_snapshot::LesseeParty;
+        let cache = load_cache();
+        let mut s = make_snapshot(true, "compliant", None);
+        s.parties.lessees = ["Marie Martin", "Paul Durand", "Léa Petit"]
+            .iter()
+            .map(|name| LesseeParty {
+                full_name: name.to_string(),
+                address: String::new(),
+                email: None,
+                birth_date: None,
+                birth_place: None,
+            })
+            .collect();
+        let html = cache
+            .render_quittance_html(&make_receipt_snapshot(&s, 10, 2026))
+            .expect("render ok");
+        assert!(html.contains("Marie Martin, Paul Durand et Léa Petit"));
+    }
+
+    #[test]
+    fn partial_payment_renders_recu_template() {
+        use bigdecimal::BigDecimal;
+        use std::str::FromStr;
+        let cache = load_cache();
+        let s = make_snapshot(false, "compliant", None);
+        let mut receipt = make_receipt_snapshot(&s, 10, 2026);
+        receipt.template_version = "recu-2026-10-17".to_string();
+        receipt.document_kind = "recu".to_string();
+        receipt.amounts.amount_paid = BigDecimal::from_str("350").unwrap();
+        receipt.amounts.balance_due = BigDecimal::from_str("350.5").unwrap();
+        let html = cache.render_quittance_html(&receipt).expect("render ok");
+        assert!(html.contains("REÇU DE PAIEMENT PARTIEL"));
+        assert!(html.contains("350,00"));
+        assert!(html.contains("350,50"));
+        assert!(html.contains("ne vaut pas"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn rent_call_lists_arrears_and_due_date() {
+        use crate::models::rent_call::RentCall;
+        use crate::models::rent_call_snapshot::ArrearsLine;
+        use std::str::FromStr;
+        let cache = load_cache();
+        let mut s = make_snapshot(false, "compliant", None);
+        s.lease_terms.start_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        s.lease_terms.end_date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        let rent_call = RentCall {
+            id: Uuid::new_v4(),
+            lease_id: s.lease_id,
+            period_month: 11,
+            period_year: 2026,
+            due_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            base_rent: BigDecimal::from_str("650").unwrap(),
+            charges: BigDecimal::from_str("50").unwrap(),
+            regularisation: BigDecimal::from(0),
+            arrears: BigDecimal::from_str("200").unwrap(),
+            total_due: BigDecimal::from_str("900").unwrap(),
+            status: "issued".to_string(),
+            receipt_id: None,
+            email_sent_at: None,
+            pdf_path: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let arrears = vec![ArrearsLine {
+            period_month: 9,
+            period_year: 2026,
+            balance: BigDecimal::from_str("200").unwrap(),
+        }];
+        let snapshot = RentCallSnapshot::from_lease_snapshot(
+            &s,
+            &rent_call,
+            arrears,
+            "avis-echeance-2026-10-17".to_string(),
+        )
+        .unwrap();
+        let html = cache.render_rent_call_html(&snapshot).expect("render ok");
+        assert!(html.contains("AVIS D'ÉCHÉANCE"));
+        assert!(html.contains("Novembre 2026"));
+        assert!(html.contains("<strong>1er novembre 2026</strong>"));
+        assert!(html.contains("Solde impayé — Septembre 2026"));
+        assert!(html.contains("900,00"));
+        assert!(!html.contains("Régularisation"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn regularisation_statement_splits_colocataires() {
+        use crate::models::charges_regularisation::{RegularisationExpense, RegularisationSnapshot};
+        use crate::services::charges_regularisation::compute_statement;
+        use std::str::FromStr;
+        let cache = load_cache();
+        let s = make_snapshot(false, "compliant", None);
+        let expense = RegularisationExpense {
+            id: Uuid::new_v4(),
+            regularisation_id: Uuid::new_v4(),
+            category: "ascenseurs".to_string(),
+            label: "Contrat d'entretien".to_string(),
+            amount: BigDecimal::from_str("600").unwrap(),
+            covered_start: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            covered_end: NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            created_at: None,
+        };
+        let statement = compute_statement(
+            2025,
+            (NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
+            &[expense],
+            &BigDecimal::from_str("480").unwrap(),
+            &["Alice Martin".to_string(), "Bruno Petit".to_string()],
+        );
+        let snapshot = RegularisationSnapshot {
+            id: Uuid::new_v4(),
+            regularisation_id: Uuid::new_v4(),
+            lease_id: s.lease_id,
+            template_version: "regularisation-charges-2026-10-17".to_string(),
+            generated_at: chrono::Utc::now(),
+            language: "fr".to_string(),
+            parties: s.parties.clone(),
+            property_address: "1 rue de Paris".to_string(),
+            statement,
+        };
+        let html = cache.render_regularisation_html(&snapshot).expect("render ok");
+        assert!(html.contains("RÉGULARISATION ANNUELLE DES CHARGES"));
+        assert!(html.contains("Ascenseurs et monte-charge"));
+        assert!(html.contains("Complément dû par le locataire"));
+        assert!(html.contains("120,00"));
+        assert!(html.contains("Répartition entre colocataires"));
+        assert!(html.contains("Bruno Petit"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn deposit_letter_itemises_deductions_and_penalty() {
+        use crate::models::deposit::{DepositDeduction, DepositSnapshot};
+        use crate::services::deposit::settle;
+        use std::str::FromStr;
+        let cache = load_cache();
+        let s = make_snapshot(false, "compliant", None);
+        let deductions = vec![DepositDeduction {
+            id: Uuid::new_v4(),
+            deposit_id: Uuid::new_v4(),
+            category: "repairs".to_string(),
+            label: "Remplacement moquette".to_string(),
+            amount: BigDecimal::from_str("150").unwrap(),
+            receipt_id: None,
+            justification: Some("Devis n° 42".to_string()),
+            created_at: None,
+        }];
+        let settlement = settle(
+            &BigDecimal::from(800),
+            &deductions,
+            NaiveDate::from_ymd_opt(2026, 4, 15),
+            &BigDecimal::from(750),
+            NaiveDate::from_ymd_opt(2026, 5, 20).unwrap(),
+        );
+        let snapshot = DepositSnapshot {
+            id: Uuid::new_v4(),
+            deposit_id: Uuid::new_v4(),
+            lease_id: s.lease_id,
+            template_version: "restitution-depot-2026-10-17".to_string(),
+            generated_at: chrono::Utc::now(),
+            language: "fr".to_string(),
+            parties: s.parties.clone(),
+            property_address: "1 rue de Paris".to_string(),
+            lease_start_date: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            keys_returned_on: NaiveDate::from_ymd_opt(2026, 2, 15),
+            exit_inventory_conforms: Some(false),
+            deductions,
+            settlement,
+        };
+        let html = cache.render_deposit_settlement_html(&snapshot).expect("render ok");
+        assert!(html.contains("RESTITUTION DU DÉPÔT DE GARANTIE"));
+        assert!(html.contains("Remplacement moquette <em>(Devis n° 42)</em>"));
+        assert!(html.contains("Majoration pour restitution tardive (2 mois"));
+        assert!(html.contains("800,00"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn amendment_lists_changes_with_effective_date() {
+        use crate::models::lease_amendment::{AmendmentChangeLine, AmendmentSnapshot};
+        let cache = load_cache();
+        let s = make_snapshot(false, "compliant", None);
+        let snapshot = AmendmentSnapshot {
+            id: Uuid::new_v4(),
+            amendment_id: Uuid::new_v4(),
+            lease_id: s.lease_id,
+            template_version: "avenant-2026-10-17".to_string(),
+            generated_at: chrono::Utc::now(),
+            language: "fr".to_string(),
+            sequence_number: 2,
+            effective_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            summary: "Location d'une place de stationnement".to_string(),
+            parties: s.parties.clone(),
+            property_address: "1 rue de Paris".to_string(),
+            lease_start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            signed_at: None,
+            changes: vec![AmendmentChangeLine {
+                label: "Loyer mensuel hors charges".to_string(),
+                before: "850.00".to_string(),
+                after: "900.00".to_string(),
+            }],
+        };
+        let html = cache.render_amendment_html(&snapshot).expect("render ok");
+        assert!(html.contains("AVENANT N°2 AU CONTRAT DE LOCATION"));
+        assert!(html.contains("<tr><td>Loyer mensuel hors charges</td><td>850.00</td><td>900.00</td></tr>"));
+        assert!(html.contains("1er novembre 2026"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn landlord_notice_for_sale_states_the_offer() {
+        use crate::models::lease_notice::NoticeSnapshot;
+        let cache = load_cache();
+        let s = make_snapshot(false, "compliant", None);
+        let snapshot = NoticeSnapshot {
+            id: Uuid::new_v4(),
+            notice_id: Uuid::new_v4(),
+            lease_id: s.lease_id,
+            template_version: "conge-2026-10-17".to_string(),
+            generated_at: chrono::Utc::now(),
+            language: "fr".to_string(),
+            given_by: "landlord".to_string(),
+            parties: s.parties.clone(),
+            property_address: "1 rue de Paris".to_string(),
+            lease_type: "unfurnished".to_string(),
+            lease_start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            served_on: NaiveDate::from_ymd_opt(2028, 3, 1).unwrap(),
+            notice_months: 6,
+            effective_date: NaiveDate::from_ymd_opt(2029, 1, 1).unwrap(),
+            ground: Some("vente".to_string()),
+            reduced_notice_reason: None,
+            beneficiary_name: None,
+            beneficiary_relationship: None,
+            sale_price: Some(BigDecimal::from(250_000)),
+            details: None,
+        };
+        let html = cache.render_notice_html(&snapshot).expect("render ok");
+        assert!(html.contains("CONGÉ DONNÉ PAR LE BAILLEUR"));
+        assert!(html.contains("Motif : Vente du logement"));
+        assert!(html.contains("250000,00 €"));
+        assert!(html.contains("<strong>1er janvier 2029</strong>"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn exit_inventory_renders_room_grid_meters_and_keys() {
+        use crate::models::inventory::{InventoryElement, InventoryRoom, InventorySnapshot, KeyHandover, MeterReading};
+        let cache = load_cache();
+        let s = make_snapshot(false, "compliant", None);
+        let snapshot = InventorySnapshot {
+            id: Uuid::new_v4(),
+            inventory_id: Uuid::new_v4(),
+            lease_id: s.lease_id,
+            template_version: "etat-des-lieux-2026-10-17".to_string(),
+            generated_at: chrono::Utc::now(),
+            language: "fr".to_string(),
+            kind: "exit".to_string(),
+            performed_on: NaiveDate::from_ymd_opt(2029, 1, 2).unwrap(),
+            parties: s.parties.clone(),
+            property_address: "1 rue de Paris".to_string(),
+            lease_start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            rooms: vec![InventoryRoom {
+                name: "Séjour".to_string(),
+                elements: vec![InventoryElement {
+                    name: "Parquet".to_string(),
+                    condition: "mauvais".to_string(),
+                    comment: Some("Rayures profondes".to_string()),
+                }],
+                comment: None,
+            }],
+            meters: vec![MeterReading {
+                meter: "Eau froide".to_string(),
+                serial_number: None,
+                reading: "0421".to_string(),
+            }],
+            keys: vec![KeyHandover { label: "Porte d'entrée".to_string(), count: 2 }],
+            general_comment: None,
+        };
+        let html = cache.render_inventory_html(&snapshot).expect("render ok");
+        assert!(html.contains("ÉTAT DES LIEUX DE SORTIE"));
+        assert!(html.contains("<tr><td>Parquet</td><td>Mauvais état</td><td>Rayures profondes</td></tr>"));
+        assert!(html.contains("Eau froide"));
+        assert!(html.contains("restitués au bailleur"));
+        assert!(html.contains("Fait le 2 janvier 2029"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn quittance_uses_sci_designation() {
+        let cache = load_cache();
+        let mut s = make_snapshot(false, "compliant", None);
+        s.apply_organization_landlord(&make_sci_org(false));
+        let html = cache
+            .render_quittance_html(&make_receipt_snapshot(&s, 10, 2026))
+            .expect("render ok");
+        assert!(html.contains("SCI MD16"));
+        assert!(html.contains("représentée par Thomas Martin (Gérant)"));
+    }
 }
 
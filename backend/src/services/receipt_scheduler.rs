@@ -0,0 +1,584 @@
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::receipt::Receipt;
+use crate::routes::payments::reconcile_lease;
+use crate::routes::rent_calls::issue_rent_call;
+use crate::routes::rent_history::rent_periods;
+use crate::models::rent_history::{amounts_over, RentPeriod};
+use crate::routes::receipts::{current_lease_snapshot, persist_receipt_snapshot};
+use crate::services::lease_renewal::renew_due_leases;
+
+/// Settings for the background job that issues receipts every month.
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    pub enabled: bool,
+    /// Day of the month (1-28) on which the monthly run happens.
+    pub run_day: u32,
+    /// How often the job wakes up to check whether a run is due.
+    pub check_interval: Duration,
+}
+
+impl SchedulerConfig {
+    /// Read `RECEIPT_SCHEDULER_ENABLED` (default true), `RECEIPT_SCHEDULER_DAY`
+    /// (default 1) and `RECEIPT_SCHEDULER_INTERVAL_SECS` (default 3600).
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("RECEIPT_SCHEDULER_ENABLED")
+            .map(|v| !matches!(v.to_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        let run_day = std::env::var("RECEIPT_SCHEDULER_DAY")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|d| d.clamp(1, 28))
+            .unwrap_or(1);
+        let check_interval = std::env::var("RECEIPT_SCHEDULER_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(3600));
+
+        SchedulerConfig { enabled, run_day, check_interval }
+    }
+}
+
+/// Start the scheduler loop on the Tokio runtime.
+pub fn spawn(db: Database, config: SchedulerConfig) {
+    if !config.enabled {
+        tracing::info!("Receipt scheduler disabled");
+        return;
+    }
+
+    tracing::info!(
+        "📅 Receipt scheduler running on day {} of each month (checking every {:?})",
+        config.run_day,
+        config.check_interval
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.check_interval);
+        loop {
+            interval.tick().await;
+            match run_pending(&db, &config, Utc::now().date_naive()).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Receipt scheduler issued {} receipt(s)", count),
+                Err(e) => tracing::error!("Receipt scheduler run failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Execute every monthly run that is due but not yet recorded in
+/// `receipt_scheduler_runs`. Each lease's part of a run is recorded in
+/// `receipt_scheduler_lease_runs`, and the month itself only once every lease
+/// went through: restarting the server never issues a month twice, and a
+/// lease that failed is retried on the next check. Every month since the
+/// first recorded run is caught up, however long the server was stopped.
+/// Each run also issues the avis d'échéance of the periods invoiced next.
+/// Leases reaching their term are renewed first, on every check, so billing
+/// runs on past the old end date. Returns the number of receipts issued.
+pub async fn run_pending(
+    db: &Database,
+    config: &SchedulerConfig,
+    today: NaiveDate,
+) -> Result<usize, AppError> {
+    let renewed = renew_due_leases(db, today).await?;
+    if renewed > 0 {
+        tracing::info!("Tacitly renewed {} lease(s)", renewed);
+    }
+
+    let current_month = first_of_month(today);
+    let latest_due = if today.day() >= config.run_day {
+        current_month
+    } else {
+        current_month - Months::new(1)
+    };
+
+    let recorded: Vec<NaiveDate> = sqlx::query_as::<_, (i32, i32)>(
+        "SELECT run_year, run_month FROM receipt_scheduler_runs",
+    )
+    .fetch_all(&db.pool)
+    .await?
+    .into_iter()
+    .filter_map(|(year, month)| NaiveDate::from_ymd_opt(year, month as u32, 1))
+    .collect();
+
+    let mut issued = 0;
+    for run_month in pending_run_months(&recorded, latest_due) {
+        let outcome = run_month_for_all_leases(db, run_month, today).await?;
+        issued += outcome.issued;
+
+        if outcome.failed > 0 {
+            tracing::warn!(
+                "Run of {}-{:02} incomplete: {} lease(s) failed and will be retried",
+                run_month.year(),
+                run_month.month(),
+                outcome.failed
+            );
+            continue;
+        }
+
+        let month_issued = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COALESCE(SUM(issued_count), 0)::BIGINT FROM receipt_scheduler_lease_runs
+            WHERE run_year = $1 AND run_month = $2
+            "#,
+        )
+        .bind(run_month.year())
+        .bind(run_month.month() as i32)
+        .fetch_one(&db.pool)
+        .await?;
+
+        let mut tx = db.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO receipt_scheduler_runs (run_year, run_month, issued_count)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (run_year, run_month) DO NOTHING
+            "#,
+        )
+        .bind(run_month.year())
+        .bind(run_month.month() as i32)
+        .bind(month_issued as i32)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM receipt_scheduler_lease_runs WHERE run_year = $1 AND run_month = $2")
+            .bind(run_month.year())
+            .bind(run_month.month() as i32)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+
+    Ok(issued)
+}
+
+/// Months to run, oldest first: every month from the first recorded run up
+/// to `latest_due` that is not recorded yet, or only `latest_due` when the
+/// scheduler never ran.
+pub fn pending_run_months(recorded: &[NaiveDate], latest_due: NaiveDate) -> Vec<NaiveDate> {
+    let Some(first) = recorded.iter().min().copied() else {
+        return vec![latest_due];
+    };
+
+    let mut months = Vec::new();
+    let mut month = first;
+    while month <= latest_due {
+        if !recorded.contains(&month) {
+            months.push(month);
+        }
+        month = month + Months::new(1);
+    }
+    months
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ScheduledLease {
+    id: Uuid,
+    start_date: NaiveDate,
+    /// Lease end, or the effective date of a running notice.
+    end_date: Option<NaiveDate>,
+    rent_payment_frequency: String,
+    rent_payment_timing: String,
+    owner_id: Option<Uuid>,
+}
+
+/// Receipts issued by one pass over the leases, and how many leases failed.
+struct MonthOutcome {
+    issued: usize,
+    failed: usize,
+}
+
+/// Run `run_month` for every lease that has not gone through it yet.
+async fn run_month_for_all_leases(
+    db: &Database,
+    run_month: NaiveDate,
+    today: NaiveDate,
+) -> Result<MonthOutcome, AppError> {
+    let leases = sqlx::query_as::<_, ScheduledLease>(
+        r#"
+        SELECT l.id, l.start_date, COALESCE(n.effective_date, l.end_date) AS end_date,
+               l.rent_payment_frequency, l.rent_payment_timing,
+               COALESCE(
+                   p.user_id,
+                   (SELECT om.user_id FROM organization_members om
+                    WHERE om.organization_id = p.organization_id
+                    ORDER BY om.joined_at LIMIT 1)
+               ) AS owner_id
+        FROM leases l
+        JOIN properties p ON l.property_id = p.id
+        LEFT JOIN lease_notices n ON n.lease_id = l.id AND n.status = 'active'
+        WHERE l.status IN ('active', 'notice_given')
+          AND NOT EXISTS (
+              SELECT 1 FROM receipt_scheduler_lease_runs r
+              WHERE r.lease_id = l.id AND r.run_year = $1 AND r.run_month = $2
+          )
+        "#,
+    )
+    .bind(run_month.year())
+    .bind(run_month.month() as i32)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let mut outcome = MonthOutcome { issued: 0, failed: 0 };
+    for lease in &leases {
+        // One broken lease must not block the others.
+        match run_lease(db, lease, run_month, today).await {
+            Ok(count) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO receipt_scheduler_lease_runs (lease_id, run_year, run_month, issued_count)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (lease_id, run_year, run_month) DO NOTHING
+                    "#,
+                )
+                .bind(lease.id)
+                .bind(run_month.year())
+                .bind(run_month.month() as i32)
+                .bind(count as i32)
+                .execute(&db.pool)
+                .await?;
+                outcome.issued += count;
+            }
+            Err(e) => {
+                tracing::error!("Scheduled run failed for lease {}: {}", lease.id, e);
+                outcome.failed += 1;
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Issue one lease's receipts for `run_month`, credit them from the ledger
+/// and send the next avis d'échéance. Every step is idempotent, so a failed
+/// lease is simply run again.
+async fn run_lease(
+    db: &Database,
+    lease: &ScheduledLease,
+    run_month: NaiveDate,
+    today: NaiveDate,
+) -> Result<usize, AppError> {
+    let issued = issue_due_receipts(db, lease, run_month).await?;
+    // Tenant credit on the ledger may already settle the new periods.
+    if let Some(owner_id) = lease.owner_id {
+        reconcile_lease(db, lease.id, owner_id).await?;
+    }
+    // After reconciliation, so the notices list up-to-date arrears.
+    issue_upcoming_rent_calls(db, lease, run_month, today).await?;
+    Ok(issued)
+}
+
+async fn issue_upcoming_rent_calls(
+    db: &Database,
+    lease: &ScheduledLease,
+    run_month: NaiveDate,
+    today: NaiveDate,
+) -> Result<usize, AppError> {
+    let periods = rent_call_periods(
+        lease.start_date,
+        lease.end_date,
+        &lease.rent_payment_frequency,
+        &lease.rent_payment_timing,
+        run_month,
+    );
+
+    let mut issued = 0;
+    for period_start in periods {
+        if issue_rent_call(db, lease.id, period_start, today, lease.owner_id).await?.is_some() {
+            issued += 1;
+        }
+    }
+
+    Ok(issued)
+}
+
+async fn issue_due_receipts(
+    db: &Database,
+    lease: &ScheduledLease,
+    run_month: NaiveDate,
+) -> Result<usize, AppError> {
+    let periods = due_periods(
+        lease.start_date,
+        lease.end_date,
+        &lease.rent_payment_frequency,
+        &lease.rent_payment_timing,
+        run_month,
+    );
+
+    let rent_periods = rent_periods(db, lease.id).await?;
+    let mut issued = 0;
+    for period_start in periods {
+        let PeriodCharge { base_rent, charges, due_date } = period_charge(
+            lease.start_date,
+            lease.end_date,
+            &rent_periods,
+            &lease.rent_payment_timing,
+            period_start,
+        )
+        .ok_or_else(|| AppError::Validation("Unable to compute month end".to_string()))?;
+
+        let inserted = sqlx::query_as::<_, Receipt>(
+            r#"
+            INSERT INTO receipts (lease_id, period_month, period_year, base_rent, charges, payment_date, due_date, status)
+            VALUES ($1, $2, $3, $4, $5, $6, $6, 'generated')
+            ON CONFLICT (lease_id, period_month, period_year) DO NOTHING
+            RETURNING id, lease_id, period_month, period_year,
+                      base_rent, charges, total_amount,
+                      payment_date, status, amount_paid, email_sent_at, pdf_path,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(lease.id)
+        .bind(period_start.month() as i32)
+        .bind(period_start.year())
+        .bind(base_rent)
+        .bind(charges)
+        .bind(due_date)
+        .fetch_optional(&db.pool)
+        .await?;
+
+        if let Some(receipt) = inserted {
+            if let Some(owner_id) = lease.owner_id {
+                let lease_snapshot = current_lease_snapshot(db, lease.id, owner_id).await?;
+                persist_receipt_snapshot(db, &lease_snapshot, &receipt).await?;
+            }
+            issued += 1;
+        }
+    }
+
+    Ok(issued)
+}
+
+/// Rent and charges owed for one month, prorated to the leased days.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodCharge {
+    pub base_rent: BigDecimal,
+    pub charges: BigDecimal,
+    pub due_date: NaiveDate,
+}
+
+/// Amounts and due date of the month starting at `period_start`, from the
+/// rent periods in force over its days. Rent paid in advance is due when the
+/// period starts; in arrears when it ends. Returns `None` when the lease does
+/// not cover any day of that month.
+pub fn period_charge(
+    lease_start: NaiveDate,
+    lease_end: Option<NaiveDate>,
+    rent_periods: &[RentPeriod],
+    timing: &str,
+    period_start: NaiveDate,
+) -> Option<PeriodCharge> {
+    let period_start = first_of_month(period_start);
+    let period_end = (period_start + Months::new(1)).pred_opt()?;
+    let covered_start = period_start.max(lease_start);
+    let covered_end = lease_end.map_or(period_end, |end| period_end.min(end));
+    if covered_start > covered_end {
+        return None;
+    }
+
+    let days_in_month = (period_end - period_start).num_days() + 1;
+
+    let (base_rent, charges) = amounts_over(rent_periods, covered_start, covered_end, days_in_month);
+
+    Some(PeriodCharge {
+        base_rent,
+        charges,
+        due_date: if timing == "a_terme_echu" { covered_end } else { covered_start },
+    })
+}
+
+/// Months whose avis d'échéance is sent by the run of `run_month`: the
+/// periods the next run will invoice, so every notice precedes its due date.
+pub fn rent_call_periods(
+    lease_start: NaiveDate,
+    lease_end: Option<NaiveDate>,
+    frequency: &str,
+    timing: &str,
+    run_month: NaiveDate,
+) -> Vec<NaiveDate> {
+    due_periods(lease_start, lease_end, frequency, timing, first_of_month(run_month) + Months::new(1))
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("day 1 always exists")
+}
+
+fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+/// Months (as first days) whose receipt is issued by the run of `run_month`.
+///
+/// - `mensuel` / `a_echoir`: the run month itself.
+/// - `mensuel` / `a_terme_echu`: the month just ended.
+/// - `trimestriel`: quarters are counted from the lease start month. Paid in
+///   advance, the three months are issued when the quarter starts; paid in
+///   arrears, when it ends (or when the lease ends mid-quarter).
+///
+/// Months outside the lease dates are dropped.
+pub fn due_periods(
+    lease_start: NaiveDate,
+    lease_end: Option<NaiveDate>,
+    frequency: &str,
+    timing: &str,
+    run_month: NaiveDate,
+) -> Vec<NaiveDate> {
+    let run_month = first_of_month(run_month);
+    let previous = run_month - Months::new(1);
+    let anchor = month_index(lease_start);
+    let quarter_offset = |m: NaiveDate| (month_index(m) - anchor).rem_euclid(3) as u32;
+
+    let candidates: Vec<NaiveDate> = match (frequency, timing) {
+        ("trimestriel", "a_terme_echu") => {
+            let ends_quarter = quarter_offset(previous) == 2;
+            let lease_ended = lease_end.is_some_and(|end| first_of_month(end) == previous);
+            if ends_quarter || lease_ended {
+                let quarter_start = previous - Months::new(quarter_offset(previous));
+                (0..=quarter_offset(previous))
+                    .map(|i| quarter_start + Months::new(i))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        }
+        ("trimestriel", _) => {
+            if quarter_offset(run_month) == 0 {
+                (0..3).map(|i| run_month + Months::new(i)).collect()
+            } else {
+                Vec::new()
+            }
+        }
+        (_, "a_terme_echu") => vec![previous],
+        _ => vec![run_month],
+    };
+
+    candidates
+        .into_iter()
+        .filter(|month| {
+            let month_end = (*month + Months::new(1)).pred_opt().unwrap_or(*month);
+            month_end >= lease_start && lease_end.is_none_or(|end| *month <= end)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(y: i32, m: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, day).unwrap()
+    }
+
+    #[test]
+    fn monthly_in_advance_issues_current_month() {
+        let periods = due_periods(d(2026, 1, 1), None, "mensuel", "a_echoir", d(2026, 10, 1));
+        assert_eq!(periods, vec![d(2026, 10, 1)]);
+    }
+
+    #[test]
+    fn monthly_in_arrears_issues_previous_month() {
+        let periods = due_periods(d(2026, 1, 1), None, "mensuel", "a_terme_echu", d(2026, 10, 1));
+        assert_eq!(periods, vec![d(2026, 9, 1)]);
+    }
+
+    #[test]
+    fn quarterly_in_advance_follows_lease_start() {
+        let start = d(2026, 2, 15);
+        assert_eq!(
+            due_periods(start, None, "trimestriel", "a_echoir", d(2026, 5, 1)),
+            vec![d(2026, 5, 1), d(2026, 6, 1), d(2026, 7, 1)]
+        );
+        assert!(due_periods(start, None, "trimestriel", "a_echoir", d(2026, 6, 1)).is_empty());
+    }
+
+    #[test]
+    fn quarterly_in_arrears_issues_completed_quarter() {
+        let start = d(2026, 1, 1);
+        assert_eq!(
+            due_periods(start, None, "trimestriel", "a_terme_echu", d(2026, 4, 1)),
+            vec![d(2026, 1, 1), d(2026, 2, 1), d(2026, 3, 1)]
+        );
+        assert!(due_periods(start, None, "trimestriel", "a_terme_echu", d(2026, 5, 1)).is_empty());
+    }
+
+    #[test]
+    fn quarterly_in_arrears_closes_partial_quarter_at_lease_end() {
+        let periods = due_periods(
+            d(2026, 1, 1),
+            Some(d(2026, 5, 20)),
+            "trimestriel",
+            "a_terme_echu",
+            d(2026, 6, 1),
+        );
+        assert_eq!(periods, vec![d(2026, 4, 1), d(2026, 5, 1)]);
+    }
+
+    #[test]
+    fn skips_months_outside_lease() {
+        assert!(due_periods(d(2026, 11, 1), None, "mensuel", "a_echoir", d(2026, 10, 1)).is_empty());
+        assert!(
+            due_periods(d(2025, 1, 1), Some(d(2026, 8, 31)), "mensuel", "a_echoir", d(2026, 10, 1))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn period_charge_prorates_and_sets_due_date_by_timing() {
+        let periods = vec![RentPeriod {
+            effective_date: d(2026, 10, 11),
+            monthly_rent: BigDecimal::from(620),
+            charges: BigDecimal::from(31),
+        }];
+        let advance = period_charge(d(2026, 10, 11), None, &periods, "a_echoir", d(2026, 10, 1)).unwrap();
+        assert_eq!(advance.base_rent, BigDecimal::from(420));
+        assert_eq!(advance.charges, BigDecimal::from(21));
+        assert_eq!(advance.due_date, d(2026, 10, 11));
+
+        let arrears = period_charge(d(2026, 1, 1), None, &periods, "a_terme_echu", d(2026, 10, 1)).unwrap();
+        assert_eq!(arrears.due_date, d(2026, 10, 31));
+
+        assert!(period_charge(d(2026, 11, 1), None, &periods, "a_echoir", d(2026, 10, 1)).is_none());
+    }
+
+    #[test]
+    fn rent_calls_precede_the_periods_they_announce() {
+        // Paid in advance: November is announced during October.
+        assert_eq!(
+            rent_call_periods(d(2026, 1, 1), None, "mensuel", "a_echoir", d(2026, 10, 1)),
+            vec![d(2026, 11, 1)]
+        );
+        // Paid in arrears: October, due on the 31st, is announced during October.
+        assert_eq!(
+            rent_call_periods(d(2026, 1, 1), None, "mensuel", "a_terme_echu", d(2026, 10, 1)),
+            vec![d(2026, 10, 1)]
+        );
+    }
+
+    #[test]
+    fn pending_months_start_at_latest_due_on_first_run() {
+        assert_eq!(pending_run_months(&[], d(2026, 10, 1)), vec![d(2026, 10, 1)]);
+    }
+
+    #[test]
+    fn pending_months_retry_incomplete_runs_and_catch_up_without_limit() {
+        // The server was stopped from March 2025 on.
+        let recorded = vec![d(2024, 12, 1), d(2025, 1, 1), d(2025, 2, 1)];
+        let pending = pending_run_months(&recorded, d(2026, 10, 1));
+        assert_eq!(pending.first(), Some(&d(2025, 3, 1)));
+        assert_eq!(pending.len(), 20);
+
+        let recorded = vec![d(2026, 8, 1), d(2026, 10, 1)];
+        assert_eq!(pending_run_months(&recorded, d(2026, 10, 1)), vec![d(2026, 9, 1)]);
+        assert!(pending_run_months(&recorded, d(2026, 8, 1)).is_empty());
+    }
+
+    #[test]
+    fn partial_first_month_is_still_issued() {
+        let periods = due_periods(d(2026, 10, 15), None, "mensuel", "a_echoir", d(2026, 10, 1));
+        assert_eq!(periods, vec![d(2026, 10, 1)]);
+    }
+}
@@ -0,0 +1,392 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::NaiveDate;
+use quittance_compliance::report::{ComplianceViolation, SEVERITY_WARNING};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::lease::CreateLease;
+
+#[derive(Debug, Error)]
+pub enum RentControlError {
+    #[error("Cannot read rent control file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid rent control data on line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// Construction periods of the lease form, onto which each city's own
+/// periods are mapped in the data file.
+const CONSTRUCTION_PERIODS: [&str; 5] = ["avant_1949", "1949_1974", "1975_1989", "1989_2005", "depuis_2005"];
+
+const RENT_CONTROL_REFERENCE: &str = "Loi n° 2018-1021 du 23 novembre 2018 (ELAN), art. 140";
+
+/// One row of a préfectoral arrêté: reference rents per m² and per month.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow, Serialize, Deserialize)]
+pub struct RentControlReference {
+    pub city: String,
+    pub zone: String,
+    pub room_count: i32,
+    pub construction_period: String,
+    pub furnished: bool,
+    pub valid_from: NaiveDate,
+    pub reference_rent: BigDecimal,
+    pub reference_rent_majorated: BigDecimal,
+    pub reference_rent_minorated: BigDecimal,
+}
+
+/// Reference rents of one dwelling: the arrêté row, per m², and the monthly
+/// amounts they come to for its surface.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RentControlLookup {
+    pub per_square_meter: RentControlReference,
+    pub habitable_surface: BigDecimal,
+    pub reference_rent: BigDecimal,
+    pub reference_rent_majorated: BigDecimal,
+    pub reference_rent_minorated: BigDecimal,
+}
+
+impl RentControlLookup {
+    pub fn for_surface(per_square_meter: RentControlReference, habitable_surface: &BigDecimal) -> Self {
+        let amount = |per_m2: &BigDecimal| (per_m2 * habitable_surface).with_scale_round(2, RoundingMode::HalfUp);
+        Self {
+            reference_rent: amount(&per_square_meter.reference_rent),
+            reference_rent_majorated: amount(&per_square_meter.reference_rent_majorated),
+            reference_rent_minorated: amount(&per_square_meter.reference_rent_minorated),
+            habitable_surface: habitable_surface.clone(),
+            per_square_meter,
+        }
+    }
+}
+
+/// Resolve the rent control data file (overridable via `RENT_CONTROL_DATA_FILE`).
+pub fn rent_control_data_file() -> PathBuf {
+    std::env::var("RENT_CONTROL_DATA_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("data/rent_control.csv"))
+}
+
+fn parse_furnished(raw: &str) -> Option<bool> {
+    match raw.to_ascii_lowercase().as_str() {
+        "true" | "1" | "meuble" | "meublé" => Some(true),
+        "false" | "0" | "non_meuble" | "non meublé" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse `city,zone,rooms,construction_period,furnished,valid_from,reference,
+/// majorated,minorated` rows. Comment lines and the header are skipped; `;`
+/// separators and decimal commas are accepted.
+pub fn parse_rent_control_csv(content: &str) -> Result<Vec<RentControlReference>, RentControlError> {
+    let mut references = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let separator = if line.contains(';') { ';' } else { ',' };
+        let fields: Vec<&str> = line.split(separator).map(str::trim).collect();
+        if fields.first().is_some_and(|f| f.eq_ignore_ascii_case("city")) {
+            continue;
+        }
+        let parse_error = |message: &str| RentControlError::Parse { line: number + 1, message: message.to_string() };
+        if fields.len() < 9 {
+            return Err(parse_error(
+                "expected city, zone, rooms, construction period, furnished, valid from and three rents",
+            ));
+        }
+        let amount = |raw: &str, name: &str| {
+            BigDecimal::from_str(&raw.replace(',', ".")).map_err(|_| parse_error(&format!("invalid {}", name)))
+        };
+
+        let room_count = fields[2]
+            .parse::<i32>()
+            .ok()
+            .filter(|rooms| *rooms >= 1)
+            .ok_or_else(|| parse_error("rooms must be a positive number"))?;
+        if !CONSTRUCTION_PERIODS.contains(&fields[3]) {
+            return Err(parse_error("unknown construction period"));
+        }
+        let furnished = parse_furnished(fields[4]).ok_or_else(|| parse_error("furnished must be true or false"))?;
+        let valid_from = NaiveDate::parse_from_str(fields[5], "%Y-%m-%d")
+            .map_err(|_| parse_error("valid from must be a YYYY-MM-DD date"))?;
+
+        references.push(RentControlReference {
+            city: normalize_city(fields[0]),
+            zone: fields[1].to_string(),
+            room_count,
+            construction_period: fields[3].to_string(),
+            furnished,
+            valid_from,
+            reference_rent: amount(fields[6], "reference rent")?,
+            reference_rent_majorated: amount(fields[7], "majorated reference rent")?,
+            reference_rent_minorated: amount(fields[8], "minorated reference rent")?,
+        });
+    }
+    Ok(references)
+}
+
+/// Load reference rents from a CSV file.
+pub fn load_rent_control_file(path: &Path) -> Result<Vec<RentControlReference>, RentControlError> {
+    parse_rent_control_csv(&std::fs::read_to_string(path)?)
+}
+
+/// Replace the stored reference rents with `references`, so rows dropped from
+/// the data file stop counting. Returns the number of rows written.
+pub async fn sync_rent_control_table(db: &Database, references: &[RentControlReference]) -> Result<usize, AppError> {
+    let mut tx = db.pool.begin().await?;
+    sqlx::query("DELETE FROM rent_control_references").execute(&mut *tx).await?;
+    for reference in references {
+        sqlx::query(
+            r#"
+            INSERT INTO rent_control_references (
+                city, zone, room_count, construction_period, furnished, valid_from,
+                reference_rent, reference_rent_majorated, reference_rent_minorated
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (city, zone, room_count, construction_period, furnished, valid_from)
+            DO UPDATE SET reference_rent = EXCLUDED.reference_rent,
+                          reference_rent_majorated = EXCLUDED.reference_rent_majorated,
+                          reference_rent_minorated = EXCLUDED.reference_rent_minorated,
+                          updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(&reference.city)
+        .bind(&reference.zone)
+        .bind(reference.room_count)
+        .bind(&reference.construction_period)
+        .bind(reference.furnished)
+        .bind(reference.valid_from)
+        .bind(&reference.reference_rent)
+        .bind(&reference.reference_rent_majorated)
+        .bind(&reference.reference_rent_minorated)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(references.len())
+}
+
+/// Load the rent control data file into the database at startup. A missing or
+/// invalid file is logged, not fatal: reference rents are then typed by hand.
+pub async fn sync_from_data_file(db: &Database) {
+    let path = rent_control_data_file();
+    match load_rent_control_file(&path) {
+        Ok(references) => match sync_rent_control_table(db, &references).await {
+            Ok(count) => tracing::info!("Loaded {} reference rents from {:?}", count, path),
+            Err(e) => tracing::error!("Failed to store reference rents: {}", e),
+        },
+        Err(e) => tracing::warn!("Reference rents not loaded from {:?}: {}", path, e),
+    }
+}
+
+fn normalize_city(city: &str) -> String {
+    city.trim().to_lowercase()
+}
+
+/// City of a French address: the words after the postcode, without the
+/// arrondissement ("75011 Paris 11e" gives "paris").
+pub fn city_from_address(address: &str) -> Option<String> {
+    let words: Vec<&str> = address
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .collect();
+    let postcode = words
+        .iter()
+        .rposition(|word| word.len() == 5 && word.chars().all(|c| c.is_ascii_digit()))?;
+    let city: Vec<&str> = words[postcode + 1..]
+        .iter()
+        .copied()
+        .take_while(|word| !word.chars().any(|c| c.is_ascii_digit()) && !word.eq_ignore_ascii_case("cedex"))
+        .collect();
+    (!city.is_empty()).then(|| normalize_city(&city.join(" ")))
+}
+
+/// What the reference rents of a dwelling depend on.
+pub struct DwellingFacts<'a> {
+    pub property_id: Uuid,
+    pub construction_period: Option<&'a str>,
+    pub main_room_count: Option<i32>,
+    /// Falls back to the property's surface.
+    pub habitable_surface: Option<&'a BigDecimal>,
+    pub furnished: bool,
+    /// The arrêté in force on this date applies.
+    pub on: NaiveDate,
+}
+
+/// Reference rents of the dwelling from the loaded arrêtés, or `None` when
+/// its city, zone or characteristics are not covered.
+pub async fn lookup_reference_rents(
+    db: &Database,
+    facts: &DwellingFacts<'_>,
+) -> Result<Option<RentControlLookup>, AppError> {
+    let (address, zone, property_surface) = sqlx::query_as::<_, (String, Option<String>, Option<BigDecimal>)>(
+        "SELECT address, rent_control_zone, surface_area FROM properties WHERE id = $1",
+    )
+    .bind(facts.property_id)
+    .fetch_optional(&db.pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Property with id {} not found", facts.property_id)))?;
+
+    let (Some(city), Some(zone), Some(construction_period), Some(rooms), Some(surface)) = (
+        city_from_address(&address),
+        zone,
+        facts.construction_period,
+        facts.main_room_count,
+        facts.habitable_surface.cloned().or(property_surface),
+    ) else {
+        return Ok(None);
+    };
+
+    let reference = sqlx::query_as::<_, RentControlReference>(
+        r#"
+        SELECT city, zone, room_count, construction_period, furnished, valid_from,
+               reference_rent, reference_rent_majorated, reference_rent_minorated
+        FROM rent_control_references
+        WHERE city = $1 AND zone = $2 AND construction_period = $3 AND furnished = $4
+          AND room_count <= $5 AND valid_from <= $6
+        ORDER BY valid_from DESC, room_count DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(city)
+    .bind(zone)
+    .bind(construction_period)
+    .bind(facts.furnished)
+    .bind(rooms)
+    .bind(facts.on)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    Ok(reference.map(|reference| RentControlLookup::for_surface(reference, &surface)))
+}
+
+/// Fill the reference rents the payload leaves out from the arrêté. Leases
+/// state them per m², like the arrêtés.
+pub fn fill_reference_rents(data: &mut CreateLease, lookup: Option<&RentControlLookup>) {
+    if let Some(lookup) = lookup {
+        let per_square_meter = &lookup.per_square_meter;
+        data.reference_rent.get_or_insert_with(|| per_square_meter.reference_rent.clone());
+        data.reference_rent_majorated
+            .get_or_insert_with(|| per_square_meter.reference_rent_majorated.clone());
+    }
+}
+
+/// Re-check the reference rents of a rent-controlled lease against the
+/// arrêté. A typed figure that differs and a dwelling the loaded data does
+/// not cover are both reported as warnings, never blocking the lease.
+pub fn rent_control_violations(data: &CreateLease, lookup: Option<&RentControlLookup>) -> Vec<ComplianceViolation> {
+    if !data.rent_controlled.unwrap_or(false) {
+        return vec![];
+    }
+    let violation = |severity: &str, code: &str, field: &str, message: String| ComplianceViolation {
+        code: code.to_string(),
+        severity: severity.to_string(),
+        field: field.to_string(),
+        legal_reference: RENT_CONTROL_REFERENCE.to_string(),
+        message,
+    };
+    let Some(lookup) = lookup else {
+        return vec![violation(
+            SEVERITY_WARNING,
+            "rent_control.reference_unverified",
+            "reference_rent",
+            "Les loyers de référence n'ont pas pu être vérifiés : aucun arrêté chargé ne couvre ce logement (commune, zone, pièces, période de construction).".to_string(),
+        )];
+    };
+
+    let expected = &lookup.per_square_meter;
+    let differs = |typed: Option<&BigDecimal>, expected: &BigDecimal| {
+        typed.is_some_and(|typed| typed.with_scale_round(2, RoundingMode::HalfUp) != *expected)
+    };
+    let arrete = format!(
+        "l'arrêté en vigueur depuis le {} (zone {})",
+        expected.valid_from.format("%d/%m/%Y"),
+        expected.zone,
+    );
+    // Only a warning: the bundled dataset is transcribed by hand, so the typed
+    // value may well be the right one.
+    let mut violations = Vec::new();
+    if differs(data.reference_rent.as_ref(), &expected.reference_rent) {
+        violations.push(violation(
+            SEVERITY_WARNING,
+            "rent_control.reference_mismatch",
+            "reference_rent",
+            format!(
+                "Le loyer de référence saisi diffère des {} €/m² de {} : vérifiez-le.",
+                expected.reference_rent.with_scale(2),
+                arrete
+            ),
+        ));
+    }
+    if differs(data.reference_rent_majorated.as_ref(), &expected.reference_rent_majorated) {
+        violations.push(violation(
+            SEVERITY_WARNING,
+            "rent_control.majorated_mismatch",
+            "reference_rent_majorated",
+            format!(
+                "Le loyer de référence majoré saisi diffère des {} €/m² de {} : vérifiez-le.",
+                expected.reference_rent_majorated.with_scale(2),
+                arrete
+            ),
+        ));
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference() -> RentControlReference {
+        RentControlReference {
+            city: "paris".to_string(),
+            zone: "1".to_string(),
+            room_count: 2,
+            construction_period: "avant_1949".to_string(),
+            furnished: true,
+            valid_from: NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            reference_rent: BigDecimal::from_str("31.54").unwrap(),
+            reference_rent_majorated: BigDecimal::from_str("37.85").unwrap(),
+            reference_rent_minorated: BigDecimal::from_str("22.08").unwrap(),
+        }
+    }
+
+    #[test]
+    fn parses_csv_with_comments_header_and_decimal_commas() {
+        let csv = "# arrêté\ncity;zone;rooms;construction_period;furnished;valid_from;reference;majorated;minorated\nParis;1;2;avant_1949;true;2025-07-01;31,54;37,85;22,08\n";
+        assert_eq!(parse_rent_control_csv(csv).unwrap(), vec![reference()]);
+    }
+
+    #[test]
+    fn rejects_unknown_construction_period() {
+        let err = parse_rent_control_csv("paris,1,2,avant_1946,true,2025-07-01,31.54,37.85,22.08").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn bundled_data_file_parses() {
+        let references = load_rent_control_file(Path::new("data/rent_control.csv")).unwrap();
+        assert!(references.iter().all(|r| r.reference_rent_majorated > r.reference_rent));
+    }
+
+    #[test]
+    fn finds_the_city_after_the_postcode() {
+        assert_eq!(city_from_address("12 rue de Lyon, 75011 Paris").as_deref(), Some("paris"));
+        assert_eq!(city_from_address("3 place Bellecour 69002 Lyon 2e").as_deref(), Some("lyon"));
+        assert_eq!(city_from_address("1 rue Nationale, 59000 LILLE CEDEX").as_deref(), Some("lille"));
+        assert_eq!(city_from_address("Lieu-dit sans code postal"), None);
+    }
+
+    #[test]
+    fn monthly_amounts_follow_the_surface() {
+        let lookup = RentControlLookup::for_surface(reference(), &BigDecimal::from_str("30.5").unwrap());
+        assert_eq!(lookup.reference_rent, BigDecimal::from_str("961.97").unwrap());
+        assert_eq!(lookup.reference_rent_majorated, BigDecimal::from_str("1154.43").unwrap());
+    }
+}
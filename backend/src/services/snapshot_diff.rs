@@ -0,0 +1,108 @@
+use serde_json::Value;
+
+use crate::models::canonical_snapshot::{SnapshotChange, SnapshotSectionDiff};
+
+/// Snapshot fields that differ between any two generations and say nothing
+/// about the contract itself.
+const GENERATION_FIELDS: [&str; 2] = ["id", "generated_at"];
+
+/// Field-level differences between two snapshots. Objects are compared key by
+/// key; arrays and scalars are compared as a whole.
+pub fn diff(before: &Value, after: &Value) -> Vec<SnapshotChange> {
+    let mut changes = Vec::new();
+    diff_into(&mut changes, String::new(), before, after);
+    changes
+}
+
+fn diff_into(changes: &mut Vec<SnapshotChange>, path: String, before: &Value, after: &Value) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                diff_into(
+                    changes,
+                    child,
+                    before.get(key).unwrap_or(&Value::Null),
+                    after.get(key).unwrap_or(&Value::Null),
+                );
+            }
+        }
+        _ if before != after => changes.push(SnapshotChange {
+            path,
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+
+/// Field-by-field differences between two snapshot versions, grouped by
+/// top-level section in snapshot order. Top-level scalars such as the legal
+/// template version are reported under their own name.
+pub fn section_diff(before: &Value, after: &Value) -> Vec<SnapshotSectionDiff> {
+    let mut sections: Vec<SnapshotSectionDiff> = Vec::new();
+    for change in diff(before, after) {
+        let section = change.path.split('.').next().unwrap_or_default().to_string();
+        if GENERATION_FIELDS.contains(&section.as_str()) {
+            continue;
+        }
+        match sections.iter_mut().find(|s| s.section == section) {
+            Some(existing) => existing.changes.push(change),
+            None => sections.push(SnapshotSectionDiff { section, changes: vec![change] }),
+        }
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_nested_fields_and_whole_arrays() {
+        let before = json!({"a": {"b": 1, "c": [1, 2]}, "d": "x"});
+        let after = json!({"a": {"b": 2, "c": [1, 3]}, "d": "x", "e": true});
+        let changes = diff(&before, &after);
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.b", "a.c", "e"]);
+        assert_eq!(changes[2].before, Value::Null);
+    }
+
+    #[test]
+    fn section_diff_groups_by_section_and_skips_generation_fields() {
+        let before = json!({
+            "id": "1",
+            "generated_at": "2026-01-01T00:00:00Z",
+            "legal_template_version": "2026-06-18",
+            "parties": {"lessee_full_name": "Jean Dupont", "lessee_email": null},
+            "financial_terms": {"monthly_rent": "850.00", "charges_monthly": "50.00"},
+            "diagnostics": {"dpe_class": "D"}
+        });
+        let after = json!({
+            "id": "2",
+            "generated_at": "2026-10-17T00:00:00Z",
+            "legal_template_version": "2026-06-18",
+            "parties": {"lessee_full_name": "Jean Dupont", "lessee_email": "jean@example.com"},
+            "financial_terms": {"monthly_rent": "900.00", "charges_monthly": "60.00"},
+            "diagnostics": {"dpe_class": "D"}
+        });
+
+        let sections = section_diff(&before, &after);
+        let names: Vec<&str> = sections.iter().map(|s| s.section.as_str()).collect();
+        assert_eq!(names, vec!["financial_terms", "parties"]);
+        assert_eq!(sections[0].changes.len(), 2);
+        assert_eq!(sections[1].changes[0].path, "parties.lessee_email");
+    }
+
+    #[test]
+    fn identical_versions_have_no_diff() {
+        let snapshot = json!({"id": "1", "parties": {"lessee_full_name": "Jean"}});
+        let regenerated = json!({"id": "2", "parties": {"lessee_full_name": "Jean"}});
+        assert!(section_diff(&snapshot, &regenerated).is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::services::rent_control::city_from_address;
+
+#[derive(Debug, Error)]
+pub enum ZoneTendueError {
+    #[error("Cannot read zone tendue file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid zone tendue data on line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// One commune of the zones tendues, keyed as it is looked up from an
+/// address: département part of the postcode and normalized name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneTendueCommune {
+    pub departement: String,
+    pub commune: String,
+}
+
+/// Resolve the zone tendue data file (overridable via `ZONE_TENDUE_DATA_FILE`).
+pub fn zone_tendue_data_file() -> PathBuf {
+    std::env::var("ZONE_TENDUE_DATA_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("data/zones_tendues.csv"))
+}
+
+/// Commune name as compared: lowercase, without accents, hyphens or
+/// apostrophes, "St" and "Ste" spelled out ("Saint-Maur-des-Fossés" and
+/// "St Maur des Fosses" give "saint maur des fosses").
+pub fn commune_key(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in name.trim().to_lowercase().chars() {
+        match c {
+            'à' | 'â' | 'ä' => folded.push('a'),
+            'é' | 'è' | 'ê' | 'ë' => folded.push('e'),
+            'î' | 'ï' => folded.push('i'),
+            'ô' | 'ö' => folded.push('o'),
+            'ù' | 'û' | 'ü' => folded.push('u'),
+            'ÿ' => folded.push('y'),
+            'ç' => folded.push('c'),
+            'œ' => folded.push_str("oe"),
+            'æ' => folded.push_str("ae"),
+            '-' | '\'' | '’' => folded.push(' '),
+            c => folded.push(c),
+        }
+    }
+    folded
+        .split_whitespace()
+        .map(|word| match word {
+            "st" => "saint",
+            "ste" => "sainte",
+            word => word,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Département part of a postcode: its first two digits, three overseas.
+fn departement_of(postcode: &str) -> &str {
+    if postcode.starts_with("97") || postcode.starts_with("98") {
+        &postcode[..3]
+    } else {
+        &postcode[..2]
+    }
+}
+
+/// Commune of a French address, keyed like the zone tendue list, or `None`
+/// when the address has no postcode followed by a city.
+pub fn commune_from_address(address: &str) -> Option<ZoneTendueCommune> {
+    let postcode = address
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .rfind(|word| word.len() == 5 && word.chars().all(|c| c.is_ascii_digit()))?;
+    let city = city_from_address(address)?;
+    Some(ZoneTendueCommune {
+        departement: departement_of(postcode).to_string(),
+        commune: commune_key(&city),
+    })
+}
+
+/// Parse `departement,commune` rows. Comment lines and the header are skipped.
+pub fn parse_zone_tendue_csv(content: &str) -> Result<Vec<ZoneTendueCommune>, ZoneTendueError> {
+    let mut communes = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let separator = if line.contains(';') { ';' } else { ',' };
+        let Some((departement, commune)) = line.split_once(separator) else {
+            return Err(ZoneTendueError::Parse {
+                line: number + 1,
+                message: "expected departement and commune".to_string(),
+            });
+        };
+        let (departement, commune) = (departement.trim(), commune.trim());
+        if departement.eq_ignore_ascii_case("departement") {
+            continue;
+        }
+        if !(2..=3).contains(&departement.len()) || !departement.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ZoneTendueError::Parse {
+                line: number + 1,
+                message: "departement must be the two or three first digits of the postcode".to_string(),
+            });
+        }
+        if commune.is_empty() {
+            return Err(ZoneTendueError::Parse { line: number + 1, message: "missing commune".to_string() });
+        }
+        communes.push(ZoneTendueCommune { departement: departement.to_string(), commune: commune_key(commune) });
+    }
+    Ok(communes)
+}
+
+/// Load the zone tendue communes from a CSV file.
+pub fn load_zone_tendue_file(path: &Path) -> Result<Vec<ZoneTendueCommune>, ZoneTendueError> {
+    parse_zone_tendue_csv(&std::fs::read_to_string(path)?)
+}
+
+/// Replace the stored list with `communes`, so communes dropped from the
+/// annex stop counting. Returns the number of communes stored.
+pub async fn sync_zone_tendue_table(db: &Database, communes: &[ZoneTendueCommune]) -> Result<usize, AppError> {
+    let mut tx = db.pool.begin().await?;
+    sqlx::query("DELETE FROM zone_tendue_communes").execute(&mut *tx).await?;
+    for commune in communes {
+        sqlx::query(
+            r#"
+            INSERT INTO zone_tendue_communes (departement, commune)
+            VALUES ($1, $2)
+            ON CONFLICT (departement, commune) DO NOTHING
+            "#,
+        )
+        .bind(&commune.departement)
+        .bind(&commune.commune)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(communes.len())
+}
+
+/// Whether the address lies in a commune of the zones tendues.
+pub async fn is_zone_tendue(db: &Database, address: &str) -> Result<bool, AppError> {
+    let Some(commune) = commune_from_address(address) else {
+        return Ok(false);
+    };
+    let listed = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM zone_tendue_communes WHERE departement = $1 AND commune = $2)",
+    )
+    .bind(&commune.departement)
+    .bind(&commune.commune)
+    .fetch_one(&db.pool)
+    .await?;
+    Ok(listed)
+}
+
+/// Zone tendue flag of a property: the owner's declaration when given,
+/// otherwise whether the address is listed.
+pub async fn classify_property(db: &Database, address: &str, declared: Option<bool>) -> Result<bool, AppError> {
+    match declared {
+        Some(declared) => Ok(declared),
+        None => is_zone_tendue(db, address).await,
+    }
+}
+
+/// Re-classify every property not classified by hand against the stored
+/// list. Returns the number of properties whose classification changed.
+pub async fn classify_properties(db: &Database) -> Result<usize, AppError> {
+    let properties = sqlx::query_as::<_, (Uuid, String, bool)>(
+        "SELECT id, address, zone_tendue FROM properties WHERE zone_tendue_declared IS NULL",
+    )
+    .fetch_all(&db.pool)
+    .await?;
+    let mut changed = 0;
+    for (id, address, zone_tendue) in properties {
+        let classified = is_zone_tendue(db, &address).await?;
+        if classified != zone_tendue {
+            sqlx::query("UPDATE properties SET zone_tendue = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2")
+                .bind(classified)
+                .bind(id)
+                .execute(&db.pool)
+                .await?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Load the zone tendue file into the database at startup and re-classify
+/// the properties. A missing or invalid file is logged and leaves the
+/// stored list and classifications as they were.
+pub async fn sync_from_data_file(db: &Database) {
+    let path = zone_tendue_data_file();
+    let communes = match load_zone_tendue_file(&path) {
+        Ok(communes) => communes,
+        Err(e) => {
+            tracing::warn!("Zone tendue communes not loaded from {:?}: {}", path, e);
+            return;
+        }
+    };
+    match sync_zone_tendue_table(db, &communes).await {
+        Ok(count) => tracing::info!("Loaded {} zone tendue communes from {:?}", count, path),
+        Err(e) => {
+            tracing::error!("Failed to store zone tendue communes: {}", e);
+            return;
+        }
+    }
+    match classify_properties(db).await {
+        Ok(changed) => tracing::info!("Re-classified {} properties against the zones tendues", changed),
+        Err(e) => tracing::error!("Failed to classify properties against the zones tendues: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_commune_names() {
+        assert_eq!(commune_key("Saint-Maur-des-Fossés"), "saint maur des fosses");
+        assert_eq!(commune_key("St Maur des Fosses"), "saint maur des fosses");
+        assert_eq!(commune_key("Marcq-en-Barœul"), "marcq en baroeul");
+        assert_eq!(commune_key("L'Haÿ-les-Roses"), "l hay les roses");
+    }
+
+    #[test]
+    fn reads_the_commune_of_an_address() {
+        assert_eq!(
+            commune_from_address("8 rue Émile Zola, 92130 Issy-les-Moulineaux"),
+            Some(ZoneTendueCommune { departement: "92".to_string(), commune: "issy les moulineaux".to_string() })
+        );
+        assert_eq!(
+            commune_from_address("2 rue de la Darse 97200 Fort-de-France").map(|c| c.departement),
+            Some("972".to_string())
+        );
+        assert_eq!(commune_from_address("Chemin des Vignes"), None);
+    }
+
+    #[test]
+    fn parses_csv_and_rejects_bad_departements() {
+        let communes = parse_zone_tendue_csv("# annex\ndepartement,commune\n75,Paris\n94;Vitry-sur-Seine\n").unwrap();
+        assert_eq!(communes.len(), 2);
+        assert_eq!(communes[1].commune, "vitry sur seine");
+
+        let err = parse_zone_tendue_csv("Paris,75").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn bundled_data_file_parses() {
+        let communes = load_zone_tendue_file(Path::new("data/zones_tendues.csv")).unwrap();
+        for (departement, commune) in [
+            ("75", "Paris"),
+            ("77", "Chelles"),
+            ("78", "Versailles"),
+            ("91", "Massy"),
+            ("95", "Argenteuil"),
+            ("44", "Pornichet"),
+        ] {
+            let expected = ZoneTendueCommune { departement: departement.to_string(), commune: commune_key(commune) };
+            assert!(communes.contains(&expected), "{} missing from the bundled list", commune);
+        }
+    }
+}
@@ -4,6 +4,7 @@ import type {
   Property,
   Tenant,
   Lease,
+  LeaseStatus,
   Receipt,
   FurnitureSet,
   FurnitureSetWithItems,
@@ -13,6 +14,42 @@ import type {
   CreateLease,
   CreateReceipt,
   RegenerateReceiptsResult,
+  SendReceiptResult,
+  SendMonthResult,
+  Payment,
+  CreatePayment,
+  LeaseLedger,
+  RentCall,
+  SendRentCallResult,
+  ChargesRegularisation,
+  RegularisationDetail,
+  CreateRegularisation,
+  CreateRegularisationExpense,
+  DepositDetail,
+  CreateDeposit,
+  PendingDepositReturn,
+  RecordDepositExit,
+  CreateDepositDeduction,
+  IrlIndex,
+  RentControlLookup,
+  RentRevisionProposal,
+  RentHistoryEntry,
+  CreateRentPeriod,
+  RentScheduleLine,
+  LeaseAmendment,
+  CreateLeaseAmendment,
+  LeaseNotice,
+  CreateLeaseNotice,
+  LeaseDeadlines,
+  ComplianceReport,
+  LeaseInventory,
+  SaveLeaseInventory,
+  InventoryKind,
+  InventoryComparison,
+  DegradationCharge,
+  DepositDeduction,
+  LeaseSnapshotVersion,
+  SnapshotDiff,
   CreateFurnitureSet,
   CreateFurnitureItem,
   UpdateFurnitureItem,
@@ -69,6 +106,20 @@ export const propertiesAPI = {
     await apiClient.delete(`/properties/${id}`)
   },
 
+  async rentControlReference(
+    propertyId: string,
+    params: {
+      construction_period: string
+      main_room_count: number
+      habitable_surface?: number
+      furnished: boolean
+      start_date: string
+    },
+  ): Promise<RentControlLookup> {
+    const response = await apiClient.get(`/properties/${propertyId}/rent-control-reference`, { params })
+    return response.data
+  },
+
   async listFurnitureSets(propertyId: string): Promise<FurnitureSet[]> {
     const response = await apiClient.get(`/properties/${propertyId}/furniture-sets`)
     return response.data
@@ -153,6 +204,12 @@ export const leasesAPI = {
     return response.data
   },
 
+  /** Dry run of the compliance rules: every violation at once, nothing saved. */
+  async validate(data: CreateLease): Promise<ComplianceReport> {
+    const response = await apiClient.post('/leases/validate', data)
+    return response.data
+  },
+
   async update(id: string, data: Partial<CreateLease>): Promise<Lease> {
     const response = await apiClient.put(`/leases/${id}`, data)
     return response.data
@@ -162,6 +219,15 @@ export const leasesAPI = {
     await apiClient.delete(`/leases/${id}`)
   },
 
+  /**
+   * Move the lease along its lifecycle. The server rejects transitions that
+   * skip a step; signing freezes the canonical snapshot.
+   */
+  async transition(id: string, status: LeaseStatus): Promise<Lease> {
+    const response = await apiClient.post(`/leases/${id}/status`, { status })
+    return response.data
+  },
+
   /**
    * Download the server-generated lease PDF as a Blob.
    * Non-compliant leases are returned with a "PROJET / NON CONFORME" watermark.
@@ -190,6 +256,151 @@ export const leasesAPI = {
   async getSnapshot(id: string): Promise<unknown> {
     const response = await apiClient.get(`/leases/${id}/snapshot`)
     return response.data
+  },
+
+  /**
+   * Compute the IRL revision the landlord may claim on the given date.
+   */
+  async proposeRentRevision(id: string, requestedOn?: string): Promise<RentRevisionProposal> {
+    const params = requestedOn ? { requested_on: requestedOn } : {}
+    const response = await apiClient.get(`/leases/${id}/rent-revision`, { params })
+    return response.data
+  },
+
+  async confirmRentRevision(id: string, requestedOn?: string): Promise<RentHistoryEntry> {
+    const response = await apiClient.post(`/leases/${id}/rent-revision`, { requested_on: requestedOn })
+    return response.data
+  },
+
+  async getRentHistory(id: string): Promise<RentHistoryEntry[]> {
+    const response = await apiClient.get(`/leases/${id}/rent-history`)
+    return response.data
+  },
+
+  async addRentPeriod(id: string, data: CreateRentPeriod): Promise<RentHistoryEntry> {
+    const response = await apiClient.post(`/leases/${id}/rent-history`, data)
+    return response.data
+  },
+
+  async deleteRentPeriod(id: string, entryId: string): Promise<void> {
+    await apiClient.delete(`/leases/${id}/rent-history/${entryId}`)
+  },
+
+  /**
+   * Month-by-month amounts owed over a year, from the amounts in force each month.
+   */
+  async getRentSchedule(id: string, year: number): Promise<RentScheduleLine[]> {
+    const response = await apiClient.get(`/leases/${id}/rent-schedule`, { params: { year } })
+    return response.data
+  },
+
+  /**
+   * Every snapshot generated for the lease, oldest first; with `asOf`, only
+   * those generated up to that date (the last one is the contract that day).
+   */
+  async listSnapshots(id: string, asOf?: string): Promise<LeaseSnapshotVersion[]> {
+    const params = asOf ? { as_of: asOf } : {}
+    const response = await apiClient.get(`/leases/${id}/snapshots`, { params })
+    return response.data
+  },
+
+  async getSnapshotVersion(id: string, snapshotId: string): Promise<unknown> {
+    const response = await apiClient.get(`/leases/${id}/snapshots/${snapshotId}`)
+    return response.data
+  },
+
+  async diffSnapshots(id: string, from: string, to: string): Promise<SnapshotDiff> {
+    const response = await apiClient.get(`/leases/${id}/snapshots/diff`, { params: { from, to } })
+    return response.data
+  },
+
+  /**
+   * The contract in force on a date: the signed snapshot plus its amendments.
+   */
+  async getContract(id: string, asOf?: string): Promise<unknown> {
+    const params = asOf ? { as_of: asOf } : {}
+    const response = await apiClient.get(`/leases/${id}/contract`, { params })
+    return response.data
+  },
+
+  async listAmendments(id: string): Promise<LeaseAmendment[]> {
+    const response = await apiClient.get(`/leases/${id}/amendments`)
+    return response.data
+  },
+
+  async createAmendment(id: string, data: CreateLeaseAmendment): Promise<LeaseAmendment> {
+    const response = await apiClient.post(`/leases/${id}/amendments`, data)
+    return response.data
+  },
+
+  async downloadAmendmentPdf(id: string, amendmentId: string): Promise<Blob> {
+    const response = await apiClient.get(`/leases/${id}/amendments/${amendmentId}/pdf`, {
+      responseType: 'blob',
+    })
+    return response.data
+  },
+
+  async listNotices(id: string): Promise<LeaseNotice[]> {
+    const response = await apiClient.get(`/leases/${id}/notices`)
+    return response.data
+  },
+
+  async createNotice(id: string, data: CreateLeaseNotice): Promise<LeaseNotice> {
+    const response = await apiClient.post(`/leases/${id}/notices`, data)
+    return response.data
+  },
+
+  async downloadNoticeLetter(id: string, noticeId: string): Promise<Blob> {
+    const response = await apiClient.get(`/leases/${id}/notices/${noticeId}/letter`, {
+      responseType: 'blob',
+    })
+    return response.data
+  },
+
+  async getDeadlines(id: string): Promise<LeaseDeadlines> {
+    const response = await apiClient.get(`/leases/${id}/deadlines`)
+    return response.data
+  },
+
+  async listUpcomingDeadlines(withinDays?: number): Promise<LeaseDeadlines[]> {
+    const params = withinDays !== undefined ? { within_days: withinDays } : {}
+    const response = await apiClient.get('/leases/deadlines', { params })
+    return response.data
+  },
+
+  async listInventories(id: string): Promise<LeaseInventory[]> {
+    const response = await apiClient.get(`/leases/${id}/inventories`)
+    return response.data
+  },
+
+  async saveInventory(id: string, kind: InventoryKind, data: SaveLeaseInventory): Promise<LeaseInventory> {
+    const response = await apiClient.put(`/leases/${id}/inventories/${kind}`, data)
+    return response.data
+  },
+
+  async downloadInventoryPdf(id: string, kind: InventoryKind): Promise<Blob> {
+    const response = await apiClient.get(`/leases/${id}/inventories/${kind}/pdf`, {
+      responseType: 'blob',
+    })
+    return response.data
+  },
+
+  async compareInventories(id: string): Promise<InventoryComparison> {
+    const response = await apiClient.get(`/leases/${id}/inventories/comparison`)
+    return response.data
+  },
+
+  async createDegradationDeductions(id: string, charges: DegradationCharge[]): Promise<DepositDeduction[]> {
+    const response = await apiClient.post(`/leases/${id}/inventories/comparison/deductions`, { charges })
+    return response.data
+  }
+}
+
+// IRL index API
+export const irlAPI = {
+  async list(): Promise<IrlIndex[]> {
+    const response = await apiClient.get('/irl')
+    return response.data
   }
 }
 
@@ -215,6 +426,18 @@ export const receiptsAPI = {
     await apiClient.delete(`/receipts/${id}`)
   },
 
+  /**
+   * Fetch the server-rendered quittance PDF.
+   * Preview (`inline`) and download return the same stored bytes.
+   */
+  async downloadPdf(id: string, inline = false): Promise<Blob> {
+    const response = await apiClient.get(`/receipts/${id}/pdf`, {
+      params: inline ? { inline: true } : {},
+      responseType: 'blob',
+    })
+    return response.data
+  },
+
   async regenerateForLease(leaseId: string, purgeExisting = false): Promise<RegenerateReceiptsResult> {
     const response = await apiClient.post(`/receipts/lease/${leaseId}/regenerate`, {
       purge_existing: purgeExisting,
@@ -222,7 +445,173 @@ export const receiptsAPI = {
     return response.data
   },
 
-  async sendEmail(id: string): Promise<void> {
-    await apiClient.post(`/receipts/${id}/send`)
+  async sendEmail(id: string): Promise<SendReceiptResult> {
+    const response = await apiClient.post(`/receipts/${id}/send`)
+    return response.data
+  },
+
+  async sendMonth(periodMonth: number, periodYear: number, resend = false): Promise<SendMonthResult> {
+    const response = await apiClient.post('/receipts/send-month', {
+      period_month: periodMonth,
+      period_year: periodYear,
+      resend,
+    })
+    return response.data
+  },
+
+  /** Record a payment of the outstanding balance for this period. */
+  async markPaid(id: string, paymentDate?: string): Promise<Receipt> {
+    const response = await apiClient.post(`/receipts/${id}/mark-paid`, {
+      payment_date: paymentDate ?? null,
+    })
+    return response.data
+  }
+}
+
+export const paymentsAPI = {
+  async list(leaseId: string): Promise<Payment[]> {
+    const response = await apiClient.get('/payments', { params: { lease_id: leaseId } })
+    return response.data
+  },
+
+  async create(data: CreatePayment): Promise<Payment> {
+    const response = await apiClient.post('/payments', data)
+    return response.data
+  },
+
+  async delete(id: string): Promise<void> {
+    await apiClient.delete(`/payments/${id}`)
+  },
+
+  async ledger(leaseId: string): Promise<LeaseLedger> {
+    const response = await apiClient.get(`/payments/lease/${leaseId}/ledger`)
+    return response.data
+  }
+}
+
+export const rentCallsAPI = {
+  async list(leaseId: string): Promise<RentCall[]> {
+    const response = await apiClient.get('/rent-calls', { params: { lease_id: leaseId } })
+    return response.data
+  },
+
+  /** Issue the avis d'échéance of a period (returns the existing one if any). */
+  async create(leaseId: string, periodMonth: number, periodYear: number): Promise<RentCall> {
+    const response = await apiClient.post('/rent-calls', {
+      lease_id: leaseId,
+      period_month: periodMonth,
+      period_year: periodYear,
+    })
+    return response.data
+  },
+
+  async downloadPdf(id: string, inline = false): Promise<Blob> {
+    const response = await apiClient.get(`/rent-calls/${id}/pdf`, {
+      params: inline ? { inline: true } : {},
+      responseType: 'blob',
+    })
+    return response.data
+  },
+
+  async sendEmail(id: string): Promise<SendRentCallResult> {
+    const response = await apiClient.post(`/rent-calls/${id}/send`)
+    return response.data
+  }
+}
+
+// Annual charges regularisation API
+export const regularisationsAPI = {
+  async list(leaseId: string): Promise<ChargesRegularisation[]> {
+    const response = await apiClient.get('/regularisations', { params: { lease_id: leaseId } })
+    return response.data
+  },
+
+  async get(id: string): Promise<RegularisationDetail> {
+    const response = await apiClient.get(`/regularisations/${id}`)
+    return response.data
+  },
+
+  async create(data: CreateRegularisation): Promise<RegularisationDetail> {
+    const response = await apiClient.post('/regularisations', data)
+    return response.data
+  },
+
+  async delete(id: string): Promise<void> {
+    await apiClient.delete(`/regularisations/${id}`)
+  },
+
+  async addExpense(id: string, data: CreateRegularisationExpense): Promise<RegularisationDetail> {
+    const response = await apiClient.post(`/regularisations/${id}/expenses`, data)
+    return response.data
+  },
+
+  async deleteExpense(id: string, expenseId: string): Promise<RegularisationDetail> {
+    const response = await apiClient.delete(`/regularisations/${id}/expenses/${expenseId}`)
+    return response.data
+  },
+
+  /**
+   * Freeze the statement; its balance is added to the next avis d'échéance.
+   */
+  async finalize(id: string): Promise<RegularisationDetail> {
+    const response = await apiClient.post(`/regularisations/${id}/finalize`)
+    return response.data
+  },
+
+  async downloadPdf(id: string, inline = false): Promise<Blob> {
+    const response = await apiClient.get(`/regularisations/${id}/pdf`, {
+      params: inline ? { inline: true } : {},
+      responseType: 'blob',
+    })
+    return response.data
+  }
+}
+
+// Security deposit API
+export const depositsAPI = {
+  async getForLease(leaseId: string): Promise<DepositDetail> {
+    const response = await apiClient.get(`/deposits/lease/${leaseId}`)
+    return response.data
+  },
+
+  async create(data: CreateDeposit): Promise<DepositDetail> {
+    const response = await apiClient.post('/deposits', data)
+    return response.data
+  },
+
+  /**
+   * Deposits awaiting return across the user's leases, most urgent first.
+   */
+  async listPendingReturns(): Promise<PendingDepositReturn[]> {
+    const response = await apiClient.get('/deposits/pending-returns')
+    return response.data
+  },
+
+  async recordExit(id: string, data: RecordDepositExit): Promise<DepositDetail> {
+    const response = await apiClient.put(`/deposits/${id}/exit`, data)
+    return response.data
+  },
+
+  async addDeduction(id: string, data: CreateDepositDeduction): Promise<DepositDetail> {
+    const response = await apiClient.post(`/deposits/${id}/deductions`, data)
+    return response.data
+  },
+
+  async deleteDeduction(id: string, deductionId: string): Promise<DepositDetail> {
+    const response = await apiClient.delete(`/deposits/${id}/deductions/${deductionId}`)
+    return response.data
+  },
+
+  async returnDeposit(id: string, returnedOn: string): Promise<DepositDetail> {
+    const response = await apiClient.post(`/deposits/${id}/return`, { returned_on: returnedOn })
+    return response.data
+  },
+
+  async downloadLetter(id: string, inline = false): Promise<Blob> {
+    const response = await apiClient.get(`/deposits/${id}/letter`, {
+      params: inline ? { inline: true } : {},
+      responseType: 'blob',
+    })
+    return response.data
   }
 }
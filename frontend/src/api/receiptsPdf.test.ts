@@ -0,0 +1,40 @@
+import { vi, describe, it, expect, beforeEach } from 'vitest'
+
+// Mock the axios instance so we can assert quittance PDFs come from the
+// server-rendered endpoint rather than being built in the browser.
+const { get } = vi.hoisted(() => ({ get: vi.fn() }))
+vi.mock('./client', () => ({
+  default: { get },
+}))
+
+import { receiptsAPI } from './index'
+
+describe('receiptsAPI pdf', () => {
+  beforeEach(() => {
+    get.mockReset()
+  })
+
+  it('downloads the quittance PDF as a blob', async () => {
+    const blob = new Blob(['pdf-bytes'])
+    get.mockResolvedValueOnce({ data: blob })
+
+    const result = await receiptsAPI.downloadPdf('receipt-123')
+
+    expect(get).toHaveBeenCalledWith('/receipts/receipt-123/pdf', {
+      params: {},
+      responseType: 'blob',
+    })
+    expect(result).toBe(blob)
+  })
+
+  it('requests the inline variant for preview', async () => {
+    get.mockResolvedValueOnce({ data: new Blob(['pdf-bytes']) })
+
+    await receiptsAPI.downloadPdf('receipt-123', true)
+
+    expect(get).toHaveBeenCalledWith('/receipts/receipt-123/pdf', {
+      params: { inline: true },
+      responseType: 'blob',
+    })
+  })
+})
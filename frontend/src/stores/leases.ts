@@ -1,7 +1,12 @@
 import { defineStore } from 'pinia'
 import { ref } from 'vue'
 import { leasesAPI } from '../api'
-import type { Lease, CreateLease } from '../types'
+import type { Lease, CreateLease, CreateLeaseNotice, LeaseStatus } from '../types'
+
+/** Active or under notice: the tenant still occupies the premises. */
+export function isLeaseInForce(lease: Lease): boolean {
+  return lease.status === 'active' || lease.status === 'notice_given'
+}
 
 export const useLeasesStore = defineStore('leases', () => {
   const leases = ref<Lease[]>([])
@@ -74,6 +79,43 @@ export const useLeasesStore = defineStore('leases', () => {
     }
   }
 
+  async function transitionLease(id: string, status: LeaseStatus) {
+    loading.value = true
+    error.value = null
+    try {
+      const updated = await leasesAPI.transition(id, status)
+      const index = leases.value.findIndex(l => l.id === id)
+      if (index !== -1) {
+        leases.value[index] = updated
+      }
+      return updated
+    } catch (err: any) {
+      error.value = err.message || 'Failed to change lease status'
+      throw err
+    } finally {
+      loading.value = false
+    }
+  }
+
+  async function giveNotice(id: string, data: CreateLeaseNotice) {
+    loading.value = true
+    error.value = null
+    try {
+      const notice = await leasesAPI.createNotice(id, data)
+      const updated = await leasesAPI.get(id)
+      const index = leases.value.findIndex(l => l.id === id)
+      if (index !== -1) {
+        leases.value[index] = updated
+      }
+      return notice
+    } catch (err: any) {
+      error.value = err.message || 'Failed to record notice'
+      throw err
+    } finally {
+      loading.value = false
+    }
+  }
+
   async function deleteLease(id: string) {
     loading.value = true
     error.value = null
@@ -89,7 +131,7 @@ export const useLeasesStore = defineStore('leases', () => {
   }
 
   function getActiveLease(propertyId: string): Lease | undefined {
-    return leases.value.find(l => l.property_id === propertyId && l.status === 'active')
+    return leases.value.find(l => l.property_id === propertyId && isLeaseInForce(l))
   }
 
   function getLeasesByProperty(propertyId: string): Lease[] {
@@ -108,6 +150,8 @@ export const useLeasesStore = defineStore('leases', () => {
     fetchLease,
     createLease,
     updateLease,
+    transitionLease,
+    giveNotice,
     deleteLease,
     getActiveLease,
     getLeasesByProperty,
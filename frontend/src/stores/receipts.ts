@@ -109,9 +109,12 @@ export const useReceiptsStore = defineStore('receipts', () => {
     loading.value = true
     error.value = null
     try {
-      await receiptsAPI.sendEmail(id)
-      // Refresh the receipt to get updated email_sent_at
-      await fetchReceipt(id)
+      const result = await receiptsAPI.sendEmail(id)
+      const index = receipts.value.findIndex(r => r.id === result.receipt.id)
+      if (index !== -1) {
+        receipts.value[index] = result.receipt
+      }
+      return result
     } catch (err: any) {
       error.value = err.message || 'Failed to send receipt'
       throw err
@@ -120,6 +123,24 @@ export const useReceiptsStore = defineStore('receipts', () => {
     }
   }
 
+  async function markReceiptPaid(id: string, paymentDate?: string) {
+    loading.value = true
+    error.value = null
+    try {
+      const receipt = await receiptsAPI.markPaid(id, paymentDate)
+      const index = receipts.value.findIndex(r => r.id === receipt.id)
+      if (index !== -1) {
+        receipts.value[index] = receipt
+      }
+      return receipt
+    } catch (err: any) {
+      error.value = err.message || 'Failed to mark receipt as paid'
+      throw err
+    } finally {
+      loading.value = false
+    }
+  }
+
   function getReceiptsByLease(leaseId: string): Receipt[] {
     return receipts.value.filter(r => r.lease_id === leaseId)
   }
@@ -138,6 +159,7 @@ export const useReceiptsStore = defineStore('receipts', () => {
     deleteReceipt,
     regenerateForLease,
     sendReceipt,
+    markReceiptPaid,
     getReceiptsByLease,
     getPendingReceipts
   }
@@ -75,6 +75,11 @@ export interface Property {
   rooms?: number
   max_occupants: number
   description?: string
+  rent_control_zone?: string
+  /** The owner's declaration when given, otherwise classified by the server from the address */
+  zone_tendue: boolean
+  /** Classification set by hand, for a commune the bundled list misses */
+  zone_tendue_declared?: boolean | null
   created_at: string
   updated_at: string
 }
@@ -142,6 +147,52 @@ export interface UpdateFurnitureItem {
   item_condition?: string
 }
 
+/**
+ * Contract regime: furnished (meublé), unfurnished (location nue) or
+ * ancillary (parking, garage or storage let under the code civil).
+ */
+export type LeaseType = 'furnished' | 'unfurnished' | 'ancillary'
+
+export type LeaseKind = 'standard' | 'student' | 'mobility'
+
+/** Tenant situations that open the bail mobilité. */
+export type MobilityReason =
+  | 'formation_professionnelle'
+  | 'etudes_superieures'
+  | 'apprentissage'
+  | 'stage'
+  | 'service_civique'
+  | 'mutation_professionnelle'
+  | 'mission_temporaire'
+
+export type LeaseStatus =
+  | 'draft'
+  | 'ready_for_signature'
+  | 'signed'
+  | 'active'
+  | 'notice_given'
+  | 'terminated'
+  | 'archived'
+
+export type ComplianceSeverity = 'blocking' | 'warning'
+
+export interface ComplianceViolation {
+  /** Stable rule identifier, e.g. "deposit.furnished_max". */
+  code: string
+  severity: ComplianceSeverity
+  /** Lease payload field to correct. */
+  field: string
+  legal_reference: string
+  message: string
+}
+
+export interface ComplianceReport {
+  rule_set_version: string
+  compliant: boolean
+  blocking: boolean
+  violations: ComplianceViolation[]
+}
+
 export interface Lease {
   id: string
   property_id: string
@@ -155,7 +206,10 @@ export interface Lease {
   deposit: number
   rent_revision: boolean
   annual_charges_regularization: boolean
-  lease_kind: 'standard' | 'student'
+  lease_kind: LeaseKind
+  lease_type: LeaseType
+  mobility_reason?: MobilityReason
+  mobility_justification?: string
   is_colocation: boolean
   tenant_count: number
   destination: 'habitation' | 'mixte_professionnel_habitation'
@@ -177,6 +231,9 @@ export interface Lease {
   rent_complement_justification?: string
   previous_tenant_departure_date?: string
   previous_tenant_last_rent?: number
+  previous_rent_revised_on?: string
+  previous_rent_irl?: number
+  comparable_rent?: number
   professional_mandate: boolean
   agency_fee_tenant?: number
   agency_fee_landlord?: number
@@ -217,9 +274,20 @@ export interface Lease {
   works_amount?: number
   works_date?: string
   rent_revision_conditions?: string
+  irl_reference_quarter?: number
+  irl_reference_year?: number
+  dpe_date?: string
+  electrical_diagnostic_date?: string
+  gas_diagnostic_date?: string
+  /** Anniversary of the latest tacit renewal. */
+  last_renewed_on?: string
   compliance_status: 'pending' | 'compliant' | 'non_compliant'
-  compliance_errors: string[]
-  status: string
+  /** Warnings found when the lease was last saved. */
+  compliance_errors: ComplianceViolation[]
+  compliance_rule_set?: string
+  status: LeaseStatus
+  signed_at?: string
+  status_changed_at?: string
   pdf_path?: string
   created_at: string
   updated_at: string
@@ -235,18 +303,538 @@ export interface Receipt {
   total_amount: number
   payment_date: string
   status: string
+  amount_paid: number
+  email_sent_at?: string
+  pdf_path?: string
+  created_at: string
+  updated_at: string
+}
+
+export type PaymentMethod = 'virement' | 'cheque' | 'especes' | 'caf'
+
+export interface Payment {
+  id: string
+  lease_id: string
+  payer_tenant_id?: string
+  receipt_id?: string
+  amount: number
+  payment_date: string
+  method: PaymentMethod | 'autre' | 'depot_garantie'
+  reference?: string
+  created_at: string
+  updated_at: string
+}
+
+export interface CreatePayment {
+  lease_id: string
+  payer_tenant_id?: string
+  receipt_id?: string
+  amount: number
+  payment_date: string
+  method: PaymentMethod
+  reference?: string
+}
+
+export interface PeriodBalance {
+  receipt_id: string
+  period_year: number
+  period_month: number
+  amount_due: number
+  amount_paid: number
+  balance: number
+  settlement: 'settled' | 'partial' | 'unpaid'
+}
+
+export interface IrlIndex {
+  year: number
+  quarter: number
+  value: number
+}
+
+export interface RentControlReference {
+  city: string
+  zone: string
+  room_count: number
+  construction_period: string
+  furnished: boolean
+  valid_from: string
+  reference_rent: number
+  reference_rent_majorated: number
+  reference_rent_minorated: number
+}
+
+export interface RentControlLookup {
+  /** Amounts per m² and per month from the arrêté */
+  per_square_meter: RentControlReference
+  habitable_surface: number
+  reference_rent: number
+  reference_rent_majorated: number
+  reference_rent_minorated: number
+}
+
+export interface RentRevisionProposal {
+  anniversary_date: string
+  effective_date: string
+  previous_index: IrlIndex
+  new_index: IrlIndex
+  current_rent: number
+  proposed_rent: number
+  late: boolean
+  forfeited_revisions: number
+  frozen: boolean
+  freeze_reason?: string
+}
+
+export interface RentHistoryEntry {
+  id: string
+  lease_id: string
+  effective_date: string
+  monthly_rent: number
+  charges: number
+  reason: 'initial' | 'irl_revision' | 'adjustment' | 'amendment'
+  previous_rent?: number
+  irl_year?: number
+  irl_quarter?: number
+  irl_value?: number
+  previous_irl_value?: number
+  note?: string
+  created_at?: string
+}
+
+export interface CreateRentPeriod {
+  effective_date: string
+  monthly_rent: number
+  charges: number
+  note?: string
+}
+
+export interface RentScheduleLine {
+  period_month: number
+  period_year: number
+  base_rent: number
+  charges: number
+  total_amount: number
+  invoiced_amount?: number
+}
+
+/** One contract field that differs between two versions, located by its snapshot path. */
+export interface SnapshotChange {
+  path: string
+  before: unknown
+  after: unknown
+}
+
+export interface LeaseSnapshotVersion {
+  id: string
+  lease_id: string
+  legal_template_version: string
+  generated_at: string
+}
+
+export interface SnapshotSectionDiff {
+  section: string
+  changes: SnapshotChange[]
+}
+
+export interface SnapshotDiff {
+  from: LeaseSnapshotVersion
+  to: LeaseSnapshotVersion
+  sections: SnapshotSectionDiff[]
+}
+
+export interface LeaseAmendment {
+  id: string
+  lease_id: string
+  sequence_number: number
+  effective_date: string
+  summary: string
+  changes: SnapshotChange[]
+  pdf_path?: string
+  created_at?: string
+}
+
+export interface CreateLeaseAmendment {
+  effective_date: string
+  summary: string
+  /** JSON merge patch over the contract sections, e.g. { financial_terms: { monthly_rent: '900.00' } }. */
+  changes: Record<string, unknown>
+}
+
+export type LandlordNoticeGround = 'vente' | 'reprise' | 'motif_legitime'
+
+export type ReducedNoticeReason =
+  | 'zone_tendue'
+  | 'premier_emploi'
+  | 'mutation'
+  | 'perte_emploi'
+  | 'nouvel_emploi'
+  | 'sante'
+  | 'rsa_aah'
+  | 'logement_social'
+
+export interface LeaseNotice {
+  id: string
+  lease_id: string
+  given_by: 'tenant' | 'landlord'
+  served_on: string
+  notice_months: number
+  /** Last billed day of the lease. */
+  effective_date: string
+  ground?: LandlordNoticeGround
+  reduced_notice_reason?: ReducedNoticeReason
+  beneficiary_name?: string
+  beneficiary_relationship?: string
+  sale_price?: string
+  details?: string
+  status: 'active' | 'withdrawn'
+  pdf_path?: string
+  created_at?: string
+}
+
+export type LeaseDeadlineKind =
+  | 'term_end'
+  | 'notice_end'
+  | 'landlord_notice'
+  | 'irl_revision'
+  | 'dpe_expiry'
+  | 'electrical_diagnostic_expiry'
+  | 'gas_diagnostic_expiry'
+
+export interface LeaseDeadline {
+  kind: LeaseDeadlineKind
+  date: string
+  label: string
+  /** Negative once the date has passed. */
+  days_remaining: number
+}
+
+export interface LeaseDeadlines {
+  lease_id: string
+  renewable: boolean
+  term_end?: string
+  ended_without_renewal: boolean
+  deadlines: LeaseDeadline[]
+}
+
+export interface CreateLeaseNotice {
+  given_by: 'tenant' | 'landlord'
+  served_on: string
+  ground?: LandlordNoticeGround
+  reduced_notice_reason?: ReducedNoticeReason
+  beneficiary_name?: string
+  beneficiary_relationship?: string
+  sale_price?: number
+  details?: string
+}
+
+export type InventoryKind = 'entry' | 'exit'
+
+/** Condition grid of the état des lieux, best first. */
+export type InventoryCondition = 'neuf' | 'bon' | 'moyen' | 'mauvais'
+
+export interface InventoryElement {
+  name: string
+  condition: InventoryCondition
+  comment?: string
+}
+
+export interface InventoryRoom {
+  name: string
+  elements: InventoryElement[]
+  comment?: string
+}
+
+export interface MeterReading {
+  meter: string
+  serial_number?: string
+  reading: string
+}
+
+export interface KeyHandover {
+  label: string
+  count: number
+}
+
+export interface LeaseInventory {
+  id: string
+  lease_id: string
+  kind: InventoryKind
+  performed_on: string
+  rooms: InventoryRoom[]
+  meters: MeterReading[]
+  keys: KeyHandover[]
+  general_comment?: string
+  created_at?: string
+  updated_at?: string
+}
+
+export interface SaveLeaseInventory {
+  performed_on: string
+  rooms: InventoryRoom[]
+  meters?: MeterReading[]
+  keys?: KeyHandover[]
+  general_comment?: string
+}
+
+export interface InventoryDegradation {
+  room: string
+  element: string
+  entry_condition: InventoryCondition
+  /** Absent when the element is missing at exit. */
+  exit_condition?: InventoryCondition
+  exit_comment?: string
+}
+
+export interface KeyShortfall {
+  label: string
+  handed_over: number
+  returned: number
+}
+
+export interface InventoryComparison {
+  entry_id: string
+  exit_id: string
+  degradations: InventoryDegradation[]
+  missing_keys: KeyShortfall[]
+  conforms: boolean
+}
+
+export interface DegradationCharge {
+  room: string
+  element: string
+  amount: number
+}
+
+export type RecoverableChargeCategory =
+  | 'ascenseurs'
+  | 'eau_chauffage_collectif'
+  | 'installations_individuelles'
+  | 'parties_communes'
+  | 'espaces_exterieurs'
+  | 'hygiene'
+  | 'equipements_divers'
+  | 'impositions_redevances'
+
+export interface ChargesRegularisation {
+  id: string
+  lease_id: string
+  year: number
+  occupancy_start: string
+  occupancy_end: string
+  total_expenses: number
+  tenant_share: number
+  provisions_collected: number
+  balance: number
+  status: 'draft' | 'finalized' | 'applied'
+  rent_call_id?: string
+  pdf_path?: string
+  finalized_at?: string
+  created_at?: string
+  updated_at?: string
+}
+
+export interface RegularisationExpense {
+  id: string
+  regularisation_id: string
+  category: RecoverableChargeCategory
+  label: string
+  amount: number
+  covered_start: string
+  covered_end: string
+  created_at?: string
+}
+
+export interface RegularisationStatement {
+  year: number
+  occupancy_start: string
+  occupancy_end: string
+  occupancy_days: number
+  lines: {
+    category: RecoverableChargeCategory
+    category_label: string
+    label: string
+    amount: number
+    covered_start: string
+    covered_end: string
+    tenant_share: number
+  }[]
+  category_totals: {
+    category: RecoverableChargeCategory
+    category_label: string
+    amount: number
+    tenant_share: number
+  }[]
+  total_expenses: number
+  tenant_share: number
+  provisions_collected: number
+  balance: number
+  colocataire_shares: {
+    tenant_name: string
+    tenant_share: number
+    provisions_collected: number
+    balance: number
+  }[]
+}
+
+export interface RegularisationDetail extends ChargesRegularisation {
+  expenses: RegularisationExpense[]
+  statement: RegularisationStatement
+}
+
+export interface CreateRegularisation {
+  lease_id: string
+  year: number
+}
+
+export interface CreateRegularisationExpense {
+  category: RecoverableChargeCategory
+  label: string
+  amount: number
+  covered_start?: string
+  covered_end?: string
+}
+
+export type DepositDeductionCategory =
+  | 'repairs'
+  | 'unpaid_rent'
+  | 'unpaid_charges'
+  | 'charges_provision'
+  | 'other'
+
+export interface Deposit {
+  id: string
+  lease_id: string
+  amount: number
+  received_on: string
+  received_method: string
+  keys_returned_on?: string
+  exit_inventory_conforms?: boolean
+  return_deadline?: string
+  returned_on?: string
+  returned_amount?: number
+  late_penalty?: number
+  status: 'held' | 'awaiting_return' | 'returned'
+  pdf_path?: string
+  created_at?: string
+  updated_at?: string
+}
+
+export interface DepositDeduction {
+  id: string
+  deposit_id: string
+  category: DepositDeductionCategory
+  label: string
+  amount: number
+  receipt_id?: string
+  justification?: string
+  created_at?: string
+}
+
+export interface DepositSettlement {
+  amount_held: number
+  total_deductions: number
+  amount_to_return: number
+  tenant_balance_due: number
+  return_deadline?: string
+  months_late: number
+  late_penalty: number
+  total_due_to_tenant: number
+  settled_on: string
+}
+
+export interface DepositDetail extends Deposit {
+  deductions: DepositDeduction[]
+  settlement: DepositSettlement
+}
+
+export interface CreateDeposit {
+  lease_id: string
+  amount?: number
+  received_on: string
+  received_method?: 'virement' | 'cheque' | 'especes' | 'prelevement' | 'autre'
+}
+
+export interface RecordDepositExit {
+  keys_returned_on: string
+  exit_inventory_conforms: boolean
+}
+
+export interface CreateDepositDeduction {
+  category: DepositDeductionCategory
+  label: string
+  amount?: number
+  receipt_id?: string
+  justification?: string
+}
+
+export interface PendingDepositReturn {
+  deposit_id: string
+  lease_id: string
+  property_address: string
+  keys_returned_on: string
+  return_deadline: string
+}
+
+export interface LeaseLedger {
+  lease_id: string
+  payments: Payment[]
+  periods: PeriodBalance[]
+  allocations: Array<{ payment_id: string; receipt_id: string; amount: number }>
+  total_due: number
+  total_paid: number
+  arrears: number
+  credit: number
+}
+
+export interface RentCall {
+  id: string
+  lease_id: string
+  period_month: number
+  period_year: number
+  due_date: string
+  base_rent: number
+  charges: number
+  regularisation: number
+  arrears: number
+  total_due: number
+  status: 'issued' | 'sent' | 'settled'
+  receipt_id?: string
   email_sent_at?: string
   pdf_path?: string
   created_at: string
   updated_at: string
 }
 
+export interface SendRentCallResult {
+  rent_call: RentCall
+  deliveries: EmailDelivery[]
+}
+
 export interface RegenerateReceiptsResult {
   created_count: number
   deleted_count: number
   receipts: Receipt[]
 }
 
+export interface EmailDelivery {
+  tenant_email: string
+  status: 'sent' | 'logged' | 'failed'
+  error_message?: string
+}
+
+export interface SendReceiptResult {
+  receipt: Receipt
+  deliveries: EmailDelivery[]
+}
+
+export interface SendMonthResult {
+  sent_count: number
+  logged_count: number
+  failed_count: number
+  skipped_count: number
+  results: SendReceiptResult[]
+}
+
 // Create/Update DTOs
 export interface CreateOrganization {
   name: string
@@ -278,6 +866,8 @@ export interface CreateProperty {
   rooms?: number
   max_occupants: number
   description?: string
+  rent_control_zone?: string
+  zone_tendue_declared?: boolean | null
 }
 
 export interface CreateTenant {
@@ -300,7 +890,10 @@ export interface CreateLease {
   deposit: number
   rent_revision: boolean
   annual_charges_regularization: boolean
-  lease_kind?: 'standard' | 'student'
+  lease_kind?: LeaseKind
+  lease_type?: LeaseType
+  mobility_reason?: MobilityReason
+  mobility_justification?: string
   is_colocation?: boolean
   destination?: 'habitation' | 'mixte_professionnel_habitation'
   habitable_surface?: number
@@ -321,6 +914,9 @@ export interface CreateLease {
   rent_complement_justification?: string
   previous_tenant_departure_date?: string
   previous_tenant_last_rent?: number
+  previous_rent_revised_on?: string
+  previous_rent_irl?: number
+  comparable_rent?: number
   professional_mandate?: boolean
   agency_fee_tenant?: number
   agency_fee_landlord?: number
@@ -361,6 +957,11 @@ export interface CreateLease {
   works_amount?: number
   works_date?: string
   rent_revision_conditions?: string
+  irl_reference_quarter?: number
+  irl_reference_year?: number
+  dpe_date?: string
+  electrical_diagnostic_date?: string
+  gas_diagnostic_date?: string
 }
 
 export interface CreateReceipt {
@@ -435,7 +1036,7 @@ export interface LeaseData {
   terms: {
     startDate: string
     duration: number
-    leaseKind?: 'standard' | 'student'
+    leaseKind?: LeaseKind
     isColocation?: boolean
     tenantCount?: number
     monthlyRent: number
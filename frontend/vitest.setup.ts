@@ -0,0 +1,8 @@
+import { readFileSync } from 'node:fs'
+import { loadComplianceEngineSync } from './src/utils/leaseCompliance'
+
+// Node cannot fetch the engine like the browser does; `npm test` builds it
+// first (see the `pretest` script).
+loadComplianceEngineSync(
+  readFileSync(new URL('./src/wasm/quittance_compliance/quittance_compliance_bg.wasm', import.meta.url)),
+)